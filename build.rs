@@ -44,8 +44,35 @@ fn gamma_lut() {
     println!("cargo:rerun-if-changed=src/srgb_gamma.rs");
 }
 
+/// Create look-up table for unpremultiplying 8-bit channels
+///
+/// Entries are 16.16 fixed-point reciprocals of alpha, scaled by 255 and
+/// rounded up.
+fn unpremultiply_lut() {
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join("unpremultiply_lut.rs");
+    let mut w = BufWriter::new(File::create(dest_path).unwrap());
+    writeln!(w, "const UNPREMULTIPLY_U8: &[u32] = &[").unwrap();
+    for a in 0..256u32 {
+        if a % 8 == 0 {
+            write!(w, "    ").unwrap();
+        }
+        // alpha of zero is unused, since all channels are zero
+        let v = match a {
+            0 => 0,
+            _ => (255_u32 << 16).div_ceil(a),
+        };
+        write!(w, "0x{v:06X?}, ").unwrap();
+        if a % 8 == 7 {
+            writeln!(w).unwrap();
+        }
+    }
+    writeln!(w, "];").unwrap();
+}
+
 fn main() {
     gamma_lut();
+    unpremultiply_lut();
 
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -0,0 +1,184 @@
+// ansi.rs      ANSI terminal color preview.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Preview pixels and rasters in a terminal, using ANSI 24-bit color escape
+//! sequences.
+//!
+//! These are mostly useful for debugging conversion output in tests and
+//! command-line tools.
+use crate::chan::Ch8;
+use crate::el::Pixel;
+use crate::raster::Raster;
+use crate::rgb::{Rgb, SRgb8};
+use std::fmt;
+
+/// Sequence to reset terminal colors
+const RESET: &str = "\x1b[0m";
+
+/// `Display` wrapper showing a pixel as a block of color
+///
+/// This struct is created by the [ansi_block] method of [Pixel].
+///
+/// [ansi_block]: ../el/trait.Pixel.html#method.ansi_block
+/// [Pixel]: ../el/trait.Pixel.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnsiBlock(SRgb8);
+
+/// `Display` wrapper showing a scaled-down preview of a raster
+///
+/// Each character shows two pixels, using the upper half block (`▀`) with
+/// foreground and background colors.
+///
+/// This struct is created by the [ansi_preview] method of [Raster].
+///
+/// [ansi_preview]: ../struct.Raster.html#method.ansi_preview
+/// [Raster]: ../struct.Raster.html
+pub struct AnsiPreview<'a, P: Pixel> {
+    /// Raster to preview
+    raster: &'a Raster<P>,
+    /// Preview width, in characters
+    width: u32,
+    /// Preview height, in pixels
+    height: u32,
+}
+
+/// Write an SGR color sequence
+///
+/// * `code` 38 for foreground or 48 for background.
+fn write_color(f: &mut fmt::Formatter, code: u8, p: SRgb8) -> fmt::Result {
+    let (r, g, b) = (Rgb::red(p), Rgb::green(p), Rgb::blue(p));
+    write!(
+        f,
+        "\x1b[{code};2;{};{};{}m",
+        u8::from(r),
+        u8::from(g),
+        u8::from(b)
+    )
+}
+
+impl AnsiBlock {
+    /// Create a block for a pixel
+    pub(crate) fn new<P>(p: P) -> Self
+    where
+        P: Pixel,
+        Ch8: From<P::Chan>,
+    {
+        AnsiBlock(p.convert())
+    }
+}
+
+impl fmt::Display for AnsiBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_color(f, 48, self.0)?;
+        write!(f, "  {RESET}")
+    }
+}
+
+impl<P> AnsiPreview<'_, P>
+where
+    P: Pixel,
+    Ch8: From<P::Chan>,
+{
+    /// Sample a preview pixel, using nearest neighbor
+    fn sample(&self, x: u32, y: u32) -> SRgb8 {
+        let r = self.raster;
+        let sx = u64::from(x) * u64::from(r.width()) / u64::from(self.width);
+        let sy = u64::from(y) * u64::from(r.height()) / u64::from(self.height);
+        r.pixel(sx as i32, sy as i32).convert()
+    }
+}
+
+impl<P> fmt::Display for AnsiPreview<'_, P>
+where
+    P: Pixel,
+    Ch8: From<P::Chan>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                write_color(f, 38, self.sample(x, y))?;
+                if y + 1 < self.height {
+                    write_color(f, 48, self.sample(x, y + 1))?;
+                }
+                write!(f, "▀")?;
+            }
+            writeln!(f, "{RESET}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<P> Raster<P>
+where
+    P: Pixel,
+    Ch8: From<P::Chan>,
+{
+    /// Get a `Display` wrapper showing a preview in a terminal.
+    ///
+    /// * `max_width` Maximum width of preview, in characters.
+    ///
+    /// Rasters wider than `max_width` are scaled down, keeping the aspect
+    /// ratio.  Each line of the preview shows two rows of pixels.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(64, 64, SRgb8::new(0xFF, 0x80, 0x00));
+    /// let preview = r.ansi_preview(16).to_string();
+    /// assert_eq!(preview.lines().count(), 8);
+    /// println!("{preview}");
+    /// ```
+    pub fn ansi_preview(&self, max_width: u32) -> AnsiPreview<'_, P> {
+        let width = self.width().min(max_width.max(1));
+        let height = if width < self.width() {
+            let h = u64::from(self.height()) * u64::from(width)
+                / u64::from(self.width());
+            (h as u32).max(1)
+        } else {
+            self.height()
+        };
+        AnsiPreview {
+            raster: self,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gray::Gray16;
+    use crate::rgb::SRgba8;
+
+    #[test]
+    fn block() {
+        let b = SRgba8::new(1, 2, 3, 4).ansi_block().to_string();
+        assert_eq!(b, "\x1b[48;2;1;2;3m  \x1b[0m");
+        let b = Gray16::new(0xFFFF).ansi_block().to_string();
+        assert_eq!(b, "\x1b[48;2;255;255;255m  \x1b[0m");
+    }
+
+    #[test]
+    fn preview() {
+        let mut r = Raster::<SRgb8>::with_clear(2, 3);
+        *r.pixel_mut(1, 0) = SRgb8::new(255, 0, 0);
+        *r.pixel_mut(0, 2) = SRgb8::new(0, 0, 255);
+        let p = r.ansi_preview(80).to_string();
+        assert_eq!(
+            p,
+            "\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\
+             \x1b[38;2;255;0;0m\x1b[48;2;0;0;0m▀\x1b[0m\n\
+             \x1b[38;2;0;0;255m▀\x1b[38;2;0;0;0m▀\x1b[0m\n"
+        );
+        let r = Raster::<SRgb8>::with_clear(100, 10);
+        let p = r.ansi_preview(20).to_string();
+        assert_eq!(p.lines().count(), 1);
+        assert_eq!(p.matches('▀').count(), 20);
+        let r = Raster::<SRgb8>::with_clear(0, 0);
+        assert_eq!(r.ansi_preview(20).to_string(), "");
+    }
+}
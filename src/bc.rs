@@ -0,0 +1,193 @@
+// bc.rs        BC1 / BC3 (DXT) block decoding.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::codec::{pixel_count, CodecError};
+use crate::raster::Raster;
+use crate::rgb::SRgba8;
+
+/// Block width and height, in pixels
+const BLOCK: u32 = 4;
+
+/// Expand an RGB 5:6:5 color to 8 bits per channel
+fn rgb565(c: u16) -> [u8; 4] {
+    let r = ((c >> 11) & 0x1F) as u8;
+    let g = ((c >> 5) & 0x3F) as u8;
+    let b = (c & 0x1F) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+        255,
+    ]
+}
+
+/// Interpolate between two colors, weighting `a` by `wa` and `b` by `wb`
+fn lerp(a: [u8; 4], b: [u8; 4], wa: u16, wb: u16) -> [u8; 4] {
+    let mut c = [255; 4];
+    for i in 0..3 {
+        let v = u16::from(a[i]) * wa + u16::from(b[i]) * wb;
+        c[i] = (v / (wa + wb)) as u8;
+    }
+    c
+}
+
+/// Decode a BC1 color block
+///
+/// * `block` 8 bytes of block data.
+/// * `punch` Allow 3-color mode with transparent black.
+fn color_block(block: &[u8], punch: bool) -> [[u8; 4]; 16] {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let (p0, p1) = (rgb565(c0), rgb565(c1));
+    let palette = if c0 > c1 || !punch {
+        [p0, p1, lerp(p0, p1, 2, 1), lerp(p0, p1, 1, 2)]
+    } else {
+        [p0, p1, lerp(p0, p1, 1, 1), [0; 4]]
+    };
+    let bits = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    std::array::from_fn(|i| palette[((bits >> (i * 2)) & 3) as usize])
+}
+
+/// Decode a BC3 alpha block
+fn alpha_block(block: &[u8]) -> [u8; 16] {
+    let (a0, a1) = (u16::from(block[0]), u16::from(block[1]));
+    let palette: [u8; 8] = if a0 > a1 {
+        std::array::from_fn(|i| match i {
+            0 => a0 as u8,
+            1 => a1 as u8,
+            _ => ((a0 * (8 - i as u16) + a1 * (i as u16 - 1)) / 7) as u8,
+        })
+    } else {
+        std::array::from_fn(|i| match i {
+            0 => a0 as u8,
+            1 => a1 as u8,
+            6 => 0,
+            7 => 255,
+            _ => ((a0 * (6 - i as u16) + a1 * (i as u16 - 1)) / 5) as u8,
+        })
+    };
+    let bits = block[2..8]
+        .iter()
+        .rev()
+        .fold(0u64, |v, b| (v << 8) | u64::from(*b));
+    std::array::from_fn(|i| palette[((bits >> (i * 3)) & 7) as usize])
+}
+
+/// Decode blocks into a raster
+///
+/// * `size` Size of each block, in bytes.
+/// * `decode` Function to decode one block.
+fn decode<F>(
+    width: u32,
+    height: u32,
+    data: &[u8],
+    size: usize,
+    decode: F,
+) -> Result<Raster<SRgba8>, CodecError>
+where
+    F: Fn(&[u8]) -> [[u8; 4]; 16],
+{
+    pixel_count(width, height)?;
+    let bw = width.div_ceil(BLOCK);
+    let bh = height.div_ceil(BLOCK);
+    let len = (bw as usize * bh as usize)
+        .checked_mul(size)
+        .ok_or(CodecError::TooBig)?;
+    let data = data.get(..len).ok_or(CodecError::Truncated)?;
+    let mut raster = Raster::with_clear(width, height);
+    for (i, block) in data.chunks_exact(size).enumerate() {
+        let bx = (i as u32 % bw) * BLOCK;
+        let by = (i as u32 / bw) * BLOCK;
+        for (j, px) in decode(block).into_iter().enumerate() {
+            let x = bx + j as u32 % BLOCK;
+            let y = by + j as u32 / BLOCK;
+            if x < width && y < height {
+                *raster.pixel_mut(x as i32, y as i32) = SRgba8::from(px);
+            }
+        }
+    }
+    Ok(raster)
+}
+
+impl Raster<SRgba8> {
+    /// Decode BC1 (DXT1) compressed texture data.
+    ///
+    /// * `width` Width of texture, in pixels.
+    /// * `height` Height of texture, in pixels.
+    /// * `data` Compressed blocks (8 bytes per 4x4 block), row-major.
+    ///
+    /// Blocks which use 3-color mode decode index 3 as transparent black.
+    /// Pixels of partial blocks outside the texture are discarded.
+    pub fn from_bc1(
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<Self, CodecError> {
+        decode(width, height, data, 8, |block| color_block(block, true))
+    }
+
+    /// Decode BC3 (DXT5) compressed texture data.
+    ///
+    /// * `width` Width of texture, in pixels.
+    /// * `height` Height of texture, in pixels.
+    /// * `data` Compressed blocks (16 bytes per 4x4 block), row-major.
+    ///
+    /// Pixels of partial blocks outside the texture are discarded.
+    pub fn from_bc3(
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<Self, CodecError> {
+        decode(width, height, data, 16, |block| {
+            let alpha = alpha_block(&block[..8]);
+            let mut color = color_block(&block[8..], false);
+            for (c, a) in color.iter_mut().zip(alpha) {
+                c[3] = a;
+            }
+            color
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bc1() {
+        // red / blue, indices: row 0 = 0, row 1 = 1, row 2 = 2, row 3 = 3
+        let block = [0x00, 0xF8, 0x1F, 0x00, 0x00, 0x55, 0xAA, 0xFF];
+        let r = Raster::<SRgba8>::from_bc1(4, 4, &block).unwrap();
+        assert_eq!(r.pixel(0, 0), SRgba8::new(255, 0, 0, 255));
+        assert_eq!(r.pixel(1, 1), SRgba8::new(0, 0, 255, 255));
+        assert_eq!(r.pixel(2, 2), SRgba8::new(170, 0, 85, 255));
+        assert_eq!(r.pixel(3, 3), SRgba8::new(85, 0, 170, 255));
+        // 3-color mode
+        let block = [0x1F, 0x00, 0x00, 0xF8, 0x00, 0x55, 0xAA, 0xFF];
+        let r = Raster::<SRgba8>::from_bc1(3, 5, &[block, block].concat());
+        let r = r.unwrap();
+        assert_eq!(r.pixel(0, 2), SRgba8::new(127, 0, 127, 255));
+        assert_eq!(r.pixel(2, 3), SRgba8::new(0, 0, 0, 0));
+        assert_eq!(r.pixel(1, 4), SRgba8::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn bc3() {
+        // alpha 255 / 0, indices 0, 1, 2, 7, ...
+        let mut block = vec![0xFF, 0x00, 0b10_001_000, 0b1110, 0, 0, 0, 0];
+        block.extend_from_slice(&[0x1F, 0x00, 0x00, 0xF8, 0, 0, 0, 0]);
+        let r = Raster::<SRgba8>::from_bc3(4, 4, &block).unwrap();
+        assert_eq!(r.pixel(0, 0), SRgba8::new(0, 0, 255, 255));
+        assert_eq!(r.pixel(1, 0), SRgba8::new(0, 0, 255, 0));
+        assert_eq!(r.pixel(2, 0), SRgba8::new(0, 0, 255, 218));
+        assert_eq!(r.pixel(3, 0), SRgba8::new(0, 0, 255, 36));
+        assert_eq!(r.pixel(0, 1), SRgba8::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn truncated() {
+        let r = Raster::<SRgba8>::from_bc1(5, 4, &[0; 8]);
+        assert_eq!(r.err(), Some(CodecError::Truncated));
+    }
+}
@@ -4,8 +4,9 @@
 //
 //! BGR color model and types.
 use crate::chan::{Ch16, Ch32, Ch8, Linear, Premultiplied, Srgb, Straight};
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// BGR arrangement of [RGB] [color model].
@@ -128,10 +129,34 @@ impl Bgr {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Bgr] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::bgr::{BgrPixel, Bgr8};
+    ///
+    /// let mut p = Bgr8::new(1, 2, 3);
+    /// assert_eq!(p.blue(), Ch8::new(1));
+    /// *p.red_mut() = Ch8::new(9);
+    /// assert_eq!(p.red(), Ch8::new(9));
+    /// ```
+    ///
+    /// [Bgr]: struct.Bgr.html
+    BgrPixel: Bgr {
+        blue, blue_mut, with_blue;
+        green, green_mut, with_green;
+        red, red_mut, with_red;
+    }
+}
+
 impl ColorModel for Bgr {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] =
+        &[ChannelLabel::Blue, ChannelLabel::Green, ChannelLabel::Red];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -154,7 +179,7 @@ impl ColorModel for Bgr {
         let green = chan[1];
         let blue = chan[2];
         let alpha = chan[3];
-        P::from_channels(&[blue, green, red, alpha])
+        from_model_channels(&[blue, green, red, alpha])
     }
 }
 
@@ -4,15 +4,17 @@
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
 //! Component channels
-use crate::private::Sealed;
+use crate::private::{MaybeSend, MaybeSync, Sealed};
 use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::ops::{Add, Div, Mul, Sub};
 
 /// *Alpha* encoding mode.
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
-pub trait Alpha: Copy + Clone + Debug + Default + PartialEq + Sealed {
+pub trait Alpha:
+    Copy + Clone + Debug + Default + PartialEq + MaybeSend + MaybeSync + Sealed
+{
     /// Encode one `Channel` using the alpha mode.
     fn encode<C: Channel>(c: C, a: C) -> C;
     /// Decode one `Channel` using the alpha mode.
@@ -66,7 +68,9 @@ include!(concat!(env!("OUT_DIR"), "/gamma_lut.rs"));
 /// *Gamma* encoding mode.
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
-pub trait Gamma: Copy + Clone + Debug + Default + PartialEq + Sealed {
+pub trait Gamma:
+    Copy + Clone + Debug + Default + PartialEq + MaybeSend + MaybeSync + Sealed
+{
     /// Convert a `Channel` value to linear.
     fn to_linear<C: Channel>(c: C) -> C;
     /// Convert a `Channel` value from linear.
@@ -129,6 +133,8 @@ pub trait Channel:
     + Div<Output = Self>
     + Mul<Output = Self>
     + Sub<Output = Self>
+    + MaybeSend
+    + MaybeSync
     + Sealed
 {
     /// Minimum intensity (*zero*)
@@ -159,6 +165,22 @@ pub trait Channel:
     fn lerp(self, rhs: Self, t: Self) -> Self;
 }
 
+/// [Channel] with integer values, where every bit pattern is valid.
+///
+/// Implemented for [Ch8] and [Ch16], but not [Ch32].
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+///
+/// [Ch8]: struct.Ch8.html
+/// [Ch16]: struct.Ch16.html
+/// [Ch32]: struct.Ch32.html
+/// [Channel]: trait.Channel.html
+pub trait IntChannel: Channel {}
+
+impl IntChannel for Ch8 {}
+
+impl IntChannel for Ch16 {}
+
 /// 8-bit color [Channel](trait.Channel.html).
 ///
 /// The `Channel` is represented by a `u8`, but multiplication and division
@@ -177,6 +199,12 @@ pub trait Channel:
 /// assert_eq!(c, Ch32::MAX);
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
 pub struct Ch8(u8);
 
 /// 16-bit color [Channel](trait.Channel.html).
@@ -197,6 +225,12 @@ pub struct Ch8(u8);
 /// assert_eq!(c, Ch32::MAX);
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
 pub struct Ch16(u16);
 
 /// 32-bit color [Channel](trait.Channel.html).
@@ -217,8 +251,64 @@ pub struct Ch16(u16);
 /// assert_eq!(c, Ch16::MAX);
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "f32", into = "f32")
+)]
 pub struct Ch32(f32);
 
+/// Error checking a [Channel](trait.Channel.html) value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelError {
+    /// Value is `NaN`
+    NotANumber,
+    /// Value is out of range for the channel
+    OutOfRange,
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChannelError::NotANumber => write!(f, "channel value is NaN"),
+            ChannelError::OutOfRange => {
+                write!(f, "channel value out of range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Implement checked conversions from wider integers
+macro_rules! try_from_int {
+    ($chan:ident, $inner:ty, $($int:ty),+) => {
+        $(
+            impl TryFrom<$int> for $chan {
+                type Error = ChannelError;
+
+                /// Convert from a wider integer, checking the range.
+                fn try_from(value: $int) -> Result<Self, Self::Error> {
+                    <$inner>::try_from(value)
+                        .map($chan)
+                        .map_err(|_| ChannelError::OutOfRange)
+                }
+            }
+        )+
+    };
+}
+
+try_from_int!(Ch8, u8, u16, u32, u64, i16, i32, i64);
+try_from_int!(Ch16, u16, u32, u64, i32, i64);
+
+// Safety: `repr(transparent)` integer; every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Ch8 {}
+
+// Safety: `repr(transparent)` integer; every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Ch8 {}
+
 impl Ch8 {
     /// Create a new 8-bit `Channel` value.
     pub const fn new(value: u8) -> Self {
@@ -361,6 +451,14 @@ where
     }
 }
 
+// Safety: `repr(transparent)` integer; every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Ch16 {}
+
+// Safety: `repr(transparent)` integer; every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Ch16 {}
+
 impl Ch16 {
     /// Create a new 16-bit `Channel` value.
     pub const fn new(value: u16) -> Self {
@@ -394,16 +492,14 @@ impl Channel for Ch16 {
 
     /// Encode an sRGB gamma value from linear intensity
     fn encode_srgb(self) -> Self {
-        let s = f32::from(u16::from(self)) / 65535.0;
-        let s = (srgb_gamma_encode(s) * 65535.0).round() as u16;
-        Self::new(s)
+        let s = srgb_gamma_encode(u16_to_f32(self.0));
+        Self::new(f32_to_u16(s))
     }
 
     /// Decode an sRGB gamma value into linear intensity
     fn decode_srgb(self) -> Self {
-        let s = f32::from(u16::from(self)) / 65535.0;
-        let s = (srgb_gamma_decode(s) * 65535.0).round() as u16;
-        Self::new(s)
+        let s = srgb_gamma_decode(u16_to_f32(self.0));
+        Self::new(f32_to_u16(s))
     }
 
     /// Linear interpolation
@@ -525,7 +621,7 @@ impl Ch32 {
     ///         less than 0.0, or `NaN`.
     /// Returns [MAX](trait.Channel.html#associatedconstant.MAX) if value is
     ///         greater than 1.0.
-    pub fn new(value: f32) -> Self {
+    pub const fn new(value: f32) -> Self {
         let v = if value.is_nan() || value < 0.0 {
             0.0
         } else if value > 1.0 {
@@ -535,6 +631,30 @@ impl Ch32 {
         };
         Ch32(v)
     }
+
+    /// Create a new 32-bit `Channel` value, checking the range.
+    ///
+    /// Unlike [new](#method.new), the value is not clamped.  This is the
+    /// checked counterpart of `From<f32>`, which rules out a `TryFrom<f32>`
+    /// impl.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::{Ch32, ChannelError};
+    ///
+    /// assert_eq!(Ch32::try_new(0.25), Ok(Ch32::new(0.25)));
+    /// assert_eq!(Ch32::try_new(1.5), Err(ChannelError::OutOfRange));
+    /// assert_eq!(Ch32::try_new(f32::NAN), Err(ChannelError::NotANumber));
+    /// ```
+    pub fn try_new(value: f32) -> Result<Self, ChannelError> {
+        if value.is_nan() {
+            Err(ChannelError::NotANumber)
+        } else if (0.0..=1.0).contains(&value) {
+            Ok(Ch32(value))
+        } else {
+            Err(ChannelError::OutOfRange)
+        }
+    }
 }
 
 impl Channel for Ch32 {
@@ -593,7 +713,7 @@ impl Channel for Ch32 {
 
 impl From<Ch8> for Ch32 {
     fn from(c: Ch8) -> Self {
-        Ch32(f32::from(c.0) / 255.0)
+        Ch32(u8_to_f32(c.0))
     }
 }
 
@@ -611,30 +731,58 @@ impl From<Ch32> for f32 {
 
 impl From<Ch32> for Ch8 {
     fn from(c: Ch32) -> Self {
-        let value = c.0;
-        debug_assert!((0.0..=1.0).contains(&value));
-        // this cast is not UB since the value is guaranteed
-        // to be between 0.0 and 1.0 (see bug #10184)
-        Ch8::new((value * 255.0).round() as u8)
+        Ch8::new(f32_to_u8(c.0))
     }
 }
 
 impl From<Ch32> for Ch16 {
     fn from(c: Ch32) -> Self {
-        let value = c.0;
-        debug_assert!((0.0..=1.0).contains(&value));
-        // this cast is not UB since the value is guaranteed
-        // to be between 0.0 and 1.0 (see bug #10184)
-        Ch16::new((value * 65535.0).round() as u16)
+        Ch16::new(f32_to_u16(c.0))
     }
 }
 
 impl From<Ch16> for Ch32 {
     fn from(c: Ch16) -> Self {
-        Ch32(f32::from(c.0) / 65535.0)
+        Ch32(u16_to_f32(c.0))
     }
 }
 
+/// Convert a `u8` value to `f32` (ranging from 0.0 to 1.0)
+///
+/// Converting back with `f32_to_u8` is guaranteed to be lossless.
+#[inline]
+fn u8_to_f32(v: u8) -> f32 {
+    f32::from(v) / 255.0
+}
+
+/// Convert an `f32` value (ranging from 0.0 to 1.0) to `u8`, rounding to
+/// nearest
+#[inline]
+fn f32_to_u8(v: f32) -> u8 {
+    debug_assert!((0.0..=1.0).contains(&v));
+    // this cast is not UB since the value is guaranteed
+    // to be between 0.0 and 1.0 (see bug #10184)
+    (v * 255.0).round() as u8
+}
+
+/// Convert a `u16` value to `f32` (ranging from 0.0 to 1.0)
+///
+/// Converting back with `f32_to_u16` is guaranteed to be lossless.
+#[inline]
+fn u16_to_f32(v: u16) -> f32 {
+    f32::from(v) / 65535.0
+}
+
+/// Convert an `f32` value (ranging from 0.0 to 1.0) to `u16`, rounding to
+/// nearest
+#[inline]
+fn f32_to_u16(v: f32) -> u16 {
+    debug_assert!((0.0..=1.0).contains(&v));
+    // this cast is not UB since the value is guaranteed
+    // to be between 0.0 and 1.0 (see bug #10184)
+    (v * 65535.0).round() as u16
+}
+
 impl Eq for Ch32 {}
 
 #[allow(clippy::derive_ord_xor_partial_ord)]
@@ -692,14 +840,14 @@ where
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_range_loop)]
 mod test {
     use crate::chan::*;
 
     #[test]
     fn lut_encode_u8() {
         for i in 0..=255 {
-            let s = i as f32 / 255.0;
-            let v = (srgb_gamma_encode(s) * 255.0).round() as u8;
+            let v = f32_to_u8(srgb_gamma_encode(u8_to_f32(i as u8)));
             assert_eq!(v, ENCODE_SRGB_U8[i]);
         }
     }
@@ -707,8 +855,7 @@ mod test {
     #[test]
     fn lut_decode_u8() {
         for i in 0..=255 {
-            let s = i as f32 / 255.0;
-            let v = (srgb_gamma_decode(s) * 255.0).round() as u8;
+            let v = f32_to_u8(srgb_gamma_decode(u8_to_f32(i as u8)));
             assert_eq!(v, DECODE_SRGB_U8[i]);
         }
     }
@@ -748,6 +895,38 @@ mod test {
         assert_eq!(Ch32::new(1.0), Ch8::new(255).into());
     }
 
+    #[test]
+    fn checked() {
+        assert_eq!(Ch8::try_from(200u16), Ok(Ch8::new(200)));
+        assert_eq!(Ch8::try_from(256u32), Err(ChannelError::OutOfRange));
+        assert_eq!(Ch8::try_from(-1i32), Err(ChannelError::OutOfRange));
+        assert_eq!(Ch16::try_from(65535u64), Ok(Ch16::new(65535)));
+        assert_eq!(Ch16::try_from(65536i64), Err(ChannelError::OutOfRange));
+        assert_eq!(Ch32::try_new(0.0), Ok(Ch32::MIN));
+        assert_eq!(Ch32::try_new(1.0), Ok(Ch32::MAX));
+        assert_eq!(Ch32::try_new(-0.1), Err(ChannelError::OutOfRange));
+        assert_eq!(Ch32::try_new(f32::INFINITY), Err(ChannelError::OutOfRange));
+        assert_eq!(Ch32::try_new(f32::NAN), Err(ChannelError::NotANumber));
+    }
+
+    #[test]
+    fn ch8_round_trip() {
+        for i in 0..=u8::MAX {
+            let c32 = Ch32::from(Ch8::new(i));
+            assert_eq!(Ch8::new(i), Ch8::from(c32));
+            assert_eq!(Ch8::new(i), Ch8::from(f32::from(c32)));
+        }
+    }
+
+    #[test]
+    fn ch16_round_trip() {
+        for i in 0..=u16::MAX {
+            let c32 = Ch32::from(Ch16::new(i));
+            assert_eq!(Ch16::new(i), Ch16::from(c32));
+            assert_eq!(Ch16::new(i), Ch16::from(f32::from(c32)));
+        }
+    }
+
     #[test]
     fn ch8_mul() {
         assert_eq!(Ch8::new(255), Ch8::new(255) * 1.0);
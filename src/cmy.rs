@@ -8,8 +8,9 @@
 use crate::chan::{
     Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
 };
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [CMY] subtractive [color model].
@@ -132,10 +133,37 @@ impl Cmy {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Cmy] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::cmy::{CmyPixel, Cmy8};
+    ///
+    /// let mut p = Cmy8::new(1, 2, 3);
+    /// assert_eq!(p.cyan(), Ch8::new(1));
+    /// *p.yellow_mut() = Ch8::new(9);
+    /// assert_eq!(p.yellow(), Ch8::new(9));
+    /// ```
+    ///
+    /// [Cmy]: struct.Cmy.html
+    CmyPixel: Cmy {
+        cyan, cyan_mut, with_cyan;
+        magenta, magenta_mut, with_magenta;
+        yellow, yellow_mut, with_yellow;
+    }
+}
+
 impl ColorModel for Cmy {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] = &[
+        ChannelLabel::Cyan,
+        ChannelLabel::Magenta,
+        ChannelLabel::Yellow,
+    ];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -158,7 +186,7 @@ impl ColorModel for Cmy {
         let magenta = P::Chan::MAX - chan[1];
         let yellow = P::Chan::MAX - chan[2];
         let alpha = chan[3];
-        P::from_channels(&[cyan, magenta, yellow, alpha])
+        from_model_channels(&[cyan, magenta, yellow, alpha])
     }
 }
 
@@ -0,0 +1,42 @@
+// codec.rs     Image codec errors.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use std::fmt;
+
+/// Error decoding an image file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    /// Missing or invalid file header
+    InvalidHeader,
+    /// Valid file, but unsupported format variant
+    Unsupported,
+    /// Image data ends before all pixels are decoded
+    Truncated,
+    /// Image dimensions too big
+    TooBig,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::InvalidHeader => write!(f, "invalid header"),
+            CodecError::Unsupported => write!(f, "unsupported format"),
+            CodecError::Truncated => write!(f, "truncated image data"),
+            CodecError::TooBig => write!(f, "image too big"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Check image dimensions, returning the number of pixels
+pub(crate) fn pixel_count(
+    width: u32,
+    height: u32,
+) -> Result<usize, CodecError> {
+    let w = i32::try_from(width).map_err(|_| CodecError::TooBig)?;
+    let h = i32::try_from(height).map_err(|_| CodecError::TooBig)?;
+    let n = w.checked_mul(h).ok_or(CodecError::TooBig)?;
+    Ok(n as usize)
+}
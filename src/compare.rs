@@ -0,0 +1,188 @@
+// compare.rs   Raster comparison metrics.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Comparison metrics between two rasters.
+//!
+//! Metrics are calculated separately for each channel, using channel values
+//! as stored (in the pixel's gamma mode), scaled to the range `0.0` to `1.0`.
+//! They are useful for codec regression testing and golden-image
+//! comparisons.
+//!
+//! * [mse]: mean squared error
+//! * [psnr]: peak signal-to-noise ratio, in decibels
+//! * [ssim]: structural similarity index
+//!
+//! ### Compare with a golden image
+//! ```
+//! use pix::compare;
+//! use pix::rgb::SRgb8;
+//! use pix::Raster;
+//!
+//! let golden = Raster::with_color(16, 16, SRgb8::new(0x40, 0x80, 0xC0));
+//! let mut r = golden.clone();
+//! *r.pixel_mut(4, 4) = SRgb8::new(0x40, 0x80, 0xC8);
+//! let psnr = compare::psnr(&golden, &r);
+//! assert!(psnr[0].is_infinite());
+//! assert!(psnr[2] > 50.0);
+//! ```
+//!
+//! [mse]: fn.mse.html
+//! [psnr]: fn.psnr.html
+//! [ssim]: fn.ssim.html
+use crate::chan::Channel;
+use crate::el::{channel_count, Pixel};
+use crate::gray::Gray32;
+use crate::raster::Raster;
+
+/// SSIM stabilizing constant for means, (0.01 * L) ^ 2
+const SSIM_C1: f32 = 0.01 * 0.01;
+
+/// SSIM stabilizing constant for variances, (0.03 * L) ^ 2
+const SSIM_C2: f32 = 0.03 * 0.03;
+
+/// SSIM Gaussian window standard deviation
+const SSIM_SIGMA: f32 = 1.5;
+
+/// Check that two rasters have the same dimensions
+fn check_size<P: Pixel>(a: &Raster<P>, b: &Raster<P>) {
+    assert_eq!(a.width(), b.width(), "Raster widths differ");
+    assert_eq!(a.height(), b.height(), "Raster heights differ");
+}
+
+/// Calculate the mean squared error of each channel.
+///
+/// * `a` First raster.
+/// * `b` Second raster.
+///
+/// # Panics
+///
+/// Panics if the rasters have different dimensions.
+pub fn mse<P: Pixel>(a: &Raster<P>, b: &Raster<P>) -> Vec<f64> {
+    check_size(a, b);
+    let mut sums = vec![0.0; channel_count::<P>()];
+    for (pa, pb) in a.pixels().iter().zip(b.pixels()) {
+        for (s, (ca, cb)) in
+            sums.iter_mut().zip(pa.channels().iter().zip(pb.channels()))
+        {
+            let d = f64::from(ca.to_f32()) - f64::from(cb.to_f32());
+            *s += d * d;
+        }
+    }
+    let len = a.pixels().len().max(1) as f64;
+    sums.into_iter().map(|s| s / len).collect()
+}
+
+/// Calculate the peak signal-to-noise ratio of each channel, in decibels.
+///
+/// * `a` First raster.
+/// * `b` Second raster.
+///
+/// Identical channels have a ratio of infinity.
+///
+/// # Panics
+///
+/// Panics if the rasters have different dimensions.
+pub fn psnr<P: Pixel>(a: &Raster<P>, b: &Raster<P>) -> Vec<f64> {
+    mse(a, b).into_iter().map(|m| -10.0 * m.log10()).collect()
+}
+
+/// Calculate the mean structural similarity index of each channel.
+///
+/// * `a` First raster.
+/// * `b` Second raster.
+///
+/// Local statistics are calculated using a Gaussian window with a standard
+/// deviation of 1.5 pixels.  Identical channels have an index of `1.0`.
+///
+/// # Panics
+///
+/// Panics if the rasters have different dimensions.
+///
+/// ### Example
+/// ```
+/// use pix::compare;
+/// use pix::gray::Gray8;
+/// use pix::Raster;
+///
+/// let mut a = Raster::<Gray8>::with_clear(32, 32);
+/// a.copy_color((8, 8, 16, 16), Gray8::new(0xFF));
+/// let mut b = a.clone();
+/// b.copy_color((8, 8, 16, 1), Gray8::new(0x80));
+/// let ssim = compare::ssim(&a, &b)[0];
+/// assert!(ssim > 0.9 && ssim < 1.0);
+/// ```
+pub fn ssim<P: Pixel>(a: &Raster<P>, b: &Raster<P>) -> Vec<f64> {
+    check_size(a, b);
+    let (width, height) = (a.width(), a.height());
+    let blur = |p: Vec<f32>| -> Vec<f32> {
+        let pixels: Vec<Gray32> = p.into_iter().map(Gray32::new).collect();
+        let r = Raster::with_pixels(width, height, pixels);
+        let r = r.gaussian_blur(SSIM_SIGMA);
+        r.pixels().iter().map(|p| p.one().to_f32()).collect()
+    };
+    (0..channel_count::<P>())
+        .map(|c| {
+            let x = plane(a, c);
+            let y = plane(b, c);
+            let xx: Vec<f32> = x.iter().map(|v| v * v).collect();
+            let yy: Vec<f32> = y.iter().map(|v| v * v).collect();
+            let xy: Vec<f32> = x.iter().zip(&y).map(|(u, v)| u * v).collect();
+            let (mx, my) = (blur(x), blur(y));
+            let (sxx, syy, sxy) = (blur(xx), blur(yy), blur(xy));
+            let mut total = 0.0;
+            for i in 0..mx.len() {
+                let (mx, my) = (mx[i], my[i]);
+                let vx = sxx[i] - mx * mx;
+                let vy = syy[i] - my * my;
+                let cov = sxy[i] - mx * my;
+                let num = (2.0 * mx * my + SSIM_C1) * (2.0 * cov + SSIM_C2);
+                let den = (mx * mx + my * my + SSIM_C1) * (vx + vy + SSIM_C2);
+                total += f64::from(num / den);
+            }
+            total / mx.len().max(1) as f64
+        })
+        .collect()
+}
+
+/// Get one channel of a raster as `f32` values
+fn plane<P: Pixel>(r: &Raster<P>, c: usize) -> Vec<f32> {
+    r.pixels()
+        .iter()
+        .map(|p| p.channels()[c].to_f32())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gray::*;
+    use crate::rgb::*;
+
+    #[test]
+    fn identical() {
+        let r = Raster::with_color(20, 10, Rgba16::new(1, 200, 3000, 40000));
+        assert_eq!(mse(&r, &r), [0.0; 4]);
+        assert!(psnr(&r, &r).iter().all(|v| v.is_infinite()));
+        for v in ssim(&r, &r) {
+            assert!((v - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn error() {
+        let a = Raster::with_color(4, 4, Gray32::new(0.5));
+        let b = Raster::with_color(4, 4, Gray32::new(0.25));
+        assert_eq!(mse(&a, &b), [0.0625]);
+        assert!((psnr(&a, &b)[0] - 12.0412).abs() < 1e-4);
+        assert!(ssim(&a, &b)[0] < 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_size() {
+        let a = Raster::<Gray8>::with_clear(4, 4);
+        let b = Raster::<Gray8>::with_clear(4, 5);
+        mse(&a, &b);
+    }
+}
@@ -0,0 +1,103 @@
+// contrast.rs  WCAG contrast ratio.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Contrast ratio between colors, as defined by [WCAG] 2.x.
+//!
+//! The ratio ranges from 1 (no contrast) to 21 (black on white).  It is
+//! calculated from [relative luminance], so colors can be in any format.
+//!
+//! ### Check text contrast
+//! ```
+//! use pix::contrast::{self, Level};
+//! use pix::rgb::SRgb8;
+//!
+//! let text = SRgb8::new(0x76, 0x76, 0x76);
+//! let background = SRgb8::new(0xFF, 0xFF, 0xFF);
+//! assert!(contrast::ratio(text, background) > 4.5);
+//! assert!(Level::Aa.check(text, background));
+//! assert!(!Level::Aaa.check(text, background));
+//! ```
+//!
+//! [relative luminance]: ../el/trait.Pixel.html#method.luminance
+//! [WCAG]: https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+use crate::el::{relative_luminance, Pixel};
+
+/// WCAG conformance level for contrast
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Level {
+    /// Level AA for normal text (4.5:1)
+    Aa,
+    /// Level AA for large text or graphics (3:1)
+    AaLarge,
+    /// Level AAA for normal text (7:1)
+    Aaa,
+    /// Level AAA for large text (4.5:1)
+    AaaLarge,
+}
+
+impl Level {
+    /// Get the minimum contrast ratio for the level
+    pub const fn min_ratio(self) -> f32 {
+        match self {
+            Level::Aa => 4.5,
+            Level::AaLarge => 3.0,
+            Level::Aaa => 7.0,
+            Level::AaaLarge => 4.5,
+        }
+    }
+
+    /// Check if two colors meet the level.
+    ///
+    /// * `fg` Foreground color.
+    /// * `bg` Background color.
+    pub fn check<F: Pixel, B: Pixel>(self, fg: F, bg: B) -> bool {
+        ratio(fg, bg) >= self.min_ratio()
+    }
+}
+
+/// Calculate the contrast ratio between two colors.
+///
+/// * `a` First color.
+/// * `b` Second color.
+///
+/// The order of colors does not matter.  *Alpha* is ignored, so translucent
+/// colors should be composited first.
+pub fn ratio<A: Pixel, B: Pixel>(a: A, b: B) -> f32 {
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gray::SGray16;
+    use crate::rgb::{Rgb32, SRgb8};
+
+    #[test]
+    fn ratios() {
+        let black = SRgb8::new(0, 0, 0);
+        let white = SRgb8::new(255, 255, 255);
+        assert!((ratio(black, white) - 21.0).abs() < 1e-4);
+        assert!((ratio(white, black) - 21.0).abs() < 1e-4);
+        assert_eq!(ratio(white, SGray16::new(0xFFFF)), 1.0);
+        // examples checked with the WebAIM contrast checker
+        let r = ratio(SRgb8::new(0x00, 0x00, 0xFF), white);
+        assert!((r - 8.59).abs() < 0.01, "{r}");
+        let r = ratio(SRgb8::new(0x77, 0x77, 0x77), white);
+        assert!((r - 4.48).abs() < 0.01, "{r}");
+        let r = ratio(Rgb32::new(0.5, 0.5, 0.5), black);
+        assert!((r - 11.0).abs() < 1e-4, "{r}");
+    }
+
+    #[test]
+    fn levels() {
+        let fg = SRgb8::new(0x77, 0x77, 0x77);
+        let bg = SRgb8::new(0xFF, 0xFF, 0xFF);
+        assert!(!Level::Aa.check(fg, bg));
+        assert!(Level::AaLarge.check(fg, bg));
+        assert!(!Level::AaaLarge.check(fg, bg));
+        assert!(Level::Aaa.check(SRgb8::new(0x59, 0x59, 0x59), bg));
+    }
+}
@@ -0,0 +1,167 @@
+// dither.rs    Ordered dithering.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Dithering methods, including ordered dithering with threshold matrices.
+//!
+//! An [OrderedDither] matrix is tiled over a raster, using absolute pixel
+//! coordinates.  Results are deterministic and seamless across tiles, so
+//! they are suitable for real-time and incremental rendering.
+//!
+//! Ordered dithering can be used for:
+//! * Bit-depth reduction, with [posterize_dithered]
+//! * Palette mapping, with [Palette::make_indexed_ordered]
+//!
+//! [OrderedDither]: struct.OrderedDither.html
+//! [Palette::make_indexed_ordered]: ../struct.Palette.html#method.make_indexed_ordered
+//! [posterize_dithered]: ../struct.Raster.html#method.posterize_dithered
+
+/// Dithering method for mapping colors to a palette.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Dither {
+    /// No dithering; each pixel is mapped to one entry
+    #[default]
+    None,
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+    /// Ordered dithering with a threshold matrix
+    Ordered(OrderedDither),
+}
+
+/// Threshold matrix for ordered dithering.
+///
+/// ### Reduce to 2 levels
+/// ```
+/// use pix::dither::OrderedDither;
+/// use pix::gray::SGray8;
+/// use pix::Raster;
+///
+/// let mut r = Raster::with_color(8, 8, SGray8::new(0x40));
+/// r.posterize_dithered((), 2, &OrderedDither::bayer(4));
+/// let white = r.pixels().iter().filter(|p| **p == SGray8::new(0xFF));
+/// assert_eq!(white.count(), 16);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderedDither {
+    /// Width of matrix
+    width: u32,
+    /// Height of matrix
+    height: u32,
+    /// Normalized thresholds, between `0.0` and `1.0` (exclusive)
+    thresholds: Box<[f32]>,
+}
+
+impl OrderedDither {
+    /// Create a dither matrix from threshold ranks.
+    ///
+    /// * `width` Width of matrix.
+    /// * `height` Height of matrix.
+    /// * `ranks` Threshold rank of each cell, in row-major order.  Ranks
+    ///   should be unique, from `0` to `width * height - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is empty, or the length of `ranks` is not
+    /// `width * height`.
+    pub fn new(width: u32, height: u32, ranks: &[u32]) -> Self {
+        let len = (width as usize) * (height as usize);
+        assert!(len > 0, "Empty dither matrix");
+        assert_eq!(ranks.len(), len, "Wrong number of ranks");
+        let thresholds = ranks
+            .iter()
+            .map(|r| (*r as f32 + 0.5) / len as f32)
+            .collect();
+        OrderedDither {
+            width,
+            height,
+            thresholds,
+        }
+    }
+
+    /// Create a Bayer dither matrix.
+    ///
+    /// * `size` Width and height of matrix; a power of two, such as 2, 4 or
+    ///   8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two between 2 and 256.
+    pub fn bayer(size: u32) -> Self {
+        assert!(
+            size.is_power_of_two() && (2..=256).contains(&size),
+            "Invalid Bayer matrix size"
+        );
+        let bits = size.trailing_zeros();
+        let mut ranks = Vec::with_capacity((size * size) as usize);
+        for y in 0..size {
+            for x in 0..size {
+                ranks.push(bayer_rank(x ^ y, y, bits));
+            }
+        }
+        Self::new(size, size, &ranks)
+    }
+
+    /// Get the width of the matrix.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the matrix.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the threshold at a pixel.
+    ///
+    /// * `x` X-coordinate of pixel.
+    /// * `y` Y-coordinate of pixel.
+    ///
+    /// The matrix is tiled, so any coordinates are valid.  Thresholds are
+    /// between `0.0` and `1.0` (exclusive), with an average of `0.5`.
+    pub fn threshold(&self, x: i32, y: i32) -> f32 {
+        let mx = x.rem_euclid(self.width as i32) as usize;
+        let my = y.rem_euclid(self.height as i32) as usize;
+        self.thresholds[my * self.width as usize + mx]
+    }
+}
+
+/// Get Bayer matrix rank by interleaving reversed bits
+fn bayer_rank(xy: u32, y: u32, bits: u32) -> u32 {
+    let mut rank = 0;
+    for b in 0..bits {
+        rank = (rank << 2) | (((xy >> b) & 1) << 1) | ((y >> b) & 1);
+    }
+    rank
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bayer() {
+        let d = OrderedDither::bayer(2);
+        let t: Vec<f32> = [(0, 0), (1, 0), (0, 1), (1, 1)]
+            .iter()
+            .map(|(x, y)| d.threshold(*x, *y) * 4.0 - 0.5)
+            .collect();
+        assert_eq!(t, [0.0, 2.0, 3.0, 1.0]);
+        let d = OrderedDither::bayer(4);
+        let t: Vec<f32> =
+            (0..4).map(|x| d.threshold(x, 1) * 16.0 - 0.5).collect();
+        assert_eq!(t, [12.0, 4.0, 14.0, 6.0]);
+        assert_eq!(d.threshold(-4, 9), d.threshold(0, 1));
+        let d = OrderedDither::bayer(8);
+        let mut ranks: Vec<u32> = (0..64)
+            .map(|i| (d.threshold(i % 8, i / 8) * 64.0) as u32)
+            .collect();
+        ranks.sort_unstable();
+        assert_eq!(ranks, (0..64).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bayer_size() {
+        OrderedDither::bayer(3);
+    }
+}
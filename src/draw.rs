@@ -0,0 +1,220 @@
+// draw.rs      Drawing lines and rectangles.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::{Linear, Premultiplied};
+use crate::el::Pixel;
+use crate::ops::Blend;
+use crate::raster::{Raster, Region};
+
+impl<P> Raster<P>
+where
+    P: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+{
+    /// Draw a horizontal line.
+    ///
+    /// * `x` Left end of line.
+    /// * `y` Row of line.
+    /// * `width` Length of line, in pixels.
+    /// * `clr` Line color.
+    /// * `op` Compositing operation.
+    ///
+    /// The line is clipped to the `Raster`.
+    pub fn draw_hline<O: Blend>(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        clr: P,
+        op: O,
+    ) {
+        self.composite_color((x, y, width, 1), clr, op);
+    }
+
+    /// Draw a vertical line.
+    ///
+    /// * `x` Column of line.
+    /// * `y` Top end of line.
+    /// * `height` Length of line, in pixels.
+    /// * `clr` Line color.
+    /// * `op` Compositing operation.
+    ///
+    /// The line is clipped to the `Raster`.
+    pub fn draw_vline<O: Blend>(
+        &mut self,
+        x: i32,
+        y: i32,
+        height: u32,
+        clr: P,
+        op: O,
+    ) {
+        self.composite_color((x, y, 1, height), clr, op);
+    }
+
+    /// Draw a line between two points, using Bresenham's algorithm.
+    ///
+    /// * `p0` Start point (*x*, *y*).
+    /// * `p1` End point (*x*, *y*), which is included in the line.
+    /// * `clr` Line color.
+    /// * `op` Compositing operation.
+    ///
+    /// The line is clipped to the `Raster`.  Each pixel is composited once,
+    /// so translucent lines have an even color.
+    ///
+    /// ### Draw a diagonal line
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Rgba8p>::with_clear(10, 10);
+    /// let clr = Rgba8p::new(0x80, 0x00, 0x00, 0x80);
+    /// r.draw_line((-2, -1), (7, 8), clr, SrcOver);
+    /// assert_eq!(r.pixel(3, 4), clr);
+    /// assert_eq!(r.pixel(4, 4), Rgba8p::new(0, 0, 0, 0));
+    /// ```
+    pub fn draw_line<O: Blend>(
+        &mut self,
+        p0: (i32, i32),
+        p1: (i32, i32),
+        clr: P,
+        op: O,
+    ) {
+        let (x0, y0) = (i64::from(p0.0), i64::from(p0.1));
+        let (dx, dy) = (i64::from(p1.0) - x0, i64::from(p1.1) - y0);
+        let (w, h) = (i64::from(self.width()), i64::from(self.height()));
+        // Step along the major axis, clipped to the raster, so that only
+        // visible points are visited
+        let x_major = dx.abs() >= dy.abs();
+        let (lo, hi) = if x_major {
+            (x0.min(x0 + dx).max(0), x0.max(x0 + dx).min(w - 1))
+        } else {
+            (y0.min(y0 + dy).max(0), y0.max(y0 + dy).min(h - 1))
+        };
+        for m in lo..=hi {
+            let (x, y) = if x_major {
+                (m, y0 + line_offset(m - x0, dx, dy))
+            } else {
+                (x0 + line_offset(m - y0, dy, dx), m)
+            };
+            if x >= 0 && x < w && y >= 0 && y < h {
+                let d = self.pixel_mut(x as i32, y as i32);
+                P::composite_color(std::slice::from_mut(d), &clr, op);
+            }
+        }
+    }
+
+    /// Draw the outline of a rectangle.
+    ///
+    /// * `reg` Rectangle.  It can be a `Region` struct, tuple of (*x*, *y*,
+    ///   *width*, *height*) or the unit type `()`.  Using `()` has the same
+    ///   result as `Raster::region()`.
+    /// * `clr` Outline color.
+    /// * `op` Compositing operation.
+    ///
+    /// The outline is one pixel wide, inside the rectangle.  To draw a filled
+    /// rectangle, use [composite_color].
+    ///
+    /// [composite_color]: #method.composite_color
+    ///
+    /// ### Draw a translucent outline
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Rgba8p>::with_clear(10, 10);
+    /// let clr = Rgba8p::new(0x00, 0x40, 0x00, 0x40);
+    /// r.draw_rect((2, 2, 5, 4), clr, SrcOver);
+    /// assert_eq!(r.pixel(2, 2), clr);
+    /// assert_eq!(r.pixel(6, 4), clr);
+    /// assert_eq!(r.pixel(4, 3), Rgba8p::new(0, 0, 0, 0));
+    /// ```
+    pub fn draw_rect<R, O>(&mut self, reg: R, clr: P, op: O)
+    where
+        R: Into<Region>,
+        O: Blend,
+    {
+        let mut reg = reg.into();
+        if reg == Region::from(()) {
+            reg = self.region();
+        }
+        let (w, h) = (reg.width(), reg.height());
+        if w == 0 || h == 0 {
+            return;
+        }
+        self.draw_hline(reg.left(), reg.top(), w, clr, op);
+        if h > 1 {
+            self.draw_hline(reg.left(), reg.bottom() - 1, w, clr, op);
+            // sides exclude corners, so that they are not composited twice
+            self.draw_vline(reg.left(), reg.top() + 1, h - 2, clr, op);
+            if w > 1 {
+                self.draw_vline(reg.right() - 1, reg.top() + 1, h - 2, clr, op);
+            }
+        }
+    }
+}
+
+/// Get the minor axis offset of a line at a major axis step.
+///
+/// * `k` Steps from the start point along the major axis.
+/// * `major` Line length along the major axis.
+/// * `minor` Line length along the minor axis.
+///
+/// Halfway points are rounded away from the start point.
+fn line_offset(k: i64, major: i64, minor: i64) -> i64 {
+    if major == 0 {
+        return 0;
+    }
+    let a = i128::from(k.abs()) * i128::from(minor.abs());
+    let d = i128::from(major.abs());
+    let off = ((2 * a + d) / (2 * d)) as i64;
+    off * minor.signum()
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::el::Pixel;
+    use crate::matte::*;
+    use crate::ops::*;
+    use crate::*;
+
+    #[test]
+    fn draw() {
+        let clr = Matte8::new(0x80);
+        let mut r = Raster::<Matte8>::with_clear(5, 4);
+        r.draw_line((4, 3), (0, 1), clr, SrcOver);
+        let v: Vec<u8> = r.pixels().iter().map(|p| u8::from(p.one())).collect();
+        assert_eq!(
+            v,
+            [
+                0, 0, 0, 0, 0,
+                0x80, 0x80, 0, 0, 0,
+                0, 0, 0x80, 0x80, 0,
+                0, 0, 0, 0, 0x80,
+            ]
+        );
+        let mut r = Raster::<Matte8>::with_clear(3, 3);
+        r.draw_line((i32::MIN, i32::MIN), (i32::MAX, i32::MAX), clr, SrcOver);
+        r.draw_line((i32::MAX, 1), (i32::MIN, 1), clr, SrcOver);
+        let v: Vec<u8> = r.pixels().iter().map(|p| u8::from(p.one())).collect();
+        assert_eq!(v, [0x80, 0, 0, 0x80, 0xBF, 0x80, 0, 0, 0x80]);
+        let mut r = Raster::<Matte8>::with_clear(4, 3);
+        r.draw_rect((), clr, SrcOver);
+        r.draw_vline(1, -5, 100, clr, SrcOver);
+        let v: Vec<u8> = r.pixels().iter().map(|p| u8::from(p.one())).collect();
+        assert_eq!(
+            v,
+            [
+                0x80, 0xBF, 0x80, 0x80,
+                0x80, 0x80, 0, 0x80,
+                0x80, 0xBF, 0x80, 0x80,
+            ]
+        );
+        r.draw_rect((1, 1, 1, 1), clr, Src);
+        r.draw_rect((2, 1, 0, 1), Matte8::new(0xFF), Src);
+        assert_eq!(r.pixel(1, 1), clr);
+        assert_eq!(r.pixel(2, 1), Matte8::new(0));
+    }
+}
@@ -0,0 +1,423 @@
+// dynraster.rs Runtime-typed rasters.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::{Ch16, Ch32, Ch8};
+use crate::el::Pixel;
+use crate::gray::{SGray16, SGray32, SGray8, SGraya16, SGraya32, SGraya8};
+use crate::raster::Raster;
+use crate::rgb::{SRgb16, SRgb32, SRgb8, SRgba16, SRgba32, SRgba8};
+use crate::ColorModel;
+use std::any::{Any, TypeId};
+
+/// Match on every variant of a dynamic enum, binding the inner value
+macro_rules! dispatch {
+    ($value:expr, $enum:ident, $v:ident => $body:expr) => {
+        match $value {
+            $enum::SGray8($v) => $body,
+            $enum::SGray16($v) => $body,
+            $enum::SGray32($v) => $body,
+            $enum::SGraya8($v) => $body,
+            $enum::SGraya16($v) => $body,
+            $enum::SGraya32($v) => $body,
+            $enum::SRgb8($v) => $body,
+            $enum::SRgb16($v) => $body,
+            $enum::SRgb32($v) => $body,
+            $enum::SRgba8($v) => $body,
+            $enum::SRgba16($v) => $body,
+            $enum::SRgba32($v) => $body,
+        }
+    };
+}
+
+/// Get the pixel format of a dynamic enum variant
+macro_rules! format_of {
+    ($value:expr, $enum:ident) => {
+        match $value {
+            $enum::SGray8(_) => PixelFormat::SGray8,
+            $enum::SGray16(_) => PixelFormat::SGray16,
+            $enum::SGray32(_) => PixelFormat::SGray32,
+            $enum::SGraya8(_) => PixelFormat::SGraya8,
+            $enum::SGraya16(_) => PixelFormat::SGraya16,
+            $enum::SGraya32(_) => PixelFormat::SGraya32,
+            $enum::SRgb8(_) => PixelFormat::SRgb8,
+            $enum::SRgb16(_) => PixelFormat::SRgb16,
+            $enum::SRgb32(_) => PixelFormat::SRgb32,
+            $enum::SRgba8(_) => PixelFormat::SRgba8,
+            $enum::SRgba16(_) => PixelFormat::SRgba16,
+            $enum::SRgba32(_) => PixelFormat::SRgba32,
+        }
+    };
+}
+
+/// Match on a pixel format, with a type alias for its pixel type
+macro_rules! with_format {
+    ($format:expr, $p:ident => $body:expr) => {
+        match $format {
+            PixelFormat::SGray8 => {
+                type $p = SGray8;
+                $body
+            }
+            PixelFormat::SGray16 => {
+                type $p = SGray16;
+                $body
+            }
+            PixelFormat::SGray32 => {
+                type $p = SGray32;
+                $body
+            }
+            PixelFormat::SGraya8 => {
+                type $p = SGraya8;
+                $body
+            }
+            PixelFormat::SGraya16 => {
+                type $p = SGraya16;
+                $body
+            }
+            PixelFormat::SGraya32 => {
+                type $p = SGraya32;
+                $body
+            }
+            PixelFormat::SRgb8 => {
+                type $p = SRgb8;
+                $body
+            }
+            PixelFormat::SRgb16 => {
+                type $p = SRgb16;
+                $body
+            }
+            PixelFormat::SRgb32 => {
+                type $p = SRgb32;
+                $body
+            }
+            PixelFormat::SRgba8 => {
+                type $p = SRgba8;
+                $body
+            }
+            PixelFormat::SRgba16 => {
+                type $p = SRgba16;
+                $body
+            }
+            PixelFormat::SRgba32 => {
+                type $p = SRgba32;
+                $body
+            }
+        }
+    };
+}
+
+/// Implement `From` for each variant of a dynamic enum
+macro_rules! impl_from {
+    ($enum:ident, $wrap:ident) => {
+        impl_from!($enum, $wrap, SGray8, SGray16, SGray32);
+        impl_from!($enum, $wrap, SGraya8, SGraya16, SGraya32);
+        impl_from!($enum, $wrap, SRgb8, SRgb16, SRgb32);
+        impl_from!($enum, $wrap, SRgba8, SRgba16, SRgba32);
+    };
+    ($enum:ident, $wrap:ident, $($p:ident),+) => {
+        $(
+            impl From<$wrap<$p>> for $enum {
+                fn from(v: $wrap<$p>) -> Self {
+                    $enum::$p(v)
+                }
+            }
+        )+
+    };
+}
+
+/// Identity wrapper, for implementing `From` on pixels
+type Bare<P> = P;
+
+/// Pixel format of a [DynRaster] or [DynPixel]
+///
+/// All formats have *sRGB* gamma and *straight* alpha, matching the pixels
+/// stored in most image files.
+///
+/// [DynPixel]: enum.DynPixel.html
+/// [DynRaster]: enum.DynRaster.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// [SGray8](gray/type.SGray8.html)
+    SGray8,
+    /// [SGray16](gray/type.SGray16.html)
+    SGray16,
+    /// [SGray32](gray/type.SGray32.html)
+    SGray32,
+    /// [SGraya8](gray/type.SGraya8.html)
+    SGraya8,
+    /// [SGraya16](gray/type.SGraya16.html)
+    SGraya16,
+    /// [SGraya32](gray/type.SGraya32.html)
+    SGraya32,
+    /// [SRgb8](rgb/type.SRgb8.html)
+    SRgb8,
+    /// [SRgb16](rgb/type.SRgb16.html)
+    SRgb16,
+    /// [SRgb32](rgb/type.SRgb32.html)
+    SRgb32,
+    /// [SRgba8](rgb/type.SRgba8.html)
+    SRgba8,
+    /// [SRgba16](rgb/type.SRgba16.html)
+    SRgba16,
+    /// [SRgba32](rgb/type.SRgba32.html)
+    SRgba32,
+}
+
+/// Raster with a pixel format chosen at runtime
+///
+/// This allows applications which load arbitrary image files to hold "some
+/// raster" without making all of their code generic.
+///
+/// ### Example
+/// ```
+/// use pix::rgb::SRgb8;
+/// use pix::{DynRaster, PixelFormat, Raster};
+///
+/// let r = Raster::with_color(4, 4, SRgb8::new(0xFF, 0x80, 0x00));
+/// let d = DynRaster::from(r);
+/// assert_eq!(d.format(), PixelFormat::SRgb8);
+/// let d = d.convert_to(PixelFormat::SGraya16);
+/// assert_eq!(d.format(), PixelFormat::SGraya16);
+/// assert_eq!(d.as_u8_slice().len(), 4 * 4 * 4);
+/// ```
+#[derive(Clone)]
+pub enum DynRaster {
+    /// `SGray8` raster
+    SGray8(Raster<SGray8>),
+    /// `SGray16` raster
+    SGray16(Raster<SGray16>),
+    /// `SGray32` raster
+    SGray32(Raster<SGray32>),
+    /// `SGraya8` raster
+    SGraya8(Raster<SGraya8>),
+    /// `SGraya16` raster
+    SGraya16(Raster<SGraya16>),
+    /// `SGraya32` raster
+    SGraya32(Raster<SGraya32>),
+    /// `SRgb8` raster
+    SRgb8(Raster<SRgb8>),
+    /// `SRgb16` raster
+    SRgb16(Raster<SRgb16>),
+    /// `SRgb32` raster
+    SRgb32(Raster<SRgb32>),
+    /// `SRgba8` raster
+    SRgba8(Raster<SRgba8>),
+    /// `SRgba16` raster
+    SRgba16(Raster<SRgba16>),
+    /// `SRgba32` raster
+    SRgba32(Raster<SRgba32>),
+}
+
+/// Pixel with a format chosen at runtime
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DynPixel {
+    /// `SGray8` pixel
+    SGray8(SGray8),
+    /// `SGray16` pixel
+    SGray16(SGray16),
+    /// `SGray32` pixel
+    SGray32(SGray32),
+    /// `SGraya8` pixel
+    SGraya8(SGraya8),
+    /// `SGraya16` pixel
+    SGraya16(SGraya16),
+    /// `SGraya32` pixel
+    SGraya32(SGraya32),
+    /// `SRgb8` pixel
+    SRgb8(SRgb8),
+    /// `SRgb16` pixel
+    SRgb16(SRgb16),
+    /// `SRgb32` pixel
+    SRgb32(SRgb32),
+    /// `SRgba8` pixel
+    SRgba8(SRgba8),
+    /// `SRgba16` pixel
+    SRgba16(SRgba16),
+    /// `SRgba32` pixel
+    SRgba32(SRgba32),
+}
+
+impl_from!(DynRaster, Raster);
+impl_from!(DynPixel, Bare);
+
+impl PixelFormat {
+    /// All pixel formats
+    pub const ALL: [Self; 12] = [
+        PixelFormat::SGray8,
+        PixelFormat::SGray16,
+        PixelFormat::SGray32,
+        PixelFormat::SGraya8,
+        PixelFormat::SGraya16,
+        PixelFormat::SGraya32,
+        PixelFormat::SRgb8,
+        PixelFormat::SRgb16,
+        PixelFormat::SRgb32,
+        PixelFormat::SRgba8,
+        PixelFormat::SRgba16,
+        PixelFormat::SRgba32,
+    ];
+
+    /// Get the format of a pixel type, if it has one
+    pub fn of<P: Pixel + 'static>() -> Option<Self> {
+        let id = TypeId::of::<P>();
+        Self::ALL
+            .into_iter()
+            .find(|f| with_format!(f, F => TypeId::of::<F>() == id))
+    }
+
+    /// Get the number of channels
+    pub fn channels(self) -> usize {
+        with_format!(self, P => P::default().channels().len())
+    }
+
+    /// Get the number of bytes per pixel
+    pub fn bytes_per_pixel(self) -> usize {
+        with_format!(self, P => std::mem::size_of::<P>())
+    }
+
+    /// Check if the format has an alpha channel
+    pub fn has_alpha(self) -> bool {
+        with_format!(self, P => <P as Pixel>::Model::ALPHA < self.channels())
+    }
+}
+
+impl DynRaster {
+    /// Construct a `DynRaster` with all pixels set to the default value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is greater than `std::i32::MAX`.
+    pub fn with_clear(format: PixelFormat, width: u32, height: u32) -> Self {
+        with_format!(format, P => Raster::<P>::with_clear(width, height).into())
+    }
+
+    /// Get the pixel format
+    pub fn format(&self) -> PixelFormat {
+        format_of!(self, DynRaster)
+    }
+
+    /// Get width of raster
+    pub fn width(&self) -> u32 {
+        dispatch!(self, DynRaster, r => r.width())
+    }
+
+    /// Get height of raster
+    pub fn height(&self) -> u32 {
+        dispatch!(self, DynRaster, r => r.height())
+    }
+
+    /// Get one pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the raster.
+    pub fn pixel(&self, x: i32, y: i32) -> DynPixel {
+        dispatch!(self, DynRaster, r => r.pixel(x, y).into())
+    }
+
+    /// Set one pixel, converting it to the raster's format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the raster.
+    pub fn set_pixel(&mut self, x: i32, y: i32, p: DynPixel) {
+        dispatch!(self, DynRaster, r => *r.pixel_mut(x, y) = p.convert())
+    }
+
+    /// Get view of pixels as a `u8` slice.
+    ///
+    /// Channels are in native-endian byte order.
+    pub fn as_u8_slice(&self) -> &[u8] {
+        dispatch!(self, DynRaster, r => r.as_u8_slice())
+    }
+
+    /// Get a reference to the raster, if it has pixel format `P`.
+    pub fn as_raster<P: Pixel + 'static>(&self) -> Option<&Raster<P>> {
+        dispatch!(self, DynRaster, r => (r as &dyn Any).downcast_ref())
+    }
+
+    /// Get a mutable reference to the raster, if it has pixel format `P`.
+    pub fn as_raster_mut<P: Pixel + 'static>(
+        &mut self,
+    ) -> Option<&mut Raster<P>> {
+        dispatch!(self, DynRaster, r => (r as &mut dyn Any).downcast_mut())
+    }
+
+    /// Convert to a raster with pixel format `P`.
+    pub fn to_raster<P>(&self) -> Raster<P>
+    where
+        P: Pixel,
+        P::Chan: From<Ch8> + From<Ch16> + From<Ch32>,
+    {
+        dispatch!(self, DynRaster, r => Raster::with_raster(r))
+    }
+
+    /// Convert to another pixel format.
+    pub fn convert_to(&self, format: PixelFormat) -> Self {
+        with_format!(format, P => self.to_raster::<P>().into())
+    }
+}
+
+impl DynPixel {
+    /// Get the pixel format
+    pub fn format(&self) -> PixelFormat {
+        format_of!(self, DynPixel)
+    }
+
+    /// Convert to pixel format `P`.
+    pub fn convert<P>(self) -> P
+    where
+        P: Pixel,
+        P::Chan: From<Ch8> + From<Ch16> + From<Ch32>,
+    {
+        dispatch!(self, DynPixel, p => p.convert())
+    }
+
+    /// Convert to another pixel format.
+    pub fn convert_to(self, format: PixelFormat) -> Self {
+        with_format!(format, P => self.convert::<P>().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgba8;
+
+    #[test]
+    fn formats() {
+        assert_eq!(PixelFormat::of::<SGraya16>(), Some(PixelFormat::SGraya16));
+        assert_eq!(PixelFormat::of::<Rgba8>(), None);
+        assert_eq!(PixelFormat::SRgb16.channels(), 3);
+        assert_eq!(PixelFormat::SRgba32.bytes_per_pixel(), 16);
+        assert!(PixelFormat::SGraya8.has_alpha());
+        assert!(!PixelFormat::SRgb8.has_alpha());
+        for f in PixelFormat::ALL {
+            let r = DynRaster::with_clear(f, 2, 3);
+            assert_eq!(r.format(), f);
+            assert_eq!((r.width(), r.height()), (2, 3));
+            assert_eq!(r.as_u8_slice().len(), 6 * f.bytes_per_pixel());
+        }
+    }
+
+    #[test]
+    fn convert() {
+        let mut r = DynRaster::with_clear(PixelFormat::SRgba8, 2, 2);
+        let p = DynPixel::from(SRgb16::new(0xFFFF, 0, 0x8080));
+        r.set_pixel(1, 0, p);
+        assert_eq!(
+            r.pixel(1, 0),
+            DynPixel::SRgba8(SRgba8::new(255, 0, 128, 255))
+        );
+        assert!(r.as_raster::<SRgb8>().is_none());
+        let raster = r.as_raster_mut::<SRgba8>().unwrap();
+        *raster.pixel_mut(0, 1) = SRgba8::new(1, 2, 3, 4);
+        let g = r.convert_to(PixelFormat::SGray16);
+        assert_eq!(g.format(), PixelFormat::SGray16);
+        let rgb: Raster<SRgb8> = r.to_raster();
+        assert_eq!(rgb.pixel(1, 0), SRgb8::new(255, 0, 128));
+        assert_eq!(rgb.pixel(0, 1), SRgb8::new(1, 2, 3));
+        let p = p.convert_to(PixelFormat::SGray8);
+        assert_eq!(p.format(), PixelFormat::SGray8);
+    }
+}
@@ -4,15 +4,134 @@
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
 //! Module for `pix::el` items
-use crate::chan::{Alpha, Channel, Gamma, Linear, Premultiplied};
+use crate::ansi::AnsiBlock;
+use crate::bgr::Bgr;
+use crate::chan::{
+    Alpha, Ch32, Ch8, Channel, Gamma, Linear, Premultiplied, Straight,
+};
+use crate::cmy::Cmy;
+use crate::gray::Gray;
+use crate::lab;
 use crate::matte::Matte;
-use crate::ops::Blend;
-use crate::private::Sealed;
-use crate::rgb::Rgb;
-use crate::ColorModel;
+use crate::oklab::{linear_rgb_to_oklab, oklab_to_linear_rgb, reduce_chroma};
+use crate::ops::{Adjust, Blend, ColorMatrix, Exposure};
+use crate::private::{MaybeSend, MaybeSync, Sealed};
+use crate::rgb::{Rgb, Rgba32, SRgba32};
+use crate::{ChannelLabel, ColorModel};
 use std::any::TypeId;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::marker::PhantomData;
+use std::ops::Range;
+
+// Include build-time unpremultiply look-up table
+include!(concat!(env!("OUT_DIR"), "/unpremultiply_lut.rs"));
+
+/// Error making a [Pixel] from channels or another pixel
+///
+/// [Pixel]: trait.Pixel.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelError {
+    /// Number of channels does not match pixel format
+    WrongChannelCount {
+        /// Expected number of channels
+        expected: usize,
+        /// Actual number of channels
+        actual: usize,
+    },
+    /// Color models do not match
+    ModelMismatch,
+    /// Pixel format has no *alpha* channel
+    NoAlpha,
+}
+
+impl fmt::Display for PixelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PixelError::WrongChannelCount { expected, actual } => {
+                write!(f, "{actual} channels, expected {expected}")
+            }
+            PixelError::ModelMismatch => write!(f, "color model mismatch"),
+            PixelError::NoAlpha => write!(f, "no alpha channel"),
+        }
+    }
+}
+
+impl std::error::Error for PixelError {}
+
+/// Method for mapping colors into the sRGB gamut.
+///
+/// Used by [convert_gamut].
+///
+/// [convert_gamut]: trait.Pixel.html#method.convert_gamut
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GamutMap {
+    /// Clamp each channel, which can shift hue and lightness
+    #[default]
+    Clip,
+    /// Reduce chroma in [Oklab], keeping lightness and hue
+    ///
+    /// [Oklab]: ../oklab/struct.Oklab.html
+    Chroma,
+}
+
+/// Color space used to mix pixels.
+///
+/// Used by [lighten] and [darken].
+///
+/// [darken]: trait.Pixel.html#method.darken
+/// [lighten]: trait.Pixel.html#method.lighten
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MixSpace {
+    /// *sRGB* gamma encoded, matching CSS `color-mix` in `srgb`
+    #[default]
+    Srgb,
+    /// Linear light, for physically correct mixing
+    Linear,
+    /// [Oklab], for perceptually even steps
+    ///
+    /// [Oklab]: ../oklab/struct.Oklab.html
+    Oklab,
+}
+
+/// Iterator over channels of a pixel, with semantic labels
+///
+/// This struct is created by the [labeled_channels] method of [Pixel].
+///
+/// [labeled_channels]: trait.Pixel.html#method.labeled_channels
+/// [Pixel]: trait.Pixel.html
+#[derive(Clone, Debug)]
+pub struct LabeledChannels<P: Pixel> {
+    /// Pixel to iterate
+    pixel: P,
+    /// Index of next channel
+    index: usize,
+}
+
+impl<P: Pixel> Iterator for LabeledChannels<P> {
+    type Item = (ChannelLabel, P::Chan);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.index;
+        let c = *self.pixel.channels().get(i)?;
+        self.index += 1;
+        let label = if i == <P as Pixel>::Model::ALPHA {
+            ChannelLabel::Alpha
+        } else {
+            <P as Pixel>::Model::LABELS
+                .get(i)
+                .copied()
+                .unwrap_or(ChannelLabel::Unknown)
+        };
+        Some((label, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.pixel.channels().len() - self.index;
+        (n, Some(n))
+    }
+}
+
+impl<P: Pixel> ExactSizeIterator for LabeledChannels<P> {}
 
 /// Pixel [channel], [color model], [alpha] and [gamma] mode.
 ///
@@ -28,7 +147,7 @@ use std::marker::PhantomData;
 ///
 /// * _Gamma_: `S` for [sRGB] gamma encoding; [linear] if omitted.
 /// * _Color model_: [`Rgb`] / [`Bgr`] / [`Gray`] / [`Cmy`] / [`Hsv`] /
-///                  [`Hsl`] / [`Hwb`] / [`YCbCr`] / [`Matte`].
+///   [`Hsl`] / [`Hwb`] / [`YCbCr`] / [`Matte`].
 /// * _Alpha_: `a` to include alpha channel enabling translucent pixels.
 /// * _Bit depth_: `8` / `16` / `32` for 8-bit integer, 16-bit integer and
 ///   32-bit floating-point [channels].
@@ -50,7 +169,12 @@ use std::marker::PhantomData;
 /// [`YCbCr`]: ../ycc/struct.YCbCr.html
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
-pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
+/// Custom [color model]s can be used with the [Pix] type.
+///
+/// [pix]: struct.Pix.html
+pub trait Pixel:
+    Clone + Copy + Debug + Default + PartialEq + MaybeSend + MaybeSync + Sealed
+{
     /// Channel type
     type Chan: Channel;
 
@@ -63,21 +187,182 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
     /// Gamma mode
     type Gamma: Gamma;
 
+    /// Array of channels, such as `[Self::Chan; 4]`
+    type Array: Copy
+        + Debug
+        + PartialEq
+        + AsRef<[Self::Chan]>
+        + AsMut<[Self::Chan]>;
+
+    /// Array of `f32` values, one per channel, such as `[f32; 4]`
+    type F32Array: Copy
+        + Debug
+        + PartialEq
+        + MaybeSend
+        + MaybeSync
+        + AsRef<[f32]>
+        + AsMut<[f32]>;
+
     /// Make a pixel from a slice of channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ch` has fewer channels than the pixel.  See
+    /// [try_from_channels](#method.try_from_channels) for a fallible
+    /// version.
     fn from_channels(ch: &[Self::Chan]) -> Self;
 
+    /// Make a pixel from a slice of channels, checking the count.
+    ///
+    /// * `ch` Channels; must have the same length as the pixel.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::el::{Pixel, PixelError};
+    /// use pix::rgb::Rgb8;
+    ///
+    /// let ch = [Ch8::new(1), Ch8::new(2)];
+    /// assert_eq!(
+    ///     Rgb8::try_from_channels(&ch),
+    ///     Err(PixelError::WrongChannelCount { expected: 3, actual: 2 }),
+    /// );
+    /// ```
+    fn try_from_channels(ch: &[Self::Chan]) -> Result<Self, PixelError> {
+        let expected = Self::default().channels().len();
+        if ch.len() == expected {
+            Ok(Self::from_channels(ch))
+        } else {
+            Err(PixelError::WrongChannelCount {
+                expected,
+                actual: ch.len(),
+            })
+        }
+    }
+
     /// Convert from a pixel with a different bit depth.
+    ///
+    /// Both pixels must have the same color model; otherwise the result is
+    /// unspecified (and panics in debug builds).  See
+    /// [try_from_bit_depth](#method.try_from_bit_depth) for a fallible
+    /// version.
     fn from_bit_depth<P>(p: P) -> Self
     where
         P: Pixel,
         Self::Chan: From<P::Chan>;
 
+    /// Convert from a pixel with a different bit depth, checking that the
+    /// color models match.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::{Pixel, PixelError};
+    /// use pix::gray::Gray16;
+    /// use pix::rgb::{Rgb16, Rgb8};
+    ///
+    /// let p = Rgb8::new(0x10, 0x20, 0x30);
+    /// let p16 = Rgb16::try_from_bit_depth(p).unwrap();
+    /// assert_eq!(p16, Rgb16::new(0x1010, 0x2020, 0x3030));
+    /// assert_eq!(
+    ///     Gray16::try_from_bit_depth(p),
+    ///     Err(PixelError::ModelMismatch),
+    /// );
+    /// ```
+    fn try_from_bit_depth<P>(p: P) -> Result<Self, PixelError>
+    where
+        P: Pixel,
+        Self::Chan: From<P::Chan>,
+    {
+        if TypeId::of::<Self::Model>() == TypeId::of::<P::Model>() {
+            Ok(Self::from_bit_depth(p))
+        } else {
+            Err(PixelError::ModelMismatch)
+        }
+    }
+
+    /// Convert from a pixel with a different bit depth, rounding to nearest.
+    ///
+    /// Unlike [from_bit_depth](#method.from_bit_depth), this does not need
+    /// a `From` bound between channel types, and rounds instead of
+    /// truncating when reducing bit depth.  Both pixels must have the same
+    /// color model (this panics in debug builds).
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgb16, Rgb8};
+    ///
+    /// let p = Rgb16::new(0x00FF, 0x8000, 0xFFFF);
+    /// assert_eq!(Rgb8::from_bit_depth(p), Rgb8::new(0x00, 0x80, 0xFF));
+    /// assert_eq!(Rgb8::from_bit_depth_lossy(p), Rgb8::new(0x01, 0x80, 0xFF));
+    /// ```
+    fn from_bit_depth_lossy<P>(p: P) -> Self
+    where
+        P: Pixel,
+    {
+        debug_assert_eq!(TypeId::of::<Self::Model>(), TypeId::of::<P::Model>());
+        let ch = p.channels();
+        let mut dst = Self::default();
+        for (i, d) in dst.channels_mut().iter_mut().enumerate() {
+            *d = ch.get(i).map_or(Self::Chan::MAX, |c| c.to_f32().into());
+        }
+        dst
+    }
+
     /// Get the channels.
     fn channels(&self) -> &[Self::Chan];
 
     /// Get the channels mutably.
     fn channels_mut(&mut self) -> &mut [Self::Chan];
 
+    /// Iterate over channels, with semantic labels.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::el::Pixel;
+    /// use pix::hsv::Hsva8;
+    /// use pix::ChannelLabel;
+    ///
+    /// let p = Hsva8::new(10, 20, 30, 40);
+    /// let mut ch = p.labeled_channels();
+    /// assert_eq!(ch.next(), Some((ChannelLabel::Hue, Ch8::new(10))));
+    /// for (label, c) in ch {
+    ///     println!("{label}: {c:?}");
+    /// }
+    /// ```
+    fn labeled_channels(&self) -> LabeledChannels<Self> {
+        LabeledChannels {
+            pixel: *self,
+            index: 0,
+        }
+    }
+
+    /// Convert into an array of channels.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::chan::Ch8;
+    /// use pix::rgb::Rgba8;
+    ///
+    /// let p = Rgba8::new(1, 2, 3, 4);
+    /// assert_eq!(p.into_array(), [1, 2, 3, 4].map(Ch8::new));
+    /// ```
+    fn into_array(self) -> Self::Array;
+
+    /// Convert into an array of `f32` channel values.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgba8;
+    ///
+    /// let p = Rgba8::new(0, 51, 102, 255);
+    /// assert_eq!(p.into_f32_array(), [0.0, 0.2, 0.4, 1.0]);
+    /// ```
+    fn into_f32_array(self) -> Self::F32Array;
+
     /// Get the first channel.
     fn one(self) -> Self::Chan {
         *self.channels().first().unwrap_or(&Self::Chan::MAX)
@@ -155,6 +440,28 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
         chan.get_mut(Self::Model::ALPHA).unwrap()
     }
 
+    /// Make a copy with a new *alpha* channel.
+    ///
+    /// Pixels without an *alpha* channel are returned unchanged.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgb8, Rgba8};
+    ///
+    /// let p = Rgba8::new(1, 2, 3, 4).with_alpha(Ch8::new(0x80));
+    /// assert_eq!(p, Rgba8::new(1, 2, 3, 0x80));
+    /// let p = Rgb8::new(1, 2, 3).with_alpha(Ch8::new(0x80));
+    /// assert_eq!(p, Rgb8::new(1, 2, 3));
+    /// ```
+    fn with_alpha(mut self, alpha: Self::Chan) -> Self {
+        if let Some(a) = self.channels_mut().get_mut(Self::Model::ALPHA) {
+            *a = alpha;
+        }
+        self
+    }
+
     /// Convert a pixel to another format
     ///
     /// * `D` Destination format.
@@ -165,11 +472,316 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
     {
         if TypeId::of::<Self::Model>() == TypeId::of::<D::Model>() {
             convert_same_model::<D, Self>(self)
+        } else if is_rgb_bgr_swap::<D, Self>() {
+            swap_red_blue::<D, Self>(self)
         } else {
             convert_thru_rgba::<D, Self>(self)
         }
     }
 
+    /// Convert a pixel to any other format, rounding to nearest.
+    ///
+    /// * `D` Destination format.
+    ///
+    /// Unlike [convert](#method.convert), this does not need a `From` bound
+    /// between channel types, so it can be used in generic code for any
+    /// pair of formats.  When the color model, *alpha* or *gamma* mode
+    /// changes, the conversion is done with 32-bit float channels.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgb32, SRgb8};
+    ///
+    /// fn to_srgb8<P: Pixel>(p: P) -> SRgb8 {
+    ///     p.convert_lossy()
+    /// }
+    ///
+    /// let p = Rgb32::new(0.001, 0.5, 1.0);
+    /// assert_eq!(to_srgb8(p), SRgb8::new(3, 188, 255));
+    /// ```
+    fn convert_lossy<D>(self) -> D
+    where
+        D: Pixel,
+    {
+        if TypeId::of::<Self::Model>() == TypeId::of::<D::Model>()
+            && TypeId::of::<Self::Alpha>() == TypeId::of::<D::Alpha>()
+            && TypeId::of::<Self::Gamma>() == TypeId::of::<D::Gamma>()
+        {
+            D::from_bit_depth_lossy(self)
+        } else {
+            convert_thru_rgba32::<D, Self>(self)
+        }
+    }
+
+    /// Convert a pixel to another format, mapping it into the sRGB gamut.
+    ///
+    /// * `map` Gamut mapping method.
+    ///
+    /// Colors from [Oklab] or [Xyz] can be outside the sRGB gamut.  A normal
+    /// conversion clamps each channel, the same as [GamutMap::Clip].  With
+    /// [GamutMap::Chroma], saturated colors are desaturated instead.
+    ///
+    /// The conversion is done with 32-bit *linear* values, so it is lossy for
+    /// 8- and 16-bit formats, like [convert_lossy].
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::{GamutMap, Pixel};
+    /// use pix::oklab::Oklab32;
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let p = Oklab32::new(0.7, 0.0, 0.25);
+    /// let clip: SRgb8 = p.convert_gamut(GamutMap::Clip);
+    /// let chroma: SRgb8 = p.convert_gamut(GamutMap::Chroma);
+    /// assert_eq!(clip, SRgb8::new(0xDA, 0x8F, 0x00));
+    /// assert_eq!(chroma, SRgb8::new(0xC1, 0x99, 0x00));
+    /// ```
+    ///
+    /// [convert_lossy]: #method.convert_lossy
+    /// [GamutMap::Chroma]: enum.GamutMap.html#variant.Chroma
+    /// [GamutMap::Clip]: enum.GamutMap.html#variant.Clip
+    /// [Oklab]: ../oklab/struct.Oklab.html
+    /// [Xyz]: ../xyz/struct.Xyz.html
+    fn convert_gamut<D>(self, map: GamutMap) -> D
+    where
+        D: Pixel,
+    {
+        let rgb = Self::Model::into_linear_rgb(self);
+        let [r, g, b] = match map {
+            GamutMap::Clip => rgb.map(|c| c.clamp(0.0, 1.0)),
+            GamutMap::Chroma => reduce_chroma(rgb),
+        };
+        Rgba32::new(r, g, b, self.alpha().to_f32()).convert_lossy()
+    }
+
+    /// Calculate relative luminance.
+    ///
+    /// Luminance is calculated in linear light using Rec. 709 coefficients,
+    /// regardless of the pixel's color model or gamma mode.  *Alpha* is
+    /// ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::el::Pixel;
+    /// use pix::hsv::SHsv8;
+    /// use pix::rgb::{Rgb32, SRgb8};
+    ///
+    /// assert_eq!(SRgb8::new(0xFF, 0xFF, 0xFF).luminance(), Ch8::new(0xFF));
+    /// assert_eq!(SRgb8::new(0x80, 0x80, 0x80).luminance(), Ch8::new(0x37));
+    /// assert_eq!(SHsv8::new(0x00, 0xFF, 0xFF).luminance(), Ch8::new(0x36));
+    /// let y = Rgb32::new(0.0, 1.0, 0.0).luminance();
+    /// assert!((f32::from(y) - 0.7152).abs() < 1e-6);
+    /// ```
+    fn luminance(self) -> Self::Chan {
+        Self::Chan::from(relative_luminance(self))
+    }
+
+    /// Calculate the CIE76 color difference to another pixel.
+    ///
+    /// * `rhs` Pixel to compare, in any format.
+    ///
+    /// This is the Euclidean distance between colors in CIE *L\*a\*b\**
+    /// (D65 white point).  A difference of about 2.3 is a "just noticeable
+    /// difference".  *Alpha* is ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgb16, SRgb8};
+    ///
+    /// let p = SRgb8::new(0xFF, 0x00, 0x00);
+    /// assert!(p.delta_e_76(p.convert::<Rgb16>()) < 0.01);
+    /// assert!(p.delta_e_76(SRgb8::new(0xFF, 0x40, 0x00)) > 2.3);
+    /// ```
+    fn delta_e_76<P: Pixel>(self, rhs: P) -> f32 {
+        lab::delta_e_76(lab::lab(self), lab::lab(rhs))
+    }
+
+    /// Calculate the CIE94 color difference to another pixel.
+    ///
+    /// * `rhs` Pixel to compare, in any format.
+    ///
+    /// This uses the graphic arts weights, with `self` as the reference
+    /// color, so it is not symmetric.  *Alpha* is ignored.
+    fn delta_e_94<P: Pixel>(self, rhs: P) -> f32 {
+        lab::delta_e_94(lab::lab(self), lab::lab(rhs))
+    }
+
+    /// Calculate the CIEDE2000 color difference to another pixel.
+    ///
+    /// * `rhs` Pixel to compare, in any format.
+    ///
+    /// This is the most perceptually uniform of the *Delta E* formulas.
+    /// *Alpha* is ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let blue0 = SRgb8::new(0x00, 0x00, 0xFF);
+    /// let blue1 = SRgb8::new(0x20, 0x20, 0xFF);
+    /// let green = SRgb8::new(0x00, 0xFF, 0x00);
+    /// assert!(blue0.delta_e_2000(blue1) < blue0.delta_e_2000(green));
+    /// ```
+    fn delta_e_2000<P: Pixel>(self, rhs: P) -> f32 {
+        lab::delta_e_2000(lab::lab(self), lab::lab(rhs))
+    }
+
+    /// Get a `Display` wrapper showing the pixel in a terminal.
+    ///
+    /// The pixel is converted to *sRGB* and shown as a block of background
+    /// color, using ANSI 24-bit escape sequences.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let p = SRgb8::new(0xFF, 0x80, 0x00);
+    /// println!("{} orange", p.ansi_block());
+    /// ```
+    fn ansi_block(self) -> AnsiBlock
+    where
+        Ch8: From<Self::Chan>,
+    {
+        AnsiBlock::new(self)
+    }
+
+    /// Linearly interpolate between two pixels.
+    ///
+    /// * `rhs` Pixel to interpolate toward.
+    /// * `t` Interpolation amount, from `MIN` (`self`) to `MAX` (`rhs`).
+    ///
+    /// Circular channels (*hue*) are interpolated the shortest way around the
+    /// color wheel.  For *straight* alpha formats, the color channels are
+    /// weighted by *alpha*, so transparent pixels do not bleed color.
+    /// Channels are interpolated with the pixel's own gamma encoding.
+    ///
+    /// # Example: Lerp
+    /// ```
+    /// use pix::chan::Ch32;
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgba32p;
+    ///
+    /// let p0 = Rgba32p::new(0.0, 0.25, 0.5, 0.5);
+    /// let p1 = Rgba32p::new(0.5, 0.25, 0.0, 1.0);
+    /// let p = p0.lerp(p1, Ch32::new(0.5));
+    /// assert_eq!(p, Rgba32p::new(0.25, 0.25, 0.25, 0.75));
+    /// ```
+    fn lerp(self, rhs: Self, t: Self::Chan) -> Self {
+        let mut dst = self;
+        let alpha = self.alpha().lerp(rhs.alpha(), t);
+        let weighted = TypeId::of::<Self::Alpha>() == TypeId::of::<Straight>()
+            && Self::Model::ALPHA < self.channels().len();
+        // circular channels
+        let d_chan = &mut dst.channels_mut()[Self::Model::CIRCULAR];
+        let s_chan = &rhs.channels()[Self::Model::CIRCULAR];
+        d_chan
+            .iter_mut()
+            .zip(s_chan)
+            .for_each(|(d, s)| *d = circ_lerp(*d, *s, t));
+        // linear channels
+        let d_chan = &mut dst.channels_mut()[Self::Model::LINEAR];
+        let s_chan = &rhs.channels()[Self::Model::LINEAR];
+        for (d, s) in d_chan.iter_mut().zip(s_chan) {
+            *d = if weighted {
+                let d0 = Premultiplied::encode(*d, self.alpha());
+                let s0 = Premultiplied::encode(*s, rhs.alpha());
+                Premultiplied::decode(d0.lerp(s0, t), alpha)
+            } else {
+                d.lerp(*s, t)
+            };
+        }
+        if let Some(a) = dst.channels_mut().get_mut(Self::Model::ALPHA) {
+            *a = alpha;
+        }
+        dst
+    }
+
+    /// Map each channel with a function.
+    ///
+    /// * `f` Function to apply to each channel, including *alpha*.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgba8;
+    ///
+    /// let p = Rgba8::new(10, 200, 30, 255).map(|c| c.min(100.into()));
+    /// assert_eq!(p, Rgba8::new(10, 100, 30, 100));
+    /// ```
+    fn map<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Self::Chan) -> Self::Chan,
+    {
+        self.channels_mut().iter_mut().for_each(|c| *c = f(*c));
+        self
+    }
+
+    /// Map each linear channel with a function.
+    ///
+    /// * `f` Function to apply to each channel.
+    ///
+    /// Circular channels (*hue*) and *alpha* are not changed.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch32;
+    /// use pix::el::Pixel;
+    /// use pix::hsv::Hsva32;
+    ///
+    /// let p = Hsva32::new(0.5, 0.25, 0.5, 0.5);
+    /// let p = p.map_linear(|c| Ch32::new(f32::from(c) * 2.0));
+    /// assert_eq!(p, Hsva32::new(0.5, 0.5, 1.0, 0.5));
+    /// ```
+    fn map_linear<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Self::Chan) -> Self::Chan,
+    {
+        self.channels_mut()[Self::Model::LINEAR]
+            .iter_mut()
+            .for_each(|c| *c = f(*c));
+        self
+    }
+
+    /// Check if two pixels are approximately equal.
+    ///
+    /// * `other` Pixel to compare.
+    /// * `epsilon` Maximum difference of each channel, from 0.0 to 1.0.
+    ///
+    /// Channels are compared as stored, scaled to the range 0.0 to 1.0.
+    /// Circular channels (*hue*) are compared the shortest way around the
+    /// color wheel.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgb32;
+    ///
+    /// let p = Rgb32::new(0.3001, 0.5, 0.5);
+    /// assert!(p.approx_eq(Rgb32::new(0.3, 0.5, 0.5), 1e-3));
+    /// assert!(!p.approx_eq(Rgb32::new(0.3, 0.5, 0.5), 1e-6));
+    /// ```
+    fn approx_eq(self, other: Self, epsilon: f32) -> bool {
+        let circular = Self::Model::CIRCULAR;
+        self.channels()
+            .iter()
+            .zip(other.channels())
+            .enumerate()
+            .all(|(i, (a, b))| {
+                let d = (a.to_f32() - b.to_f32()).abs();
+                let d = if circular.contains(&i) {
+                    d.min(1.0 - d)
+                } else {
+                    d
+                };
+                d <= epsilon
+            })
+    }
+
     /// Copy a color to a pixel slice
     fn copy_color(dst: &mut [Self], clr: &Self) {
         for d in dst.iter_mut() {
@@ -184,6 +796,136 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
         }
     }
 
+    /// Reorder the channels of a slice of pixels.
+    ///
+    /// * `dst` Destination slice.
+    /// * `src` Source slice.
+    /// * `order` Index of source channel for each destination channel.
+    ///
+    /// Channels are copied as stored, without any color model, *alpha* or
+    /// gamma conversion.  8-bit pixels are shuffled as bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `order` is not the number of channels in `D`,
+    /// or if any index is not a channel in `Self`.
+    ///
+    /// # Example: Swizzle RGBA to BGRA
+    /// ```
+    /// use pix::bgr::Bgra8;
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgba8;
+    ///
+    /// let src = [Rgba8::new(0x10, 0x20, 0x30, 0x40); 4];
+    /// let mut dst = [Bgra8::default(); 4];
+    /// Rgba8::swizzle_slice(&mut dst, &src, &[2, 1, 0, 3]);
+    /// assert_eq!(dst[0], Bgra8::new(0x30, 0x20, 0x10, 0x40));
+    /// ```
+    fn swizzle_slice<D>(dst: &mut [D], src: &[Self], order: &[usize])
+    where
+        D: Pixel<Chan = Self::Chan>,
+    {
+        let count = Self::default().channels().len();
+        assert_eq!(order.len(), D::default().channels().len());
+        assert!(order.iter().all(|i| *i < count), "Invalid channel index");
+        if TypeId::of::<Self::Chan>() == TypeId::of::<Ch8>() {
+            let (dst, src) = as_u8_slices(dst, src);
+            let n = std::mem::size_of::<D>();
+            let m = std::mem::size_of::<Self>();
+            // 8-bit pixels have no padding, so each byte is one channel
+            assert!(n == order.len() && m == count);
+            assert!(dst.len() % n == 0 && src.len() % m == 0);
+            for (d, s) in dst.chunks_exact_mut(n).zip(src.chunks_exact(m)) {
+                for (c, i) in d.iter_mut().zip(order) {
+                    *c = s[*i];
+                }
+            }
+        } else {
+            for (d, s) in dst.iter_mut().zip(src) {
+                let chan = s.channels();
+                for (c, i) in d.channels_mut().iter_mut().zip(order) {
+                    *c = chan[*i];
+                }
+            }
+        }
+    }
+
+    /// Premultiply a slice of *straight* alpha pixels.
+    ///
+    /// * `dst` Destination slice of *premultiplied* pixels.
+    /// * `src` Source slice of *straight* pixels.
+    ///
+    /// 8-bit *linear* pixels are converted using fast integer math, with
+    /// correctly rounded results.
+    ///
+    /// # Example: Premultiply
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgba8, Rgba8p};
+    ///
+    /// let src = [Rgba8::new(0xFF, 0x80, 0x00, 0x80); 4];
+    /// let mut dst = [Rgba8p::default(); 4];
+    /// Rgba8::premultiply_slice(&mut dst, &src);
+    /// assert_eq!(dst[0], Rgba8p::new(0x80, 0x40, 0x00, 0x80));
+    /// ```
+    fn premultiply_slice<D>(dst: &mut [D], src: &[Self])
+    where
+        Self: Pixel<Alpha = Straight>,
+        D: Pixel<
+            Chan = Self::Chan,
+            Model = Self::Model,
+            Alpha = Premultiplied,
+            Gamma = Self::Gamma,
+        >,
+    {
+        if is_linear_u8::<D, Self>() {
+            let (dst, src) = as_u8_slices(dst, src);
+            alpha_u8::<Self>(dst, src, premultiply_u8);
+        } else {
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d = s.convert();
+            }
+        }
+    }
+
+    /// Unpremultiply a slice of *premultiplied* alpha pixels.
+    ///
+    /// * `dst` Destination slice of *straight* pixels.
+    /// * `src` Source slice of *premultiplied* pixels.
+    ///
+    /// 8-bit *linear* pixels are converted using a division look-up table,
+    /// with correctly rounded results.
+    ///
+    /// # Example: Unpremultiply
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgba8, Rgba8p};
+    ///
+    /// let src = [Rgba8p::new(0x80, 0x40, 0x00, 0x80); 4];
+    /// let mut dst = [Rgba8::default(); 4];
+    /// Rgba8p::unpremultiply_slice(&mut dst, &src);
+    /// assert_eq!(dst[0], Rgba8::new(0xFF, 0x80, 0x00, 0x80));
+    /// ```
+    fn unpremultiply_slice<D>(dst: &mut [D], src: &[Self])
+    where
+        Self: Pixel<Alpha = Premultiplied>,
+        D: Pixel<
+            Chan = Self::Chan,
+            Model = Self::Model,
+            Alpha = Straight,
+            Gamma = Self::Gamma,
+        >,
+    {
+        if is_linear_u8::<D, Self>() {
+            let (dst, src) = as_u8_slices(dst, src);
+            alpha_u8::<Self>(dst, src, unpremultiply_u8);
+        } else {
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d = s.convert();
+            }
+        }
+    }
+
     /// Composite a color with a pixel slice
     fn composite_color<O>(dst: &mut [Self], clr: &Self, op: O)
     where
@@ -218,6 +960,369 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
         }
     }
 
+    /// Composite a color with a pixel slice at a constant opacity.
+    ///
+    /// * `dst` Destination slice.
+    /// * `clr` Source color.
+    /// * `op` Compositing operation.
+    /// * `opacity` Multiplier for source *alpha*.
+    fn composite_color_opacity<O>(
+        dst: &mut [Self],
+        clr: &Self,
+        op: O,
+        opacity: Self::Chan,
+    ) where
+        Self: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+        O: Blend,
+    {
+        for d in dst.iter_mut() {
+            d.composite_channels_opacity(clr, op, &opacity);
+        }
+    }
+
+    /// Composite two slices of pixels at a constant opacity.
+    ///
+    /// * `dst` Destination slice.
+    /// * `src` Source slice.
+    /// * `op` Compositing operation.
+    /// * `opacity` Multiplier for source *alpha*.
+    ///
+    /// # Example: 50% opacity
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    ///
+    /// let mut dst = [Rgba8p::new(0x00, 0x00, 0xFF, 0xFF)];
+    /// let src = [Rgba8p::new(0xFF, 0x00, 0x00, 0xFF)];
+    /// Rgba8p::composite_slice_opacity(&mut dst, &src, SrcOver, 0x80.into());
+    /// assert_eq!(dst[0], Rgba8p::new(0x80, 0x00, 0x7F, 0xFF));
+    /// ```
+    fn composite_slice_opacity<O>(
+        dst: &mut [Self],
+        src: &[Self],
+        op: O,
+        opacity: Self::Chan,
+    ) where
+        Self: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+        O: Blend,
+    {
+        for (d, s) in dst.iter_mut().zip(src) {
+            d.composite_channels_opacity(s, op, &opacity);
+        }
+    }
+
+    /// Convert a slice of pixels and composite onto a destination slice.
+    ///
+    /// This is equivalent to converting `src` into a temporary slice and then
+    /// calling [composite_slice], but done in a single pass.
+    ///
+    /// [composite_slice]: #method.composite_slice
+    fn composite_slice_convert<S, O>(dst: &mut [Self], src: &[S], op: O)
+    where
+        Self: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+        S: Pixel,
+        Self::Chan: From<S::Chan>,
+        O: Blend,
+    {
+        for (d, s) in dst.iter_mut().zip(src) {
+            d.composite_channels(&s.convert(), op);
+        }
+    }
+
+    /// Adjust the colors of a slice of pixels.
+    ///
+    /// * `dst` Slice of pixels to adjust.
+    /// * `op` Adjustment operation.
+    ///
+    /// Pixels are converted to *linear* gamma with *straight* alpha for
+    /// adjustment, then converted back.  8-bit *linear*, *straight* alpha
+    /// `Rgb` pixels are adjusted directly, using [adjust_u8].
+    ///
+    /// [adjust_u8]: ../ops/trait.Adjust.html#method.adjust_u8
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::ops::Brightness;
+    /// use pix::rgb::Rgba8;
+    ///
+    /// let mut p = [Rgba8::new(0x40, 0x80, 0xF0, 0x80)];
+    /// Rgba8::adjust_slice(&mut p, Brightness(0.25));
+    /// assert_eq!(p[0], Rgba8::new(0x80, 0xC0, 0xFF, 0x80));
+    /// ```
+    fn adjust_slice<O>(dst: &mut [Self], op: O)
+    where
+        Ch32: From<Self::Chan>,
+        Self::Chan: From<Ch32>,
+        O: Adjust,
+    {
+        if is_rgb_u8::<Self>() {
+            adjust_u8::<Self, O>(dst, op);
+            return;
+        }
+        for d in dst.iter_mut() {
+            let c: Rgba32 = d.convert();
+            let ch = c.channels();
+            let mut rgba = [ch[0], ch[1], ch[2], ch[3]].map(|c| c.to_f32());
+            op.adjust(&mut rgba);
+            *d = Rgba32::from_channels(&rgba.map(Ch32::new)).convert();
+        }
+    }
+
+    /// Increase the saturation of a pixel.
+    ///
+    /// * `amount` Amount to increase; `0.0` for no change, `1.0` to double.
+    ///   Amounts below `-1.0` are clamped, producing gray.
+    ///
+    /// Saturation is scaled in linear light, preserving Rec. 709 luminance.
+    /// Pixels of any color model are converted internally.  See
+    /// [ColorMatrix::saturation] for details.
+    ///
+    /// [ColorMatrix::saturation]: ../ops/struct.ColorMatrix.html#method.saturation
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::hsl::SHsl8;
+    ///
+    /// let p = SHsl8::new(0x80, 0x40, 0x80).saturate(0.5);
+    /// assert!(p.two() > 0x40.into());
+    /// ```
+    fn saturate(self, amount: f32) -> Self
+    where
+        Ch32: From<Self::Chan>,
+        Self::Chan: From<Ch32>,
+    {
+        let factor = (1.0 + amount).max(0.0);
+        let mut p = [self];
+        Self::adjust_slice(&mut p, ColorMatrix::saturation(factor));
+        p[0]
+    }
+
+    /// Decrease the saturation of a pixel.
+    ///
+    /// * `amount` Amount to decrease; `0.0` for no change, `1.0` for gray.
+    ///
+    /// Saturation is scaled in linear light, preserving Rec. 709 luminance.
+    /// Pixels of any color model are converted internally.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::Rgb8;
+    ///
+    /// let p = Rgb8::new(0xFF, 0x00, 0x00).desaturate(1.0);
+    /// assert_eq!(p, Rgb8::new(0x36, 0x36, 0x36));
+    /// ```
+    fn desaturate(self, amount: f32) -> Self
+    where
+        Ch32: From<Self::Chan>,
+        Self::Chan: From<Ch32>,
+    {
+        let factor = (1.0 - amount).max(0.0);
+        let mut p = [self];
+        Self::adjust_slice(&mut p, ColorMatrix::saturation(factor));
+        p[0]
+    }
+
+    /// Adjust the exposure of a pixel.
+    ///
+    /// * `stops` Exposure change; `1.0` doubles the light, `-1.0` halves it.
+    ///
+    /// Channels are scaled in linear light, so gamma encoded formats are
+    /// handled correctly.  See [Exposure] for slices and rasters.
+    ///
+    /// [Exposure]: ../ops/struct.Exposure.html
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::{Rgb8, SRgb8};
+    ///
+    /// let p = Rgb8::new(0x20, 0x40, 0x60).exposure(1.0);
+    /// assert_eq!(p, Rgb8::new(0x40, 0x80, 0xC0));
+    /// let p = SRgb8::new(0x80, 0x80, 0x80).exposure(1.0);
+    /// assert_eq!(p, SRgb8::new(0xAF, 0xAF, 0xAF));
+    /// ```
+    fn exposure(self, stops: f32) -> Self
+    where
+        Ch32: From<Self::Chan>,
+        Self::Chan: From<Ch32>,
+    {
+        let mut p = [self];
+        Self::adjust_slice(&mut p, Exposure(stops));
+        p[0]
+    }
+
+    /// Lighten a pixel by mixing it toward white.
+    ///
+    /// * `t` Amount to mix, from `0.0` (no change) to `1.0` (white).
+    /// * `space` Color space for mixing.
+    ///
+    /// *Alpha* is not changed.
+    ///
+    /// ### Hover state
+    /// ```
+    /// use pix::el::{MixSpace, Pixel};
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let button = SRgb8::new(0x20, 0x60, 0xC0);
+    /// let hover = button.lighten(0.5, MixSpace::Srgb);
+    /// assert_eq!(hover, SRgb8::new(0x90, 0xB0, 0xE0));
+    /// ```
+    fn lighten(self, t: f32, space: MixSpace) -> Self {
+        mix_toward(self, 1.0, t, space)
+    }
+
+    /// Darken a pixel by mixing it toward black.
+    ///
+    /// * `t` Amount to mix, from `0.0` (no change) to `1.0` (black).
+    /// * `space` Color space for mixing.
+    ///
+    /// *Alpha* is not changed.
+    ///
+    /// ### Pressed state
+    /// ```
+    /// use pix::el::{MixSpace, Pixel};
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let button = SRgb8::new(0x20, 0x60, 0xC0);
+    /// let pressed = button.darken(0.25, MixSpace::Srgb);
+    /// assert_eq!(pressed, SRgb8::new(0x18, 0x48, 0x90));
+    /// ```
+    fn darken(self, t: f32, space: MixSpace) -> Self {
+        mix_toward(self, 0.0, t, space)
+    }
+
+    /// Posterize a slice of pixels.
+    ///
+    /// * `dst` Slice of pixels to posterize.
+    /// * `levels` Number of levels for each channel.
+    ///
+    /// Channels are quantized as stored, using the pixel's gamma mode.
+    /// *Alpha* and circular channels (*hue*) are not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is less than 2.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let mut p = [SRgb8::new(0x20, 0x70, 0xD0)];
+    /// SRgb8::posterize_slice(&mut p, 3);
+    /// assert_eq!(p[0], SRgb8::new(0x00, 0x80, 0xFF));
+    /// ```
+    fn posterize_slice(dst: &mut [Self], levels: u32) {
+        assert!(levels >= 2);
+        let steps = (levels - 1) as f32;
+        for d in dst.iter_mut() {
+            let alpha = d.alpha();
+            for c in &mut d.channels_mut()[Self::Model::LINEAR] {
+                let v = Self::Alpha::decode(*c, alpha).to_f32();
+                let v = Self::Chan::from((v * steps).round() / steps);
+                *c = Self::Alpha::encode(v, alpha);
+            }
+        }
+    }
+
+    /// Threshold a slice of pixels to black and white.
+    ///
+    /// * `dst` Slice of pixels to threshold.
+    /// * `value` Minimum luminance to be set to white; lower values are set
+    ///   to black.
+    ///
+    /// Luminance is compared using the pixel's gamma mode.  *Alpha* is not
+    /// changed.  To threshold mattes, use [Matte::threshold].
+    ///
+    /// [Matte::threshold]: ../matte/struct.Matte.html#method.threshold
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgba8;
+    ///
+    /// let mut p = [
+    ///     SRgba8::new(0x20, 0x70, 0xD0, 0xFF),
+    ///     SRgba8::new(0xC0, 0x80, 0x40, 0x80),
+    /// ];
+    /// SRgba8::threshold_slice(&mut p, 0x80.into());
+    /// assert_eq!(p[0], SRgba8::new(0x00, 0x00, 0x00, 0xFF));
+    /// assert_eq!(p[1], SRgba8::new(0xFF, 0xFF, 0xFF, 0x80));
+    /// ```
+    fn threshold_slice(dst: &mut [Self], value: Self::Chan) {
+        for d in dst.iter_mut() {
+            let g: Pix2<Self::Chan, Gray, Straight, Self::Gamma> = d.convert();
+            let v = if g.channels()[0] >= value {
+                Self::Chan::MAX
+            } else {
+                Self::Chan::MIN
+            };
+            *d =
+                Pix2::<Self::Chan, Gray, Straight, Self::Gamma>::from_channels(
+                    &[v, g.alpha()],
+                )
+                .convert();
+        }
+    }
+
+    /// Invert the colors of a pixel, producing its RGB negative.
+    ///
+    /// Each *red*, *green* and *blue* component `c` is replaced by `MAX` -
+    /// `c`, as stored in the pixel's gamma mode.  Pixels in other color
+    /// models (such as [Hsl]) are inverted through RGB.  *Alpha* is not
+    /// changed, except for [Matte] pixels, where it is inverted to produce the
+    /// complementary mask.
+    ///
+    /// [Hsl]: ../hsl/struct.Hsl.html
+    /// [Matte]: ../matte/struct.Matte.html
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::hsl::Hsla8;
+    /// use pix::rgb::SRgba8;
+    ///
+    /// let p = SRgba8::new(0x20, 0x80, 0xFF, 0x40);
+    /// assert_eq!(p.invert(), SRgba8::new(0xDF, 0x7F, 0x00, 0x40));
+    /// let p = Hsla8::new(0x00, 0xFF, 0x40, 0xFF);
+    /// assert_eq!(p.invert(), Hsla8::new(0x80, 0xFF, 0xBF, 0xFF));
+    /// ```
+    fn invert(self) -> Self {
+        let model = TypeId::of::<Self::Model>();
+        if model == TypeId::of::<Matte>() {
+            let mut dst = self;
+            let a = dst.alpha_mut();
+            *a = Self::Chan::MAX - *a;
+            return dst;
+        }
+        if model == TypeId::of::<Rgb>()
+            || model == TypeId::of::<Bgr>()
+            || model == TypeId::of::<Gray>()
+            || model == TypeId::of::<Cmy>()
+        {
+            return invert_linear(self);
+        }
+        let rgba = Self::Model::into_rgba::<Self>(self);
+        Self::Model::from_rgba::<Self>(invert_linear(rgba))
+    }
+
+    /// Invert the channels of a slice of pixels.
+    ///
+    /// * `dst` Slice of pixels to invert.
+    ///
+    /// See [invert] for details.
+    ///
+    /// [invert]: #method.invert
+    fn invert_slice(dst: &mut [Self]) {
+        for d in dst.iter_mut() {
+            *d = d.invert();
+        }
+    }
+
     /// Composite the channels of two pixels
     fn composite_channels<O>(&mut self, src: &Self, op: O)
     where
@@ -271,11 +1376,62 @@ pub trait Pixel: Clone + Copy + Debug + Default + PartialEq + Sealed {
             .for_each(|(d, s)| O::composite(d, da1, &(*s * *alpha), sa1));
         O::composite(self.alpha_mut(), da1, &(src.alpha() * *alpha), sa1);
     }
+
+    /// Composite the channels of two pixels at a constant opacity
+    fn composite_channels_opacity<O>(
+        &mut self,
+        src: &Self,
+        op: O,
+        opacity: &Self::Chan,
+    ) where
+        Self: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+        O: Blend,
+    {
+        let sa = src.alpha() * *opacity;
+        let da1 = Self::Chan::MAX - self.alpha();
+        let sa1 = Self::Chan::MAX - sa;
+        // circular channels are not premultiplied, so opacity only affects t
+        let d_chan = &mut self.channels_mut()[Self::Model::CIRCULAR];
+        let s_chan = &src.channels()[Self::Model::CIRCULAR];
+        d_chan
+            .iter_mut()
+            .zip(s_chan)
+            .for_each(|(d, s)| circ_composite(d, da1, *s, sa1, op));
+        // linear channels
+        let d_chan = &mut self.channels_mut()[Self::Model::LINEAR];
+        let s_chan = &src.channels()[Self::Model::LINEAR];
+        d_chan
+            .iter_mut()
+            .zip(s_chan)
+            .for_each(|(d, s)| O::composite(d, da1, &(*s * *opacity), sa1));
+        O::composite(self.alpha_mut(), da1, &sa, sa1);
+    }
+}
+
+/// Get the number of channels in a pixel format
+pub(crate) fn channel_count<P: Pixel>() -> usize {
+    P::default().channels().len()
+}
+
+/// Make a pixel from color model channels followed by *alpha*.
+///
+/// Channels beyond the pixel's count (such as *alpha* for a pixel without
+/// one) are dropped, and missing channels are set to `MAX`, so this never
+/// panics, even for a custom channel count.
+pub(crate) fn from_model_channels<P: Pixel>(ch: &[P::Chan]) -> P {
+    let n = channel_count::<P>();
+    P::try_from_channels(ch.get(..n).unwrap_or(ch)).unwrap_or_else(|_| {
+        let mut p = P::default();
+        for (i, c) in p.channels_mut().iter_mut().enumerate() {
+            *c = ch.get(i).copied().unwrap_or(P::Chan::MAX);
+        }
+        p
+    })
 }
 
 /// Calculate composite for a circular channel
 #[inline]
-fn circ_composite<C, O>(d: &mut C, da1: C, mut s: C, sa1: C, _op: O)
+fn circ_composite<C, O>(d: &mut C, da1: C, s: C, sa1: C, _op: O)
 where
     C: Channel,
     O: Blend,
@@ -284,33 +1440,147 @@ where
     // 1. Calcualte `t`, ranging from MIN (dst) to MAX (src), using composite
     let mut t = C::MIN;
     O::composite(&mut t, da1, &(C::MAX - sa1), sa1);
-    // 2. If difference > 180 degrees, rotate both by 180 degrees
-    let rotate = s.max(*d) - s.min(*d) > C::MID;
+    // 2. Lerp between src and dest.
+    *d = circ_lerp(*d, s, t);
+}
+
+/// Linear interpolation of a circular channel (shortest way around)
+#[inline]
+fn circ_lerp<C: Channel>(mut d: C, mut s: C, t: C) -> C {
+    // 1. If difference > 180 degrees, rotate both by 180 degrees
+    let rotate = s.max(d) - s.min(d) > C::MID;
     if rotate {
-        if s > *d {
+        if s > d {
             s = s - C::MID;
-            *d = *d + C::MID;
+            d = d + C::MID;
         } else {
             s = s + C::MID;
-            *d = *d - C::MID;
+            d = d - C::MID;
         }
     }
-    // 3. Lerp between src and dest.
-    *d = d.lerp(s, t);
-    // 4. If rotated, rotate by 180 degrees
+    // 2. Lerp between src and dest.
+    d = d.lerp(s, t);
+    // 3. If rotated, rotate by 180 degrees
     if rotate {
-        if *d < C::MID {
-            *d = *d + C::MID;
+        if d < C::MID {
+            d = d + C::MID;
         } else {
-            *d = *d - C::MID;
+            d = d - C::MID;
         }
     }
+    d
+}
+
+/// Check if two pixel formats have the same layout with 8-bit linear channels
+fn is_linear_u8<D: Pixel, S: Pixel>() -> bool {
+    TypeId::of::<S::Chan>() == TypeId::of::<Ch8>()
+        && TypeId::of::<S::Gamma>() == TypeId::of::<Linear>()
+        && TypeId::of::<D::Chan>() == TypeId::of::<Ch8>()
+        && std::mem::size_of::<D>() == std::mem::size_of::<S>()
+}
+
+/// Check if a pixel format is 8-bit linear straight alpha `Rgb`
+fn is_rgb_u8<P: Pixel>() -> bool {
+    TypeId::of::<P::Chan>() == TypeId::of::<Ch8>()
+        && TypeId::of::<P::Model>() == TypeId::of::<Rgb>()
+        && TypeId::of::<P::Gamma>() == TypeId::of::<Linear>()
+        && TypeId::of::<P::Alpha>() == TypeId::of::<Straight>()
+}
+
+/// Adjust a slice of 8-bit linear straight alpha `Rgb` pixels
+fn adjust_u8<P: Pixel, O: Adjust>(dst: &mut [P], op: O) {
+    let n = std::mem::size_of::<P>();
+    // Pixel structs are repr(C), so channels are laid out contiguously
+    let (_, dst, _) = unsafe { dst.align_to_mut::<u8>() };
+    for d in dst.chunks_exact_mut(n) {
+        let mut rgba = [d[0], d[1], d[2], d.get(3).copied().unwrap_or(255)];
+        op.adjust_u8(&mut rgba);
+        d.copy_from_slice(&rgba[..n]);
+    }
+}
+
+/// Check if two pixel formats differ only by `Rgb` / `Bgr` channel order
+fn is_rgb_bgr_swap<D: Pixel, S: Pixel>() -> bool {
+    let (dm, sm) = (TypeId::of::<D::Model>(), TypeId::of::<S::Model>());
+    let (rgb, bgr) = (TypeId::of::<Rgb>(), TypeId::of::<Bgr>());
+    ((dm == rgb && sm == bgr) || (dm == bgr && sm == rgb))
+        && TypeId::of::<D::Chan>() == TypeId::of::<S::Chan>()
+        && TypeId::of::<D::Alpha>() == TypeId::of::<S::Alpha>()
+        && TypeId::of::<D::Gamma>() == TypeId::of::<S::Gamma>()
+        && std::mem::size_of::<D>() == std::mem::size_of::<S>()
+}
+
+/// Swap *red* and *blue* channels to convert between `Rgb` and `Bgr`
+fn swap_red_blue<D, S>(src: S) -> D
+where
+    D: Pixel,
+    S: Pixel,
+    D::Chan: From<S::Chan>,
+{
+    let mut dst = D::default();
+    let chan = src.channels();
+    for (d, s) in dst.channels_mut().iter_mut().zip(chan) {
+        *d = D::Chan::from(*s);
+    }
+    let d = dst.channels_mut();
+    d[0] = D::Chan::from(chan[2]);
+    d[2] = D::Chan::from(chan[0]);
+    dst
+}
+
+/// Get destination / source pixel slices as `u8` slices
+fn as_u8_slices<'a, D: Pixel, S: Pixel>(
+    dst: &'a mut [D],
+    src: &'a [S],
+) -> (&'a mut [u8], &'a [u8]) {
+    let len = dst.len().min(src.len());
+    // Pixel structs are repr(C), so channels are laid out contiguously
+    let (_, dst, _) = unsafe { dst[..len].align_to_mut::<u8>() };
+    let (_, src, _) = unsafe { src[..len].align_to::<u8>() };
+    (dst, src)
+}
+
+/// Convert alpha mode of 8-bit channels
+///
+/// * `dst` Destination channels.
+/// * `src` Source channels.
+/// * `conv` Channel conversion function (channel, alpha).
+fn alpha_u8<S: Pixel>(dst: &mut [u8], src: &[u8], conv: fn(u8, u8) -> u8) {
+    let n = std::mem::size_of::<S>();
+    let alpha = S::Model::ALPHA;
+    let linear: Range<usize> = S::Model::LINEAR;
+    for (d, s) in dst.chunks_exact_mut(n).zip(src.chunks_exact(n)) {
+        d.copy_from_slice(s);
+        if let Some(a) = s.get(alpha) {
+            for c in &mut d[linear.clone()] {
+                *c = conv(*c, *a);
+            }
+        }
+    }
+}
+
+/// Premultiply an 8-bit channel by alpha (rounded)
+#[inline]
+fn premultiply_u8(c: u8, a: u8) -> u8 {
+    let t = u16::from(c) * u16::from(a) + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+/// Unpremultiply an 8-bit channel by alpha (rounded)
+#[inline]
+fn unpremultiply_u8(c: u8, a: u8) -> u8 {
+    let v = (u32::from(c) * UNPREMULTIPLY_U8[usize::from(a)] + 0x8000) >> 16;
+    v.min(255) as u8
 }
 
 /// Rgba pixel type for color model conversions
 pub type PixRgba<P> =
     Pix4<<P as Pixel>::Chan, Rgb, <P as Pixel>::Alpha, <P as Pixel>::Gamma>;
 
+/// Gray pixel type holding one channel of another pixel type
+pub type PixGray<P> =
+    Pix1<<P as Pixel>::Chan, Gray, Straight, <P as Pixel>::Gamma>;
+
 /// Convert a pixel to another format with the same color model.
 ///
 /// * `D` Destination pixel format.
@@ -365,47 +1635,149 @@ where
     D::Model::from_rgba::<D>(rgba)
 }
 
-/// [Pixel] with one [channel] in its [color model].
+/// Invert the linear (non-*alpha*) channels of a pixel
+fn invert_linear<P: Pixel>(p: P) -> P {
+    let mut dst = p;
+    let alpha = p.alpha();
+    for c in &mut dst.channels_mut()[P::Model::LINEAR] {
+        let v = P::Chan::MAX - P::Alpha::decode(*c, alpha);
+        *c = P::Alpha::encode(v, alpha);
+    }
+    dst
+}
+
+/// Calculate relative luminance of a pixel, in linear light
+pub(crate) fn relative_luminance<P: Pixel>(p: P) -> f32 {
+    const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+    let rgb: Pix3<Ch32, Rgb, Straight, Linear> = p.convert_lossy();
+    rgb.channels()
+        .iter()
+        .zip(LUMA)
+        .map(|(c, l)| c.to_f32() * l)
+        .sum::<f32>()
+        .min(1.0)
+}
+
+/// Float Rgba pixel type for lossy conversions
+type PixRgba32<P> = Pix4<Ch32, Rgb, <P as Pixel>::Alpha, <P as Pixel>::Gamma>;
+
+/// Convert a pixel to another format, thru 32-bit *red*, *green*, *blue*
+/// and *alpha* components
+fn convert_thru_rgba32<D, S>(src: S) -> D
+where
+    D: Pixel,
+    S: Pixel,
+{
+    let rgba = S::Model::into_rgba::<S>(src);
+    let rgba = PixRgba32::<S>::from_bit_depth_lossy(rgba);
+    let rgba = convert_same_model::<PixRgba32<D>, PixRgba32<S>>(rgba);
+    let rgba = PixRgba::<D>::from_bit_depth_lossy(rgba);
+    D::Model::from_rgba::<D>(rgba)
+}
+
+/// Mix a pixel toward black (`0.0`) or white (`1.0`), keeping *alpha*
+fn mix_toward<P: Pixel>(p: P, gray: f32, t: f32, space: MixSpace) -> P {
+    let t = t.clamp(0.0, 1.0);
+    let mix = |c: Ch32| c.to_f32() + (gray - c.to_f32()) * t;
+    match space {
+        MixSpace::Srgb => {
+            let [r, g, b, a] = p.convert_lossy::<SRgba32>().into_array();
+            SRgba32::new(mix(r), mix(g), mix(b), a.to_f32()).convert_lossy()
+        }
+        MixSpace::Linear => {
+            let [r, g, b, a] = p.convert_lossy::<Rgba32>().into_array();
+            Rgba32::new(mix(r), mix(g), mix(b), a.to_f32()).convert_lossy()
+        }
+        MixSpace::Oklab => {
+            let [r, g, b, a] = p.convert_lossy::<Rgba32>().into_array();
+            let rgb = [r, g, b].map(Ch32::to_f32);
+            let [l, ca, cb] = linear_rgb_to_oklab(rgb);
+            let lab = [l + (gray - l) * t, ca * (1.0 - t), cb * (1.0 - t)];
+            let [r, g, b] = oklab_to_linear_rgb(lab);
+            Rgba32::new(r, g, b, a.to_f32()).convert_lossy()
+        }
+    }
+}
+
+/// [Pixel] with `N` [channel]s in its [color model].
 ///
+/// The [Pix1], [Pix2], [Pix3] and [Pix4] aliases are used for the built-in
+/// color models.  Custom color models can use more than four channels, with
+/// [alpha] as the last one.
+///
+/// [alpha]: ../chan/trait.Alpha.html
 /// [channel]: ../chan/trait.Channel.html
 /// [color model]: ../trait.ColorModel.html
+/// [pix1]: type.Pix1.html
+/// [pix2]: type.Pix2.html
+/// [pix3]: type.Pix3.html
+/// [pix4]: type.Pix4.html
 /// [pixel]: trait.Pixel.html
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
-pub struct Pix1<C, M, A, G>
+pub struct Pix<C, M, A, G, const N: usize>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    channels: [C; 1],
+    channels: [C; N],
     _model: PhantomData<M>,
     _alpha: PhantomData<A>,
     _gamma: PhantomData<G>,
 }
 
-impl<C, M, A, G> Pix1<C, M, A, G>
+/// [Pixel] with one [channel] in its [color model].
+///
+/// [channel]: ../chan/trait.Channel.html
+/// [color model]: ../trait.ColorModel.html
+/// [pixel]: trait.Pixel.html
+pub type Pix1<C, M, A, G> = Pix<C, M, A, G, 1>;
+
+/// [Pixel] with two [channel]s in its [color model].
+///
+/// [channel]: ../chan/trait.Channel.html
+/// [color model]: ../trait.ColorModel.html
+/// [pixel]: trait.Pixel.html
+pub type Pix2<C, M, A, G> = Pix<C, M, A, G, 2>;
+
+/// [Pixel] with three [channel]s in its [color model].
+///
+/// [channel]: ../chan/trait.Channel.html
+/// [color model]: ../trait.ColorModel.html
+/// [pixel]: trait.Pixel.html
+pub type Pix3<C, M, A, G> = Pix<C, M, A, G, 3>;
+
+/// [Pixel] with four [channel]s in its [color model].
+///
+/// [channel]: ../chan/trait.Channel.html
+/// [color model]: ../trait.ColorModel.html
+/// [pixel]: trait.Pixel.html
+pub type Pix4<C, M, A, G> = Pix<C, M, A, G, 4>;
+
+impl<C, M, A, G, const N: usize> Pix<C, M, A, G, N>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    /// Create a one-channel color.
+    /// Create a color from an array of channels.
+    ///
+    /// This can be used in `const` contexts.
     ///
     /// ## Example
     /// ```
-    /// use pix::gray::Gray8;
+    /// use pix::chan::{Ch8, Channel};
+    /// use pix::gray::Graya8;
+    /// use pix::rgb::SRgb8;
     ///
-    /// let opaque_gray = Gray8::new(128);
+    /// const CLR: Graya8 = Graya8::from_array([Ch8::new(0x80), Ch8::MAX]);
+    /// const RGB: SRgb8 = SRgb8::from_array([Ch8::new(0x40), Ch8::MIN, Ch8::MAX]);
     /// ```
-    pub fn new<H>(one: H) -> Self
-    where
-        C: From<H>,
-    {
-        let channels = [C::from(one); 1];
-        Pix1 {
+    pub const fn from_array(channels: [C; N]) -> Self {
+        Pix {
             channels,
             _model: PhantomData,
             _alpha: PhantomData,
@@ -414,62 +1786,30 @@ where
     }
 }
 
-impl<C, M, A, G> Pixel for Pix1<C, M, A, G>
+impl<C, M, A, G> Pix1<C, M, A, G>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    type Chan = C;
-    type Model = M;
-    type Alpha = A;
-    type Gamma = G;
-
-    fn from_channels(ch: &[C]) -> Self {
-        let one = ch[0];
-        Self::new::<C>(one)
-    }
-
-    fn from_bit_depth<P>(p: P) -> Self
+    /// Create a one-channel color.
+    ///
+    /// ## Example
+    /// ```
+    /// use pix::gray::Gray8;
+    ///
+    /// let opaque_gray = Gray8::new(128);
+    /// ```
+    pub fn new<H>(one: H) -> Self
     where
-        P: Pixel,
-        Self::Chan: From<P::Chan>,
+        C: From<H>,
     {
-        debug_assert_eq!(TypeId::of::<Self::Model>(), TypeId::of::<P::Model>());
-        let one = Self::Chan::from(p.one());
-        Self::new::<Self::Chan>(one)
-    }
-
-    fn channels(&self) -> &[Self::Chan] {
-        &self.channels
-    }
-
-    fn channels_mut(&mut self) -> &mut [Self::Chan] {
-        &mut self.channels
+        let channels = [C::from(one); 1];
+        Self::from_array(channels)
     }
 }
 
-/// [Pixel] with two [channel]s in its [color model].
-///
-/// [channel]: ../chan/trait.Channel.html
-/// [color model]: ../trait.ColorModel.html
-/// [pixel]: trait.Pixel.html
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-#[repr(C)]
-pub struct Pix2<C, M, A, G>
-where
-    C: Channel,
-    M: ColorModel,
-    A: Alpha,
-    G: Gamma,
-{
-    channels: [C; 2],
-    _model: PhantomData<M>,
-    _alpha: PhantomData<A>,
-    _gamma: PhantomData<G>,
-}
-
 impl<C, M, A, G> Pix2<C, M, A, G>
 where
     C: Channel,
@@ -492,106 +1832,78 @@ where
         let one = C::from(one);
         let two = C::from(two);
         let channels = [one, two];
-        Pix2 {
-            channels,
-            _model: PhantomData,
-            _alpha: PhantomData,
-            _gamma: PhantomData,
-        }
+        Self::from_array(channels)
     }
 }
 
-impl<C, M, A, G> Pixel for Pix2<C, M, A, G>
+impl<C, M, A, G> Pix3<C, M, A, G>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    type Chan = C;
-    type Model = M;
-    type Alpha = A;
-    type Gamma = G;
-
-    fn from_channels(ch: &[C]) -> Self {
-        let one = ch[0];
-        let two = ch[1];
-        Self::new::<C>(one, two)
-    }
-
-    fn from_bit_depth<P>(p: P) -> Self
+    /// Create a three-channel color.
+    ///
+    /// ## Example
+    /// ```
+    /// use pix::rgb::Rgb8;
+    ///
+    /// let rgb = Rgb8::new(128, 200, 255);
+    /// ```
+    pub fn new<H>(one: H, two: H, three: H) -> Self
     where
-        P: Pixel,
-        Self::Chan: From<P::Chan>,
+        C: From<H>,
     {
-        debug_assert_eq!(TypeId::of::<Self::Model>(), TypeId::of::<P::Model>());
-        let one = Self::Chan::from(p.one());
-        let two = Self::Chan::from(p.two());
-        Self::new::<Self::Chan>(one, two)
-    }
-
-    fn channels(&self) -> &[Self::Chan] {
-        &self.channels
-    }
-
-    fn channels_mut(&mut self) -> &mut [Self::Chan] {
-        &mut self.channels
+        let one = C::from(one);
+        let two = C::from(two);
+        let three = C::from(three);
+        let channels = [one, two, three];
+        Self::from_array(channels)
     }
 }
 
-/// [Pixel] with three [channel]s in its [color model].
-///
-/// [channel]: ../chan/trait.Channel.html
-/// [color model]: ../trait.ColorModel.html
-/// [pixel]: trait.Pixel.html
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-#[repr(C)]
-pub struct Pix3<C, M, A, G>
-where
-    C: Channel,
-    M: ColorModel,
-    A: Alpha,
-    G: Gamma,
-{
-    channels: [C; 3],
-    _model: PhantomData<M>,
-    _alpha: PhantomData<A>,
-    _gamma: PhantomData<G>,
-}
-
-impl<C, M, A, G> Pix3<C, M, A, G>
+impl<C, M, A, G> Pix4<C, M, A, G>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    /// Create a three-channel color.
+    /// Create a four-channel color.
     ///
     /// ## Example
     /// ```
-    /// use pix::rgb::Rgb8;
+    /// use pix::rgb::Rgba8;
     ///
-    /// let rgb = Rgb8::new(128, 200, 255);
+    /// let rgba = Rgba8::new(128, 200, 255, 128);
     /// ```
-    pub fn new<H>(one: H, two: H, three: H) -> Self
+    pub fn new<H>(one: H, two: H, three: H, four: H) -> Self
     where
         C: From<H>,
     {
         let one = C::from(one);
         let two = C::from(two);
         let three = C::from(three);
-        let channels = [one, two, three];
-        Pix3 {
-            channels,
-            _model: PhantomData,
-            _alpha: PhantomData,
-            _gamma: PhantomData,
-        }
+        let four = C::from(four);
+        let channels = [one, two, three, four];
+        Self::from_array(channels)
+    }
+}
+
+impl<C, M, A, G, const N: usize> Default for Pix<C, M, A, G, N>
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn default() -> Self {
+        Self::from_array([C::default(); N])
     }
 }
 
-impl<C, M, A, G> Pixel for Pix3<C, M, A, G>
+impl<C, M, A, G, const N: usize> Pixel for Pix<C, M, A, G, N>
 where
     C: Channel,
     M: ColorModel,
@@ -602,12 +1914,13 @@ where
     type Model = M;
     type Alpha = A;
     type Gamma = G;
+    type Array = [C; N];
+    type F32Array = [f32; N];
 
     fn from_channels(ch: &[C]) -> Self {
-        let one = ch[0];
-        let two = ch[1];
-        let three = ch[2];
-        Self::new::<C>(one, two, three)
+        let mut channels = [C::default(); N];
+        channels.copy_from_slice(&ch[..N]);
+        Self::from_array(channels)
     }
 
     fn from_bit_depth<P>(p: P) -> Self
@@ -616,10 +1929,11 @@ where
         Self::Chan: From<P::Chan>,
     {
         debug_assert_eq!(TypeId::of::<Self::Model>(), TypeId::of::<P::Model>());
-        let one = Self::Chan::from(p.one());
-        let two = Self::Chan::from(p.two());
-        let three = Self::Chan::from(p.three());
-        Self::new::<Self::Chan>(one, two, three)
+        let ch = p.channels();
+        let channels = std::array::from_fn(|i| {
+            ch.get(i).map_or(Self::Chan::MAX, |c| Self::Chan::from(*c))
+        });
+        Self::from_array(channels)
     }
 
     fn channels(&self) -> &[Self::Chan] {
@@ -629,103 +1943,277 @@ where
     fn channels_mut(&mut self) -> &mut [Self::Chan] {
         &mut self.channels
     }
+
+    fn into_array(self) -> Self::Array {
+        self.channels
+    }
+
+    fn into_f32_array(self) -> Self::F32Array {
+        self.channels.map(|c| c.to_f32())
+    }
 }
 
-/// [Pixel] with four [channel]s in its [color model].
-///
-/// [channel]: ../chan/trait.Channel.html
-/// [color model]: ../trait.ColorModel.html
-/// [pixel]: trait.Pixel.html
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-#[repr(C)]
-pub struct Pix4<C, M, A, G>
+impl<C, M, A, G, H, const N: usize> From<[H; N]> for Pix<C, M, A, G, N>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
+    C: From<H>,
 {
-    channels: [C; 4],
-    _model: PhantomData<M>,
-    _alpha: PhantomData<A>,
-    _gamma: PhantomData<G>,
+    /// Make a pixel from an array of channel values.
+    fn from(ch: [H; N]) -> Self {
+        Self::from_array(ch.map(C::from))
+    }
 }
 
-impl<C, M, A, G> Pix4<C, M, A, G>
+impl<C, M, A, G, H, const N: usize> From<Pix<C, M, A, G, N>> for [H; N]
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
+    H: From<C>,
 {
-    /// Create a four-channel color.
-    ///
-    /// ## Example
-    /// ```
-    /// use pix::rgb::Rgba8;
-    ///
-    /// let rgba = Rgba8::new(128, 200, 255, 128);
-    /// ```
-    pub fn new<H>(one: H, two: H, three: H, four: H) -> Self
+    /// Get the channel values as an array.
+    fn from(p: Pix<C, M, A, G, N>) -> Self {
+        p.channels.map(H::from)
+    }
+}
+
+impl<C, M, A, G, H> From<(H, H)> for Pix2<C, M, A, G>
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    C: From<H>,
+{
+    /// Make a pixel from a tuple of channel values.
+    fn from(ch: (H, H)) -> Self {
+        let (one, two) = ch;
+        Self::new::<H>(one, two)
+    }
+}
+
+impl<C, M, A, G, H> From<Pix2<C, M, A, G>> for (H, H)
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    H: From<C>,
+{
+    /// Get the channel values as a tuple.
+    fn from(p: Pix2<C, M, A, G>) -> Self {
+        let [one, two] = p.channels.map(H::from);
+        (one, two)
+    }
+}
+
+impl<C, M, A, G, H> From<(H, H, H)> for Pix3<C, M, A, G>
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    C: From<H>,
+{
+    /// Make a pixel from a tuple of channel values.
+    fn from(ch: (H, H, H)) -> Self {
+        let (one, two, three) = ch;
+        Self::new::<H>(one, two, three)
+    }
+}
+
+impl<C, M, A, G, H> From<Pix3<C, M, A, G>> for (H, H, H)
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    H: From<C>,
+{
+    /// Get the channel values as a tuple.
+    fn from(p: Pix3<C, M, A, G>) -> Self {
+        let [one, two, three] = p.channels.map(H::from);
+        (one, two, three)
+    }
+}
+
+impl<C, M, A, G, H> From<(H, H, H, H)> for Pix4<C, M, A, G>
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    C: From<H>,
+{
+    /// Make a pixel from a tuple of channel values.
+    fn from(ch: (H, H, H, H)) -> Self {
+        let (one, two, three, four) = ch;
+        Self::new::<H>(one, two, three, four)
+    }
+}
+
+impl<C, M, A, G, H> From<Pix4<C, M, A, G>> for (H, H, H, H)
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    H: From<C>,
+{
+    /// Get the channel values as a tuple.
+    fn from(p: Pix4<C, M, A, G>) -> Self {
+        let [one, two, three, four] = p.channels.map(H::from);
+        (one, two, three, four)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C, M, A, G, const N: usize> serde::Serialize for Pix<C, M, A, G, N>
+where
+    C: Channel + serde::Serialize,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Serialize as a sequence of channels.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        C: From<H>,
+        S: serde::Serializer,
     {
-        let one = C::from(one);
-        let two = C::from(two);
-        let three = C::from(three);
-        let four = C::from(four);
-        let channels = [one, two, three, four];
-        Pix4 {
-            channels,
-            _model: PhantomData,
-            _alpha: PhantomData,
-            _gamma: PhantomData,
+        use serde::ser::SerializeTuple;
+        let mut tup = serializer.serialize_tuple(N)?;
+        for c in &self.channels {
+            tup.serialize_element(c)?;
         }
+        tup.end()
     }
 }
 
-impl<C, M, A, G> Pixel for Pix4<C, M, A, G>
+#[cfg(feature = "serde")]
+impl<'de, C, M, A, G, const N: usize> serde::Deserialize<'de>
+    for Pix<C, M, A, G, N>
+where
+    C: Channel + serde::Deserialize<'de>,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Deserialize from a sequence of channels.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        /// Visitor for channel sequences
+        struct Visitor<C, const N: usize>(PhantomData<C>);
+
+        impl<'de, C, const N: usize> serde::de::Visitor<'de> for Visitor<C, N>
+        where
+            C: Channel + serde::Deserialize<'de>,
+        {
+            type Value = [C; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length {N}")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<[C; N], S::Error>
+            where
+                S: serde::de::SeqAccess<'de>,
+            {
+                let mut channels = [C::default(); N];
+                for (i, c) in channels.iter_mut().enumerate() {
+                    *c = seq.next_element()?.ok_or_else(|| {
+                        serde::de::Error::invalid_length(i, &self)
+                    })?;
+                }
+                Ok(channels)
+            }
+        }
+
+        let visitor = Visitor::<C, N>(PhantomData);
+        deserializer
+            .deserialize_tuple(N, visitor)
+            .map(Self::from_array)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<C, M, A, G, const N: usize> approx::AbsDiffEq for Pix<C, M, A, G, N>
 where
     C: Channel,
     M: ColorModel,
     A: Alpha,
     G: Gamma,
 {
-    type Chan = C;
-    type Model = M;
-    type Alpha = A;
-    type Gamma = G;
+    type Epsilon = f32;
 
-    fn from_channels(ch: &[C]) -> Self {
-        let one = ch[0];
-        let two = ch[1];
-        let three = ch[2];
-        let four = ch[3];
-        Self::new::<C>(one, two, three, four)
+    fn default_epsilon() -> f32 {
+        f32::EPSILON
     }
 
-    fn from_bit_depth<P>(p: P) -> Self
-    where
-        P: Pixel,
-        Self::Chan: From<P::Chan>,
-    {
-        debug_assert_eq!(TypeId::of::<Self::Model>(), TypeId::of::<P::Model>());
-        let one = Self::Chan::from(p.one());
-        let two = Self::Chan::from(p.two());
-        let three = Self::Chan::from(p.three());
-        let four = Self::Chan::from(p.four());
-        Self::new::<Self::Chan>(one, two, three, four)
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.approx_eq(*other, epsilon)
     }
+}
 
-    fn channels(&self) -> &[Self::Chan] {
-        &self.channels
+#[cfg(feature = "approx")]
+impl<C, M, A, G, const N: usize> approx::RelativeEq for Pix<C, M, A, G, N>
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn default_max_relative() -> f32 {
+        f32::EPSILON
     }
 
-    fn channels_mut(&mut self) -> &mut [Self::Chan] {
-        &mut self.channels
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: f32,
+        max_relative: f32,
+    ) -> bool {
+        let circular = M::CIRCULAR;
+        let mut pairs = self.channels.iter().zip(other.channels).enumerate();
+        pairs.all(|(i, (a, b))| {
+            let (mut a, b) = (a.to_f32(), b.to_f32());
+            // compare hue the shortest way around the color wheel
+            if circular.contains(&i) && (a - b).abs() > 0.5 {
+                a += if a < b { 1.0 } else { -1.0 };
+            }
+            a.relative_eq(&b, epsilon, max_relative)
+        })
     }
 }
 
+// Safety: `repr(C)` with only channels and zero-sized markers (no padding)
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, M, A, G, const N: usize> bytemuck::Zeroable
+    for Pix<C, M, A, G, N>
+where
+    C: Channel + bytemuck::Zeroable,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+{
+}
+
+// Safety: `repr(C)` with only channels and zero-sized markers (no padding)
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, M, A, G, const N: usize> bytemuck::Pod for Pix<C, M, A, G, N>
+where
+    C: Channel + bytemuck::Pod,
+    M: ColorModel,
+    A: Alpha + 'static,
+    G: Gamma + 'static,
+{
+}
+
 #[cfg(test)]
 mod test {
     use crate::el::*;
@@ -733,6 +2221,317 @@ mod test {
     use crate::matte::*;
     use crate::rgb::*;
 
+    #[test]
+    fn five_channels() {
+        use crate::chan::{Ch32, Linear, Straight};
+        use crate::filter::{EdgeMode, Filter};
+        use crate::{ColorModel, Raster};
+        use std::ops::Range;
+
+        /// Cyan, magenta, yellow and key (black)
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Cmyk;
+
+        impl ColorModel for Cmyk {
+            const CIRCULAR: Range<usize> = 0..0;
+            const LINEAR: Range<usize> = 0..4;
+            const ALPHA: usize = 4;
+
+            fn into_rgba<P: Pixel<Model = Self>>(p: P) -> PixRgba<P> {
+                let ch = p.channels();
+                let k = 1.0 - ch[3].to_f32();
+                let [r, g, b] = [0, 1, 2].map(|i| (1.0 - ch[i].to_f32()) * k);
+                PixRgba::<P>::new(r, g, b, p.alpha().to_f32())
+            }
+
+            fn from_rgba<P: Pixel<Model = Self>>(rgba: PixRgba<P>) -> P {
+                let [r, g, b, a] = rgba.into_array().map(|c| c.to_f32());
+                let k = 1.0 - r.max(g).max(b);
+                let d = if k < 1.0 { 1.0 - k } else { 1.0 };
+                let [c, m, y] = [r, g, b].map(|v| (1.0 - v - k) / d);
+                P::from_channels(&[c, m, y, k, a].map(P::Chan::from))
+            }
+        }
+
+        type Cmyka32 = Pix<Ch32, Cmyk, Straight, Linear, 5>;
+
+        let p = Cmyka32::from([0.0, 1.0, 1.0, 0.5, 0.75]);
+        assert_eq!(p.alpha(), Ch32::new(0.75));
+        let rgba: Rgba32 = p.convert();
+        assert_eq!(rgba, Rgba32::new(0.5, 0.0, 0.0, 0.75));
+        assert_eq!(rgba.convert::<Cmyka32>(), p);
+        assert_eq!(Cmyka32::default().channels().len(), 5);
+
+        // filters must handle more than four channels
+        let near = |q: &Cmyka32| {
+            q.channels()
+                .iter()
+                .zip(p.channels())
+                .all(|(a, b)| (a.to_f32() - b.to_f32()).abs() < 1e-4)
+        };
+        let r = Raster::<Cmyka32>::with_color(4, 4, p);
+        let s = r.scaled(8, 6, Filter::Lanczos3);
+        assert_eq!((s.width(), s.height()), (8, 6));
+        assert!(s.pixels().iter().all(near));
+        assert!(r.scaled(2, 2, Filter::Bilinear).pixels().iter().all(near));
+        assert!(r.gaussian_blur(1.5).pixels().iter().all(near));
+        assert!(r.gaussian_blur(5.0).pixels().iter().all(near));
+        assert!(r.box_blur(1, 2).pixels().iter().all(near));
+        assert!(near(&r.sample(
+            1.5,
+            2.5,
+            Filter::CatmullRom,
+            EdgeMode::Clamp
+        )));
+    }
+
+    #[test]
+    fn saturate() {
+        let p = SRgb8::new(0x80, 0x60, 0x40);
+        let p1 = p.saturate(1.0);
+        assert_eq!(p1, SRgb8::new(0x94, 0x5A, 0x00));
+        assert_eq!(p.saturate(0.0), p);
+        assert_eq!(p.desaturate(0.0), p);
+        let gray = p.desaturate(1.0);
+        assert_eq!(gray.one(), gray.two());
+        assert_eq!(gray.two(), gray.three());
+        assert_eq!(gray, p.desaturate(2.0));
+        assert_eq!(gray, p.saturate(-1.0));
+        assert_eq!(gray, p.saturate(-3.0));
+        let g = SGraya8::new(0x80, 0x40);
+        assert_eq!(g.saturate(1.0), g);
+    }
+
+    #[test]
+    fn lighten_darken() {
+        let p = SRgba8::new(0x20, 0x60, 0xC0, 0x80);
+        for space in [MixSpace::Srgb, MixSpace::Linear, MixSpace::Oklab] {
+            assert_eq!(p.lighten(0.0, space), p);
+            assert_eq!(p.darken(0.0, space), p);
+            let w = p.lighten(1.0, space);
+            assert_eq!(w, SRgba8::new(0xFF, 0xFF, 0xFF, 0x80), "{space:?}");
+            let b = p.darken(2.0, space);
+            assert_eq!(b, SRgba8::new(0, 0, 0, 0x80), "{space:?}");
+        }
+        let p = SRgb8::new(0x00, 0x00, 0x00);
+        assert_eq!(
+            p.lighten(0.5, MixSpace::Srgb),
+            SRgb8::new(0x80, 0x80, 0x80)
+        );
+        assert_eq!(
+            p.lighten(0.5, MixSpace::Linear),
+            SRgb8::new(0xBC, 0xBC, 0xBC)
+        );
+        assert_eq!(
+            p.lighten(0.5, MixSpace::Oklab),
+            SRgb8::new(0x63, 0x63, 0x63)
+        );
+        let p = Gray16::new(0x8000);
+        assert_eq!(p.darken(0.5, MixSpace::Linear), Gray16::new(0x4000));
+    }
+
+    #[test]
+    fn convert_gamut() {
+        use crate::oklab::Oklab32;
+        use crate::xyz::Xyza32;
+        let p = SRgba8::new(0x20, 0x80, 0xC0, 0x80);
+        for map in [GamutMap::Clip, GamutMap::Chroma] {
+            assert_eq!(p.convert_gamut::<SRgba8>(map), p);
+            let p8 = p.convert_gamut::<Rgba8p>(map);
+            assert!(p8.approx_eq(p.convert(), 1e-2), "{p8:?}");
+        }
+        let p = Oklab32::new(0.5, 0.3, 0.0);
+        let rgb: Rgb32 = p.convert_gamut(GamutMap::Clip);
+        assert_eq!(rgb, p.convert());
+        let rgb: Rgb32 = p.convert_gamut(GamutMap::Chroma);
+        let [l, a, b] = linear_rgb_to_oklab(rgb.into_array().map(f32::from));
+        assert!((l - 0.5).abs() < 1e-3, "{l}");
+        assert!(a > 0.1 && a < 0.3 && b.abs() < 1e-3, "{a} {b}");
+        let p = Xyza32::new(0.2, 0.5, 0.1, 0.5);
+        let clip: Rgba8 = p.convert_gamut(GamutMap::Clip);
+        let chroma: Rgba8 = p.convert_gamut(GamutMap::Chroma);
+        assert_eq!(clip, p.convert());
+        assert_eq!(chroma, Rgba8::new(0x00, 0xA9, 0x1F, 0x80));
+    }
+
+    #[test]
+    fn luminance() {
+        use crate::chan::{Ch16, Ch8};
+        assert_eq!(SGray8::new(0x80).luminance(), Ch8::new(0x37));
+        assert_eq!(Gray8::new(0x80).luminance(), Ch8::new(0x80));
+        assert_eq!(
+            Rgba16p::new(0, 0, 0x8000, 0x8000).luminance(),
+            Ch16::new(4732)
+        );
+        assert_eq!(Matte8::new(0x40).luminance(), Ch8::new(0xFF));
+    }
+
+    #[test]
+    fn convert_lossy() {
+        let p = Rgba16::new(0x00FF, 0x7F7F, 0x0080, 0xFFFF);
+        let p8 = Rgba8::from_bit_depth_lossy(p);
+        assert_eq!(p8, Rgba8::new(0x01, 0x7F, 0x00, 0xFF));
+        assert_eq!(p.convert_lossy::<Rgba8>(), p8);
+        // missing channels are filled with MAX (opaque alpha)
+        let p = Rgb16::new(0x1000, 0x8000, 0xFFFF);
+        assert_eq!(
+            Rgba8::from_bit_depth_lossy(p),
+            Rgba8::new(0x10, 0x80, 0xFF, 0xFF)
+        );
+        assert_eq!(p.convert_lossy::<Rgba8>(), p.convert::<Rgba8>());
+        // close to convert when no precision is lost
+        let p = SRgba8::new(0x10, 0x80, 0xC0, 0x80);
+        let p16 = p.convert_lossy::<Rgba16p>();
+        assert!(p16.approx_eq(p.convert::<Rgba16p>(), 1e-4), "{p16:?}");
+        assert_eq!(p.convert_lossy::<SGraya8>(), p.convert::<SGraya8>());
+        let p = Rgb32::new(0.001, 0.002, 0.0);
+        assert_eq!(p.convert::<SRgb8>(), SRgb8::new(0, 13, 0));
+        assert_eq!(p.convert_lossy::<SRgb8>(), SRgb8::new(3, 7, 0));
+    }
+
+    #[test]
+    fn map() {
+        use crate::chan::{Ch16, Ch8, Channel};
+        let p = SRgba8::new(1, 2, 3, 4).map(|c| c + Ch8::new(1));
+        assert_eq!(p, SRgba8::new(2, 3, 4, 5));
+        let p = Rgba16::new(1, 2, 3, 4).map_linear(|_| Ch16::MAX);
+        assert_eq!(p, Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 4));
+        let p = Matte8::new(7).map_linear(|_| Ch8::MAX);
+        assert_eq!(p, Matte8::new(7));
+    }
+
+    #[test]
+    fn labeled_channels() {
+        use crate::ChannelLabel::*;
+        let p = SRgba8::new(1, 2, 3, 4);
+        let l: Vec<_> = p.labeled_channels().map(|(l, _)| l).collect();
+        assert_eq!(l, [Red, Green, Blue, Alpha]);
+        let l: Vec<_> = Graya16::new(1, 2).labeled_channels().collect();
+        assert_eq!(l[1], (Alpha, crate::chan::Ch16::new(2)));
+        let l: Vec<_> = Matte8::new(9).labeled_channels().collect();
+        assert_eq!(l, vec![(Alpha, crate::chan::Ch8::new(9))]);
+        assert_eq!(crate::ycc::YCbCr8::default().labeled_channels().len(), 3);
+        assert_eq!(Cb.to_string(), "Cb");
+    }
+
+    #[test]
+    fn approx_eq() {
+        let p = Rgba32::new(0.25, 0.5, 0.75, 1.0);
+        assert!(p.approx_eq(Rgba32::new(0.2501, 0.5, 0.75, 1.0), 1e-3));
+        assert!(!p.approx_eq(Rgba32::new(0.25, 0.5, 0.76, 1.0), 1e-3));
+        assert!(SRgb8::new(10, 20, 30).approx_eq(SRgb8::new(11, 20, 30), 0.01));
+        // hue wraps around
+        let h = crate::hsv::Hsv32::new(0.999, 0.5, 0.5);
+        assert!(h.approx_eq(crate::hsv::Hsv32::new(0.001, 0.5, 0.5), 0.01));
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    fn approx_traits() {
+        let p = Rgb32::new(0.3001, 0.5, 0.5);
+        approx::assert_abs_diff_eq!(
+            p,
+            Rgb32::new(0.3, 0.5, 0.5),
+            epsilon = 1e-3
+        );
+        approx::assert_relative_eq!(
+            p,
+            Rgb32::new(0.3, 0.5, 0.5),
+            max_relative = 1e-3
+        );
+        approx::assert_abs_diff_ne!(p, Rgb32::new(0.3, 0.5, 0.6));
+        let h = crate::hsv::Hsv32::new(0.9999, 0.5, 0.5);
+        approx::assert_relative_eq!(
+            h,
+            crate::hsv::Hsv32::new(0.0, 0.5, 0.5),
+            epsilon = 1e-3
+        );
+        approx::assert_relative_ne!(h, crate::hsv::Hsv32::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn try_from_channels() {
+        use crate::chan::Ch8;
+        let ch = [Ch8::new(1), Ch8::new(2), Ch8::new(3), Ch8::new(4)];
+        assert_eq!(SRgb8::try_from_channels(&ch[..3]), Ok(SRgb8::new(1, 2, 3)));
+        assert_eq!(
+            SRgb8::try_from_channels(&ch),
+            Err(PixelError::WrongChannelCount {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            SGraya8::try_from_channels(&ch[..1]),
+            Err(PixelError::WrongChannelCount {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn extra_channels() {
+        use crate::chan::{Ch8, Straight};
+        type Rgbx8 = Pix<Ch8, Rgb, Straight, Linear, 5>;
+        let p: Rgbx8 = Rgba8::new(1, 2, 3, 4).convert();
+        assert_eq!(p, Rgbx8::from([1, 2, 3, 4, 0xFF]));
+    }
+
+    #[test]
+    fn try_from_bit_depth() {
+        assert_eq!(
+            Rgba16::try_from_bit_depth(Rgba8::new(1, 2, 3, 4)),
+            Ok(Rgba16::new(0x0101, 0x0202, 0x0303, 0x0404))
+        );
+        assert_eq!(
+            Gray8::try_from_bit_depth(Matte16::new(1)),
+            Err(PixelError::ModelMismatch)
+        );
+    }
+
+    #[test]
+    fn swizzle() {
+        let src = [Rgb16::new(1, 2, 3), Rgb16::new(4, 5, 6)];
+        let mut dst = [Graya16::default(); 2];
+        Rgb16::swizzle_slice(&mut dst, &src, &[1, 1]);
+        assert_eq!(dst, [Graya16::new(2, 2), Graya16::new(5, 5)]);
+        let src = [SRgb8::new(1, 2, 3), SRgb8::new(4, 5, 6)];
+        let mut dst = [SRgba8::default(); 2];
+        SRgb8::swizzle_slice(&mut dst, &src, &[2, 0, 1, 0]);
+        assert_eq!(dst, [SRgba8::new(3, 1, 2, 1), SRgba8::new(6, 4, 5, 4)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swizzle_invalid() {
+        let mut dst = [Rgb8::default()];
+        Rgb8::swizzle_slice(&mut dst, &[Rgb8::default()], &[0, 1, 3]);
+    }
+
+    #[test]
+    fn rgb_bgr() {
+        use crate::bgr::*;
+        let p: Bgra8 = Rgba8::new(1, 2, 3, 4).convert();
+        assert_eq!(p, Bgra8::new(3, 2, 1, 4));
+        let p: SRgb32 = SBgr32::new(0.25, 0.5, 1.0).convert();
+        assert_eq!(p, SRgb32::new(1.0, 0.5, 0.25));
+        let p: Bgra8 = Rgb8::new(1, 2, 3).convert();
+        assert_eq!(p, Bgra8::new(3, 2, 1, 255));
+    }
+
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn bytemuck() {
+        let p = [Rgba8::new(1, 2, 3, 4), Rgba8::new(5, 6, 7, 8)];
+        let b: &[u8] = bytemuck::cast_slice(&p);
+        assert_eq!(b, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let g: &[SGray16] = bytemuck::cast_slice(&[0x1234_u16, 0x5678]);
+        assert_eq!(g, [SGray16::new(0x1234), SGray16::new(0x5678)]);
+        let m: Matte8 = bytemuck::Zeroable::zeroed();
+        assert_eq!(m, Matte8::new(0));
+    }
+
     #[test]
     fn check_sizes() {
         assert_eq!(std::mem::size_of::<Matte8>(), 1);
@@ -847,6 +2646,96 @@ mod test {
         );
     }
 
+    #[test]
+    fn lerp_rgb() {
+        assert_eq!(
+            Rgb8::new(0x40, 0x7F, 0xBF),
+            Rgb8::new(0x00, 0xFF, 0x80)
+                .lerp(Rgb8::new(0x80, 0x00, 0xFF), 0x80.into()),
+        );
+        assert_eq!(
+            Rgb32::new(0.25, 0.5, 0.75),
+            Rgb32::new(0.0, 0.5, 1.0)
+                .lerp(Rgb32::new(1.0, 0.5, 0.0), 0.25.into()),
+        );
+    }
+
+    #[test]
+    fn lerp_straight_alpha() {
+        // transparent pixel color does not bleed into result
+        let p0 = Rgba8::new(0xFF, 0x00, 0x00, 0x00);
+        let p1 = Rgba8::new(0x00, 0x00, 0xFF, 0xFF);
+        assert_eq!(
+            Rgba8::new(0x00, 0x00, 0xFF, 0x80),
+            p0.lerp(p1, 0x80.into()),
+        );
+        let p0 = Rgba32::new(1.0, 0.0, 0.0, 0.0);
+        let p1 = Rgba32::new(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(Rgba32::new(0.0, 0.0, 1.0, 0.5), p0.lerp(p1, 0.5.into()),);
+    }
+
+    #[test]
+    fn lerp_hue() {
+        use crate::hsv::*;
+        // shortest way around the color wheel
+        assert_eq!(
+            Hsv32::new(0.0, 1.0, 1.0),
+            Hsv32::new(0.9, 1.0, 1.0)
+                .lerp(Hsv32::new(0.1, 1.0, 1.0), 0.5.into()),
+        );
+        assert_eq!(
+            Hsv8::new(0x30, 0xFF, 0xFF),
+            Hsv8::new(0x20, 0xFF, 0xFF)
+                .lerp(Hsv8::new(0x40, 0xFF, 0xFF), 0x80.into()),
+        );
+    }
+
+    #[test]
+    fn premultiply_u8_exact() {
+        for a in 0..=255 {
+            for c in 0..=255 {
+                let v = (u32::from(c) * u32::from(a) + 127) / 255;
+                assert_eq!(premultiply_u8(c, a), v as u8);
+            }
+        }
+    }
+
+    #[test]
+    fn unpremultiply_u8_exact() {
+        for a in 1..=255 {
+            for c in 0..=255 {
+                let (c2, a2) = (u32::from(c) * 510, u32::from(a) * 2);
+                let v = ((c2 + u32::from(a)) / a2).min(255);
+                assert_eq!(unpremultiply_u8(c, a), v as u8);
+            }
+        }
+    }
+
+    #[test]
+    fn premultiply_slices() {
+        let src = [
+            Rgba8::new(0xFF, 0x80, 0x40, 0x80),
+            Rgba8::new(0x20, 0x40, 0x80, 0x00),
+        ];
+        let mut dst = [Rgba8p::default(); 2];
+        Rgba8::premultiply_slice(&mut dst, &src);
+        assert_eq!(
+            dst,
+            [
+                Rgba8p::new(0x80, 0x40, 0x20, 0x80),
+                Rgba8p::new(0x00, 0x00, 0x00, 0x00),
+            ]
+        );
+        let mut back = [Rgba8::default(); 2];
+        Rgba8p::unpremultiply_slice(&mut back, &dst);
+        assert_eq!(back[0], Rgba8::new(0xFF, 0x80, 0x40, 0x80));
+        // sRGB falls back to conversion thru linear gamma
+        let src = [SRgba8::new(0x20, 0x40, 0x80, 0x80)];
+        let mut dst = [SRgba8p::default()];
+        SRgba8::premultiply_slice(&mut dst, &src);
+        assert_eq!(dst[0], SRgba8p::new(0x16, 0x2A, 0x5C, 0x80));
+    }
+
     #[test]
     fn straight_to_premultiplied_srgb() {
         assert_eq!(
@@ -862,4 +2751,32 @@ mod test {
             SRgba32::new(0.5, 1.0, 0.75, 0.75).convert(),
         );
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let p = Rgba8::new(1, 2, 3, 4);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "[1,2,3,4]");
+        assert_eq!(serde_json::from_str::<Rgba8>(&json).unwrap(), p);
+        let json = serde_json::to_string(&Gray32::new(0.5)).unwrap();
+        assert_eq!(json, "[0.5]");
+        let p = serde_json::from_str::<Gray32>("[2.0]").unwrap();
+        assert_eq!(p, Gray32::new(1.0));
+        assert!(serde_json::from_str::<Rgb8>("[1,2]").is_err());
+    }
+
+    #[test]
+    fn arrays() {
+        let p = Rgba8::from([1u8, 2, 3, 4]);
+        assert_eq!(p, Rgba8::new(1, 2, 3, 4));
+        assert_eq!(<[u8; 4]>::from(p), [1, 2, 3, 4]);
+        assert_eq!(Rgb16::from((1u16, 2, 3)), Rgb16::new(1, 2, 3));
+        let (r, g, b): (u16, u16, u16) = Rgb16::new(4, 5, 6).into();
+        assert_eq!((r, g, b), (4, 5, 6));
+        let [v]: [f32; 1] = Gray32::new(0.25).into();
+        assert_eq!(v, 0.25);
+        let p: Graya8 = (0x80u8, 0xFF).into();
+        assert_eq!(p.into_array(), [Ch8::new(0x80), Ch8::new(0xFF)]);
+    }
 }
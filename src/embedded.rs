@@ -0,0 +1,212 @@
+// embedded.rs  Interop with `embedded-graphics`.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Interop with the `embedded-graphics` crate (feature `embedded-graphics`).
+//!
+//! Pixel conversions are provided between [SRgb8] and `Rgb888` / `Rgb565`,
+//! and between [SGray8] and `Gray8`.  A [DrawRaster] adapter implements
+//! `DrawTarget` for any [Raster], so that embedded UIs can be rendered to
+//! an image for previews or tests.
+//!
+//! [DrawRaster]: struct.DrawRaster.html
+//! [Raster]: ../struct.Raster.html
+//! [SGray8]: ../gray/type.SGray8.html
+//! [SRgb8]: ../rgb/type.SRgb8.html
+use crate::chan::Ch8;
+use crate::el::Pixel;
+use crate::gray::SGray8;
+use crate::raster::Raster;
+use crate::rgb::{Rgb, SRgb8};
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::{
+    Gray8, GrayColor, PixelColor, Rgb565, Rgb888, RgbColor,
+};
+use embedded_graphics_core::primitives::Rectangle;
+use std::convert::Infallible;
+use std::marker::PhantomData;
+
+impl From<Rgb888> for SRgb8 {
+    /// Convert from an `embedded-graphics` `Rgb888` color.
+    fn from(c: Rgb888) -> Self {
+        SRgb8::new(c.r(), c.g(), c.b())
+    }
+}
+
+impl From<SRgb8> for Rgb888 {
+    /// Convert to an `embedded-graphics` `Rgb888` color.
+    fn from(p: SRgb8) -> Self {
+        let (r, g, b) = (Rgb::red(p), Rgb::green(p), Rgb::blue(p));
+        Rgb888::new(r.into(), g.into(), b.into())
+    }
+}
+
+impl From<Rgb565> for SRgb8 {
+    /// Convert from an `embedded-graphics` `Rgb565` color.
+    fn from(c: Rgb565) -> Self {
+        SRgb8::from(Rgb888::from(c))
+    }
+}
+
+impl From<SRgb8> for Rgb565 {
+    /// Convert to an `embedded-graphics` `Rgb565` color, rounding to the
+    /// nearest value.
+    fn from(p: SRgb8) -> Self {
+        Rgb565::from(Rgb888::from(p))
+    }
+}
+
+impl From<Gray8> for SGray8 {
+    /// Convert from an `embedded-graphics` `Gray8` color.
+    fn from(c: Gray8) -> Self {
+        SGray8::new(c.luma())
+    }
+}
+
+impl From<SGray8> for Gray8 {
+    /// Convert to an `embedded-graphics` `Gray8` color.
+    fn from(p: SGray8) -> Self {
+        Gray8::new(u8::from(p.one()))
+    }
+}
+
+/// `DrawTarget` adapter over a [Raster]
+///
+/// * `P` `Pixel` format of `Raster`.
+/// * `C` `embedded-graphics` color type.
+///
+/// Colors are converted to *sRGB*, then to the raster's pixel format.
+/// Pixels drawn outside of the raster are ignored.
+///
+/// ### Example
+/// ```
+/// use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+/// use embedded_graphics_core::prelude::*;
+/// use pix::embedded::DrawRaster;
+/// use pix::rgb::SRgb8;
+/// use pix::Raster;
+///
+/// let mut r = Raster::<SRgb8>::with_clear(8, 8);
+/// let mut target = DrawRaster::<_, Rgb565>::new(&mut r);
+/// Pixel(Point::new(2, 3), Rgb565::RED).draw(&mut target).unwrap();
+/// assert_eq!(r.pixel(2, 3), SRgb8::new(255, 0, 0));
+/// ```
+///
+/// [Raster]: ../struct.Raster.html
+pub struct DrawRaster<'a, P: Pixel, C> {
+    /// Target raster
+    raster: &'a mut Raster<P>,
+    /// Color type
+    _color: PhantomData<C>,
+}
+
+impl<'a, P: Pixel, C> DrawRaster<'a, P, C> {
+    /// Create a `DrawTarget` adapter for a raster.
+    pub fn new(raster: &'a mut Raster<P>) -> Self {
+        DrawRaster {
+            raster,
+            _color: PhantomData,
+        }
+    }
+}
+
+impl<P, C> DrawRaster<'_, P, C>
+where
+    P: Pixel,
+    P::Chan: From<Ch8>,
+    C: Into<Rgb888>,
+{
+    /// Convert a color to the raster's pixel format
+    fn pixel(color: C) -> P {
+        SRgb8::from(color.into()).convert()
+    }
+}
+
+impl<P: Pixel, C> OriginDimensions for DrawRaster<'_, P, C> {
+    fn size(&self) -> Size {
+        Size::new(self.raster.width(), self.raster.height())
+    }
+}
+
+impl<P, C> DrawTarget for DrawRaster<'_, P, C>
+where
+    P: Pixel,
+    P::Chan: From<Ch8>,
+    C: PixelColor + Into<Rgb888>,
+{
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = embedded_graphics_core::Pixel<C>>,
+    {
+        let (width, height) = (self.raster.width(), self.raster.height());
+        for embedded_graphics_core::Pixel(pt, color) in pixels {
+            if (pt.x as u32) < width && (pt.y as u32) < height {
+                *self.raster.pixel_mut(pt.x, pt.y) = Self::pixel(color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(
+        &mut self,
+        area: &Rectangle,
+        color: Self::Color,
+    ) -> Result<(), Self::Error> {
+        let reg = (
+            area.top_left.x,
+            area.top_left.y,
+            area.size.width,
+            area.size.height,
+        );
+        self.raster.copy_color(reg, Self::pixel(color));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgba16;
+    use embedded_graphics_core::geometry::Point;
+    use embedded_graphics_core::Drawable;
+
+    #[test]
+    fn colors() {
+        assert_eq!(SRgb8::from(Rgb888::new(1, 2, 3)), SRgb8::new(1, 2, 3));
+        assert_eq!(Rgb888::from(SRgb8::new(1, 2, 3)), Rgb888::new(1, 2, 3));
+        assert_eq!(
+            SRgb8::from(Rgb565::new(31, 0, 16)),
+            SRgb8::new(255, 0, 132)
+        );
+        assert_eq!(
+            Rgb565::from(SRgb8::new(255, 128, 0)),
+            Rgb565::new(31, 32, 0)
+        );
+        assert_eq!(SGray8::from(Gray8::new(77)), SGray8::new(77));
+        assert_eq!(Gray8::from(SGray8::new(77)), Gray8::new(77));
+    }
+
+    #[test]
+    fn draw_target() {
+        let mut r = Raster::<Rgba16>::with_clear(4, 4);
+        let mut t = DrawRaster::<_, Gray8>::new(&mut r);
+        assert_eq!(t.size(), Size::new(4, 4));
+        let area = Rectangle::new(Point::new(2, -1), Size::new(5, 2));
+        t.fill_solid(&area, Gray8::WHITE).unwrap();
+        embedded_graphics_core::Pixel(Point::new(9, 0), Gray8::WHITE)
+            .draw(&mut t)
+            .unwrap();
+        embedded_graphics_core::Pixel(Point::new(0, 3), Gray8::new(0))
+            .draw(&mut t)
+            .unwrap();
+        let white = Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF);
+        assert_eq!(r.pixel(3, 0), white);
+        assert_eq!(r.pixel(1, 0), Rgba16::default());
+        assert_eq!(r.pixel(2, 1), Rgba16::default());
+        assert_eq!(r.pixel(0, 3), Rgba16::new(0, 0, 0, 0xFFFF));
+    }
+}
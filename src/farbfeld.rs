@@ -0,0 +1,104 @@
+// farbfeld.rs  Farbfeld codec.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::codec::{pixel_count, CodecError};
+use crate::el::Pixel;
+use crate::raster::Raster;
+use crate::rgb::SRgba16;
+
+/// Farbfeld magic value
+const MAGIC: &[u8] = b"farbfeld";
+
+/// Length of farbfeld header
+const HEADER_LEN: usize = 16;
+
+/// Read a big-endian `u32`
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+impl Raster<SRgba16> {
+    /// Decode a [farbfeld] file.
+    ///
+    /// * `ff` File contents.
+    ///
+    /// ### Round trip
+    /// ```
+    /// use pix::rgb::SRgba16;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(3, 2, SRgba16::new(0x1234, 0, 0xFFFF, 0x8000));
+    /// let ff = r.to_farbfeld();
+    /// assert_eq!(ff.len(), 16 + 3 * 2 * 8);
+    /// let r2 = Raster::<SRgba16>::from_farbfeld(&ff).unwrap();
+    /// assert_eq!(r2.pixels(), r.pixels());
+    /// ```
+    ///
+    /// [farbfeld]: https://tools.suckless.org/farbfeld/
+    pub fn from_farbfeld(ff: &[u8]) -> Result<Self, CodecError> {
+        if ff.len() < HEADER_LEN || !ff.starts_with(MAGIC) {
+            return Err(CodecError::InvalidHeader);
+        }
+        let width = be_u32(&ff[8..]);
+        let height = be_u32(&ff[12..]);
+        let len = pixel_count(width, height)?
+            .checked_mul(8)
+            .ok_or(CodecError::TooBig)?;
+        let data = ff[HEADER_LEN..].get(..len).ok_or(CodecError::Truncated)?;
+        let pixels: Vec<_> = data
+            .chunks_exact(8)
+            .map(|p| {
+                let c = |i: usize| u16::from_be_bytes([p[i], p[i + 1]]);
+                SRgba16::new(c(0), c(2), c(4), c(6))
+            })
+            .collect();
+        Ok(Raster::with_pixels(width, height, pixels))
+    }
+
+    /// Encode as a [farbfeld] file.
+    ///
+    /// [farbfeld]: https://tools.suckless.org/farbfeld/
+    pub fn to_farbfeld(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(HEADER_LEN + self.pixels().len() * 8);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.width().to_be_bytes());
+        bytes.extend_from_slice(&self.height().to_be_bytes());
+        for p in self.pixels() {
+            for c in p.channels() {
+                bytes.extend_from_slice(&u16::from(*c).to_be_bytes());
+            }
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn farbfeld() {
+        let mut ff = b"farbfeld\0\0\0\x01\0\0\0\x01".to_vec();
+        ff.extend_from_slice(&[0x12, 0x34, 0, 1, 0xFF, 0xFF, 0x80, 0]);
+        let r = Raster::<SRgba16>::from_farbfeld(&ff).unwrap();
+        assert_eq!(r.pixel(0, 0), SRgba16::new(0x1234, 1, 0xFFFF, 0x8000));
+        assert_eq!(r.to_farbfeld(), ff);
+    }
+
+    #[test]
+    fn errors() {
+        use CodecError::*;
+        let r = Raster::<SRgba16>::from_farbfeld(b"farbfeld\0\0\0\x01");
+        assert_eq!(r.err(), Some(InvalidHeader));
+        let r = Raster::<SRgba16>::from_farbfeld(b"farbfelt\0\0\0\0\0\0\0\0");
+        assert_eq!(r.err(), Some(InvalidHeader));
+        let r =
+            Raster::<SRgba16>::from_farbfeld(b"farbfeld\0\0\0\x01\0\0\0\x01");
+        assert_eq!(r.err(), Some(Truncated));
+        let r =
+            Raster::<SRgba16>::from_farbfeld(b"farbfeld\xFF\0\0\0\0\0\0\x01");
+        assert_eq!(r.err(), Some(TooBig));
+    }
+}
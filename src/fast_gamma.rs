@@ -0,0 +1,202 @@
+// fast_gamma.rs    Fast approximate gamma transfer functions.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Fast approximate gamma transfer functions for `f32` values.
+//!
+//! These are useful when exact results are not required, such as for
+//! previews or thumbnails.  Powers are calculated with polynomial `log2` /
+//! `exp2` approximations, and loops are branch-free so that they can be
+//! auto-vectorized.
+//!
+//! Values are clamped to the range `0.0` to `1.0`.  Maximum absolute errors
+//! versus the exact transfer functions are:
+//!
+//! | Function          | Max error |
+//! |-------------------|-----------|
+//! | [srgb_encode]     | 5.0e-7    |
+//! | [srgb_decode]     | 1.0e-6    |
+//! | [pq_encode]       | 5.0e-5    |
+//! | [pq_decode]       | 1.0e-4    |
+//!
+//! [pq_decode]: fn.pq_decode.html
+//! [pq_encode]: fn.pq_encode.html
+//! [srgb_decode]: fn.srgb_decode.html
+//! [srgb_encode]: fn.srgb_encode.html
+
+/// PQ constant `m1`
+const PQ_M1: f32 = 2610.0 / 16384.0;
+
+/// PQ constant `m2`
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+
+/// PQ constant `c1`
+const PQ_C1: f32 = 3424.0 / 4096.0;
+
+/// PQ constant `c2`
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+
+/// PQ constant `c3`
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+/// Encode sRGB gamma values from linear intensity.
+///
+/// ### Example
+/// ```
+/// use pix::fast_gamma::srgb_encode;
+///
+/// let mut v = [0.0, 0.5, 1.0];
+/// srgb_encode(&mut v);
+/// assert!((v[1] - 0.735_357).abs() < 5.0e-7);
+/// ```
+pub fn srgb_encode(values: &mut [f32]) {
+    for v in values.iter_mut() {
+        let x = v.clamp(0.0, 1.0);
+        let s = pow(x, 1.0 / 2.4) * 1.055 - 0.055;
+        *v = if x < 0.003_130_8 { x * 12.92 } else { s };
+    }
+}
+
+/// Decode sRGB gamma values into linear intensity.
+pub fn srgb_decode(values: &mut [f32]) {
+    for v in values.iter_mut() {
+        let x = v.clamp(0.0, 1.0);
+        let s = pow((x + 0.055) / 1.055, 2.4);
+        *v = if x < 0.04045 { x / 12.92 } else { s };
+    }
+}
+
+/// Encode PQ (SMPTE ST 2084) values from linear intensity.
+///
+/// Linear intensity of `1.0` is 10,000 cd/m².
+pub fn pq_encode(values: &mut [f32]) {
+    for v in values.iter_mut() {
+        let y = pow(v.clamp(0.0, 1.0), PQ_M1);
+        *v = pow((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y), PQ_M2);
+    }
+}
+
+/// Decode PQ (SMPTE ST 2084) values into linear intensity.
+///
+/// Linear intensity of `1.0` is 10,000 cd/m².
+pub fn pq_decode(values: &mut [f32]) {
+    for v in values.iter_mut() {
+        let e = pow(v.clamp(0.0, 1.0), 1.0 / PQ_M2);
+        let n = (e - PQ_C1).max(0.0);
+        *v = pow(n / (PQ_C2 - PQ_C3 * e), 1.0 / PQ_M1);
+    }
+}
+
+/// Approximate `x` raised to power `y` (`x` must be non-negative)
+#[inline]
+fn pow(x: f32, y: f32) -> f32 {
+    let p = exp2(y * log2(x));
+    if x > 0.0 {
+        p
+    } else {
+        0.0
+    }
+}
+
+/// Approximate base-2 logarithm of a positive normal value
+#[inline]
+fn log2(x: f32) -> f32 {
+    let bits = x.to_bits();
+    let e = ((bits >> 23) & 0xFF) as i32 - 127;
+    let t = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000) - 1.0;
+    // log2(1 + t) = t * P(t), for t in [0, 1)
+    let p = 0.015_127_686_f32;
+    let p = p * t - 0.078_157_397;
+    let p = p * t + 0.192_383_93;
+    let p = p * t - 0.324_615_6;
+    let p = p * t + 0.473_113_1;
+    let p = p * t - 0.720_515_5;
+    let p = p * t + 1.442_664;
+    (e as f32) + t * p
+}
+
+/// Approximate base-2 exponential
+#[inline]
+fn exp2(x: f32) -> f32 {
+    let x = x.clamp(-126.0, 126.0);
+    let i = x.floor();
+    let f = x - i;
+    // 2 ^ f, for f in [0, 1)
+    let p = 0.000_218_783_48_f32;
+    let p = p * f + 0.001_238_756_8;
+    let p = p * f + 0.009_684_609;
+    let p = p * f + 0.055_480_41;
+    let p = p * f + 0.240_230_5;
+    let p = p * f + 0.693_146_94;
+    let p = p * f + 1.0;
+    f32::from_bits(p.to_bits().wrapping_add(((i as i32) << 23) as u32))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chan::{Ch32, Channel};
+
+    /// Exact PQ encode
+    fn pq_encode_exact(v: f64) -> f64 {
+        let (m1, m2) = (PQ_M1 as f64, PQ_M2 as f64);
+        let (c1, c2, c3) = (PQ_C1 as f64, PQ_C2 as f64, PQ_C3 as f64);
+        let y = v.powf(m1);
+        ((c1 + c2 * y) / (1.0 + c3 * y)).powf(m2)
+    }
+
+    /// Exact PQ decode
+    fn pq_decode_exact(v: f64) -> f64 {
+        let (m1, m2) = (PQ_M1 as f64, PQ_M2 as f64);
+        let (c1, c2, c3) = (PQ_C1 as f64, PQ_C2 as f64, PQ_C3 as f64);
+        let e = v.powf(1.0 / m2);
+        ((e - c1).max(0.0) / (c2 - c3 * e)).powf(1.0 / m1)
+    }
+
+    /// Get maximum error of an approximation
+    fn max_error<A, E>(approx: A, exact: E) -> f64
+    where
+        A: Fn(&mut [f32]),
+        E: Fn(f32) -> f64,
+    {
+        let mut v: Vec<f32> = (0..=100_000).map(|i| i as f32 / 1e5).collect();
+        let x = v.clone();
+        approx(&mut v);
+        v.iter()
+            .zip(x)
+            .map(|(a, x)| (*a as f64 - exact(x)).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn srgb() {
+        let err = max_error(srgb_encode, |x| {
+            Ch32::new(x).encode_srgb().to_f32() as f64
+        });
+        assert!(err < 5.0e-7, "{err}");
+        let err = max_error(srgb_decode, |x| {
+            Ch32::new(x).decode_srgb().to_f32() as f64
+        });
+        assert!(err < 1.0e-6, "{err}");
+    }
+
+    #[test]
+    fn pq() {
+        let err = max_error(pq_encode, |x| pq_encode_exact(x as f64));
+        assert!(err < 5.0e-5, "{err}");
+        let err = max_error(pq_decode, |x| pq_decode_exact(x as f64));
+        assert!(err < 1.0e-4, "{err}");
+    }
+
+    #[test]
+    fn clamped() {
+        let mut v = [-1.0, 2.0];
+        srgb_encode(&mut v);
+        assert_eq!(v[0], 0.0);
+        assert!((v[1] - 1.0).abs() < 5.0e-7);
+        let mut v = [-1.0, 2.0];
+        pq_decode(&mut v);
+        assert_eq!(v[0], 0.0);
+        assert!((v[1] - 1.0).abs() < 1.0e-4);
+    }
+}
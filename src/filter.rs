@@ -0,0 +1,1779 @@
+// filter.rs    Filtering support.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Support for resampling and filtering operations.
+//!
+//! Filters combine many weighted source pixels into one destination pixel
+//! using an [Accumulator].  Accumulation happens in *linear* gamma, and color
+//! channels are weighted by *alpha*, even for formats stored with *straight*
+//! alpha.  This prevents dark halos around the edges of translucent areas.
+//!
+//! Resampling [Filter]s are used for scaling and sampling a [Raster].  A
+//! convolution [Kernel] can be applied with [convolved].  Pixels beyond the
+//! edges are handled using an [EdgeMode].
+//!
+//! [Gradient] operators are used for edge detection.
+//!
+//! [Accumulator]: struct.Accumulator.html
+//! [convolved]: ../struct.Raster.html#method.convolved
+//! [EdgeMode]: enum.EdgeMode.html
+//! [Filter]: enum.Filter.html
+//! [Gradient]: enum.Gradient.html
+//! [Kernel]: struct.Kernel.html
+//! [Raster]: ../struct.Raster.html
+use crate::chan::{Ch32, Channel, Gamma, Straight};
+use crate::el::Pixel;
+use crate::gray::Gray32;
+use crate::private::{MaybeSend, MaybeSync};
+use crate::raster::{for_each_row, Raster};
+use crate::ColorModel;
+use std::any::TypeId;
+use std::f32::consts::{PI, TAU};
+use std::marker::PhantomData;
+
+/// Resampling filter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Filter {
+    /// Nearest neighbor (no interpolation)
+    Nearest,
+
+    /// Bilinear (triangle) interpolation
+    #[default]
+    Bilinear,
+
+    /// Bicubic Catmull-Rom spline (sharp)
+    CatmullRom,
+
+    /// Bicubic Mitchell-Netravali filter (B = C = 1/3)
+    Mitchell,
+
+    /// Lanczos windowed sinc with 3 lobes
+    Lanczos3,
+}
+
+/// Handling of pixels beyond the edges of a [Raster].
+///
+/// Used for [convolved], [sample], [scaled_edge], [gaussian_blur_with],
+/// [gradients_edge] and [rotated] (which uses a constant background color).
+/// Use `Wrap` for tiling textures and panoramas, so that filtering is
+/// seamless across opposite edges.
+///
+/// [convolved]: ../struct.Raster.html#method.convolved
+/// [gaussian_blur_with]: ../struct.Raster.html#method.gaussian_blur_with
+/// [gradients_edge]: ../struct.Raster.html#method.gradients_edge
+/// [raster]: ../struct.Raster.html
+/// [rotated]: ../struct.Raster.html#method.rotated
+/// [sample]: ../struct.Raster.html#method.sample
+/// [scaled_edge]: ../struct.Raster.html#method.scaled_edge
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EdgeMode<P: Pixel> {
+    /// Repeat the nearest edge pixel
+    #[default]
+    Clamp,
+
+    /// Wrap around to the opposite edge (tile)
+    Wrap,
+
+    /// Reflect at the edges, repeating edge pixels (`cba|abc`)
+    Mirror,
+
+    /// Use a constant color
+    Constant(P),
+}
+
+/// Gradient (edge detection) operator.
+///
+/// Derivative weights are normalized, so that a step from `0.0` to `1.0`
+/// has a gradient of `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Gradient {
+    /// Sobel operator (smoothing weights 1, 2, 1)
+    #[default]
+    Sobel,
+
+    /// Scharr operator (smoothing weights 3, 10, 3), which is more
+    /// rotationally symmetric
+    Scharr,
+}
+
+/// Convolution kernel, with an odd width and height.
+///
+/// Weights are in row-major order, and are not normalized.
+///
+/// ### Sharpen kernel
+/// ```
+/// use pix::filter::Kernel;
+///
+/// let k = Kernel::new(3, 3, vec![
+///     0.0, -1.0, 0.0,
+///     -1.0, 5.0, -1.0,
+///     0.0, -1.0, 0.0,
+/// ]);
+/// assert_eq!(k.weight(1, 1), 5.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kernel {
+    /// Width of kernel
+    width: u32,
+    /// Height of kernel
+    height: u32,
+    /// Weights in row-major order
+    weights: Vec<f32>,
+}
+
+/// Source pixel indices and weights for one destination pixel.
+///
+/// Indices can be beyond the source edges, to be handled by an [EdgeMode].
+///
+/// [EdgeMode]: enum.EdgeMode.html
+type Contribution = Vec<(i32, f32)>;
+
+/// Alpha weighting mode for filtering [straight] alpha pixels.
+///
+/// [Premultiplied] pixels are always filtered as stored.
+///
+/// [premultiplied]: ../chan/struct.Premultiplied.html
+/// [straight]: ../chan/struct.Straight.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlphaWeight {
+    /// Weight color channels by *alpha* (operate premultiplied)
+    #[default]
+    Premultiplied,
+
+    /// Filter every channel independently
+    ///
+    /// This is only correct for opaque images; translucent edges get darker.
+    Independent,
+}
+
+impl Filter {
+    /// Get the support radius (in source pixels, when not scaled)
+    pub fn support(self) -> f32 {
+        match self {
+            Filter::Nearest => 0.5,
+            Filter::Bilinear => 1.0,
+            Filter::CatmullRom | Filter::Mitchell => 2.0,
+            Filter::Lanczos3 => 3.0,
+        }
+    }
+
+    /// Get the filter weight at a distance from the sample center
+    pub fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            Filter::Nearest => {
+                if x < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Filter::Bilinear => (1.0 - x).max(0.0),
+            Filter::CatmullRom => cubic(x, 0.0, 0.5),
+            Filter::Mitchell => cubic(x, 1.0 / 3.0, 1.0 / 3.0),
+            Filter::Lanczos3 => {
+                if x < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Calculate source contributions for each destination pixel
+    ///
+    /// * `src` Number of source pixels.
+    /// * `dst` Number of destination pixels.
+    fn contributions(self, src: usize, dst: usize) -> Vec<Contribution> {
+        let scale = src as f32 / dst as f32;
+        let last = src as i32 - 1;
+        (0..dst)
+            .map(|i| {
+                let center = (i as f32 + 0.5) * scale;
+                if self == Filter::Nearest {
+                    let x = (center as i32).min(last);
+                    return vec![(x, 1.0)];
+                }
+                // widen filter when down-scaling to avoid aliasing
+                let fscale = scale.max(1.0);
+                let center = center - 0.5;
+                let support = self.support() * fscale;
+                let left = (center - support).ceil() as i32;
+                let right = (center + support).floor() as i32;
+                let mut con: Contribution = (left..=right)
+                    .map(|x| {
+                        let w = self.weight((x as f32 - center) / fscale);
+                        (x, w)
+                    })
+                    .filter(|(_, w)| *w != 0.0)
+                    .collect();
+                let total: f32 = con.iter().map(|(_, w)| w).sum();
+                con.iter_mut().for_each(|(_, w)| *w /= total);
+                con
+            })
+            .collect()
+    }
+}
+
+impl<P: Pixel> EdgeMode<P> {
+    /// Map a coordinate into the range `0..len`.
+    ///
+    /// Returns `None` for `Constant` mode when out of range, or if `len` is
+    /// zero.
+    fn index(self, x: i32, len: i32) -> Option<i32> {
+        if (0..len).contains(&x) {
+            return Some(x);
+        }
+        if len <= 0 {
+            return None;
+        }
+        match self {
+            EdgeMode::Clamp => Some(x.clamp(0, len - 1)),
+            EdgeMode::Wrap => Some(x.rem_euclid(len)),
+            EdgeMode::Mirror => {
+                let m = x.rem_euclid(2 * len);
+                Some(if m < len { m } else { 2 * len - 1 - m })
+            }
+            EdgeMode::Constant(_) => None,
+        }
+    }
+}
+
+impl Gradient {
+    /// Get normalized smoothing weights, perpendicular to the derivative
+    fn smoothing(self) -> [f32; 3] {
+        match self {
+            Gradient::Sobel => [0.25, 0.5, 0.25],
+            Gradient::Scharr => [3.0 / 16.0, 10.0 / 16.0, 3.0 / 16.0],
+        }
+    }
+}
+
+impl Kernel {
+    /// Create a new convolution kernel.
+    ///
+    /// * `width` Width of kernel (odd).
+    /// * `height` Height of kernel (odd).
+    /// * `weights` Weights in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is even
+    /// * If `weights` length is not equal to `width` * `height`
+    pub fn new(width: u32, height: u32, weights: Vec<f32>) -> Self {
+        assert!(width % 2 == 1 && height % 2 == 1);
+        assert_eq!(weights.len(), width as usize * height as usize);
+        Kernel {
+            width,
+            height,
+            weights,
+        }
+    }
+
+    /// Get width of kernel.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get height of kernel.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get all weights, in row-major order.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Get one weight.
+    pub fn weight(&self, x: u32, y: u32) -> f32 {
+        assert!(x < self.width && y < self.height);
+        self.weights[(y * self.width + x) as usize]
+    }
+
+    /// Scale weights so that they sum to one.
+    ///
+    /// If the sum is zero, the kernel is unchanged.
+    pub fn normalized(mut self) -> Self {
+        let total: f32 = self.weights.iter().sum();
+        if total != 0.0 {
+            self.weights.iter_mut().for_each(|w| *w /= total);
+        }
+        self
+    }
+}
+
+/// Make normalized 1D Gaussian weights, with a radius of 3 sigma
+fn gaussian_weights(sigma: f32) -> Vec<f32> {
+    let radius = (sigma * 3.0).ceil() as i32;
+    let s2 = 2.0 * sigma * sigma;
+    let weights: Vec<f32> = (-radius..=radius)
+        .map(|x| (-(x * x) as f32 / s2).exp())
+        .collect();
+    let total: f32 = weights.iter().sum();
+    weights.into_iter().map(|w| w / total).collect()
+}
+
+/// Calculate area-averaging source contributions for each destination pixel
+///
+/// * `src` Number of source pixels.
+/// * `dst` Number of destination pixels (no more than `src`).
+fn area_contributions(src: usize, dst: usize) -> Vec<Contribution> {
+    let scale = src as f32 / dst as f32;
+    (0..dst)
+        .map(|i| {
+            let start = i as f32 * scale;
+            let end = (i + 1) as f32 * scale;
+            let left = start.floor() as i32;
+            let right = (end.ceil() as i32).min(src as i32);
+            (left..right)
+                .map(|x| {
+                    let overlap = end.min((x + 1) as f32) - start.max(x as f32);
+                    (x, overlap / scale)
+                })
+                .filter(|(_, w)| *w > 0.0)
+                .collect()
+        })
+        .collect()
+}
+
+/// Extend a line of accumulators beyond its ends, using an edge mode
+///
+/// * `line` Accumulators to extend (not empty).
+/// * `pad` Number of elements to add at each end.
+/// * `edge` Handling of elements beyond the ends.
+fn extended<P: Pixel>(
+    line: &[Accumulator<P>],
+    pad: usize,
+    edge: EdgeMode<P>,
+) -> Vec<Accumulator<P>> {
+    let mut constant = line[0];
+    constant.clear();
+    if let EdgeMode::Constant(clr) = edge {
+        constant.add(clr, 1.0);
+    }
+    let (len, pad) = (line.len() as i32, pad as i32);
+    (-pad..len + pad)
+        .map(|x| match edge.index(x, len) {
+            Some(x) => line[x as usize],
+            None => constant,
+        })
+        .collect()
+}
+
+/// Convolve a line of accumulators with a symmetric 1D kernel
+///
+/// * `line` Accumulators to filter.
+/// * `weights` Kernel weights (odd length), centered on each element.
+/// * `edge` Handling of elements beyond the ends.
+fn convolve_line<P: Pixel>(
+    line: &mut [Accumulator<P>],
+    weights: &[f32],
+    edge: EdgeMode<P>,
+) {
+    if line.is_empty() {
+        return;
+    }
+    let radius = weights.len() / 2;
+    let src = extended(line, radius, edge);
+    for (x, d) in line.iter_mut().enumerate() {
+        d.clear();
+        for (i, w) in weights.iter().enumerate() {
+            d.add_accumulator(&src[x + i], *w);
+        }
+    }
+}
+
+/// Filter a line of accumulators with a box (moving average) filter
+///
+/// * `line` Accumulators to filter.
+/// * `radius` Number of elements on each side of the center.
+/// * `edge` Handling of elements beyond the ends.
+///
+/// A running sum is used, so this takes constant time per element.
+fn box_line<P: Pixel>(
+    line: &mut [Accumulator<P>],
+    radius: u32,
+    edge: EdgeMode<P>,
+) {
+    let Some(first) = line.first().copied() else {
+        return;
+    };
+    let radius = radius as usize;
+    // one extra element on each end for the running sum
+    let src = extended(line, radius + 1, edge);
+    let scale = 1.0 / (2 * radius + 1) as f32;
+    let mut sum = first;
+    sum.clear();
+    for a in &src[1..2 * radius + 2] {
+        sum.add_accumulator(a, 1.0);
+    }
+    for (x, d) in line.iter_mut().enumerate() {
+        d.clear();
+        d.add_accumulator(&sum, scale);
+        sum.add_accumulator(&src[x + 2 * radius + 2], 1.0);
+        sum.add_accumulator(&src[x + 1], -1.0);
+    }
+}
+
+/// Filter a line of accumulators with a recursive Gaussian approximation
+///
+/// * `line` Accumulators to filter.
+/// * `sigma` Standard deviation of the Gaussian.
+/// * `edge` Handling of elements beyond the ends.
+///
+/// This uses the 3rd-order IIR filter of Young and van Vliet, with a
+/// forward and backward pass.  It is only accurate for `sigma` >= 2.5.
+fn recursive_gaussian_line<P: Pixel>(
+    line: &mut [Accumulator<P>],
+    sigma: f32,
+    edge: EdgeMode<P>,
+) {
+    let q = 0.98711 * sigma - 0.96330;
+    let (q2, q3) = (q * q, q * q * q);
+    let b0 = 1.57825 + 2.44413 * q + 1.4281 * q2 + 0.422205 * q3;
+    let b1 = (2.44413 * q + 2.85619 * q2 + 1.26661 * q3) / b0;
+    let b2 = -(1.4281 * q2 + 1.26661 * q3) / b0;
+    let b3 = 0.422205 * q3 / b0;
+    let bb = 1.0 - (b1 + b2 + b3);
+    if line.is_empty() {
+        return;
+    }
+    // pad far enough for the response to settle
+    let pad = (sigma * 4.0).ceil() as usize;
+    let mut buf = extended(line, pad, edge);
+    let mut prev = [buf[0]; 3];
+    for v in buf.iter_mut() {
+        let x = *v;
+        v.clear();
+        v.add_accumulator(&x, bb);
+        v.add_accumulator(&prev[0], b1);
+        v.add_accumulator(&prev[1], b2);
+        v.add_accumulator(&prev[2], b3);
+        prev = [*v, prev[0], prev[1]];
+    }
+    let mut prev = [prev[0]; 3];
+    for v in buf.iter_mut().rev() {
+        let x = *v;
+        v.clear();
+        v.add_accumulator(&x, bb);
+        v.add_accumulator(&prev[0], b1);
+        v.add_accumulator(&prev[1], b2);
+        v.add_accumulator(&prev[2], b3);
+        prev = [*v, prev[0], prev[1]];
+    }
+    line.copy_from_slice(&buf[pad..pad + line.len()]);
+}
+
+/// Mitchell-Netravali cubic filter
+fn cubic(x: f32, b: f32, c: f32) -> f32 {
+    if x < 1.0 {
+        ((12.0 - 9.0 * b - 6.0 * c) * x * x * x
+            + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+            + (6.0 - 2.0 * b))
+            / 6.0
+    } else if x < 2.0 {
+        ((-b - 6.0 * c) * x * x * x
+            + (6.0 * b + 30.0 * c) * x * x
+            + (-12.0 * b - 48.0 * c) * x
+            + (8.0 * b + 24.0 * c))
+            / 6.0
+    } else {
+        0.0
+    }
+}
+
+/// Normalized sinc function
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = x * PI;
+        x.sin() / x
+    }
+}
+
+/// Accumulator of weighted pixels.
+///
+/// ### Example: Average two pixels
+/// ```
+/// use pix::filter::{Accumulator, AlphaWeight};
+/// use pix::rgb::Rgba8;
+///
+/// let mut acc = Accumulator::new(AlphaWeight::Premultiplied);
+/// acc.add(Rgba8::new(255, 0, 0, 0), 0.5);
+/// acc.add(Rgba8::new(0, 0, 255, 255), 0.5);
+/// // fully transparent red does not bleed into the result
+/// assert_eq!(acc.result(), Rgba8::new(0, 0, 255, 128));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Accumulator<P: Pixel> {
+    /// Alpha weighting mode
+    mode: AlphaWeight,
+    /// Weighted linear channel sums
+    chan: P::F32Array,
+    /// Weighted circular channel sums (cosine / sine)
+    circ: [f32; 2],
+    /// Sum of weights
+    weight: f32,
+    _pixel: PhantomData<P>,
+}
+
+impl<P: Pixel> Accumulator<P> {
+    /// Create a new empty accumulator.
+    pub fn new(mode: AlphaWeight) -> Self {
+        let mut chan = P::default().into_f32_array();
+        chan.as_mut().fill(0.0);
+        Accumulator {
+            mode,
+            chan,
+            circ: [0.0; 2],
+            weight: 0.0,
+            _pixel: PhantomData,
+        }
+    }
+
+    /// Check if color channels are weighted by alpha
+    fn is_weighted(&self) -> bool {
+        self.mode == AlphaWeight::Premultiplied
+            && TypeId::of::<P::Alpha>() == TypeId::of::<Straight>()
+    }
+
+    /// Clear accumulated values.
+    pub fn clear(&mut self) {
+        self.chan.as_mut().fill(0.0);
+        self.circ = [0.0; 2];
+        self.weight = 0.0;
+    }
+
+    /// Add a weighted pixel.
+    ///
+    /// * `p` Pixel to add.
+    /// * `w` Weight of pixel (may be negative).
+    pub fn add(&mut self, p: P, w: f32) {
+        let alpha = p.alpha().to_f32();
+        let cw = if self.is_weighted() { w * alpha } else { w };
+        let chan = p.channels();
+        for c in P::Model::CIRCULAR {
+            let h = chan[c].to_f32() * TAU;
+            self.circ[0] += h.cos() * cw;
+            self.circ[1] += h.sin() * cw;
+        }
+        for c in P::Model::LINEAR {
+            self.chan.as_mut()[c] += to_linear::<P>(chan[c]) * cw;
+        }
+        if let Some(a) = self.chan.as_mut().get_mut(P::Model::ALPHA) {
+            *a += alpha * w;
+        }
+        self.weight += w;
+    }
+
+    /// Add the sums from another weighted accumulator.
+    ///
+    /// * `acc` Accumulator to add.
+    /// * `w` Weight of `acc` sums.
+    ///
+    /// This allows filtering in separate passes.
+    pub fn add_accumulator(&mut self, acc: &Self, w: f32) {
+        for (c, a) in self.chan.as_mut().iter_mut().zip(acc.chan.as_ref()) {
+            *c += a * w;
+        }
+        for (c, a) in self.circ.iter_mut().zip(acc.circ) {
+            *c += a * w;
+        }
+        self.weight += acc.weight * w;
+    }
+
+    /// Get the sum of all weights.
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Get the resulting (normalized) pixel.
+    ///
+    /// If the sum of weights is zero, the default pixel is returned.
+    pub fn result(&self) -> P {
+        if self.weight == 0.0 {
+            return P::default();
+        }
+        self.normalized(self.weight, None)
+    }
+
+    /// Get the resulting pixel, without normalizing by the sum of weights.
+    ///
+    /// This is needed for kernels which do not sum to one, such as edge
+    /// detection.  Channels are clamped to their valid ranges.
+    pub fn sum(&self) -> P {
+        self.normalized(1.0, None)
+    }
+
+    /// Get the resulting pixel, without normalizing, using a given *alpha*
+    /// instead of the weighted sum.
+    ///
+    /// Color channels weighted by *alpha* are divided by `alpha`.
+    fn sum_with_alpha(&self, alpha: f32) -> P {
+        self.normalized(1.0, Some(alpha))
+    }
+
+    /// Get the resulting pixel, normalized by a weight
+    fn normalized(&self, weight: f32, alpha: Option<f32>) -> P {
+        let mut p = P::default();
+        let sums = self.chan.as_ref();
+        let alpha = match (alpha, sums.get(P::Model::ALPHA)) {
+            (_, None) => 1.0,
+            (Some(a), Some(_)) => a.clamp(0.0, 1.0),
+            (None, Some(a)) => (a / weight).clamp(0.0, 1.0),
+        };
+        let norm = if self.is_weighted() {
+            weight * alpha
+        } else {
+            weight
+        };
+        let chan = p.channels_mut();
+        for c in P::Model::CIRCULAR {
+            let h = self.circ[1].atan2(self.circ[0]) / TAU;
+            chan[c] = P::Chan::from(h.rem_euclid(1.0));
+        }
+        for c in P::Model::LINEAR {
+            let v = if norm > 0.0 { sums[c] / norm } else { 0.0 };
+            chan[c] = from_linear::<P>(v);
+        }
+        if let Some(a) = chan.get_mut(P::Model::ALPHA) {
+            *a = P::Chan::from(alpha);
+        }
+        p
+    }
+}
+
+/// Convert a channel to a linear `f32` value
+fn to_linear<P: Pixel>(c: P::Chan) -> f32 {
+    P::Gamma::to_linear(Ch32::new(c.to_f32())).to_f32()
+}
+
+/// Convert a linear `f32` value to a channel
+fn from_linear<P: Pixel>(v: f32) -> P::Chan {
+    P::Chan::from(P::Gamma::from_linear(Ch32::new(v)).to_f32())
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a scaled copy of a `Raster`.
+    ///
+    /// * `width` Width of scaled `Raster`.
+    /// * `height` Height of scaled `Raster`.
+    /// * `filter` Resampling filter.
+    ///
+    /// Resampling is done in separate horizontal and vertical passes, in
+    /// *linear* gamma, with color channels weighted by *alpha*.
+    ///
+    /// ### Scale to half size
+    /// ```
+    /// use pix::filter::Filter;
+    /// use pix::rgb::SRgba8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(64, 48, SRgba8::new(40, 80, 120, 255));
+    /// let s = r.scaled(32, 24, Filter::Lanczos3);
+    /// assert_eq!(s.width(), 32);
+    /// assert_eq!(s.pixel(10, 10), SRgba8::new(40, 80, 120, 255));
+    /// ```
+    pub fn scaled(&self, width: u32, height: u32, filter: Filter) -> Self {
+        self.scaled_edge(width, height, filter, EdgeMode::Clamp)
+    }
+
+    /// Construct a scaled copy of a `Raster`, with an edge mode.
+    ///
+    /// * `width` Width of scaled `Raster`.
+    /// * `height` Height of scaled `Raster`.
+    /// * `filter` Resampling filter.
+    /// * `edge` Handling of pixels beyond the edges of the source `Raster`.
+    ///
+    /// This is the same as [scaled], which uses `EdgeMode::Clamp`.  Use
+    /// `EdgeMode::Wrap` for tiling textures.
+    ///
+    /// [scaled]: #method.scaled
+    ///
+    /// ### Scale a tiling texture
+    /// ```
+    /// use pix::filter::{EdgeMode, Filter};
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(4, 1);
+    /// *r.pixel_mut(3, 0) = Gray8::new(0xFF);
+    /// let s = r.scaled_edge(8, 1, Filter::Bilinear, EdgeMode::Wrap);
+    /// assert_eq!(s.pixel(0, 0), Gray8::new(0x40));
+    /// let s = r.scaled(8, 1, Filter::Bilinear);
+    /// assert_eq!(s.pixel(0, 0), Gray8::new(0x00));
+    /// ```
+    pub fn scaled_edge(
+        &self,
+        width: u32,
+        height: u32,
+        filter: Filter,
+        edge: EdgeMode<P>,
+    ) -> Self {
+        self.scaled_with(width, height, filter, edge, AlphaWeight::default())
+    }
+
+    /// Construct a scaled copy of a `Raster`, with an edge mode and alpha
+    /// weighting.
+    ///
+    /// * `width` Width of scaled `Raster`.
+    /// * `height` Height of scaled `Raster`.
+    /// * `filter` Resampling filter.
+    /// * `edge` Handling of pixels beyond the edges of the source `Raster`.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// ### Scale without alpha weighting
+    /// ```
+    /// use pix::filter::{AlphaWeight, EdgeMode, Filter};
+    /// use pix::rgb::Rgba8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Rgba8>::with_clear(2, 1);
+    /// *r.pixel_mut(0, 0) = Rgba8::new(0xFF, 0, 0, 0xFF);
+    /// let edge = EdgeMode::Clamp;
+    /// let w = AlphaWeight::Independent;
+    /// let s = r.scaled_with(1, 1, Filter::Bilinear, edge, w);
+    /// assert_eq!(s.pixel(0, 0), Rgba8::new(0x80, 0, 0, 0x80));
+    /// ```
+    pub fn scaled_with(
+        &self,
+        width: u32,
+        height: u32,
+        filter: Filter,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        let (sw, sh) = (self.width() as usize, self.height() as usize);
+        let (dw, dh) = (width as usize, height as usize);
+        if sw == 0 || sh == 0 || dw == 0 || dh == 0 {
+            return self.blank(width, height);
+        }
+        let cols = filter.contributions(sw, dw);
+        let rows = filter.contributions(sh, dh);
+        self.resampled(&cols, &rows, edge, weight)
+    }
+
+    /// Construct a chain of mipmap levels.
+    ///
+    /// Each level is half the width and height of the previous one (rounded
+    /// down), until a 1x1 level is reached.  The chain does not include the
+    /// source `Raster`.
+    ///
+    /// Pixels are area-averaged in *linear* gamma, with color channels
+    /// weighted by *alpha*.  For odd sizes, source pixels contribute in
+    /// proportion to their overlap.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(256, 100, SRgb8::new(0xFF, 0, 0));
+    /// r.copy_color((0, 0, 128, 100), SRgb8::new(0, 0xFF, 0));
+    /// let mips = r.mipmaps();
+    /// assert_eq!(mips.len(), 8);
+    /// assert_eq!((mips[1].width(), mips[1].height()), (64, 25));
+    /// let last = &mips[7];
+    /// assert_eq!((last.width(), last.height()), (1, 1));
+    /// assert_eq!(last.pixel(0, 0), SRgb8::new(0xBC, 0xBC, 0));
+    /// ```
+    pub fn mipmaps(&self) -> Vec<Self> {
+        let mut levels: Vec<Self> = Vec::new();
+        let mut src = self;
+        while src.width() > 1 || src.height() > 1 {
+            let (sw, sh) = (src.width() as usize, src.height() as usize);
+            if sw == 0 || sh == 0 {
+                break;
+            }
+            let cols = area_contributions(sw, (sw / 2).max(1));
+            let rows = area_contributions(sh, (sh / 2).max(1));
+            let edge = EdgeMode::Clamp;
+            let weight = AlphaWeight::default();
+            levels.push(src.resampled(&cols, &rows, edge, weight));
+            src = &levels[levels.len() - 1];
+        }
+        levels
+    }
+
+    /// Resample using source contributions for each column and row
+    fn resampled(
+        &self,
+        cols: &[Contribution],
+        rows: &[Contribution],
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        let (sw, sh) = (self.width() as usize, self.height() as usize);
+        let (dw, dh) = (cols.len(), rows.len());
+        let mut r = self.blank(dw as u32, dh as u32);
+        let clr = match edge {
+            EdgeMode::Constant(clr) => clr,
+            _ => P::default(),
+        };
+        let acc = Accumulator::new(weight);
+        // horizontal pass
+        let mut tmp = vec![acc; dw * sh];
+        let srows = self.pixels().chunks_exact(sw);
+        let trows = srows.zip(tmp.chunks_exact_mut(dw));
+        for_each_row(trows, dw * sh, |(srow, trow)| {
+            for (t, col) in trow.iter_mut().zip(cols) {
+                for (x, w) in col {
+                    match edge.index(*x, self.width() as i32) {
+                        Some(x) => t.add(srow[x as usize], *w),
+                        None => t.add(clr, *w),
+                    }
+                }
+            }
+        });
+        // vertical pass
+        let drows = r.pixels_mut().chunks_exact_mut(dw);
+        for_each_row(drows.zip(rows), dw * dh, |(drow, row)| {
+            for (x, d) in drow.iter_mut().enumerate() {
+                let mut a = acc;
+                for (y, w) in row {
+                    match edge.index(*y, self.height() as i32) {
+                        Some(y) => {
+                            a.add_accumulator(&tmp[y as usize * dw + x], *w)
+                        }
+                        None => a.add(clr, *w),
+                    }
+                }
+                *d = a.result();
+            }
+        });
+        r
+    }
+
+    /// Construct a copy of a `Raster` convolved with a [Kernel].
+    ///
+    /// * `kernel` Convolution kernel.
+    /// * `edge` Handling of pixels beyond the edges.
+    ///
+    /// The kernel is flipped, as in mathematical convolution; for a
+    /// symmetric kernel this makes no difference.  Weighted sums are not
+    /// normalized.  Color channels are weighted by *alpha*, as described in
+    /// the [filter] module.  *Alpha* is averaged using the absolute kernel
+    /// weights, so that kernels which sum to zero (such as edge detection)
+    /// keep the source *alpha*.
+    ///
+    /// [filter]: filter/index.html
+    /// [kernel]: filter/struct.Kernel.html
+    ///
+    /// ### Box blur
+    /// ```
+    /// use pix::filter::{EdgeMode, Kernel};
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(5, 5);
+    /// *r.pixel_mut(2, 2) = Gray8::new(0xFF);
+    /// let k = Kernel::new(3, 3, vec![1.0; 9]).normalized();
+    /// let b = r.convolved(&k, EdgeMode::Clamp);
+    /// assert_eq!(b.pixel(1, 1), Gray8::new(0x1C));
+    /// ```
+    pub fn convolved(&self, kernel: &Kernel, edge: EdgeMode<P>) -> Self {
+        self.convolved_with(kernel, edge, AlphaWeight::default())
+    }
+
+    /// Construct a copy of a `Raster` convolved with a [Kernel], with alpha
+    /// weighting.
+    ///
+    /// * `kernel` Convolution kernel.
+    /// * `edge` Handling of pixels beyond the edges.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// [kernel]: filter/struct.Kernel.html
+    pub fn convolved_with(
+        &self,
+        kernel: &Kernel,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        let mut r = self.blank(self.width(), self.height());
+        let len = r.pixels().len();
+        let (kw, kh) = (kernel.width() as i32, kernel.height() as i32);
+        let (rx, ry) = (kw / 2, kh / 2);
+        let total: f32 = kernel.weights().iter().map(|w| w.abs()).sum();
+        for_each_row(r.rows_mut(()).enumerate(), len, |(y, row)| {
+            for (x, d) in row.iter_mut().enumerate() {
+                let mut acc = Accumulator::new(weight);
+                let mut alpha = 0.0;
+                for (i, w) in kernel.weights().iter().enumerate() {
+                    if *w != 0.0 {
+                        let i = i as i32;
+                        let px = x as i32 + (kw - 1 - i % kw) - rx;
+                        let py = y as i32 + (kh - 1 - i / kw) - ry;
+                        let p = self.pixel_edge(px, py, edge);
+                        alpha += p.alpha().to_f32() * w.abs();
+                        acc.add(p, *w);
+                    }
+                }
+                let alpha = if total > 0.0 { alpha / total } else { 0.0 };
+                *d = acc.sum_with_alpha(alpha);
+            }
+        });
+        r
+    }
+
+    /// Construct a blurred copy of a `Raster`, using a Gaussian filter.
+    ///
+    /// * `sigma` Standard deviation of the Gaussian, in pixels.
+    ///
+    /// Rows and columns are filtered in separate passes.  When `sigma` is
+    /// greater than 4.0, a recursive approximation is used, which takes
+    /// constant time per pixel.  Filtering happens in *linear* light, with
+    /// color channels weighted by *alpha*.  Edge pixels are clamped; use
+    /// [gaussian_blur_with] for other edge modes.
+    ///
+    /// [gaussian_blur_with]: #method.gaussian_blur_with
+    ///
+    /// ### Blur
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgba8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<SRgba8>::with_clear(32, 32);
+    /// r.copy_color((8, 8, 16, 16), SRgba8::new(0xFF, 0x80, 0x00, 0xFF));
+    /// let b = r.gaussian_blur(2.0);
+    /// assert_eq!(b.pixel(16, 16), SRgba8::new(0xFF, 0x80, 0x00, 0xFF));
+    /// assert_eq!(b.pixel(8, 8).alpha(), 0x5C.into());
+    /// ```
+    pub fn gaussian_blur(&self, sigma: f32) -> Self {
+        self.gaussian_blur_with(sigma, EdgeMode::Clamp, AlphaWeight::default())
+    }
+
+    /// Construct a blurred copy of a `Raster`, using a Gaussian filter with
+    /// an edge mode and alpha weighting.
+    ///
+    /// * `sigma` Standard deviation of the Gaussian, in pixels.
+    /// * `edge` Handling of pixels beyond the edges.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// See [gaussian_blur].
+    ///
+    /// [gaussian_blur]: #method.gaussian_blur
+    pub fn gaussian_blur_with(
+        &self,
+        sigma: f32,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        if sigma > 4.0 {
+            self.filtered_lines(edge, weight, |line, edge| {
+                recursive_gaussian_line(line, sigma, edge)
+            })
+        } else if sigma > 0.0 {
+            let weights = gaussian_weights(sigma);
+            self.filtered_lines(edge, weight, |line, edge| {
+                convolve_line(line, &weights, edge)
+            })
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Construct a blurred copy of a `Raster`, using a box filter.
+    ///
+    /// * `radius` Number of pixels on each side of the center.
+    /// * `passes` Number of times to apply the filter.
+    ///
+    /// Running sums are used, so the time per pixel does not depend on
+    /// `radius`.  Three passes are a good approximation of a Gaussian blur,
+    /// such as for drop shadows.  Filtering happens in *linear* light, with
+    /// color channels weighted by *alpha*.  Edge pixels are clamped; use
+    /// [box_blur_with] for other edge modes.
+    ///
+    /// [box_blur_with]: #method.box_blur_with
+    ///
+    /// ### Soft shadow
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Matte8>::with_clear(64, 64);
+    /// r.copy_color((16, 16, 32, 32), Matte8::new(0xFF));
+    /// let shadow = r.box_blur(4, 3);
+    /// assert_eq!(shadow.pixel(32, 32), Matte8::new(0xFF));
+    /// assert_eq!(shadow.pixel(16, 32), Matte8::new(0x8A));
+    /// ```
+    pub fn box_blur(&self, radius: u32, passes: u32) -> Self {
+        self.box_blur_with(
+            radius,
+            passes,
+            EdgeMode::Clamp,
+            AlphaWeight::default(),
+        )
+    }
+
+    /// Construct a blurred copy of a `Raster`, using a box filter with an
+    /// edge mode and alpha weighting.
+    ///
+    /// * `radius` Number of pixels on each side of the center.
+    /// * `passes` Number of times to apply the filter.
+    /// * `edge` Handling of pixels beyond the edges.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// See [box_blur].
+    ///
+    /// [box_blur]: #method.box_blur
+    pub fn box_blur_with(
+        &self,
+        radius: u32,
+        passes: u32,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        if radius == 0 || passes == 0 {
+            return self.clone();
+        }
+        self.filtered_lines(edge, weight, |line, edge| {
+            for _ in 0..passes {
+                box_line(line, radius, edge);
+            }
+        })
+    }
+
+    /// Construct a sharpened copy of a `Raster`, using an unsharp mask.
+    ///
+    /// * `radius` Standard deviation of the Gaussian blur, in pixels.
+    /// * `amount` Strength of sharpening (`1.0` adds 100% of the detail).
+    /// * `threshold` Minimum difference from the blurred value to sharpen a
+    ///   pixel (`0.0` to `1.0`).  This avoids amplifying noise in smooth
+    ///   areas.
+    ///
+    /// The difference between each pixel and a [gaussian_blur]red copy is
+    /// scaled by `amount` and added back, in *linear* light.  The threshold
+    /// is compared in *linear* light too.
+    ///
+    /// [gaussian_blur]: #method.gaussian_blur
+    ///
+    /// ### Sharpen
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(16, 16, SRgb8::new(0x40, 0x40, 0x40));
+    /// r.copy_color((8, 0, 8, 16), SRgb8::new(0x80, 0x80, 0x80));
+    /// let s = r.unsharp_mask(1.0, 1.0, 0.0);
+    /// assert!(s.pixel(8, 8).one() > r.pixel(8, 8).one());
+    /// assert!(s.pixel(7, 8).one() < r.pixel(7, 8).one());
+    /// ```
+    pub fn unsharp_mask(
+        &self,
+        radius: f32,
+        amount: f32,
+        threshold: f32,
+    ) -> Self {
+        self.unsharp_mask_with(
+            radius,
+            amount,
+            threshold,
+            EdgeMode::Clamp,
+            AlphaWeight::default(),
+        )
+    }
+
+    /// Construct a sharpened copy of a `Raster`, using an unsharp mask with
+    /// an edge mode and alpha weighting.
+    ///
+    /// * `radius` Standard deviation of the Gaussian blur, in pixels.
+    /// * `amount` Strength of sharpening (`1.0` adds 100% of the detail).
+    /// * `threshold` Minimum difference from the blurred value to sharpen a
+    ///   pixel (`0.0` to `1.0`).
+    /// * `edge` Handling of pixels beyond the edges.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// See [unsharp_mask].
+    ///
+    /// [unsharp_mask]: #method.unsharp_mask
+    pub fn unsharp_mask_with(
+        &self,
+        radius: f32,
+        amount: f32,
+        threshold: f32,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> Self {
+        let mut r = self.gaussian_blur_with(radius, edge, weight);
+        let len = r.pixels().len();
+        let rows = r.pixels_mut().chunks_mut(1.max(self.width() as usize));
+        let srows = self.pixels().chunks(1.max(self.width() as usize));
+        for_each_row(rows.zip(srows), len, |(drow, srow)| {
+            for (d, s) in drow.iter_mut().zip(srow) {
+                let diff = P::Model::LINEAR
+                    .map(|c| {
+                        let sc = to_linear::<P>(s.channels()[c]);
+                        (sc - to_linear::<P>(d.channels()[c])).abs()
+                    })
+                    .fold(0.0, f32::max);
+                if diff < threshold {
+                    *d = *s;
+                } else {
+                    let mut acc = Accumulator::new(weight);
+                    acc.add(*s, 1.0 + amount);
+                    acc.add(*d, -amount);
+                    *d = acc.sum();
+                }
+            }
+        });
+        r
+    }
+
+    /// Calculate luminance gradients, for edge detection.
+    ///
+    /// * `op` Gradient operator.
+    ///
+    /// Returns signed (*x*, *y*) gradient pairs for each pixel, in the same
+    /// order as [pixels].  Luminance is calculated in *linear* light.  Edge
+    /// pixels are clamped.
+    ///
+    /// [pixels]: #method.pixels
+    ///
+    /// ### Gradient direction
+    /// ```
+    /// use pix::filter::Gradient;
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(4, 4);
+    /// r.copy_color((2, 0, 2, 4), Gray8::new(0xFF));
+    /// let g = r.gradients(Gradient::Sobel);
+    /// assert_eq!(g[4 + 1], (1.0, 0.0));
+    /// assert_eq!(g[4 + 3], (0.0, 0.0));
+    /// ```
+    pub fn gradients(&self, op: Gradient) -> Vec<(f32, f32)>
+    where
+        Ch32: From<P::Chan>,
+    {
+        self.gradients_edge(op, EdgeMode::Clamp)
+    }
+
+    /// Calculate luminance gradients, with an edge mode.
+    ///
+    /// * `op` Gradient operator.
+    /// * `edge` Handling of pixels beyond the edges.
+    ///
+    /// This is the same as [gradients], which uses `EdgeMode::Clamp`.
+    ///
+    /// [gradients]: #method.gradients
+    ///
+    /// ### Tiling texture
+    /// ```
+    /// use pix::filter::{EdgeMode, Gradient};
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(4, 1);
+    /// *r.pixel_mut(3, 0) = Gray8::new(0xFF);
+    /// let g = r.gradients_edge(Gradient::Sobel, EdgeMode::Wrap);
+    /// assert_eq!(g[0], (-1.0, 0.0));
+    /// assert_eq!(r.gradients(Gradient::Sobel)[0], (0.0, 0.0));
+    /// ```
+    pub fn gradients_edge(
+        &self,
+        op: Gradient,
+        edge: EdgeMode<P>,
+    ) -> Vec<(f32, f32)>
+    where
+        Ch32: From<P::Chan>,
+    {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        let lum: Vec<f32> = self
+            .pixels()
+            .iter()
+            .map(|p| p.convert::<Gray32>().one().to_f32())
+            .collect();
+        let constant = match edge {
+            EdgeMode::Constant(clr) => clr.convert::<Gray32>().one().to_f32(),
+            _ => 0.0,
+        };
+        let get = |x: i32, y: i32| match (edge.index(x, w), edge.index(y, h)) {
+            (Some(x), Some(y)) => lum[(y * w + x) as usize],
+            _ => constant,
+        };
+        let s = op.smoothing();
+        let mut grad = Vec::with_capacity(lum.len());
+        for y in 0..h {
+            for x in 0..w {
+                let mut gx = 0.0;
+                let mut gy = 0.0;
+                for (i, s) in (-1..=1).zip(s) {
+                    gx += s * (get(x + 1, y + i) - get(x - 1, y + i));
+                    gy += s * (get(x + i, y + 1) - get(x + i, y - 1));
+                }
+                grad.push((gx, gy));
+            }
+        }
+        grad
+    }
+
+    /// Calculate luminance gradient magnitudes, for edge detection.
+    ///
+    /// * `op` Gradient operator.
+    ///
+    /// Magnitudes are clamped to `1.0`.  See [gradients].
+    ///
+    /// [gradients]: #method.gradients
+    ///
+    /// ### Edge mask
+    /// ```
+    /// use pix::filter::Gradient;
+    /// use pix::gray::Gray32;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<SRgb8>::with_clear(8, 8);
+    /// r.copy_color((2, 2, 4, 4), SRgb8::new(0xFF, 0xFF, 0xFF));
+    /// let m = r.gradient_magnitude(Gradient::Scharr);
+    /// assert_eq!(m.pixel(0, 0), Gray32::new(0.0));
+    /// assert_eq!(m.pixel(1, 4), Gray32::new(1.0));
+    /// ```
+    pub fn gradient_magnitude(&self, op: Gradient) -> Raster<Gray32>
+    where
+        Ch32: From<P::Chan>,
+    {
+        let mut r = Raster::with_clear(self.width(), self.height());
+        for (p, (gx, gy)) in r.pixels_mut().iter_mut().zip(self.gradients(op)) {
+            *p = Gray32::new::<Ch32>(Ch32::new(gx.hypot(gy)));
+        }
+        r
+    }
+
+    /// Construct a copy of a `Raster` with a filter applied to each row,
+    /// then to each column.
+    ///
+    /// * `edge` Handling of pixels beyond the edges.
+    /// * `weight` Alpha weighting mode.
+    /// * `f` Filter function for one line of accumulators.
+    fn filtered_lines<F>(
+        &self,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(&mut [Accumulator<P>], EdgeMode<P>) + MaybeSend + MaybeSync,
+    {
+        let mut r = self.blank(self.width(), self.height());
+        let (w, h) = (self.width() as usize, self.height() as usize);
+        if w == 0 || h == 0 {
+            return r;
+        }
+        let len = w * h;
+        let acc = Accumulator::new(weight);
+        let mut buf: Vec<Accumulator<P>> = self
+            .pixels()
+            .iter()
+            .map(|p| {
+                let mut a = acc;
+                a.add(*p, 1.0);
+                a
+            })
+            .collect();
+        for_each_row(buf.chunks_exact_mut(w), len, |line| f(line, edge));
+        // transpose, so that columns are contiguous
+        let mut cols = vec![acc; len];
+        for (y, row) in buf.chunks_exact(w).enumerate() {
+            for (x, a) in row.iter().enumerate() {
+                cols[x * h + y] = *a;
+            }
+        }
+        for_each_row(cols.chunks_exact_mut(h), len, |line| f(line, edge));
+        for (y, row) in r.pixels_mut().chunks_exact_mut(w).enumerate() {
+            for (x, p) in row.iter_mut().enumerate() {
+                *p = cols[x * h + y].result();
+            }
+        }
+        r
+    }
+
+    /// Get a pixel, using an edge mode for coordinates out of bounds
+    fn pixel_edge(&self, x: i32, y: i32, edge: EdgeMode<P>) -> P {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        match (edge.index(x, w), edge.index(y, h)) {
+            (Some(x), Some(y)) => self.pixels()[(y * w + x) as usize],
+            _ => match edge {
+                EdgeMode::Constant(clr) => clr,
+                _ => P::default(),
+            },
+        }
+    }
+
+    /// Sample a filtered pixel at a point.
+    ///
+    /// * `x` X-position of sample, in pixel coordinates.
+    /// * `y` Y-position of sample, in pixel coordinates.
+    /// * `filter` Resampling filter.
+    /// * `edge` Handling of pixels beyond the edges of the `Raster`.
+    ///
+    /// Pixel centers are at half-pixel offsets, so the center of the top-left
+    /// pixel is (`0.5`, `0.5`).  Filtering is done in *linear* gamma, with
+    /// color channels weighted by *alpha*.  Sampling an empty `Raster`
+    /// returns the `Constant` edge color, or the default pixel.
+    ///
+    /// ### Sample between pixels
+    /// ```
+    /// use pix::filter::{EdgeMode, Filter};
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(2, 1);
+    /// *r.pixel_mut(1, 0) = Gray8::new(0xFF);
+    /// let p = r.sample(1.0, 0.5, Filter::Bilinear, EdgeMode::Clamp);
+    /// assert_eq!(p, Gray8::new(0x80));
+    /// let p = r.sample(2.5, 0.5, Filter::Bilinear, EdgeMode::Wrap);
+    /// assert_eq!(p, Gray8::new(0x00));
+    /// ```
+    pub fn sample(
+        &self,
+        x: f32,
+        y: f32,
+        filter: Filter,
+        edge: EdgeMode<P>,
+    ) -> P {
+        self.sample_with(x, y, filter, edge, AlphaWeight::default())
+    }
+
+    /// Sample a filtered pixel at a point, with alpha weighting.
+    ///
+    /// * `x` X-position of sample, in pixel coordinates.
+    /// * `y` Y-position of sample, in pixel coordinates.
+    /// * `filter` Resampling filter.
+    /// * `edge` Handling of pixels beyond the edges of the `Raster`.
+    /// * `weight` Alpha weighting mode for *straight* alpha pixels.
+    ///
+    /// See [sample].
+    ///
+    /// [sample]: #method.sample
+    pub fn sample_with(
+        &self,
+        x: f32,
+        y: f32,
+        filter: Filter,
+        edge: EdgeMode<P>,
+        weight: AlphaWeight,
+    ) -> P {
+        if filter == Filter::Nearest {
+            return self.pixel_edge(x.floor() as i32, y.floor() as i32, edge);
+        }
+        let (cx, cy) = (x - 0.5, y - 0.5);
+        let support = filter.support();
+        let (x0, x1) = ((cx - support).ceil(), (cx + support).floor());
+        let (y0, y1) = ((cy - support).ceil(), (cy + support).floor());
+        let mut acc = Accumulator::new(weight);
+        for py in y0 as i32..=y1 as i32 {
+            let wy = filter.weight(py as f32 - cy);
+            if wy == 0.0 {
+                continue;
+            }
+            for px in x0 as i32..=x1 as i32 {
+                let w = wy * filter.weight(px as f32 - cx);
+                if w != 0.0 {
+                    acc.add(self.pixel_edge(px, py, edge), w);
+                }
+            }
+        }
+        acc.result()
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use super::*;
+    use crate::chan::Ch8;
+    use crate::gray::*;
+    use crate::hsv::*;
+    use crate::rgb::*;
+    use crate::Region;
+
+    #[test]
+    fn contributions() {
+        for f in [
+            Filter::Nearest,
+            Filter::Bilinear,
+            Filter::CatmullRom,
+            Filter::Mitchell,
+            Filter::Lanczos3,
+        ] {
+            for (src, dst) in [(10, 3), (3, 10), (7, 7)] {
+                let con = f.contributions(src, dst);
+                assert_eq!(con.len(), dst);
+                for c in con {
+                    let total: f32 = c.iter().map(|(_, w)| w).sum();
+                    assert!((total - 1.0).abs() < 1e-5);
+                    let range = -(src as i32)..2 * src as i32;
+                    assert!(c.iter().all(|(x, _)| range.contains(x)));
+                }
+            }
+        }
+        assert_eq!(
+            Filter::Nearest.contributions(4, 2),
+            [[(1, 1.0)], [(3, 1.0)]]
+        );
+        assert_eq!(
+            Filter::Bilinear.contributions(2, 4)[1],
+            [(0, 0.75), (1, 0.25)]
+        );
+    }
+
+    #[test]
+    fn edge_index() {
+        let modes = [
+            EdgeMode::Clamp,
+            EdgeMode::Wrap,
+            EdgeMode::Mirror,
+            EdgeMode::Constant(Gray8::new(0)),
+        ];
+        let idx = |m: EdgeMode<Gray8>| {
+            (-4..7)
+                .map(|x| m.index(x, 3).unwrap_or(-1))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(idx(modes[0]), [0, 0, 0, 0, 0, 1, 2, 2, 2, 2, 2]);
+        assert_eq!(idx(modes[1]), [2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0]);
+        assert_eq!(idx(modes[2]), [2, 2, 1, 0, 0, 1, 2, 2, 1, 0, 0]);
+        assert_eq!(idx(modes[3]), [-1, -1, -1, -1, 0, 1, 2, -1, -1, -1, -1]);
+    }
+
+    #[test]
+    fn unnormalized_sum() {
+        let mut acc = Accumulator::new(AlphaWeight::default());
+        acc.add(Rgb32::new(0.25, 0.5, 1.0), 2.0);
+        acc.add(Rgb32::new(0.5, 0.5, 0.25), -1.0);
+        assert_eq!(acc.sum(), Rgb32::new(0.0, 0.5, 1.0));
+        let k = Kernel::new(1, 3, vec![1.0, 2.0, 1.0]).normalized();
+        assert_eq!(k.weights(), [0.25, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn separable() {
+        let mut a0 = Accumulator::new(AlphaWeight::default());
+        a0.add(Rgba32::new(1.0, 0.0, 0.0, 0.5), 0.5);
+        let mut a1 = Accumulator::new(AlphaWeight::default());
+        a1.add(Rgba32::new(0.0, 0.0, 1.0, 1.0), 1.0);
+        let mut acc = Accumulator::new(AlphaWeight::default());
+        acc.add_accumulator(&a0, 2.0);
+        acc.add_accumulator(&a1, 1.0);
+        let r = acc.result();
+        assert_eq!(r, Rgba32::new(1.0 / 3.0, 0.0, 2.0 / 3.0, 0.75));
+    }
+
+    #[test]
+    fn straight_weighted() {
+        let mut acc = Accumulator::new(AlphaWeight::Premultiplied);
+        acc.add(Rgba32::new(1.0, 1.0, 1.0, 0.0), 0.75);
+        acc.add(Rgba32::new(0.0, 0.5, 0.0, 1.0), 0.25);
+        assert_eq!(acc.result(), Rgba32::new(0.0, 0.5, 0.0, 0.25));
+    }
+
+    #[test]
+    fn straight_independent() {
+        let mut acc = Accumulator::new(AlphaWeight::Independent);
+        acc.add(Rgba32::new(1.0, 1.0, 1.0, 0.0), 0.75);
+        acc.add(Rgba32::new(0.0, 0.5, 0.0, 1.0), 0.25);
+        assert_eq!(acc.result(), Rgba32::new(0.75, 0.875, 0.75, 0.25));
+    }
+
+    #[test]
+    fn premultiplied() {
+        let mut acc = Accumulator::new(AlphaWeight::Independent);
+        acc.add(Rgba8p::new(0x00, 0x00, 0x00, 0x00), 1.0);
+        acc.add(Rgba8p::new(0x80, 0x40, 0x20, 0x80), 1.0);
+        assert_eq!(acc.result(), Rgba8p::new(0x40, 0x20, 0x10, 0x40));
+    }
+
+    #[test]
+    fn srgb_linear_light() {
+        let mut acc = Accumulator::new(AlphaWeight::default());
+        acc.add(SGray8::new(0x00), 1.0);
+        acc.add(SGray8::new(0xFF), 1.0);
+        assert_eq!(acc.result(), SGray8::new(0xBC));
+        acc.clear();
+        assert_eq!(acc.weight(), 0.0);
+        assert_eq!(acc.result(), SGray8::new(0x00));
+    }
+
+    #[test]
+    fn circular_hue() {
+        let mut acc = Accumulator::new(AlphaWeight::default());
+        acc.add(Hsv32::new(0.875, 1.0, 1.0), 1.0);
+        acc.add(Hsv32::new(0.125, 1.0, 0.5), 1.0);
+        let p = acc.result();
+        assert!(Hsv::hue(p).to_f32() < 1e-6 || Hsv::hue(p).to_f32() > 0.9999);
+        assert_eq!(Hsv::value(p), Ch32::new(0.75));
+    }
+
+    #[test]
+    fn scaled_bilinear() {
+        let mut r = Raster::<Gray8>::with_clear(2, 2);
+        *r.pixel_mut(1, 0) = Gray8::new(0x80);
+        *r.pixel_mut(1, 1) = Gray8::new(0x80);
+        let s = r.scaled(4, 1, Filter::Bilinear);
+        let v = [
+            Gray8::new(0x00), Gray8::new(0x20),
+            Gray8::new(0x60), Gray8::new(0x80),
+        ];
+        assert_eq!(s.pixels(), &v);
+        let s = r.scaled(1, 1, Filter::Nearest);
+        assert_eq!(s.pixels(), &[Gray8::new(0x80)]);
+    }
+
+    #[test]
+    fn scaled_no_halo() {
+        let mut r = Raster::with_color(4, 4, Rgba8::new(0xFF, 0xFF, 0xFF, 0));
+        r.copy_color((2, 0, 2, 4), Rgba8::new(0x00, 0x80, 0x00, 0xFF));
+        for f in [Filter::Bilinear, Filter::Mitchell, Filter::Lanczos3] {
+            let s = r.scaled(3, 3, f);
+            for p in s.pixels() {
+                if p.alpha() > Ch8::MIN {
+                    assert_eq!(Rgb::red(*p), Ch8::MIN);
+                }
+            }
+        }
+        let s = r.scaled(0, 5, Filter::CatmullRom);
+        assert_eq!(s.height(), 5);
+    }
+
+    #[test]
+    fn alpha_weight() {
+        let mut r = Raster::with_color(2, 1, Rgba8::new(0xFF, 0xFF, 0xFF, 0));
+        *r.pixel_mut(0, 0) = Rgba8::new(0x00, 0x80, 0x00, 0xFF);
+        let (edge, w) = (EdgeMode::Clamp, AlphaWeight::Independent);
+        let s = r.scaled(1, 1, Filter::Bilinear);
+        assert_eq!(s.pixel(0, 0), Rgba8::new(0x00, 0x80, 0x00, 0x80));
+        let s = r.scaled_with(1, 1, Filter::Bilinear, edge, w);
+        assert_eq!(s.pixel(0, 0), Rgba8::new(0x80, 0xC0, 0x80, 0x80));
+        let p = r.sample_with(1.0, 0.5, Filter::Bilinear, edge, w);
+        assert_eq!(p, Rgba8::new(0x80, 0xC0, 0x80, 0x80));
+        let k = Kernel::new(3, 1, vec![0.0, 0.5, 0.5]);
+        let c = r.convolved_with(&k, edge, w);
+        assert_eq!(c.pixel(1, 0), Rgba8::new(0x80, 0xC0, 0x80, 0x80));
+        let b = r.box_blur_with(1, 1, edge, w);
+        assert_eq!(b.pixel(1, 0).alpha(), Ch8::new(0x55));
+        assert!(Rgb::red(b.pixel(1, 0)) > Ch8::MIN);
+        assert_eq!(Rgb::red(r.box_blur(1, 1).pixel(1, 0)), Ch8::MIN);
+        let b = r.gaussian_blur_with(1.0, edge, w);
+        assert!(Rgb::red(b.pixel(0, 0)) > Ch8::MIN);
+        let s = r.unsharp_mask_with(1.0, 1.0, 0.0, edge, w);
+        assert_eq!(s.width(), 2);
+    }
+
+    #[test]
+    fn convolved() {
+        let mut r = Raster::<Graya8>::with_clear(3, 2);
+        *r.pixel_mut(0, 0) = Graya8::new(0xFF, 0xFF);
+        *r.pixel_mut(2, 1) = Graya8::new(0x80, 0x80);
+        let k = Kernel::new(3, 1, vec![0.0, 0.0, 1.0]);
+        let c = r.convolved(&k, EdgeMode::Wrap);
+        assert_eq!(c.pixel(1, 0), Graya8::new(0xFF, 0xFF));
+        assert_eq!(c.pixel(0, 1), Graya8::new(0x80, 0x80));
+        let c = r.convolved(&k, EdgeMode::Constant(Graya8::new(9, 0xFF)));
+        assert_eq!(c.pixel(0, 0), Graya8::new(9, 0xFF));
+        let c = r.convolved(&k, EdgeMode::Mirror);
+        assert_eq!(c.pixel(0, 0), Graya8::new(0xFF, 0xFF));
+        // asymmetric kernels are flipped
+        let mut r = Raster::<Gray8>::with_clear(5, 3);
+        *r.pixel_mut(2, 1) = Gray8::new(0xFF);
+        let k = Kernel::new(3, 1, vec![1.0, 0.0, 0.0]);
+        let c = r.convolved(&k, EdgeMode::Clamp);
+        assert_eq!(c.pixel(1, 1), Gray8::new(0xFF));
+        assert_eq!(c.pixel(2, 1), Gray8::new(0));
+        assert_eq!(c.pixel(3, 1), Gray8::new(0));
+        let k = Kernel::new(1, 3, vec![0.0, 0.0, 1.0]);
+        let c = r.convolved(&k, EdgeMode::Clamp);
+        assert_eq!(c.pixel(2, 2), Gray8::new(0xFF));
+        assert_eq!(c.pixel(2, 0), Gray8::new(0));
+        // edge detection on a flat area
+        let r = Raster::with_color(4, 4, Rgb8::new(10, 20, 30));
+        let k = Kernel::new(3, 3, vec![
+            -1.0, 0.0, 1.0,
+            -2.0, 0.0, 2.0,
+            -1.0, 0.0, 1.0,
+        ]);
+        let c = r.convolved(&k, EdgeMode::Clamp);
+        assert!(c.pixels().iter().all(|p| *p == Rgb8::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn convolved_zero_sum_alpha() {
+        let k = Kernel::new(3, 3, vec![
+            0.0, -1.0, 0.0,
+            -1.0, 4.0, -1.0,
+            0.0, -1.0, 0.0,
+        ]);
+        let mut r = Raster::with_color(3, 3, Rgba8::new(0, 0, 0, 0xFF));
+        *r.pixel_mut(1, 1) = Rgba8::new(0xFF, 0xFF, 0xFF, 0xFF);
+        let c = r.convolved(&k, EdgeMode::Clamp);
+        assert_eq!(c.pixel(1, 1), Rgba8::new(0xFF, 0xFF, 0xFF, 0xFF));
+        assert_eq!(c.pixel(0, 1), Rgba8::new(0, 0, 0, 0xFF));
+        assert_eq!(c.pixel(0, 0), Rgba8::new(0, 0, 0, 0xFF));
+        let rgb = Raster::<Rgb8>::with_raster(&r).convolved(&k, EdgeMode::Clamp);
+        assert_eq!(rgb.pixel(1, 1), Rgb8::new(0xFF, 0xFF, 0xFF));
+        // translucent edge, half covered by the kernel
+        let mut r = Raster::with_color(3, 1, Rgba8::new(0, 0, 0, 0));
+        *r.pixel_mut(1, 0) = Rgba8::new(0xFF, 0xFF, 0xFF, 0xFF);
+        let k = Kernel::new(3, 1, vec![-1.0, 0.0, 1.0]);
+        let c = r.convolved(&k, EdgeMode::Clamp);
+        assert_eq!(c.pixel(0, 0).alpha(), Ch8::new(0x80));
+        assert_eq!(c.pixel(1, 0).alpha(), Ch8::new(0));
+    }
+
+    #[test]
+    fn gaussian_blur() {
+        let mut r = Raster::<Gray32>::with_clear(41, 1);
+        *r.pixel_mut(20, 0) = Gray32::new(1.0);
+        for (sigma, tol) in [(1.5, 1e-6), (4.5, 0.004)] {
+            let b = r.gaussian_blur(sigma);
+            let k = gaussian_weights(sigma);
+            let radius = k.len() as i32 / 2;
+            for (x, p) in b.pixels().iter().enumerate() {
+                let i = x as i32 - 20 + radius;
+                let e = k.get(i as usize).copied().unwrap_or(0.0);
+                let v = p.one().to_f32();
+                assert!((v - e).abs() < tol, "{sigma} {x}: {v} {e}");
+            }
+        }
+        let c = Raster::with_color(9, 9, SRgb8::new(0x20, 0x40, 0x80));
+        assert_eq!(c.gaussian_blur(5.0).pixels(), c.pixels());
+    }
+
+    #[test]
+    fn box_blur() {
+        let mut r = Raster::<Gray32>::with_clear(9, 1);
+        *r.pixel_mut(4, 0) = Gray32::new(1.0);
+        let b = r.box_blur(1, 1);
+        let row: Vec<f32> = b.pixels().iter().map(|p| p.one().to_f32()).collect();
+        let t = 1.0 / 3.0;
+        assert_eq!(row, [0.0, 0.0, 0.0, t, t, t, 0.0, 0.0, 0.0]);
+        let b = r.box_blur(1, 2);
+        let row: Vec<f32> = b.pixels().iter().map(|p| p.one().to_f32()).collect();
+        let e = [0.0, 0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0, 0.0];
+        for (v, e) in row.iter().zip(e) {
+            assert!((v - e / 9.0).abs() < 1e-6);
+        }
+        let c = Raster::with_color(9, 9, SRgba8::new(0x20, 0x40, 0x80, 0x80));
+        assert_eq!(c.box_blur(20, 3).pixels(), c.pixels());
+    }
+
+    #[test]
+    fn blur_edge() {
+        let mut r = Raster::<Gray32>::with_clear(8, 1);
+        *r.pixel_mut(0, 0) = Gray32::new(1.0);
+        let w = AlphaWeight::default();
+        let b = r.box_blur_with(1, 1, EdgeMode::Wrap, w);
+        let t = Gray32::new(1.0 / 3.0);
+        assert_eq!(b.pixel(7, 0), t);
+        assert_eq!(b.pixel(6, 0), Gray32::new(0.0));
+        let b = r.box_blur_with(1, 1, EdgeMode::Mirror, w);
+        assert_eq!(b.pixel(0, 0), Gray32::new(2.0 / 3.0));
+        assert_eq!(b.pixel(7, 0), Gray32::new(0.0));
+        let b = r.box_blur_with(1, 1, EdgeMode::Clamp, w);
+        assert_eq!(b.pixel(0, 0), Gray32::new(2.0 / 3.0));
+        let c = EdgeMode::Constant(Gray32::new(1.0));
+        let b = r.box_blur_with(1, 1, c, w);
+        // constant pixels are also above and below
+        let v = b.pixel(7, 0).one().to_f32();
+        assert!((v - 7.0 / 9.0).abs() < 1e-6, "{v}");
+        for sigma in [1.0, 5.0] {
+            let b = r.gaussian_blur_with(sigma, EdgeMode::Wrap, w);
+            let (first, last) = (b.pixel(1, 0).one(), b.pixel(7, 0).one());
+            assert!((first.to_f32() - last.to_f32()).abs() < 1e-3, "{sigma}");
+            let b = r.gaussian_blur_with(sigma, EdgeMode::Clamp, w);
+            assert!(b.pixel(1, 0).one() > b.pixel(7, 0).one());
+            let b = r.gaussian_blur_with(sigma, EdgeMode::Mirror, w);
+            assert!(b.pixel(1, 0).one() > b.pixel(7, 0).one());
+        }
+        // a wrapped blur of a tiling texture is seamless
+        let c = Raster::with_color(4, 4, Gray32::new(0.5));
+        let b = c.gaussian_blur_with(6.0, EdgeMode::Wrap, w);
+        for p in b.pixels() {
+            assert!((p.one().to_f32() - 0.5).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn unsharp_mask() {
+        let mut r = Raster::with_color(8, 1, Gray8::new(0x40));
+        r.copy_color((4, 0, 4, 1), Gray8::new(0x50));
+        let s = r.unsharp_mask(1.0, 2.0, 0.0);
+        assert!(s.pixel(3, 0).one() < r.pixel(3, 0).one());
+        assert!(s.pixel(4, 0).one() > r.pixel(4, 0).one());
+        assert_eq!(s.pixel(0, 0), r.pixel(0, 0));
+        let s = r.unsharp_mask(1.0, 2.0, 0.1);
+        assert_eq!(s.pixels(), r.pixels());
+        // threshold is compared in linear light, like the sharpening
+        let mut r = Raster::with_color(8, 1, SGray8::new(0x40));
+        r.copy_color((4, 0, 4, 1), SGray8::new(0x50));
+        assert_ne!(r.unsharp_mask(1.0, 2.0, 0.0).pixels(), r.pixels());
+        assert_eq!(r.unsharp_mask(1.0, 2.0, 0.013).pixels(), r.pixels());
+    }
+
+    #[test]
+    fn gradients() {
+        let mut r = Raster::<Gray32>::with_clear(3, 3);
+        *r.pixel_mut(1, 1) = Gray32::new(1.0);
+        let g = r.gradients(Gradient::Sobel);
+        assert_eq!(g[0], (0.25, 0.25));
+        assert_eq!(g[3], (0.5, 0.0));
+        assert_eq!(g[4], (0.0, 0.0));
+        assert_eq!(g[7], (0.0, -0.5));
+        let g = r.gradients(Gradient::Scharr);
+        assert_eq!(g[8], (-0.1875, -0.1875));
+        let m = r.gradient_magnitude(Gradient::Sobel);
+        assert_eq!(m.pixel(2, 1), Gray32::new(0.5));
+        let g = r.gradients_edge(Gradient::Sobel, EdgeMode::Wrap);
+        assert_eq!(g[0], (0.25, 0.25));
+        assert_eq!(g[4], (0.0, 0.0));
+        let g = r.gradients_edge(Gradient::Sobel, EdgeMode::Mirror);
+        assert_eq!(g[3], (0.5, 0.0));
+    }
+
+    #[test]
+    fn sample() {
+        let mut r = Raster::<Rgba8p>::with_clear(3, 3);
+        *r.pixel_mut(1, 1) = Rgba8p::new(0xFF, 0x00, 0x00, 0xFF);
+        let e = EdgeMode::Clamp;
+        let p = r.sample(1.5, 1.5, Filter::CatmullRom, e);
+        assert_eq!(p, Rgba8p::new(0xFF, 0x00, 0x00, 0xFF));
+        let p = r.sample(1.5, 1.0, Filter::Bilinear, e);
+        assert_eq!(p, Rgba8p::new(0x80, 0x00, 0x00, 0x80));
+        let c = EdgeMode::Constant(Rgba8p::new(0, 0, 0xFF, 0xFF));
+        let p = r.sample(-1.0, 1.5, Filter::Nearest, c);
+        assert_eq!(p, Rgba8p::new(0, 0, 0xFF, 0xFF));
+        let p = r.sample(0.0, 0.5, Filter::Bilinear, c);
+        assert_eq!(p, Rgba8p::new(0, 0, 0x80, 0x80));
+        let p = r.sample(4.5, 4.5, Filter::Nearest, EdgeMode::Wrap);
+        assert_eq!(p, Rgba8p::new(0xFF, 0x00, 0x00, 0xFF));
+        let r = Raster::<Rgba8p>::with_clear(0, 0);
+        for f in [Filter::Nearest, Filter::Bilinear, Filter::Lanczos3] {
+            for e in [EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror] {
+                assert_eq!(r.sample(0.5, 0.5, f, e), Rgba8p::default());
+            }
+            assert_eq!(r.sample(0.5, 0.5, f, c), Rgba8p::new(0, 0, 0xFF, 0xFF));
+        }
+        let k = Kernel::new(3, 3, vec![1.0; 9]);
+        assert_eq!(r.convolved(&k, EdgeMode::Wrap).width(), 0);
+    }
+
+    #[test]
+    fn mipmaps() {
+        let mut r = Raster::<Graya8>::with_clear(5, 3);
+        r.copy_color((0, 0, 1, 3), Graya8::new(0xFF, 0xFF));
+        let mips = r.mipmaps();
+        assert_eq!(mips.len(), 2);
+        assert_eq!(mips[0].region(), Region::new(0, 0, 2, 1));
+        // left pixel covers 2.5 columns, 1 of which is opaque
+        assert_eq!(mips[0].pixel(0, 0), Graya8::new(0xFF, 0x66));
+        assert_eq!(mips[0].pixel(1, 0), Graya8::new(0, 0));
+        assert_eq!(mips[1].pixel(0, 0), Graya8::new(0xFF, 0x33));
+        assert!(Raster::<Gray8>::with_clear(1, 1).mipmaps().is_empty());
+        assert!(Raster::<Gray8>::with_clear(0, 4).mipmaps().is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+// format.rs    Pixel memory layouts for graphics APIs.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::bgr::Bgr;
+use crate::chan::{Ch16, Ch32, Ch8, Srgb};
+use crate::el::Pixel;
+use crate::gray::Gray;
+use crate::matte::Matte;
+use crate::rgb::Rgb;
+use std::any::TypeId;
+
+/// Channel data type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Depth {
+    /// 8-bit unsigned normalized
+    U8,
+    /// 16-bit unsigned normalized
+    U16,
+    /// 32-bit float
+    F32,
+}
+
+/// Order of channels in memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Order {
+    /// One channel (gray or matte)
+    R,
+    /// Two channels (gray with alpha)
+    Rg,
+    /// Red, green, blue
+    Rgb,
+    /// Red, green, blue, alpha
+    Rgba,
+    /// Blue, green, red
+    Bgr,
+    /// Blue, green, red, alpha
+    Bgra,
+}
+
+/// Memory layout of a pixel format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Layout {
+    /// Channel order
+    pub order: Order,
+    /// Channel depth
+    pub depth: Depth,
+    /// Channels encoded with *sRGB* gamma
+    pub srgb: bool,
+}
+
+impl Layout {
+    /// Get the memory layout of a pixel format.
+    ///
+    /// Only formats which can be sampled directly by graphics APIs (RGB,
+    /// BGR, gray and matte) have a layout; alpha mode is not considered.
+    pub fn of<P: Pixel>() -> Option<Self> {
+        let model = TypeId::of::<P::Model>();
+        let len = P::default().channels().len();
+        let order = if model == TypeId::of::<Rgb>() {
+            [None, None, None, Some(Order::Rgb), Some(Order::Rgba)][len]
+        } else if model == TypeId::of::<Bgr>() {
+            [None, None, None, Some(Order::Bgr), Some(Order::Bgra)][len]
+        } else if model == TypeId::of::<Gray>() {
+            [None, Some(Order::R), Some(Order::Rg), None, None][len]
+        } else if model == TypeId::of::<Matte>() {
+            Some(Order::R)
+        } else {
+            None
+        }?;
+        let chan = TypeId::of::<P::Chan>();
+        let depth = if chan == TypeId::of::<Ch8>() {
+            Depth::U8
+        } else if chan == TypeId::of::<Ch16>() {
+            Depth::U16
+        } else if chan == TypeId::of::<Ch32>() {
+            Depth::F32
+        } else {
+            return None;
+        };
+        let srgb = TypeId::of::<P::Gamma>() == TypeId::of::<Srgb>();
+        Some(Layout { order, depth, srgb })
+    }
+}
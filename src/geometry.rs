@@ -0,0 +1,286 @@
+// geometry.rs  Raster rotation and flipping.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::el::Pixel;
+use crate::filter::{EdgeMode, Filter};
+use crate::raster::{for_each_row, Raster};
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a copy of a `Raster` rotated 90 degrees clockwise.
+    ///
+    /// ### Rotate
+    /// ```
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(3, 2);
+    /// *r.pixel_mut(2, 0) = Gray8::new(0xFF);
+    /// let r = r.rotated_90();
+    /// assert_eq!((r.width(), r.height()), (2, 3));
+    /// assert_eq!(r.pixel(1, 2), Gray8::new(0xFF));
+    /// ```
+    pub fn rotated_90(&self) -> Self {
+        self.reoriented(true, false, true)
+    }
+
+    /// Construct a copy of a `Raster` rotated 180 degrees.
+    pub fn rotated_180(&self) -> Self {
+        self.reoriented(false, true, true)
+    }
+
+    /// Construct a copy of a `Raster` rotated 270 degrees clockwise (90
+    /// degrees counter-clockwise).
+    pub fn rotated_270(&self) -> Self {
+        self.reoriented(true, true, false)
+    }
+
+    /// Construct a transposed copy of a `Raster`.
+    ///
+    /// Rows and columns are swapped, mirroring across the diagonal from the
+    /// top-left corner.
+    #[doc(alias = "transpose")]
+    pub fn transposed(&self) -> Self {
+        self.reoriented(true, false, false)
+    }
+
+    /// Construct a copy of a `Raster` flipped horizontally (mirrored).
+    pub fn flipped_horizontal(&self) -> Self {
+        self.reoriented(false, true, false)
+    }
+
+    /// Construct a copy of a `Raster` flipped vertically.
+    pub fn flipped_vertical(&self) -> Self {
+        self.reoriented(false, false, true)
+    }
+
+    /// Flip a `Raster` horizontally (mirror) in place.
+    ///
+    /// ### Mirror a sprite
+    /// ```
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(3, 2);
+    /// *r.pixel_mut(0, 1) = Gray8::new(0xFF);
+    /// r.flip_horizontal();
+    /// assert_eq!(r.pixel(2, 1), Gray8::new(0xFF));
+    /// ```
+    pub fn flip_horizontal(&mut self) {
+        let width = self.width() as usize;
+        if width > 0 {
+            for row in self.pixels_mut().chunks_exact_mut(width) {
+                row.reverse();
+            }
+        }
+    }
+
+    /// Flip a `Raster` vertically in place.
+    ///
+    /// This can be used to correct bottom-up bitmaps.
+    pub fn flip_vertical(&mut self) {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        let (top, bottom) = self.pixels_mut().split_at_mut(height / 2 * width);
+        // skip middle row of odd heights
+        let bottom = &mut bottom[(height % 2) * width..];
+        let trows = top.chunks_exact_mut(width.max(1));
+        let brows = bottom.chunks_exact_mut(width.max(1)).rev();
+        for (t, b) in trows.zip(brows) {
+            t.swap_with_slice(b);
+        }
+    }
+
+    /// Construct a copy of a `Raster` rotated by an arbitrary angle.
+    ///
+    /// * `angle` Clockwise angle, in radians.
+    /// * `filter` Resampling filter.
+    /// * `background` Color for areas outside of the source `Raster`.
+    ///
+    /// The result is expanded to contain the entire rotated `Raster`.
+    ///
+    /// ### Rotate by 30 degrees
+    /// ```
+    /// use pix::filter::Filter;
+    /// use pix::rgb::Rgba8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(40, 20, Rgba8::new(255, 0, 0, 255));
+    /// let bg = Rgba8::new(0, 0, 0, 0);
+    /// let r = r.rotated(30_f32.to_radians(), Filter::Bilinear, bg);
+    /// assert_eq!((r.width(), r.height()), (45, 38));
+    /// ```
+    pub fn rotated(&self, angle: f32, filter: Filter, background: P) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let (w, h) = (self.width() as f32, self.height() as f32);
+        // allow for rounding errors at multiples of 90 degrees
+        let width = ((w * cos).abs() + (h * sin).abs() - 1e-3).ceil();
+        let height = ((w * sin).abs() + (h * cos).abs() - 1e-3).ceil();
+        let center = (w / 2.0, h / 2.0);
+        let size = (width.max(0.0) as u32, height.max(0.0) as u32);
+        self.rotate(angle, center, size, filter, background)
+    }
+
+    /// Construct a copy of a `Raster` rotated about a center point.
+    ///
+    /// * `angle` Clockwise angle, in radians.
+    /// * `center` Center of rotation (*x*, *y*), in pixel coordinates.
+    /// * `filter` Resampling filter.
+    /// * `background` Color for areas outside of the source `Raster`.
+    ///
+    /// The result is cropped to the same size as the source `Raster`.
+    pub fn rotated_about(
+        &self,
+        angle: f32,
+        center: (f32, f32),
+        filter: Filter,
+        background: P,
+    ) -> Self {
+        let size = (self.width(), self.height());
+        self.rotate(angle, center, size, filter, background)
+    }
+
+    /// Rotate into a new `Raster`, centered on the source center point
+    fn rotate(
+        &self,
+        angle: f32,
+        center: (f32, f32),
+        size: (u32, u32),
+        filter: Filter,
+        background: P,
+    ) -> Self {
+        let mut r = self.blank(size.0, size.1);
+        let (sin, cos) = angle.sin_cos();
+        // offset of center point within destination
+        let dx = center.0 + (size.0 as f32 - self.width() as f32) / 2.0;
+        let dy = center.1 + (size.1 as f32 - self.height() as f32) / 2.0;
+        let edge = EdgeMode::Constant(background);
+        let len = r.pixels().len();
+        for_each_row(r.rows_mut(()).enumerate(), len, |(y, row)| {
+            let v = y as f32 + 0.5 - dy;
+            for (x, p) in row.iter_mut().enumerate() {
+                let u = x as f32 + 0.5 - dx;
+                let sx = cos * u + sin * v + center.0;
+                let sy = cos * v - sin * u + center.1;
+                *p = self.sample(sx, sy, filter, edge);
+            }
+        });
+        r
+    }
+
+    /// Construct a reoriented copy of a `Raster`.
+    ///
+    /// * `swap` Swap X and Y axes.
+    /// * `flip_x` Flip source X axis.
+    /// * `flip_y` Flip source Y axis.
+    fn reoriented(&self, swap: bool, flip_x: bool, flip_y: bool) -> Self {
+        // block size for cache-friendly access when swapping axes
+        const BLOCK: usize = 16;
+        let (sw, sh) = (self.width() as usize, self.height() as usize);
+        let (dw, dh) = if swap { (sh, sw) } else { (sw, sh) };
+        let mut r = self.blank(dw as u32, dh as u32);
+        let (src, dst) = (self.pixels(), r.pixels_mut());
+        for by in (0..dh).step_by(BLOCK) {
+            for bx in (0..dw).step_by(BLOCK) {
+                for y in by..(by + BLOCK).min(dh) {
+                    for x in bx..(bx + BLOCK).min(dw) {
+                        let (u, v) = if swap { (y, x) } else { (x, y) };
+                        let sx = if flip_x { sw - 1 - u } else { u };
+                        let sy = if flip_y { sh - 1 - v } else { v };
+                        dst[y * dw + x] = src[sy * sw + sx];
+                    }
+                }
+            }
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::filter::*;
+    use crate::gray::*;
+    use crate::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn reoriented() {
+        let mut r = Raster::<Gray8>::with_clear(3, 2);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray8::new(i as u8);
+        }
+        let g = |v: &[u8]| v.iter().map(|v| Gray8::new(*v)).collect::<Vec<_>>();
+        let r90 = r.rotated_90();
+        assert_eq!((r90.width(), r90.height()), (2, 3));
+        assert_eq!(r90.pixels(), &g(&[3, 0, 4, 1, 5, 2])[..]);
+        let r180 = r.rotated_180();
+        assert_eq!((r180.width(), r180.height()), (3, 2));
+        assert_eq!(r180.pixels(), &g(&[5, 4, 3, 2, 1, 0])[..]);
+        let r270 = r.rotated_270();
+        assert_eq!(r270.pixels(), &g(&[2, 5, 1, 4, 0, 3])[..]);
+        let t = r.transposed();
+        assert_eq!(t.pixels(), &g(&[0, 3, 1, 4, 2, 5])[..]);
+        assert_eq!(r90.rotated_270().pixels(), r.pixels());
+        assert_eq!(t.transposed().pixels(), r.pixels());
+        let big = Raster::with_color(40, 17, Gray8::new(7));
+        assert_eq!(big.rotated_90().rotated_90().pixels(),
+            big.rotated_180().pixels());
+    }
+
+    #[test]
+    fn flipped() {
+        let g = |v: &[u8]| v.iter().map(|v| Gray8::new(*v)).collect::<Vec<_>>();
+        for h in 1..4 {
+            let mut r = Raster::<Gray8>::with_clear(2, h);
+            for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+                *p = Gray8::new(i as u8);
+            }
+            let mut f = r.clone();
+            f.flip_vertical();
+            assert_eq!(f.pixels(), r.flipped_vertical().pixels());
+            f.flip_horizontal();
+            assert_eq!(f.pixels(), r.rotated_180().pixels());
+            f.flip_vertical();
+            assert_eq!(f.pixels(), r.flipped_horizontal().pixels());
+        }
+        let mut r = Raster::<Gray8>::with_clear(3, 3);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray8::new(i as u8);
+        }
+        r.flip_vertical();
+        assert_eq!(r.pixels(), &g(&[6, 7, 8, 3, 4, 5, 0, 1, 2])[..]);
+        r.flip_horizontal();
+        assert_eq!(r.pixels(), &g(&[8, 7, 6, 5, 4, 3, 2, 1, 0])[..]);
+        let mut e = Raster::<Gray8>::with_clear(0, 3);
+        e.flip_vertical();
+        e.flip_horizontal();
+    }
+
+    #[test]
+    fn rotated() {
+        let mut r = Raster::<Gray8>::with_clear(3, 2);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray8::new(i as u8 + 1);
+        }
+        let bg = Gray8::new(0xFF);
+        let r0 = r.rotated(0.0, Filter::Bilinear, bg);
+        assert_eq!(r0.pixels(), r.pixels());
+        let r90 = r.rotated(FRAC_PI_2, Filter::Nearest, bg);
+        assert_eq!((r90.width(), r90.height()), (2, 3));
+        assert_eq!(r90.pixels(), r.rotated_90().pixels());
+        let r180 = r.rotated_about(PI, (1.5, 1.0), Filter::CatmullRom, bg);
+        assert_eq!(r180.pixels(), r.rotated_180().pixels());
+        let r45 = r.rotated_about(FRAC_PI_4, (0.5, 0.5), Filter::Nearest, bg);
+        let v = [
+            Gray8::new(1), Gray8::new(0xFF), Gray8::new(0xFF),
+            Gray8::new(5), Gray8::new(2), Gray8::new(0xFF),
+        ];
+        assert_eq!(r45.pixels(), &v);
+        let r = Raster::with_color(10, 10, Gray8::new(0x80));
+        let r45 = r.rotated(FRAC_PI_4, Filter::Lanczos3, bg);
+        assert_eq!((r45.width(), r45.height()), (15, 15));
+        assert_eq!(r45.pixel(0, 0), bg);
+        assert_eq!(r45.pixel(7, 7), Gray8::new(0x80));
+    }
+}
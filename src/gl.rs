@@ -0,0 +1,184 @@
+// gl.rs        OpenGL texture formats.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Texture format mapping for OpenGL.
+//!
+//! [GlFormat::of] finds the `format`, `internalformat` and `type` parameters
+//! for uploading pixels with `glTexImage2D`.  Constants are plain `u32`
+//! values, so no OpenGL bindings are required.
+//!
+//! ### Upload a raster
+//! ```
+//! use pix::gl::{self, GlFormat};
+//! use pix::rgb::SRgb8;
+//! use pix::Raster;
+//!
+//! let r = Raster::<SRgb8>::with_clear(15, 10);
+//! let fmt = GlFormat::of::<SRgb8>().unwrap();
+//! assert_eq!(fmt.format, gl::RGB);
+//! assert_eq!(fmt.internal_format, gl::SRGB8);
+//! assert_eq!(fmt.ty, gl::UNSIGNED_BYTE);
+//! assert_eq!(gl::unpack_alignment::<SRgb8>(r.width()), 1);
+//! ```
+//!
+//! [GlFormat::of]: struct.GlFormat.html#method.of
+use crate::el::Pixel;
+use crate::format::{Depth, Layout, Order};
+
+/// `GL_RED` pixel format
+pub const RED: u32 = 0x1903;
+/// `GL_RG` pixel format
+pub const RG: u32 = 0x8227;
+/// `GL_RGB` pixel format
+pub const RGB: u32 = 0x1907;
+/// `GL_RGBA` pixel format
+pub const RGBA: u32 = 0x1908;
+/// `GL_BGR` pixel format
+pub const BGR: u32 = 0x80E0;
+/// `GL_BGRA` pixel format
+pub const BGRA: u32 = 0x80E1;
+
+/// `GL_UNSIGNED_BYTE` pixel type
+pub const UNSIGNED_BYTE: u32 = 0x1401;
+/// `GL_UNSIGNED_SHORT` pixel type
+pub const UNSIGNED_SHORT: u32 = 0x1403;
+/// `GL_FLOAT` pixel type
+pub const FLOAT: u32 = 0x1406;
+
+/// `GL_R8` internal format
+pub const R8: u32 = 0x8229;
+/// `GL_R16` internal format
+pub const R16: u32 = 0x822A;
+/// `GL_R32F` internal format
+pub const R32F: u32 = 0x822E;
+/// `GL_RG8` internal format
+pub const RG8: u32 = 0x822B;
+/// `GL_RG16` internal format
+pub const RG16: u32 = 0x822C;
+/// `GL_RG32F` internal format
+pub const RG32F: u32 = 0x8230;
+/// `GL_RGB8` internal format
+pub const RGB8: u32 = 0x8051;
+/// `GL_RGB16` internal format
+pub const RGB16: u32 = 0x8054;
+/// `GL_RGB32F` internal format
+pub const RGB32F: u32 = 0x8815;
+/// `GL_RGBA8` internal format
+pub const RGBA8: u32 = 0x8058;
+/// `GL_RGBA16` internal format
+pub const RGBA16: u32 = 0x805B;
+/// `GL_RGBA32F` internal format
+pub const RGBA32F: u32 = 0x8814;
+/// `GL_SRGB8` internal format
+pub const SRGB8: u32 = 0x8C41;
+/// `GL_SRGB8_ALPHA8` internal format
+pub const SRGB8_ALPHA8: u32 = 0x8C43;
+
+/// OpenGL texture format parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlFormat {
+    /// Pixel data `format`, such as [RGBA](constant.RGBA.html)
+    pub format: u32,
+    /// Texture `internalformat`, such as [RGBA8](constant.RGBA8.html)
+    pub internal_format: u32,
+    /// Pixel data `type`, such as
+    /// [UNSIGNED_BYTE](constant.UNSIGNED_BYTE.html)
+    pub ty: u32,
+}
+
+impl GlFormat {
+    /// Get the OpenGL format of a pixel type.
+    ///
+    /// Only RGB, BGR, gray and matte pixels are supported.  Alpha mode is
+    /// not part of the format; *premultiplied* pixels map to the same
+    /// format as *straight*.  Since OpenGL only has *sRGB* internal formats
+    /// for 8-bit color, other *sRGB* pixel types have no format.
+    pub fn of<P: Pixel>() -> Option<Self> {
+        let layout = Layout::of::<P>()?;
+        let format = match layout.order {
+            Order::R => RED,
+            Order::Rg => RG,
+            Order::Rgb => RGB,
+            Order::Rgba => RGBA,
+            Order::Bgr => BGR,
+            Order::Bgra => BGRA,
+        };
+        let ty = match layout.depth {
+            Depth::U8 => UNSIGNED_BYTE,
+            Depth::U16 => UNSIGNED_SHORT,
+            Depth::F32 => FLOAT,
+        };
+        let internal_format = match (layout.order, layout.depth, layout.srgb) {
+            (Order::R, Depth::U8, false) => R8,
+            (Order::R, Depth::U16, false) => R16,
+            (Order::R, Depth::F32, false) => R32F,
+            (Order::Rg, Depth::U8, false) => RG8,
+            (Order::Rg, Depth::U16, false) => RG16,
+            (Order::Rg, Depth::F32, false) => RG32F,
+            (Order::Rgb | Order::Bgr, Depth::U8, false) => RGB8,
+            (Order::Rgb | Order::Bgr, Depth::U8, true) => SRGB8,
+            (Order::Rgb | Order::Bgr, Depth::U16, false) => RGB16,
+            (Order::Rgb | Order::Bgr, Depth::F32, false) => RGB32F,
+            (Order::Rgba | Order::Bgra, Depth::U8, false) => RGBA8,
+            (Order::Rgba | Order::Bgra, Depth::U8, true) => SRGB8_ALPHA8,
+            (Order::Rgba | Order::Bgra, Depth::U16, false) => RGBA16,
+            (Order::Rgba | Order::Bgra, Depth::F32, false) => RGBA32F,
+            _ => return None,
+        };
+        Some(GlFormat {
+            format,
+            internal_format,
+            ty,
+        })
+    }
+}
+
+/// Get the `GL_UNPACK_ALIGNMENT` for rows of pixels.
+///
+/// * `width` Width of rows, in pixels.
+///
+/// Returns the largest valid alignment (8, 4, 2 or 1) which evenly divides
+/// the row length in bytes.  Raster rows are not padded, so the default
+/// alignment of 4 is wrong for some widths, such as odd-width RGB.
+pub fn unpack_alignment<P: Pixel>(width: u32) -> i32 {
+    let row_len = width as usize * std::mem::size_of::<P>();
+    [8, 4, 2]
+        .into_iter()
+        .find(|a| row_len % *a as usize == 0)
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bgr::*;
+    use crate::gray::*;
+    use crate::hsv::*;
+    use crate::rgb::*;
+
+    #[test]
+    fn formats() {
+        let f = GlFormat::of::<SRgba8p>().unwrap();
+        assert_eq!((f.format, f.internal_format), (RGBA, SRGB8_ALPHA8));
+        assert_eq!(f.ty, UNSIGNED_BYTE);
+        let f = GlFormat::of::<Bgra8>().unwrap();
+        assert_eq!((f.format, f.internal_format), (BGRA, RGBA8));
+        let f = GlFormat::of::<Gray16>().unwrap();
+        assert_eq!((f.format, f.internal_format), (RED, R16));
+        assert_eq!(f.ty, UNSIGNED_SHORT);
+        let f = GlFormat::of::<Rgb32>().unwrap();
+        assert_eq!((f.format, f.internal_format, f.ty), (RGB, RGB32F, FLOAT));
+        assert_eq!(GlFormat::of::<SRgba16>(), None);
+        assert_eq!(GlFormat::of::<SGray8>(), None);
+        assert_eq!(GlFormat::of::<Hsv8>(), None);
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(unpack_alignment::<Rgb8>(3), 1);
+        assert_eq!(unpack_alignment::<Rgb8>(2), 2);
+        assert_eq!(unpack_alignment::<Rgba8>(3), 4);
+        assert_eq!(unpack_alignment::<Rgba16>(3), 8);
+    }
+}
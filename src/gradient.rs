@@ -0,0 +1,251 @@
+// gradient.rs  Gradient fills.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Linear and radial gradient fills.
+//!
+//! A [GradientFill] is used to fill a region of a `Raster` with
+//! [fill_gradient].
+//!
+//! Colors between stops are interpolated using the gradient's pixel format,
+//! which selects the interpolation space.  For example, `SRgba32` blends in
+//! *sRGB* gamma, `Rgba32p` in linear light, `Hsla32` around the color wheel
+//! and `Oklaba32` in a perceptually uniform space.
+//!
+//! [fill_gradient]: ../struct.Raster.html#method.fill_gradient
+//! [GradientFill]: struct.GradientFill.html
+use crate::el::Pixel;
+
+/// Geometry of a [GradientFill].
+///
+/// Coordinates are in pixels, relative to the raster.  Pixel centers are at
+/// half-pixel offsets.
+///
+/// [GradientFill]: struct.GradientFill.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientShape {
+    /// Linear gradient, from `start` (position `0.0`) to `end` (position
+    /// `1.0`)
+    Linear {
+        /// Start point (*x*, *y*)
+        start: (f32, f32),
+        /// End point (*x*, *y*)
+        end: (f32, f32),
+    },
+    /// Radial gradient, from `center` (position `0.0`) to `radius` (position
+    /// `1.0`)
+    Radial {
+        /// Center point (*x*, *y*)
+        center: (f32, f32),
+        /// Radius of the gradient circle
+        radius: f32,
+    },
+}
+
+/// Gradient behavior for positions outside `0.0` to `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Spread {
+    /// Extend the first / last stop colors
+    #[default]
+    Pad,
+    /// Repeat the gradient
+    Repeat,
+    /// Repeat the gradient, reflecting every other cycle
+    Reflect,
+}
+
+/// Gradient with color stops and a shape.
+///
+/// * `P` Pixel format used for interpolation between stops.
+///
+/// ### Vignette
+/// ```
+/// use pix::gradient::GradientFill;
+/// use pix::rgb::{SRgba8, SRgba32};
+/// use pix::Raster;
+///
+/// let mut g = GradientFill::radial((50.0, 50.0), 50.0);
+/// g.add_stop(0.5, SRgba32::new(0.0, 0.0, 0.0, 0.0));
+/// g.add_stop(1.0, SRgba32::new(0.0, 0.0, 0.0, 1.0));
+/// let mut r = Raster::<SRgba8>::with_clear(100, 100);
+/// r.fill_gradient((), &g);
+/// assert_eq!(r.pixel(50, 50), SRgba8::new(0, 0, 0, 0));
+/// assert_eq!(r.pixel(0, 0), SRgba8::new(0, 0, 0, 0xFF));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientFill<P: Pixel> {
+    /// Gradient shape
+    shape: GradientShape,
+    /// Spread mode
+    spread: Spread,
+    /// Color stops, sorted by position
+    stops: Vec<(f32, P)>,
+}
+
+impl<P: Pixel> GradientFill<P> {
+    /// Create a gradient with no color stops.
+    ///
+    /// * `shape` Gradient geometry.
+    pub fn new(shape: GradientShape) -> Self {
+        GradientFill {
+            shape,
+            spread: Spread::default(),
+            stops: Vec::new(),
+        }
+    }
+
+    /// Create a linear gradient with no color stops.
+    ///
+    /// * `start` Start point (*x*, *y*), at position `0.0`.
+    /// * `end` End point (*x*, *y*), at position `1.0`.
+    pub fn linear(start: (f32, f32), end: (f32, f32)) -> Self {
+        Self::new(GradientShape::Linear { start, end })
+    }
+
+    /// Create a radial gradient with no color stops.
+    ///
+    /// * `center` Center point (*x*, *y*), at position `0.0`.
+    /// * `radius` Distance from center to position `1.0`.
+    pub fn radial(center: (f32, f32), radius: f32) -> Self {
+        Self::new(GradientShape::Radial { center, radius })
+    }
+
+    /// Get the gradient shape.
+    pub fn shape(&self) -> GradientShape {
+        self.shape
+    }
+
+    /// Get the spread mode.
+    pub fn spread(&self) -> Spread {
+        self.spread
+    }
+
+    /// Set the spread mode.
+    pub fn set_spread(&mut self, spread: Spread) {
+        self.spread = spread;
+    }
+
+    /// Get the color stops, sorted by position.
+    pub fn stops(&self) -> &[(f32, P)] {
+        &self.stops
+    }
+
+    /// Add a color stop.
+    ///
+    /// * `pos` Position of stop, from `0.0` to `1.0`.
+    /// * `clr` Color at the stop.
+    ///
+    /// Stops at the same position make a hard transition; they are kept in
+    /// the order added.
+    pub fn add_stop(&mut self, pos: f32, clr: P) {
+        let pos = pos.clamp(0.0, 1.0);
+        let i = self.stops.partition_point(|(p, _)| *p <= pos);
+        self.stops.insert(i, (pos, clr));
+    }
+
+    /// Get the gradient position at a point
+    fn position(&self, x: f32, y: f32) -> f32 {
+        match self.shape {
+            GradientShape::Linear { start, end } => {
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let len2 = dx * dx + dy * dy;
+                if len2 > 0.0 {
+                    ((x - start.0) * dx + (y - start.1) * dy) / len2
+                } else {
+                    0.0
+                }
+            }
+            GradientShape::Radial { center, radius } => {
+                let d = (x - center.0).hypot(y - center.1);
+                if radius > 0.0 {
+                    d / radius
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Get the color at a gradient position.
+    ///
+    /// * `pos` Gradient position, adjusted by the spread mode.
+    ///
+    /// With no stops, the default pixel value is returned.
+    pub fn color(&self, pos: f32) -> P {
+        let pos = match self.spread {
+            Spread::Pad => pos.clamp(0.0, 1.0),
+            Spread::Repeat => pos - pos.floor(),
+            Spread::Reflect => {
+                let p = pos.rem_euclid(2.0);
+                if p > 1.0 {
+                    2.0 - p
+                } else {
+                    p
+                }
+            }
+        };
+        let i = self.stops.partition_point(|(p, _)| *p <= pos);
+        let before = i.checked_sub(1).map(|j| self.stops[j]);
+        match (before, self.stops.get(i).copied()) {
+            (Some((p0, c0)), Some((p1, c1))) => {
+                let t = (pos - p0) / (p1 - p0);
+                c0.lerp(c1, P::Chan::from(t))
+            }
+            (Some((_, c)), None) | (None, Some((_, c))) => c,
+            (None, None) => P::default(),
+        }
+    }
+
+    /// Get the color at a point.
+    ///
+    /// * `x` X-coordinate, in pixels.
+    /// * `y` Y-coordinate, in pixels.
+    pub fn color_at(&self, x: f32, y: f32) -> P {
+        self.color(self.position(x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chan::Channel;
+    use crate::gray::*;
+    use crate::hsl::*;
+
+    #[test]
+    fn stops() {
+        let mut g = GradientFill::linear((0.0, 0.0), (10.0, 0.0));
+        assert_eq!(g.color(0.5), Gray32::default());
+        g.add_stop(1.0, Gray32::new(1.0));
+        g.add_stop(0.5, Gray32::new(0.5));
+        g.add_stop(0.5, Gray32::new(0.0));
+        assert_eq!(g.color(0.25), Gray32::new(0.5));
+        assert_eq!(g.color(0.5), Gray32::new(0.0));
+        assert_eq!(g.color(0.75), Gray32::new(0.5));
+        assert_eq!(g.color_at(10.0, 5.0), Gray32::new(1.0));
+        assert_eq!(g.color_at(-5.0, 5.0), Gray32::new(0.5));
+    }
+
+    #[test]
+    fn spread() {
+        let mut g = GradientFill::radial((0.0, 0.0), 4.0);
+        g.add_stop(0.0, Gray32::new(0.0));
+        g.add_stop(1.0, Gray32::new(1.0));
+        assert_eq!(g.color_at(0.0, 5.0), Gray32::new(1.0));
+        g.set_spread(Spread::Repeat);
+        assert_eq!(g.color_at(0.0, 5.0), Gray32::new(0.25));
+        g.set_spread(Spread::Reflect);
+        assert_eq!(g.color_at(0.0, 5.0), Gray32::new(0.75));
+        assert_eq!(g.color_at(-10.0, 0.0), Gray32::new(0.5));
+    }
+
+    #[test]
+    fn hue() {
+        let mut g = GradientFill::linear((0.0, 0.0), (0.0, 1.0));
+        g.add_stop(0.0, Hsl32::new(0.9, 1.0, 0.5));
+        g.add_stop(1.0, Hsl32::new(0.1, 1.0, 0.5));
+        // interpolated the short way around the color wheel
+        let c = g.color(0.25);
+        assert!((c.one().to_f32() - 0.95).abs() < 1e-6);
+    }
+}
@@ -7,10 +7,12 @@
 //!
 //! [`gray`]: https://en.wikipedia.org/wiki/Grayscale
 use crate::chan::{
-    Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
+    Alpha, Ch16, Ch32, Ch8, Channel, Gamma, Linear, Premultiplied, Srgb,
+    Straight,
 };
-use crate::el::{Pix1, Pix2, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::el::{from_model_channels, Pix1, Pix2, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// Gray [color model].
@@ -64,10 +66,31 @@ impl Gray {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Gray] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::gray::{GrayPixel, Graya8};
+    ///
+    /// let mut p = Graya8::new(1, 2);
+    /// assert_eq!(p.value(), Ch8::new(1));
+    /// *p.value_mut() = Ch8::new(9);
+    /// assert_eq!(p.value(), Ch8::new(9));
+    /// ```
+    ///
+    /// [Gray]: struct.Gray.html
+    GrayPixel: Gray {
+        value, value_mut, with_value;
+    }
+}
+
 impl ColorModel for Gray {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..1;
     const ALPHA: usize = 1;
+    const LABELS: &'static [ChannelLabel] = &[ChannelLabel::Luma];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -93,10 +116,39 @@ impl ColorModel for Gray {
         let blue = chan[2].to_f32() * BLUE_COEF;
         let value = P::Chan::from(red + green + blue);
         let alpha = chan[3];
-        P::from_channels(&[value, alpha])
+        from_model_channels(&[value, alpha])
     }
 }
 
+impl<C, A, G> Pix1<C, Gray, A, G>
+where
+    C: Channel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Black
+    pub const BLACK: Self = Self::from_array([C::MIN]);
+
+    /// White
+    pub const WHITE: Self = Self::from_array([C::MAX]);
+}
+
+impl<C, A, G> Pix2<C, Gray, A, G>
+where
+    C: Channel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Opaque black
+    pub const BLACK: Self = Self::from_array([C::MIN, C::MAX]);
+
+    /// Opaque white
+    pub const WHITE: Self = Self::from_array([C::MAX, C::MAX]);
+
+    /// Transparent black
+    pub const TRANSPARENT: Self = Self::from_array([C::MIN, C::MIN]);
+}
+
 /// [Gray](struct.Gray.html) 8-bit opaque (no *alpha* channel)
 /// [linear](../chan/struct.Linear.html) gamma
 /// [pixel](../el/trait.Pixel.html) format.
@@ -234,4 +286,12 @@ mod test {
         assert_eq!(Matte16::new(0xA2A2), SGraya8::new(0xBA, 0xA2).convert());
         assert_eq!(Matte8::new(0x80), SGraya32::new(0.75, 0.5).convert());
     }
+
+    #[test]
+    fn constants() {
+        assert_eq!(Gray8::WHITE, Gray8::new(0xFF));
+        assert_eq!(SGray16::BLACK, SGray16::new(0));
+        assert_eq!(Graya8p::TRANSPARENT, Graya8p::new(0, 0));
+        assert_eq!(Graya32::WHITE, Graya32::new(1.0, 1.0));
+    }
 }
@@ -9,9 +9,10 @@
 use crate::chan::{
     Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
 };
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
 use crate::hue::{rgb_to_hue_chroma_value, Hexcone};
-use crate::ColorModel;
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [HSL] bi-hexcone [color model].
@@ -158,10 +159,37 @@ impl Hsl {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Hsl] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::hsl::{HslPixel, Hsl8};
+    ///
+    /// let mut p = Hsl8::new(1, 2, 3);
+    /// assert_eq!(p.hue(), Ch8::new(1));
+    /// *p.lightness_mut() = Ch8::new(9);
+    /// assert_eq!(p.lightness(), Ch8::new(9));
+    /// ```
+    ///
+    /// [Hsl]: struct.Hsl.html
+    HslPixel: Hsl {
+        hue, hue_mut, with_hue;
+        saturation, saturation_mut, with_saturation;
+        lightness, lightness_mut, with_lightness;
+    }
+}
+
 impl ColorModel for Hsl {
     const CIRCULAR: Range<usize> = 0..1;
     const LINEAR: Range<usize> = 1..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] = &[
+        ChannelLabel::Hue,
+        ChannelLabel::Saturation,
+        ChannelLabel::Lightness,
+    ];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -191,7 +219,7 @@ impl ColorModel for Hsl {
         let lightness = val - chroma * P::Chan::from(0.5);
         let min_l = lightness.min(P::Chan::MAX - lightness);
         let sat_l = (val - lightness) / min_l;
-        P::from_channels(&[hue, sat_l, lightness, alpha])
+        from_model_channels(&[hue, sat_l, lightness, alpha])
     }
 }
 
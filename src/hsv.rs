@@ -9,9 +9,10 @@
 use crate::chan::{
     Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
 };
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
 use crate::hue::{rgb_to_hue_chroma_value, Hexcone};
-use crate::ColorModel;
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [HSV] hexcone [color model], also known as HSB.
@@ -158,10 +159,38 @@ impl Hsv {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Hsv] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::hsv::{HsvPixel, Hsv8};
+    ///
+    /// let mut p = Hsv8::new(1, 2, 3);
+    /// assert_eq!(p.hue(), Ch8::new(1));
+    /// *p.value_mut() = Ch8::new(9);
+    /// assert_eq!(p.value(), Ch8::new(9));
+    /// assert_eq!(p.with_hue(Ch8::new(7)), Hsv8::new(7, 2, 9));
+    /// ```
+    ///
+    /// [Hsv]: struct.Hsv.html
+    HsvPixel: Hsv {
+        hue, hue_mut, with_hue;
+        saturation, saturation_mut, with_saturation;
+        value, value_mut, with_value;
+    }
+}
+
 impl ColorModel for Hsv {
     const CIRCULAR: Range<usize> = 0..1;
     const LINEAR: Range<usize> = 1..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] = &[
+        ChannelLabel::Hue,
+        ChannelLabel::Saturation,
+        ChannelLabel::Value,
+    ];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -189,7 +218,7 @@ impl ColorModel for Hsv {
         let alpha = chan[3];
         let (hue, chroma, val) = rgb_to_hue_chroma_value(red, green, blue);
         let sat_v = chroma / val;
-        P::from_channels(&[hue, sat_v, val, alpha])
+        from_model_channels(&[hue, sat_v, val, alpha])
     }
 }
 
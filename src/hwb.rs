@@ -8,9 +8,10 @@
 use crate::chan::{
     Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
 };
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
 use crate::hue::{rgb_to_hue_chroma_value, Hexcone};
-use crate::ColorModel;
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [HWB] [color model].
@@ -169,10 +170,37 @@ impl Hwb {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Hwb] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::hwb::{HwbPixel, Hwb8};
+    ///
+    /// let mut p = Hwb8::new(1, 2, 3);
+    /// assert_eq!(p.hue(), Ch8::new(1));
+    /// *p.blackness_mut() = Ch8::new(9);
+    /// assert_eq!(p.blackness(), Ch8::new(9));
+    /// ```
+    ///
+    /// [Hwb]: struct.Hwb.html
+    HwbPixel: Hwb {
+        hue, hue_mut, with_hue;
+        whiteness, whiteness_mut, with_whiteness;
+        blackness, blackness_mut, with_blackness;
+    }
+}
+
 impl ColorModel for Hwb {
     const CIRCULAR: Range<usize> = 0..1;
     const LINEAR: Range<usize> = 1..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] = &[
+        ChannelLabel::Hue,
+        ChannelLabel::Whiteness,
+        ChannelLabel::Blackness,
+    ];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -203,7 +231,7 @@ impl ColorModel for Hwb {
         let sat_v = chroma / val;
         let whiteness = (P::Chan::MAX - sat_v) * val;
         let blackness = P::Chan::MAX - val;
-        P::from_channels(&[hue, whiteness, blackness, alpha])
+        from_model_channels(&[hue, whiteness, blackness, alpha])
     }
 }
 
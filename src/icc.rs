@@ -0,0 +1,456 @@
+// icc.rs       ICC color profiles.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! ICC color profiles, with support for matrix / TRC RGB transforms.
+//!
+//! An [IccProfile] can be attached to a [Raster] to record the color space
+//! of its pixels.  RGB profiles using a matrix and tone reproduction curves
+//! (TRC) can be parsed into a [MatrixProfile], which converts pixels to the
+//! linear *sRGB* primaries used by this crate.
+//!
+//! [IccProfile]: struct.IccProfile.html
+//! [MatrixProfile]: struct.MatrixProfile.html
+//! [Raster]: ../struct.Raster.html
+use crate::chan::{Ch32, Channel, Straight};
+use crate::el::{Pix4, Pixel};
+use crate::raster::Raster;
+use crate::rgb::{Rgb, Rgba32};
+use std::fmt;
+
+/// Length of ICC profile header
+const HEADER_LEN: usize = 128;
+
+/// Conversion from *D50* XYZ to linear *sRGB* (Bradford adapted)
+const XYZ_D50_TO_SRGB: [[f32; 3]; 3] = [
+    [3.133_856, -1.616_867, -0.490_615],
+    [-0.978_768, 1.916_142, 0.033_454],
+    [0.071_945, -0.228_991, 1.405_243],
+];
+
+/// Error parsing an ICC profile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IccError {
+    /// Profile data ends before header or tag
+    Truncated,
+    /// Missing or invalid profile header
+    InvalidHeader,
+    /// Profile is not an RGB / XYZ profile
+    NotRgb,
+    /// Required tag missing (signature)
+    MissingTag([u8; 4]),
+    /// Tag has invalid or unsupported type (signature)
+    InvalidTag([u8; 4]),
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IccError::Truncated => write!(f, "truncated profile"),
+            IccError::InvalidHeader => write!(f, "invalid header"),
+            IccError::NotRgb => write!(f, "not an RGB profile"),
+            IccError::MissingTag(s) => {
+                write!(f, "missing tag: {}", String::from_utf8_lossy(s))
+            }
+            IccError::InvalidTag(s) => {
+                write!(f, "invalid tag: {}", String::from_utf8_lossy(s))
+            }
+        }
+    }
+}
+
+impl std::error::Error for IccError {}
+
+/// ICC color profile
+///
+/// The profile data is kept as-is, so that it can be embedded when encoding
+/// an image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IccProfile {
+    /// Raw profile data
+    data: Box<[u8]>,
+}
+
+/// Tone reproduction curve
+#[derive(Clone, Debug, PartialEq)]
+pub enum ToneCurve {
+    /// Simple power function
+    Gamma(f32),
+    /// Parametric function, with parameters `[g, a, b, c, d, e, f]`:
+    ///
+    /// * `(a * x + b) ^ g + e` when `x >= d`
+    /// * `c * x + f` when `x < d`
+    Parametric([f32; 7]),
+    /// Table of evenly spaced samples, linearly interpolated
+    ///
+    /// An empty table is the identity function, and a table with one sample
+    /// is constant.
+    Table(Box<[u16]>),
+}
+
+/// Matrix / TRC RGB profile
+///
+/// Converts encoded RGB values to *D50* XYZ by applying a tone curve to each
+/// channel, followed by a 3x3 matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixProfile {
+    /// Matrix from linear RGB to *D50* XYZ (rows X, Y, Z)
+    pub matrix: [[f32; 3]; 3],
+    /// Tone curves for red, green and blue channels
+    pub curves: [ToneCurve; 3],
+}
+
+/// Read a big-endian `u32`
+fn be_u32(data: &[u8], pos: usize) -> Result<u32, IccError> {
+    let b = data.get(pos..pos + 4).ok_or(IccError::Truncated)?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Read a big-endian `u16`
+fn be_u16(data: &[u8], pos: usize) -> Result<u16, IccError> {
+    let b = data.get(pos..pos + 2).ok_or(IccError::Truncated)?;
+    Ok(u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Read an `s15Fixed16Number`
+fn s15f16(data: &[u8], pos: usize) -> Result<f32, IccError> {
+    Ok(be_u32(data, pos)? as i32 as f32 / 65536.0)
+}
+
+impl IccProfile {
+    /// Create a profile from raw ICC data.
+    ///
+    /// * `data` Profile data, as embedded in an image file.
+    ///
+    /// Only the header is validated; tags are parsed on demand.
+    pub fn new<B>(data: B) -> Result<Self, IccError>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let data = data.into();
+        if data.len() < HEADER_LEN + 4 {
+            return Err(IccError::Truncated);
+        }
+        if &data[36..40] != b"acsp" {
+            return Err(IccError::InvalidHeader);
+        }
+        if (be_u32(&data, 0)? as usize) > data.len() {
+            return Err(IccError::Truncated);
+        }
+        Ok(IccProfile { data })
+    }
+
+    /// Get the raw profile data
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the data color space signature (`b"RGB "`, `b"GRAY"`, etc.)
+    pub fn color_space(&self) -> [u8; 4] {
+        [self.data[16], self.data[17], self.data[18], self.data[19]]
+    }
+
+    /// Find a tag, returning its data
+    fn tag(&self, sig: &[u8; 4]) -> Result<&[u8], IccError> {
+        let count = be_u32(&self.data, HEADER_LEN)? as usize;
+        for i in 0..count {
+            let pos = HEADER_LEN + 4 + i * 12;
+            let entry =
+                self.data.get(pos..pos + 12).ok_or(IccError::Truncated)?;
+            if &entry[..4] == sig {
+                let offset = be_u32(entry, 4)? as usize;
+                let size = be_u32(entry, 8)? as usize;
+                return self
+                    .data
+                    .get(offset..offset.saturating_add(size))
+                    .ok_or(IccError::Truncated);
+            }
+        }
+        Err(IccError::MissingTag(*sig))
+    }
+
+    /// Read an `XYZType` tag
+    fn xyz(&self, sig: &[u8; 4]) -> Result<[f32; 3], IccError> {
+        let tag = self.tag(sig)?;
+        if !tag.starts_with(b"XYZ ") {
+            return Err(IccError::InvalidTag(*sig));
+        }
+        Ok([s15f16(tag, 8)?, s15f16(tag, 12)?, s15f16(tag, 16)?])
+    }
+
+    /// Read a `curveType` or `parametricCurveType` tag
+    fn curve(&self, sig: &[u8; 4]) -> Result<ToneCurve, IccError> {
+        let tag = self.tag(sig)?;
+        match tag.get(..4) {
+            Some(b"curv") => {
+                let count = be_u32(tag, 8)? as usize;
+                match count {
+                    0 => Ok(ToneCurve::Gamma(1.0)),
+                    1 => Ok(ToneCurve::Gamma(
+                        f32::from(be_u16(tag, 12)?) / 256.0,
+                    )),
+                    _ => Ok(ToneCurve::Table(
+                        (0..count)
+                            .map(|i| be_u16(tag, 12 + i * 2))
+                            .collect::<Result<_, _>>()?,
+                    )),
+                }
+            }
+            Some(b"para") => {
+                let p = |i: usize| s15f16(tag, 12 + i * 4);
+                let [g, a, b, c, d, e, f] = match be_u16(tag, 8)? {
+                    0 => [p(0)?, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                    1 => {
+                        let (a, b) = (p(1)?, p(2)?);
+                        [p(0)?, a, b, 0.0, -b / a, 0.0, 0.0]
+                    }
+                    2 => {
+                        let (a, b, c) = (p(1)?, p(2)?, p(3)?);
+                        [p(0)?, a, b, 0.0, -b / a, c, c]
+                    }
+                    3 => [p(0)?, p(1)?, p(2)?, p(3)?, p(4)?, 0.0, 0.0],
+                    4 => [p(0)?, p(1)?, p(2)?, p(3)?, p(4)?, p(5)?, p(6)?],
+                    _ => return Err(IccError::InvalidTag(*sig)),
+                };
+                Ok(ToneCurve::Parametric([g, a, b, c, d, e, f]))
+            }
+            _ => Err(IccError::InvalidTag(*sig)),
+        }
+    }
+
+    /// Parse a matrix / TRC RGB profile.
+    ///
+    /// The `rXYZ`, `gXYZ`, `bXYZ`, `rTRC`, `gTRC` and `bTRC` tags are
+    /// required.
+    pub fn matrix_profile(&self) -> Result<MatrixProfile, IccError> {
+        if &self.data[16..20] != b"RGB " || &self.data[20..24] != b"XYZ " {
+            return Err(IccError::NotRgb);
+        }
+        let r = self.xyz(b"rXYZ")?;
+        let g = self.xyz(b"gXYZ")?;
+        let b = self.xyz(b"bXYZ")?;
+        let matrix =
+            [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+        let curves = [
+            self.curve(b"rTRC")?,
+            self.curve(b"gTRC")?,
+            self.curve(b"bTRC")?,
+        ];
+        Ok(MatrixProfile { matrix, curves })
+    }
+}
+
+impl ToneCurve {
+    /// Evaluate the curve, converting an encoded value to linear.
+    ///
+    /// * `x` Encoded value, from 0 to 1.
+    pub fn eval(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            ToneCurve::Gamma(g) => x.powf(*g),
+            ToneCurve::Parametric([g, a, b, c, d, e, f]) => {
+                if x >= *d {
+                    (a * x + b).max(0.0).powf(*g) + e
+                } else {
+                    c * x + f
+                }
+            }
+            ToneCurve::Table(t) if t.is_empty() => x,
+            ToneCurve::Table(t) if t.len() == 1 => f32::from(t[0]) / 65535.0,
+            ToneCurve::Table(t) => {
+                let pos = x * (t.len() - 1) as f32;
+                let i = (pos as usize).min(t.len() - 2);
+                let frac = pos - i as f32;
+                let v0 = f32::from(t[i]);
+                let v1 = f32::from(t[i + 1]);
+                (v0 + (v1 - v0) * frac) / 65535.0
+            }
+        }
+    }
+}
+
+impl MatrixProfile {
+    /// Get an *sRGB* profile, with *D50* adapted colorants
+    pub fn srgb() -> Self {
+        let curve = ToneCurve::Parametric([
+            2.4,
+            1.0 / 1.055,
+            0.055 / 1.055,
+            1.0 / 12.92,
+            0.04045,
+            0.0,
+            0.0,
+        ]);
+        MatrixProfile {
+            matrix: [
+                [0.436_075, 0.385_065, 0.143_080],
+                [0.222_504, 0.716_879, 0.060_617],
+                [0.013_932, 0.097_105, 0.714_173],
+            ],
+            curves: [curve.clone(), curve.clone(), curve],
+        }
+    }
+
+    /// Convert encoded RGB values to *D50* XYZ
+    pub fn to_xyz(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let lin: [f32; 3] =
+            std::array::from_fn(|i| self.curves[i].eval(rgb[i]));
+        mul(&self.matrix, lin)
+    }
+
+    /// Convert encoded RGB values to linear *sRGB*
+    pub fn to_linear_srgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        mul(&XYZ_D50_TO_SRGB, self.to_xyz(rgb))
+    }
+
+    /// Transform a raster encoded with this profile.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    /// * `D` `Pixel` format of destination `Raster`.
+    /// * `src` Source raster.  Its gamma mode is ignored, since the tone
+    ///   curves of the profile are used instead.
+    ///
+    /// Colors outside of the *sRGB* gamut are clipped.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::icc::MatrixProfile;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let src = Raster::with_color(2, 2, SRgb8::new(0x40, 0x80, 0xC0));
+    /// let dst: Raster<SRgb8> = MatrixProfile::srgb().transform(&src);
+    /// assert_eq!(dst.pixel(0, 0), SRgb8::new(0x40, 0x80, 0xC0));
+    /// ```
+    pub fn transform<S, D>(&self, src: &Raster<S>) -> Raster<D>
+    where
+        S: Pixel<Model = Rgb>,
+        D: Pixel,
+        Ch32: From<S::Chan>,
+        D::Chan: From<Ch32>,
+    {
+        src.map(|p| {
+            let p: Pix4<Ch32, Rgb, Straight, S::Gamma> = p.convert();
+            let ch = p.channels();
+            let rgb = [ch[0].to_f32(), ch[1].to_f32(), ch[2].to_f32()];
+            let [r, g, b] = self.to_linear_srgb(rgb);
+            let [r, g, b] = [r, g, b].map(Ch32::new);
+            Rgba32::from_array([r, g, b, ch[3]]).convert()
+        })
+    }
+}
+
+/// Multiply a matrix by a vector
+fn mul(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    std::array::from_fn(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::{Rgb32, SRgb8};
+
+    /// Build a minimal matrix / TRC profile
+    fn profile(trc: &[u8]) -> Vec<u8> {
+        let srgb = MatrixProfile::srgb();
+        let sigs: [&[u8; 4]; 6] =
+            [b"rXYZ", b"gXYZ", b"bXYZ", b"rTRC", b"gTRC", b"bTRC"];
+        let mut tags = Vec::new();
+        for i in 0..3 {
+            let mut t = b"XYZ \0\0\0\0".to_vec();
+            for row in srgb.matrix {
+                let v = (row[i] * 65536.0).round() as i32;
+                t.extend_from_slice(&v.to_be_bytes());
+            }
+            tags.push(t);
+        }
+        for _ in 0..3 {
+            tags.push(trc.to_vec());
+        }
+        let mut data = vec![0; HEADER_LEN];
+        data[16..20].copy_from_slice(b"RGB ");
+        data[20..24].copy_from_slice(b"XYZ ");
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend_from_slice(&6u32.to_be_bytes());
+        let mut offset = data.len() + 6 * 12;
+        for (sig, t) in sigs.iter().zip(&tags) {
+            data.extend_from_slice(&sig[..]);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(t.len() as u32).to_be_bytes());
+            offset += t.len();
+        }
+        for t in tags {
+            data.extend_from_slice(&t);
+        }
+        let len = data.len() as u32;
+        data[..4].copy_from_slice(&len.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn parse() {
+        // parametric type 0, gamma 2.0
+        let para = b"para\0\0\0\0\0\0\0\0\0\x02\0\0";
+        let icc = IccProfile::new(profile(para)).unwrap();
+        assert_eq!(icc.color_space(), *b"RGB ");
+        let mp = icc.matrix_profile().unwrap();
+        assert_eq!(
+            mp.curves[0],
+            ToneCurve::Parametric([2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+        );
+        assert!((mp.matrix[1][1] - 0.716_879).abs() < 1e-4);
+        // curve with gamma 1.8
+        let curv = b"curv\0\0\0\0\0\0\0\x01\x01\xCD";
+        let mp = IccProfile::new(profile(curv)).unwrap().matrix_profile();
+        assert_eq!(mp.unwrap().curves[2], ToneCurve::Gamma(461.0 / 256.0));
+        // table
+        let curv = b"curv\0\0\0\0\0\0\0\x03\0\0\x40\0\xFF\xFF";
+        let mp = IccProfile::new(profile(curv)).unwrap().matrix_profile();
+        let c = &mp.unwrap().curves[1];
+        assert!((c.eval(0.25) - 0.125).abs() < 1e-4);
+        assert!((c.eval(1.0) - 1.0).abs() < 1e-4);
+        assert_eq!(ToneCurve::Table(Box::new([])).eval(0.25), 0.25);
+        let c = ToneCurve::Table(Box::new([0x8000]));
+        assert!((c.eval(0.75) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(IccProfile::new(vec![0; 64]), Err(IccError::Truncated));
+        assert_eq!(IccProfile::new(vec![0; 256]), Err(IccError::InvalidHeader));
+        let mut data = profile(b"text\0\0\0\0");
+        let icc = IccProfile::new(data.clone()).unwrap();
+        assert_eq!(icc.matrix_profile(), Err(IccError::InvalidTag(*b"rTRC")));
+        data[16..20].copy_from_slice(b"GRAY");
+        let icc = IccProfile::new(data).unwrap();
+        assert_eq!(icc.matrix_profile(), Err(IccError::NotRgb));
+    }
+
+    #[test]
+    fn transform() {
+        let mut src = Raster::<SRgb8>::with_clear(8, 8);
+        for (i, p) in src.pixels_mut().iter_mut().enumerate() {
+            let v = (i * 4) as u8;
+            *p = SRgb8::new(v, 255 - v, v / 2);
+        }
+        let dst: Raster<Rgb32> = MatrixProfile::srgb().transform(&src);
+        for (p, q) in src.pixels().iter().zip(dst.pixels()) {
+            let p: Rgb32 = p.convert();
+            assert!(p.approx_eq(*q, 1e-4), "{p:?} {q:?}");
+        }
+    }
+
+    #[test]
+    fn color_managed() {
+        // linear gamma profile
+        let curv = b"curv\0\0\0\0\0\0\0\0";
+        let mut src = Raster::with_color(2, 2, SRgb8::new(0x80, 0x80, 0x80));
+        let r = Raster::<Rgb32>::with_color_managed(&src).unwrap();
+        assert!((r.pixel(1, 1).channels()[0].to_f32() - 0.2158).abs() < 1e-3);
+        src.set_profile(Some(IccProfile::new(profile(curv)).unwrap()));
+        let r = Raster::<Rgb32>::with_color_managed(&src).unwrap();
+        assert!(r
+            .pixel(1, 1)
+            .approx_eq(Rgb32::new(0.502, 0.502, 0.502), 1e-3));
+    }
+}
@@ -0,0 +1,280 @@
+// imagebuf.rs  Conversions with the `image` crate.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::{Ch16, Ch32, Ch8, Channel, Srgb, Straight};
+use crate::el::{Pix1, Pix2, Pix3, Pix4, Pixel};
+use crate::gray::{Gray, SGray16, SGray8, SGraya16, SGraya8};
+use crate::raster::Raster;
+use crate::rgb::{Rgb, SRgb16, SRgb32, SRgb8, SRgba16, SRgba32, SRgba8};
+use crate::ColorModel;
+use image::{
+    DynamicImage, ImageBuffer, Luma, LumaA, Primitive, Rgb as IRgb,
+    Rgba as IRgba,
+};
+use std::any::TypeId;
+
+/// Copy raster channels into a vector of subpixels
+fn subpixels<P, S>(raster: &Raster<P>) -> Vec<S>
+where
+    P: Pixel,
+    S: From<P::Chan>,
+{
+    raster
+        .pixels()
+        .iter()
+        .flat_map(|p| p.channels().iter().map(|c| S::from(*c)))
+        .collect()
+}
+
+/// Make an image buffer from a raster
+fn image_buffer<P, I>(raster: &Raster<P>) -> ImageBuffer<I, Vec<I::Subpixel>>
+where
+    P: Pixel,
+    I: image::Pixel,
+    I::Subpixel: From<P::Chan>,
+{
+    ImageBuffer::from_raw(raster.width(), raster.height(), subpixels(raster))
+        .expect("invalid buffer length")
+}
+
+/// Make a raster from an image buffer
+fn raster<P, I>(img: &ImageBuffer<I, Vec<I::Subpixel>>) -> Raster<P>
+where
+    P: Pixel,
+    I: image::Pixel,
+    P::Chan: From<I::Subpixel>,
+{
+    let mut r = Raster::<P>::with_clear(img.width(), img.height());
+    let n = usize::from(I::CHANNEL_COUNT);
+    let subpixels = img.as_raw().chunks_exact(n);
+    for (p, sub) in r.pixels_mut().iter_mut().zip(subpixels) {
+        for (c, s) in p.channels_mut().iter_mut().zip(sub) {
+            *c = P::Chan::from(*s);
+        }
+    }
+    r
+}
+
+impl<C, S> From<&Raster<Pix1<C, Gray, Straight, Srgb>>>
+    for ImageBuffer<Luma<S>, Vec<S>>
+where
+    C: Channel,
+    S: Primitive + From<C>,
+    Luma<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an sRGB gray raster to a `Luma` image buffer.
+    fn from(raster: &Raster<Pix1<C, Gray, Straight, Srgb>>) -> Self {
+        image_buffer(raster)
+    }
+}
+
+impl<C, S> From<&Raster<Pix2<C, Gray, Straight, Srgb>>>
+    for ImageBuffer<LumaA<S>, Vec<S>>
+where
+    C: Channel,
+    S: Primitive + From<C>,
+    LumaA<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an sRGB gray alpha raster to a `LumaA` image buffer.
+    fn from(raster: &Raster<Pix2<C, Gray, Straight, Srgb>>) -> Self {
+        image_buffer(raster)
+    }
+}
+
+impl<C, S> From<&Raster<Pix3<C, Rgb, Straight, Srgb>>>
+    for ImageBuffer<IRgb<S>, Vec<S>>
+where
+    C: Channel,
+    S: Primitive + From<C>,
+    IRgb<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an sRGB RGB raster to an `Rgb` image buffer.
+    fn from(raster: &Raster<Pix3<C, Rgb, Straight, Srgb>>) -> Self {
+        image_buffer(raster)
+    }
+}
+
+impl<C, S> From<&Raster<Pix4<C, Rgb, Straight, Srgb>>>
+    for ImageBuffer<IRgba<S>, Vec<S>>
+where
+    C: Channel,
+    S: Primitive + From<C>,
+    IRgba<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an sRGB RGBA raster to an `Rgba` image buffer.
+    fn from(raster: &Raster<Pix4<C, Rgb, Straight, Srgb>>) -> Self {
+        image_buffer(raster)
+    }
+}
+
+impl<C, S> From<&ImageBuffer<Luma<S>, Vec<S>>>
+    for Raster<Pix1<C, Gray, Straight, Srgb>>
+where
+    C: Channel + From<S>,
+    S: Primitive,
+    Luma<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert a `Luma` image buffer to an sRGB gray raster.
+    fn from(img: &ImageBuffer<Luma<S>, Vec<S>>) -> Self {
+        raster(img)
+    }
+}
+
+impl<C, S> From<&ImageBuffer<LumaA<S>, Vec<S>>>
+    for Raster<Pix2<C, Gray, Straight, Srgb>>
+where
+    C: Channel + From<S>,
+    S: Primitive,
+    LumaA<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert a `LumaA` image buffer to an sRGB gray alpha raster.
+    fn from(img: &ImageBuffer<LumaA<S>, Vec<S>>) -> Self {
+        raster(img)
+    }
+}
+
+impl<C, S> From<&ImageBuffer<IRgb<S>, Vec<S>>>
+    for Raster<Pix3<C, Rgb, Straight, Srgb>>
+where
+    C: Channel + From<S>,
+    S: Primitive,
+    IRgb<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an `Rgb` image buffer to an sRGB RGB raster.
+    fn from(img: &ImageBuffer<IRgb<S>, Vec<S>>) -> Self {
+        raster(img)
+    }
+}
+
+impl<C, S> From<&ImageBuffer<IRgba<S>, Vec<S>>>
+    for Raster<Pix4<C, Rgb, Straight, Srgb>>
+where
+    C: Channel + From<S>,
+    S: Primitive,
+    IRgba<S>: image::Pixel<Subpixel = S>,
+{
+    /// Convert an `Rgba` image buffer to an sRGB RGBA raster.
+    fn from(img: &ImageBuffer<IRgba<S>, Vec<S>>) -> Self {
+        raster(img)
+    }
+}
+
+impl<P> From<&Raster<P>> for DynamicImage
+where
+    P: Pixel,
+    Ch8: From<P::Chan>,
+    Ch16: From<P::Chan>,
+    Ch32: From<P::Chan>,
+{
+    /// Convert a raster to a `DynamicImage`, with *sRGB* gamma.
+    ///
+    /// The closest `DynamicImage` format is chosen, based on the color
+    /// model, alpha channel and bit depth of the raster.
+    fn from(raster: &Raster<P>) -> Self {
+        use DynamicImage::*;
+        let chan = TypeId::of::<P::Chan>();
+        let gray = TypeId::of::<P::Model>() == TypeId::of::<Gray>();
+        let alpha = P::Model::ALPHA < P::default().channels().len();
+        if chan == TypeId::of::<Ch8>() {
+            match (gray, alpha) {
+                (true, false) => ImageLuma8(image_buffer(
+                    &Raster::<SGray8>::with_raster(raster),
+                )),
+                (true, true) => ImageLumaA8(image_buffer(
+                    &Raster::<SGraya8>::with_raster(raster),
+                )),
+                (false, false) => ImageRgb8(image_buffer(
+                    &Raster::<SRgb8>::with_raster(raster),
+                )),
+                (false, true) => ImageRgba8(image_buffer(
+                    &Raster::<SRgba8>::with_raster(raster),
+                )),
+            }
+        } else if chan == TypeId::of::<Ch16>() {
+            match (gray, alpha) {
+                (true, false) => ImageLuma16(image_buffer(
+                    &Raster::<SGray16>::with_raster(raster),
+                )),
+                (true, true) => ImageLumaA16(image_buffer(
+                    &Raster::<SGraya16>::with_raster(raster),
+                )),
+                (false, false) => ImageRgb16(image_buffer(
+                    &Raster::<SRgb16>::with_raster(raster),
+                )),
+                (false, true) => ImageRgba16(image_buffer(
+                    &Raster::<SRgba16>::with_raster(raster),
+                )),
+            }
+        } else if alpha {
+            ImageRgba32F(image_buffer(&Raster::<SRgba32>::with_raster(raster)))
+        } else {
+            ImageRgb32F(image_buffer(&Raster::<SRgb32>::with_raster(raster)))
+        }
+    }
+}
+
+impl<P> From<&DynamicImage> for Raster<P>
+where
+    P: Pixel,
+    P::Chan: From<Ch8> + From<Ch16> + From<Ch32>,
+{
+    /// Convert a `DynamicImage` to a raster, assuming *sRGB* gamma.
+    fn from(img: &DynamicImage) -> Self {
+        use DynamicImage::*;
+        match img {
+            ImageLuma8(b) => Raster::with_raster(&Raster::<SGray8>::from(b)),
+            ImageLumaA8(b) => Raster::with_raster(&Raster::<SGraya8>::from(b)),
+            ImageRgb8(b) => Raster::with_raster(&Raster::<SRgb8>::from(b)),
+            ImageRgba8(b) => Raster::with_raster(&Raster::<SRgba8>::from(b)),
+            ImageLuma16(b) => Raster::with_raster(&Raster::<SGray16>::from(b)),
+            ImageLumaA16(b) => {
+                Raster::with_raster(&Raster::<SGraya16>::from(b))
+            }
+            ImageRgb16(b) => Raster::with_raster(&Raster::<SRgb16>::from(b)),
+            ImageRgba16(b) => Raster::with_raster(&Raster::<SRgba16>::from(b)),
+            ImageRgb32F(b) => Raster::with_raster(&Raster::<SRgb32>::from(b)),
+            _ => {
+                let b = img.to_rgba32f();
+                Raster::with_raster(&Raster::<SRgba32>::from(&b))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gray::Gray16;
+    use crate::rgb::{Rgb8, Rgba8p};
+
+    #[test]
+    fn image_buffer() {
+        let mut r = Raster::<SRgba8>::with_clear(2, 2);
+        *r.pixel_mut(1, 0) = SRgba8::new(1, 2, 3, 4);
+        let img = image::RgbaImage::from(&r);
+        assert_eq!(img.get_pixel(1, 0), &IRgba([1, 2, 3, 4]));
+        let r2 = Raster::<SRgba8>::from(&img);
+        assert_eq!(r2.pixels(), r.pixels());
+        let img = ImageBuffer::<Luma<u16>, _>::from_pixel(1, 1, Luma([7]));
+        let r = Raster::<SGray16>::from(&img);
+        assert_eq!(r.pixel(0, 0), SGray16::new(7));
+    }
+
+    #[test]
+    fn dynamic_image() {
+        let r = Raster::with_color(2, 1, SRgb8::new(10, 20, 30));
+        let img = DynamicImage::from(&r);
+        assert_eq!(img.color(), image::ColorType::Rgb8);
+        let r2 = Raster::<SRgba8>::from(&img);
+        assert_eq!(r2.pixel(1, 0), SRgba8::new(10, 20, 30, 255));
+        let r = Raster::with_color(1, 1, Rgba8p::new(0, 0, 0, 0));
+        let img = DynamicImage::from(&r);
+        assert_eq!(img.color(), image::ColorType::Rgba8);
+        let r = Raster::with_color(1, 1, Gray16::new(0xFFFF));
+        let img = DynamicImage::from(&r);
+        assert_eq!(img.color(), image::ColorType::L16);
+        let r = Raster::<Rgb8>::from(&img);
+        assert_eq!(r.pixel(0, 0), Rgb8::new(255, 255, 255));
+    }
+}
@@ -0,0 +1,199 @@
+// key.rs       Chroma keying.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Chroma keying (green / blue screen removal).
+//!
+//! A [ChromaKey] makes pixels near a key color transparent.  Distance from
+//! the key is measured in [Oklab], so the tolerance behaves the same for any
+//! key color or pixel format.
+//!
+//! Keying can be applied directly to a `Raster` with [chroma_key], or a
+//! separate matte can be created with [chroma_matte].
+//!
+//! ### Remove a green screen
+//! ```
+//! use pix::el::Pixel;
+//! use pix::key::ChromaKey;
+//! use pix::rgb::SRgba8;
+//! use pix::Raster;
+//!
+//! let green = SRgba8::new(0x00, 0xB1, 0x40, 0xFF);
+//! let mut r = Raster::with_color(8, 8, green);
+//! *r.pixel_mut(3, 3) = SRgba8::new(0xE0, 0xA0, 0x80, 0xFF);
+//! r.chroma_key(ChromaKey::new(green, 0.1, 0.05)).unwrap();
+//! assert_eq!(r.pixel(0, 0).alpha(), 0.into());
+//! assert_eq!(r.pixel(3, 3).alpha(), 0xFF.into());
+//! ```
+//!
+//! [chroma_key]: ../struct.Raster.html#method.chroma_key
+//! [chroma_matte]: ../struct.Raster.html#method.chroma_matte
+//! [ChromaKey]: struct.ChromaKey.html
+//! [Oklab]: ../oklab/struct.Oklab.html
+use crate::chan::{Alpha, Channel, Linear, Premultiplied};
+use crate::el::{Pix1, Pixel, PixelError};
+use crate::matte::Matte;
+use crate::oklab::linear_rgb_to_oklab;
+use crate::raster::Raster;
+use crate::rgb::Rgba32;
+use crate::ColorModel;
+
+/// Chroma key operation
+///
+/// Pixels within `tolerance` of the key color are fully keyed out.  Coverage
+/// then ramps up linearly, reaching fully opaque at `tolerance + softness`.
+/// Distances are Euclidean in [Oklab], where `1.0` is the lightness range
+/// from black to white.
+///
+/// [Oklab]: ../oklab/struct.Oklab.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChromaKey {
+    /// Key color (Oklab)
+    key: [f32; 3],
+    /// Distance keyed out completely
+    tolerance: f32,
+    /// Width of soft edge
+    softness: f32,
+}
+
+/// Get Oklab components of a pixel, ignoring *alpha*
+fn oklab<P: Pixel>(p: P) -> [f32; 3] {
+    let [r, g, b, _a] = p.convert_lossy::<Rgba32>().into_array();
+    linear_rgb_to_oklab([r, g, b].map(Channel::to_f32))
+}
+
+impl ChromaKey {
+    /// Create a new chroma key.
+    ///
+    /// * `key` Key color, in any pixel format.
+    /// * `tolerance` Oklab distance from `key` to make fully transparent.
+    /// * `softness` Width of the soft edge beyond `tolerance`.
+    pub fn new<P: Pixel>(key: P, tolerance: f32, softness: f32) -> Self {
+        ChromaKey {
+            key: oklab(key),
+            tolerance: tolerance.max(0.0),
+            softness: softness.max(0.0),
+        }
+    }
+
+    /// Get the coverage of a pixel.
+    ///
+    /// Returns `0.0` for pixels matching the key, up to `1.0` for pixels
+    /// which are not keyed.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::key::ChromaKey;
+    /// use pix::rgb::SRgb8;
+    ///
+    /// let key = ChromaKey::new(SRgb8::new(0, 0, 0xFF), 0.1, 0.1);
+    /// assert_eq!(key.coverage(SRgb8::new(0, 0, 0xF0)), 0.0);
+    /// assert_eq!(key.coverage(SRgb8::new(0xFF, 0xFF, 0)), 1.0);
+    /// ```
+    pub fn coverage<P: Pixel>(&self, p: P) -> f32 {
+        let [l0, a0, b0] = self.key;
+        let [l1, a1, b1] = oklab(p);
+        let dist =
+            ((l0 - l1).powi(2) + (a0 - a1).powi(2) + (b0 - b1).powi(2)).sqrt();
+        if dist <= self.tolerance {
+            0.0
+        } else if dist >= self.tolerance + self.softness {
+            1.0
+        } else {
+            (dist - self.tolerance) / self.softness
+        }
+    }
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Key out pixels matching a chroma key.
+    ///
+    /// * `key` Chroma key operation.
+    ///
+    /// *Alpha* is multiplied by the [coverage] of each pixel.  For
+    /// *premultiplied* pixel formats, the color channels are also multiplied.
+    ///
+    /// Returns [PixelError::NoAlpha] if the pixel format does not contain an
+    /// *alpha* channel.
+    ///
+    /// [coverage]: key/struct.ChromaKey.html#method.coverage
+    /// [PixelError::NoAlpha]: el/enum.PixelError.html#variant.NoAlpha
+    pub fn chroma_key(&mut self, key: ChromaKey) -> Result<(), PixelError> {
+        if P::Model::ALPHA >= P::default().channels().len() {
+            return Err(PixelError::NoAlpha);
+        }
+        for p in self.pixels_mut() {
+            let m = P::Chan::from(key.coverage(*p));
+            let chan = p.channels_mut();
+            for c in &mut chan[P::Model::LINEAR] {
+                *c = P::Alpha::encode(*c, m);
+            }
+            let a = p.alpha_mut();
+            *a = *a * m;
+        }
+        Ok(())
+    }
+
+    /// Create a matte `Raster` from a chroma key.
+    ///
+    /// * `key` Chroma key operation.
+    ///
+    /// The matte contains the [coverage] of each pixel, and can be used with
+    /// [apply_matte] or compositing operations.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::key::ChromaKey;
+    /// use pix::matte::Matte8;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let blue = SRgb8::new(0x00, 0x40, 0xFF);
+    /// let mut r = Raster::with_color(4, 4, blue);
+    /// *r.pixel_mut(2, 2) = SRgb8::new(0xFF, 0xFF, 0xFF);
+    /// let m = r.chroma_matte(ChromaKey::new(blue, 0.1, 0.1));
+    /// assert_eq!(m.pixel(0, 0), Matte8::new(0x00));
+    /// assert_eq!(m.pixel(2, 2), Matte8::new(0xFF));
+    /// ```
+    ///
+    /// [apply_matte]: #method.apply_matte
+    /// [coverage]: key/struct.ChromaKey.html#method.coverage
+    pub fn chroma_matte(
+        &self,
+        key: ChromaKey,
+    ) -> Raster<Pix1<P::Chan, Matte, Premultiplied, Linear>> {
+        self.map(|p| Pix1::new(key.coverage(p)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::{Rgba8p, SRgb8, SRgba8};
+
+    #[test]
+    fn coverage() {
+        let key = ChromaKey::new(SRgb8::new(0x00, 0xB1, 0x40), 0.1, 0.1);
+        assert_eq!(key.coverage(SRgba8::new(0x00, 0xB1, 0x40, 0x80)), 0.0);
+        assert_eq!(key.coverage(SRgb8::new(0x10, 0xC0, 0x50)), 0.0);
+        assert_eq!(key.coverage(SRgb8::new(0xFF, 0x00, 0xFF)), 1.0);
+        let c = key.coverage(SRgb8::new(0xA0, 0xB1, 0x60));
+        assert!(c > 0.0 && c < 1.0, "{c}");
+        // hard edge
+        let key = ChromaKey::new(SRgb8::new(0x00, 0xB1, 0x40), 0.1, 0.0);
+        assert_eq!(key.coverage(SRgb8::new(0xA0, 0xB1, 0x60)), 1.0);
+    }
+
+    #[test]
+    fn key_premultiplied() {
+        let green = Rgba8p::new(0x00, 0xFF, 0x00, 0xFF);
+        let mut r = Raster::with_color(2, 2, green);
+        *r.pixel_mut(1, 0) = Rgba8p::new(0x80, 0x40, 0x20, 0xFF);
+        r.chroma_key(ChromaKey::new(green, 0.05, 0.0)).unwrap();
+        assert_eq!(r.pixel(0, 0), Rgba8p::new(0, 0, 0, 0));
+        assert_eq!(r.pixel(1, 0), Rgba8p::new(0x80, 0x40, 0x20, 0xFF));
+        let mut r = Raster::with_color(2, 2, SRgb8::new(0, 0xFF, 0));
+        let key = ChromaKey::new(green, 0.05, 0.0);
+        assert_eq!(r.chroma_key(key), Err(PixelError::NoAlpha));
+    }
+}
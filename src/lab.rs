@@ -0,0 +1,173 @@
+// lab.rs       CIE L*a*b* color differences.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::Channel;
+use crate::el::Pixel;
+use crate::rgb::Rgb32;
+use std::f32::consts::PI;
+
+/// D65 white point, matching the `Xyz` model matrix
+const WHITE: [f32; 3] = [0.9505, 1.0, 1.089];
+
+/// CIE constant epsilon (216 / 24389)
+const EPSILON: f32 = 216.0 / 24389.0;
+
+/// CIE constant kappa (24389 / 27)
+const KAPPA: f32 = 24389.0 / 27.0;
+
+/// Convert a pixel to CIE *L\*a\*b\** components
+pub(crate) fn lab<P: Pixel>(p: P) -> [f32; 3] {
+    let rgb: Rgb32 = p.convert_lossy();
+    let [r, g, b] = rgb.into_array().map(|c| c.to_f32());
+    let x = r * 0.4124 + g * 0.3576 + b * 0.1805;
+    let y = r * 0.2126 + g * 0.7152 + b * 0.0722;
+    let z = r * 0.0193 + g * 0.1192 + b * 0.9505;
+    let f = |t: f32| {
+        if t > EPSILON {
+            t.cbrt()
+        } else {
+            (KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let [fx, fy, fz] = [x / WHITE[0], y / WHITE[1], z / WHITE[2]].map(f);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Calculate CIE76 color difference (Euclidean distance)
+pub(crate) fn delta_e_76(lab0: [f32; 3], lab1: [f32; 3]) -> f32 {
+    let [l0, a0, b0] = lab0;
+    let [l1, a1, b1] = lab1;
+    ((l0 - l1).powi(2) + (a0 - a1).powi(2) + (b0 - b1).powi(2)).sqrt()
+}
+
+/// Calculate CIE94 color difference (graphic arts weights)
+///
+/// `lab0` is the reference color.
+pub(crate) fn delta_e_94(lab0: [f32; 3], lab1: [f32; 3]) -> f32 {
+    let [l0, a0, b0] = lab0;
+    let [l1, a1, b1] = lab1;
+    let c0 = a0.hypot(b0);
+    let c1 = a1.hypot(b1);
+    let dl = l0 - l1;
+    let dc = c0 - c1;
+    let dh2 = ((a0 - a1).powi(2) + (b0 - b1).powi(2) - dc * dc).max(0.0);
+    let sc = 1.0 + 0.045 * c0;
+    let sh = 1.0 + 0.015 * c0;
+    (dl * dl + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+/// Calculate CIEDE2000 color difference
+pub(crate) fn delta_e_2000(lab0: [f32; 3], lab1: [f32; 3]) -> f32 {
+    let [l0, a0, b0] = lab0;
+    let [l1, a1, b1] = lab1;
+    let c_mean = (a0.hypot(b0) + a1.hypot(b1)) / 2.0;
+    let c7 = c_mean.powi(7);
+    let g = 0.5 * (1.0 - (c7 / (c7 + 25.0_f32.powi(7))).sqrt());
+    let a0 = a0 * (1.0 + g);
+    let a1 = a1 * (1.0 + g);
+    let c0 = a0.hypot(b0);
+    let c1 = a1.hypot(b1);
+    let h0 = hue_degrees(a0, b0);
+    let h1 = hue_degrees(a1, b1);
+    let dl = l1 - l0;
+    let dc = c1 - c0;
+    let dh = if c0 * c1 == 0.0 {
+        0.0
+    } else if (h1 - h0).abs() <= 180.0 {
+        h1 - h0
+    } else if h1 <= h0 {
+        h1 - h0 + 360.0
+    } else {
+        h1 - h0 - 360.0
+    };
+    let dh = 2.0 * (c0 * c1).sqrt() * (dh.to_radians() / 2.0).sin();
+    let l_mean = (l0 + l1) / 2.0;
+    let c_mean = (c0 + c1) / 2.0;
+    let h_mean = if c0 * c1 == 0.0 {
+        h0 + h1
+    } else if (h0 - h1).abs() <= 180.0 {
+        (h0 + h1) / 2.0
+    } else if h0 + h1 < 360.0 {
+        (h0 + h1 + 360.0) / 2.0
+    } else {
+        (h0 + h1 - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let d_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let c7 = c_mean.powi(7);
+    let rc = 2.0 * (c7 / (c7 + 25.0_f32.powi(7))).sqrt();
+    let l50 = (l_mean - 50.0).powi(2);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+    let (dl, dc, dh) = (dl / sl, dc / sc, dh / sh);
+    (dl * dl + dc * dc + dh * dh + rt * dc * dh).sqrt()
+}
+
+/// Get hue angle in degrees, from 0 to 360
+fn hue_degrees(a: f32, b: f32) -> f32 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        let h = b.atan2(a) * 180.0 / PI;
+        if h < 0.0 {
+            h + 360.0
+        } else {
+            h
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::SRgb8;
+
+    #[test]
+    fn to_lab() {
+        let [l, a, b] = lab(SRgb8::new(255, 255, 255));
+        assert!((l - 100.0).abs() < 1e-3 && a.abs() < 1e-3 && b.abs() < 1e-3);
+        assert_eq!(lab(SRgb8::new(0, 0, 0)), [0.0, 0.0, 0.0]);
+        let [l, a, b] = lab(SRgb8::new(255, 0, 0));
+        assert!((l - 53.233).abs() < 1e-2, "{l}");
+        assert!((a - 80.105).abs() < 1e-2, "{a}");
+        assert!((b - 67.223).abs() < 1e-2, "{b}");
+    }
+
+    #[test]
+    fn cie94() {
+        let lab0 = [50.0, 2.6772, -79.7751];
+        let lab1 = [50.0, 0.0, -82.7485];
+        assert!((delta_e_94(lab0, lab1) - 1.3950).abs() < 1e-4);
+        assert_eq!(delta_e_94(lab0, lab0), 0.0);
+    }
+
+    #[test]
+    fn ciede2000() {
+        // test data from Sharma, Wu and Dalal (2005)
+        let data = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+        ];
+        for (lab0, lab1, de) in data {
+            let d = delta_e_2000(lab0, lab1);
+            assert!((d - de).abs() < 1e-3, "{lab0:?} {lab1:?} {d}");
+            let d = delta_e_2000(lab1, lab0);
+            assert!((d - de).abs() < 1e-3, "{lab0:?} {lab1:?} {d}");
+        }
+    }
+}
@@ -69,26 +69,90 @@
 //!
 #![warn(missing_docs)]
 
+pub mod ansi;
+#[cfg(feature = "bc")]
+mod bc;
 pub mod bgr;
 pub mod chan;
 pub mod cmy;
+#[cfg(any(
+    feature = "bc",
+    feature = "farbfeld",
+    feature = "pnm",
+    feature = "qoi"
+))]
+mod codec;
+pub mod compare;
+pub mod contrast;
+pub mod dither;
+mod draw;
+mod dynraster;
 pub mod el;
+#[cfg(feature = "embedded-graphics")]
+pub mod embedded;
+#[cfg(feature = "farbfeld")]
+mod farbfeld;
+#[cfg(feature = "fast-gamma")]
+pub mod fast_gamma;
+pub mod filter;
+mod format;
+mod geometry;
+pub mod gl;
+pub mod gradient;
 pub mod gray;
 pub mod hsl;
 pub mod hsv;
 mod hue;
 pub mod hwb;
+pub mod icc;
+#[cfg(feature = "image")]
+mod imagebuf;
+pub mod key;
+mod lab;
+pub mod lut;
 pub mod matte;
 mod model;
+#[cfg(feature = "ndarray")]
+mod ndarr;
 pub mod oklab;
 pub mod ops;
+pub mod packed;
 mod palette;
+mod parse;
+mod planar;
+#[cfg(feature = "pnm")]
+mod pnm;
 mod private;
+#[cfg(feature = "qoi")]
+mod qoi;
+#[cfg(feature = "rand")]
+pub mod random;
 mod raster;
 pub mod rgb;
+mod tiled;
+pub mod tonemap;
+pub mod vk;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
 pub mod xyz;
 pub mod ycc;
 
-pub use crate::model::ColorModel;
+#[cfg(any(
+    feature = "bc",
+    feature = "farbfeld",
+    feature = "pnm",
+    feature = "qoi"
+))]
+pub use crate::codec::CodecError;
+pub use crate::dynraster::{DynPixel, DynRaster, PixelFormat};
+pub use crate::model::{ChannelLabel, ColorModel};
 pub use crate::palette::Palette;
-pub use crate::raster::{Raster, Region, Rows, RowsMut};
+#[cfg(feature = "palette-formats")]
+pub use crate::palette::PaletteError;
+pub use crate::parse::ParseColorError;
+pub use crate::planar::PlanarRaster;
+pub use crate::raster::{
+    BufferError, Raster, RasterView, RasterViewMut, Region, Rows, RowsMut,
+    RowsMutChunks,
+};
+pub use crate::tiled::TiledRaster;
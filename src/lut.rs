@@ -0,0 +1,463 @@
+// lut.rs       3D color look-up tables.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! 3D color look-up tables, for color grading.
+//!
+//! A [Lut3d] can be built programmatically, or parsed from an Adobe / Resolve
+//! `.cube` file (feature `cube`).  It implements [Adjust], so it can be
+//! applied to pixel slices with [adjust_slice] or to rasters with [adjust].
+//!
+//! [adjust]: ../struct.Raster.html#method.adjust
+//! [Adjust]: ../ops/trait.Adjust.html
+//! [adjust_slice]: ../el/trait.Pixel.html#method.adjust_slice
+//! [Lut3d]: struct.Lut3d.html
+use crate::chan::{Ch32, Channel};
+use crate::ops::Adjust;
+#[cfg(feature = "cube")]
+use std::fmt;
+
+/// Interpolation between [Lut3d] grid points.
+///
+/// [Lut3d]: struct.Lut3d.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Trilinear interpolation between 8 surrounding points
+    #[default]
+    Trilinear,
+    /// Tetrahedral interpolation between 4 surrounding points
+    Tetrahedral,
+}
+
+/// 3D color look-up table.
+///
+/// The table is a cube of *red*, *green* and *blue* output values, with
+/// `size` grid points along each axis.  Input values between grid points are
+/// interpolated; *alpha* is not changed.
+///
+/// By default, lookups use *sRGB*-encoded values, since most grading LUTs
+/// are built for display-referred input.  Use [set_linear] for LUTs built for
+/// *linear* values.
+///
+/// ### Invert colors
+/// ```
+/// use pix::lut::Lut3d;
+/// use pix::rgb::SRgb8;
+/// use pix::Raster;
+///
+/// let lut = Lut3d::new(2, |[r, g, b]| [1.0 - r, 1.0 - g, 1.0 - b]);
+/// let mut r = Raster::with_color(4, 4, SRgb8::new(0x20, 0x80, 0xFF));
+/// r.adjust((), &lut);
+/// assert_eq!(r.pixel(0, 0), SRgb8::new(0xDF, 0x7F, 0x00));
+/// ```
+///
+/// [set_linear]: #method.set_linear
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut3d {
+    /// Number of grid points along each axis
+    size: usize,
+    /// Output values, with *red* changing fastest, then *green*, *blue*
+    table: Box<[[f32; 3]]>,
+    /// Minimum input values
+    domain_min: [f32; 3],
+    /// Maximum input values
+    domain_max: [f32; 3],
+    /// Interpolation mode
+    interpolation: Interpolation,
+    /// Lookup using linear (instead of sRGB-encoded) values
+    linear: bool,
+}
+
+/// Error parsing a `.cube` file
+#[cfg(feature = "cube")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CubeError {
+    /// Missing `LUT_3D_SIZE` keyword
+    MissingSize,
+    /// Invalid `LUT_3D_SIZE` value
+    InvalidSize,
+    /// Unsupported keyword (such as `LUT_1D_SIZE`)
+    Unsupported(String),
+    /// Invalid line, with line number
+    InvalidLine(usize),
+    /// Number of table entries does not match size
+    WrongCount(usize),
+}
+
+#[cfg(feature = "cube")]
+impl fmt::Display for CubeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CubeError::MissingSize => write!(f, "missing LUT_3D_SIZE"),
+            CubeError::InvalidSize => write!(f, "invalid LUT_3D_SIZE"),
+            CubeError::Unsupported(k) => write!(f, "unsupported keyword: {k}"),
+            CubeError::InvalidLine(n) => write!(f, "invalid line: {n}"),
+            CubeError::WrongCount(c) => write!(f, "wrong entry count: {c}"),
+        }
+    }
+}
+
+#[cfg(feature = "cube")]
+impl std::error::Error for CubeError {}
+
+impl Lut3d {
+    /// Build a `Lut3d` by sampling a function at each grid point.
+    ///
+    /// * `size` Number of grid points along each axis.
+    /// * `f` Function mapping input *red*, *green* and *blue* (`0.0` to
+    ///   `1.0`) to output values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2.
+    pub fn new<F>(size: usize, f: F) -> Self
+    where
+        F: Fn([f32; 3]) -> [f32; 3],
+    {
+        assert!(size >= 2);
+        let scale = ((size - 1) as f32).recip();
+        let mut table = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let rgb = [r, g, b].map(|v| v as f32 * scale);
+                    table.push(f(rgb));
+                }
+            }
+        }
+        Self::with_table(size, table)
+    }
+
+    /// Build an identity `Lut3d`, which does not change colors.
+    ///
+    /// * `size` Number of grid points along each axis.
+    pub fn identity(size: usize) -> Self {
+        Self::new(size, |rgb| rgb)
+    }
+
+    /// Build a `Lut3d` from a table of output values.
+    ///
+    /// * `B` Owned table type (`Vec` or boxed slice).
+    /// * `size` Number of grid points along each axis.
+    /// * `table` Output values, with *red* changing fastest, then *green*,
+    ///   then *blue*.
+    ///
+    /// # Panics
+    ///
+    /// * If `size` is less than 2
+    /// * If `table` length is not equal to `size` cubed
+    pub fn with_table<B>(size: usize, table: B) -> Self
+    where
+        B: Into<Box<[[f32; 3]]>>,
+    {
+        assert!(size >= 2);
+        let table = table.into();
+        assert_eq!(table.len(), size * size * size);
+        Lut3d {
+            size,
+            table,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            interpolation: Interpolation::default(),
+            linear: false,
+        }
+    }
+
+    /// Parse a `Lut3d` from the contents of a `.cube` file.
+    ///
+    /// * `cube` Text of `.cube` file.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::lut::Lut3d;
+    ///
+    /// let cube = "TITLE \"Swap\"\n\
+    ///     LUT_3D_SIZE 2\n\
+    ///     0 0 0\n0 0 1\n0 1 0\n0 1 1\n1 0 0\n1 0 1\n1 1 0\n1 1 1\n";
+    /// let lut = Lut3d::from_cube(cube).unwrap();
+    /// assert_eq!(lut.lookup([1.0, 0.5, 0.0]), [0.0, 0.5, 1.0]);
+    /// ```
+    #[cfg(feature = "cube")]
+    pub fn from_cube(cube: &str) -> Result<Self, CubeError> {
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+        for (i, line) in cube.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let Some(key) = words.next() else { continue };
+            match key {
+                "TITLE" => (),
+                "LUT_3D_SIZE" => {
+                    let s = words.next().and_then(|w| w.parse().ok());
+                    match s {
+                        Some(s) if (2..=256).contains(&s) => size = Some(s),
+                        _ => return Err(CubeError::InvalidSize),
+                    }
+                }
+                "DOMAIN_MIN" => {
+                    domain_min = parse_triple(words)
+                        .ok_or(CubeError::InvalidLine(i + 1))?
+                }
+                "DOMAIN_MAX" => {
+                    domain_max = parse_triple(words)
+                        .ok_or(CubeError::InvalidLine(i + 1))?
+                }
+                "LUT_3D_INPUT_RANGE" => {
+                    let v = parse_pair(words)
+                        .ok_or(CubeError::InvalidLine(i + 1))?;
+                    domain_min = [v[0]; 3];
+                    domain_max = [v[1]; 3];
+                }
+                _ if key.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    return Err(CubeError::Unsupported(key.to_string()));
+                }
+                _ => {
+                    let rgb = parse_triple(line.split_whitespace())
+                        .ok_or(CubeError::InvalidLine(i + 1))?;
+                    table.push(rgb);
+                }
+            }
+        }
+        let size = size.ok_or(CubeError::MissingSize)?;
+        if table.len() != size * size * size {
+            return Err(CubeError::WrongCount(table.len()));
+        }
+        let mut lut = Self::with_table(size, table);
+        lut.domain_min = domain_min;
+        lut.domain_max = domain_max;
+        Ok(lut)
+    }
+
+    /// Get the number of grid points along each axis.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the table of output values.
+    pub fn table(&self) -> &[[f32; 3]] {
+        &self.table
+    }
+
+    /// Get the table of output values mutably.
+    pub fn table_mut(&mut self) -> &mut [[f32; 3]] {
+        &mut self.table
+    }
+
+    /// Get the input domain (minimum and maximum values).
+    pub fn domain(&self) -> ([f32; 3], [f32; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+
+    /// Set the input domain.
+    ///
+    /// * `min` Minimum input values, mapped to the first grid point.
+    /// * `max` Maximum input values, mapped to the last grid point.
+    pub fn set_domain(&mut self, min: [f32; 3], max: [f32; 3]) {
+        self.domain_min = min;
+        self.domain_max = max;
+    }
+
+    /// Get the interpolation mode.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Set the interpolation mode.
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Check whether lookups use *linear* values.
+    pub fn is_linear(&self) -> bool {
+        self.linear
+    }
+
+    /// Set whether lookups use *linear* (instead of *sRGB*-encoded) values.
+    pub fn set_linear(&mut self, linear: bool) {
+        self.linear = linear;
+    }
+
+    /// Get table entry at grid point
+    fn entry(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+        self.table[(b * self.size + g) * self.size + r]
+    }
+
+    /// Look up output values.
+    ///
+    /// * `rgb` Input *red*, *green* and *blue* values.
+    ///
+    /// Values outside the domain are clamped to the nearest grid point.
+    pub fn lookup(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let mut i = [0; 3];
+        let mut f = [0.0; 3];
+        for c in 0..3 {
+            let range = self.domain_max[c] - self.domain_min[c];
+            let v = ((rgb[c] - self.domain_min[c]) / range).clamp(0.0, 1.0);
+            let v = v * last;
+            // clamp so that the upper grid point is in bounds
+            let v0 = v.floor().min(last - 1.0);
+            i[c] = v0 as usize;
+            f[c] = v - v0;
+        }
+        let [r, g, b] = i;
+        let [fr, fg, fb] = f;
+        let c000 = self.entry(r, g, b);
+        let c111 = self.entry(r + 1, g + 1, b + 1);
+        match self.interpolation {
+            Interpolation::Trilinear => {
+                let c100 = self.entry(r + 1, g, b);
+                let c010 = self.entry(r, g + 1, b);
+                let c110 = self.entry(r + 1, g + 1, b);
+                let c001 = self.entry(r, g, b + 1);
+                let c101 = self.entry(r + 1, g, b + 1);
+                let c011 = self.entry(r, g + 1, b + 1);
+                let c00 = lerp(c000, c100, fr);
+                let c10 = lerp(c010, c110, fr);
+                let c01 = lerp(c001, c101, fr);
+                let c11 = lerp(c011, c111, fr);
+                lerp(lerp(c00, c10, fg), lerp(c01, c11, fg), fb)
+            }
+            Interpolation::Tetrahedral => {
+                // pick the tetrahedron containing the point, then
+                // walk its edges from c000 to c111
+                let (c1, c2, f1, f2, f3) = if fr > fg {
+                    if fg > fb {
+                        let c1 = self.entry(r + 1, g, b);
+                        let c2 = self.entry(r + 1, g + 1, b);
+                        (c1, c2, fr, fg, fb)
+                    } else if fr > fb {
+                        let c1 = self.entry(r + 1, g, b);
+                        let c2 = self.entry(r + 1, g, b + 1);
+                        (c1, c2, fr, fb, fg)
+                    } else {
+                        let c1 = self.entry(r, g, b + 1);
+                        let c2 = self.entry(r + 1, g, b + 1);
+                        (c1, c2, fb, fr, fg)
+                    }
+                } else if fb > fg {
+                    let c1 = self.entry(r, g, b + 1);
+                    let c2 = self.entry(r, g + 1, b + 1);
+                    (c1, c2, fb, fg, fr)
+                } else if fb > fr {
+                    let c1 = self.entry(r, g + 1, b);
+                    let c2 = self.entry(r, g + 1, b + 1);
+                    (c1, c2, fg, fb, fr)
+                } else {
+                    let c1 = self.entry(r, g + 1, b);
+                    let c2 = self.entry(r + 1, g + 1, b);
+                    (c1, c2, fg, fr, fb)
+                };
+                let mut out = [0.0; 3];
+                for (c, o) in out.iter_mut().enumerate() {
+                    *o = c000[c]
+                        + f1 * (c1[c] - c000[c])
+                        + f2 * (c2[c] - c1[c])
+                        + f3 * (c111[c] - c2[c]);
+                }
+                out
+            }
+        }
+    }
+}
+
+impl Adjust for &Lut3d {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        let mut rgb = [rgba[0], rgba[1], rgba[2]];
+        if !self.linear {
+            rgb = rgb.map(|v| Ch32::new(v).encode_srgb().to_f32());
+        }
+        let mut rgb = self.lookup(rgb);
+        if !self.linear {
+            rgb = rgb.map(|v| Ch32::new(v).decode_srgb().to_f32());
+        }
+        rgba[..3].copy_from_slice(&rgb);
+    }
+}
+
+/// Linear interpolation of three values
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|c| a[c] + (b[c] - a[c]) * t)
+}
+
+/// Parse three `f32` values
+#[cfg(feature = "cube")]
+fn parse_triple<'a, I>(mut words: I) -> Option<[f32; 3]>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let v = [words.next()?, words.next()?, words.next()?];
+    let v = [v[0].parse().ok()?, v[1].parse().ok()?, v[2].parse().ok()?];
+    words.next().is_none().then_some(v)
+}
+
+/// Parse two `f32` values
+#[cfg(feature = "cube")]
+fn parse_pair<'a, I>(mut words: I) -> Option<[f32; 2]>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let v = [words.next()?.parse().ok()?, words.next()?.parse().ok()?];
+    words.next().is_none().then_some(v)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn identity() {
+        let mut lut = Lut3d::identity(5);
+        for interp in [Interpolation::Trilinear, Interpolation::Tetrahedral] {
+            lut.set_interpolation(interp);
+            for rgb in [[0.1, 0.7, 0.3], [0.9, 0.2, 0.6], [0.5, 0.5, 0.5]] {
+                let out = lut.lookup(rgb);
+                for c in 0..3 {
+                    assert!((out[c] - rgb[c]).abs() < 1e-6, "{out:?}");
+                }
+            }
+        }
+        assert_eq!(lut.lookup([-1.0, 2.0, 1.0]), [0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn tetrahedral() {
+        // non-linear table, where trilinear and tetrahedral differ
+        let mut lut = Lut3d::new(2, |[r, g, b]| [r * g, g * b, b * r]);
+        let rgb = [0.5, 0.25, 0.75];
+        assert_eq!(lut.lookup(rgb), [0.125, 0.1875, 0.375]);
+        lut.set_interpolation(Interpolation::Tetrahedral);
+        assert_eq!(lut.lookup(rgb), [0.25, 0.25, 0.5]);
+    }
+
+    #[cfg(feature = "cube")]
+    #[test]
+    fn cube() {
+        let cube = "# comment\n\
+            LUT_3D_SIZE 2\n\
+            DOMAIN_MIN 0 0 0\n\
+            DOMAIN_MAX 2 2 2\n\
+            0 0 0\n1 0 0\n0 1 0\n1 1 0\n\
+            0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+        let lut = Lut3d::from_cube(cube).unwrap();
+        assert_eq!(lut.domain(), ([0.0; 3], [2.0; 3]));
+        assert_eq!(lut.lookup([1.0, 2.0, 0.0]), [0.5, 1.0, 0.0]);
+        assert_eq!(
+            Lut3d::from_cube("LUT_1D_SIZE 2\n"),
+            Err(CubeError::Unsupported("LUT_1D_SIZE".to_string()))
+        );
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(CubeError::WrongCount(1))
+        );
+        assert_eq!(
+            Lut3d::from_cube("LUT_3D_SIZE 2\n0 0\n"),
+            Err(CubeError::InvalidLine(2))
+        );
+    }
+}
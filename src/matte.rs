@@ -5,7 +5,7 @@
 //
 //! Matte color model and types.
 use crate::chan::{Ch16, Ch32, Ch8, Channel, Linear, Premultiplied};
-use crate::el::{Pix1, PixRgba, Pixel};
+use crate::el::{from_model_channels, Pix1, PixRgba, Pixel};
 use crate::ColorModel;
 use std::ops::Range;
 
@@ -18,6 +18,85 @@ use std::ops::Range;
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Matte {}
 
+/// Boolean operation for combining two [Matte]s.
+///
+/// Partial coverage is treated as fuzzy logic, so binary mattes produce exact
+/// boolean results.
+///
+/// [Matte]: struct.Matte.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatteOp {
+    /// Intersection (minimum of both)
+    And,
+    /// Union (maximum of both)
+    Or,
+    /// Exclusive or (difference between both)
+    Xor,
+    /// Destination with source removed
+    AndNot,
+}
+
+impl Matte {
+    /// Threshold a slice of mattes to binary values.
+    ///
+    /// * `dst` Slice of matte pixels.
+    /// * `value` Minimum alpha to be set to `MAX`; lower values are set to
+    ///   `MIN`.
+    ///
+    /// # Example: Threshold
+    /// ```
+    /// use pix::matte::{Matte, Matte8};
+    ///
+    /// let mut m = [Matte8::new(0x20), Matte8::new(0x80), Matte8::new(0xC0)];
+    /// Matte::threshold(&mut m, 0x80.into());
+    /// assert_eq!(m, [Matte8::new(0), Matte8::new(0xFF), Matte8::new(0xFF)]);
+    /// ```
+    pub fn threshold<P>(dst: &mut [P], value: P::Chan)
+    where
+        P: Pixel<Model = Self>,
+    {
+        for d in dst.iter_mut() {
+            let a = d.alpha_mut();
+            *a = if *a >= value {
+                P::Chan::MAX
+            } else {
+                P::Chan::MIN
+            };
+        }
+    }
+
+    /// Combine a slice of mattes with another using a boolean operation.
+    ///
+    /// * `dst` Destination slice of matte pixels.
+    /// * `src` Source slice of matte pixels.
+    /// * `op` Boolean operation.
+    ///
+    /// # Example: Combine
+    /// ```
+    /// use pix::matte::{Matte, Matte8, MatteOp};
+    ///
+    /// let mut m = [Matte8::new(0xFF), Matte8::new(0xFF), Matte8::new(0)];
+    /// let s = [Matte8::new(0xFF), Matte8::new(0), Matte8::new(0)];
+    /// Matte::combine(&mut m, &s, MatteOp::AndNot);
+    /// assert_eq!(m, [Matte8::new(0), Matte8::new(0xFF), Matte8::new(0)]);
+    /// ```
+    pub fn combine<P>(dst: &mut [P], src: &[P], op: MatteOp)
+    where
+        P: Pixel<Model = Self>,
+    {
+        for (d, s) in dst.iter_mut().zip(src) {
+            let sa = s.alpha();
+            let da = d.alpha_mut();
+            *da = match op {
+                MatteOp::And => (*da).min(sa),
+                MatteOp::Or => (*da).max(sa),
+                MatteOp::Xor => (*da).max(sa) - (*da).min(sa),
+                MatteOp::AndNot => (*da).min(P::Chan::MAX - sa),
+            };
+        }
+    }
+}
+
 impl ColorModel for Matte {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..0;
@@ -38,7 +117,7 @@ impl ColorModel for Matte {
         P: Pixel<Model = Self>,
     {
         let chan = rgba.channels();
-        P::from_channels(&[chan[3]])
+        from_model_channels(&[chan[3]])
     }
 }
 
@@ -53,3 +132,36 @@ pub type Matte16 = Pix1<Ch16, Matte, Premultiplied, Linear>;
 /// [Matte](struct.Matte.html) 32-bit alpha [linear](../chan/struct.Linear.html)
 /// gamma [pixel](../el/trait.Pixel.html) format.
 pub type Matte32 = Pix1<Ch32, Matte, Premultiplied, Linear>;
+
+#[cfg(test)]
+mod test {
+    use crate::matte::*;
+
+    #[test]
+    fn threshold() {
+        let mut m = [Matte16::new(0x7FFF), Matte16::new(0x8000)];
+        Matte::threshold(&mut m, 0x8000.into());
+        assert_eq!(m, [Matte16::new(0), Matte16::new(0xFFFF)]);
+        let mut m = [Matte32::new(0.25), Matte32::new(0.75)];
+        Matte::threshold(&mut m, 0.5.into());
+        assert_eq!(m, [Matte32::new(0.0), Matte32::new(1.0)]);
+    }
+
+    #[test]
+    fn combine() {
+        let a = [0x00, 0x00, 0xFF, 0xFF, 0x40].map(Matte8::new);
+        let b = [0x00, 0xFF, 0x00, 0xFF, 0xC0].map(Matte8::new);
+        let mut m = a;
+        Matte::combine(&mut m, &b, MatteOp::And);
+        assert_eq!(m, [0x00, 0x00, 0x00, 0xFF, 0x40].map(Matte8::new));
+        let mut m = a;
+        Matte::combine(&mut m, &b, MatteOp::Or);
+        assert_eq!(m, [0x00, 0xFF, 0xFF, 0xFF, 0xC0].map(Matte8::new));
+        let mut m = a;
+        Matte::combine(&mut m, &b, MatteOp::Xor);
+        assert_eq!(m, [0x00, 0xFF, 0xFF, 0x00, 0x80].map(Matte8::new));
+        let mut m = a;
+        Matte::combine(&mut m, &b, MatteOp::AndNot);
+        assert_eq!(m, [0x00, 0x00, 0xFF, 0x00, 0x3F].map(Matte8::new));
+    }
+}
@@ -3,15 +3,159 @@
 // Copyright (c) 2020  Douglas P Lau
 //
 //! Color models
+use crate::chan::Channel;
 use crate::el::{PixRgba, Pixel};
+use crate::private::{MaybeSend, MaybeSync};
+use crate::rgb::Rgba32;
 use std::any::Any;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::ops::Range;
 
+/// Semantic label of a pixel channel
+///
+/// Labels for color channels come from the [ColorModel], using its
+/// `LABELS` constant.
+///
+/// [ColorModel]: trait.ColorModel.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChannelLabel {
+    /// *Red* ([Rgb](crate::rgb::Rgb), [Bgr](crate::bgr::Bgr))
+    Red,
+    /// *Green* ([Rgb](crate::rgb::Rgb), [Bgr](crate::bgr::Bgr))
+    Green,
+    /// *Blue* ([Rgb](crate::rgb::Rgb), [Bgr](crate::bgr::Bgr))
+    Blue,
+    /// *Cyan* ([Cmy](crate::cmy::Cmy))
+    Cyan,
+    /// *Magenta* ([Cmy](crate::cmy::Cmy))
+    Magenta,
+    /// *Yellow* ([Cmy](crate::cmy::Cmy))
+    Yellow,
+    /// *Luma* ([Gray](crate::gray::Gray), [YCbCr](crate::ycc::YCbCr))
+    Luma,
+    /// *Blue-difference chroma* ([YCbCr](crate::ycc::YCbCr))
+    Cb,
+    /// *Red-difference chroma* ([YCbCr](crate::ycc::YCbCr))
+    Cr,
+    /// *Hue* ([Hsv](crate::hsv::Hsv), [Hsl](crate::hsl::Hsl),
+    /// [Hwb](crate::hwb::Hwb))
+    Hue,
+    /// *Saturation* ([Hsv](crate::hsv::Hsv), [Hsl](crate::hsl::Hsl))
+    Saturation,
+    /// *Value* ([Hsv](crate::hsv::Hsv))
+    Value,
+    /// *Lightness* ([Hsl](crate::hsl::Hsl), [Oklab](crate::oklab::Oklab))
+    Lightness,
+    /// *Whiteness* ([Hwb](crate::hwb::Hwb))
+    Whiteness,
+    /// *Blackness* ([Hwb](crate::hwb::Hwb))
+    Blackness,
+    /// *Green/red* axis ([Oklab](crate::oklab::Oklab))
+    GreenRed,
+    /// *Blue/yellow* axis ([Oklab](crate::oklab::Oklab))
+    BlueYellow,
+    /// CIE *X* ([Xyz](crate::xyz::Xyz))
+    X,
+    /// CIE *Y* ([Xyz](crate::xyz::Xyz))
+    Y,
+    /// CIE *Z* ([Xyz](crate::xyz::Xyz))
+    Z,
+    /// *Alpha*
+    Alpha,
+    /// Channel of a color model without labels
+    Unknown,
+}
+
+impl ChannelLabel {
+    /// Get the name of the channel
+    pub const fn name(self) -> &'static str {
+        use ChannelLabel::*;
+        match self {
+            Red => "red",
+            Green => "green",
+            Blue => "blue",
+            Cyan => "cyan",
+            Magenta => "magenta",
+            Yellow => "yellow",
+            Luma => "luma",
+            Cb => "Cb",
+            Cr => "Cr",
+            Hue => "hue",
+            Saturation => "saturation",
+            Value => "value",
+            Lightness => "lightness",
+            Whiteness => "whiteness",
+            Blackness => "blackness",
+            GreenRed => "a",
+            BlueYellow => "b",
+            X => "X",
+            Y => "Y",
+            Z => "Z",
+            Alpha => "alpha",
+            Unknown => "?",
+        }
+    }
+}
+
+impl fmt::Display for ChannelLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 /// Model for pixel colors.
 ///
 /// Existing color models are [Rgb], [Bgr], [Cmy], [Gray], [Hsv], [Hsl], [Hwb],
-/// [YCbCr] and [Matte].
+/// [YCbCr], [Matte], [Oklab] and [Xyz].
+///
+/// ### Custom Color Models
+///
+/// Unlike [Pixel], this trait is not sealed.  Other crates can implement it
+/// for niche color models, and use them with the generic [Pix] pixel type.
+/// An implementation must uphold this contract:
+///
+/// * `CIRCULAR` and `LINEAR` are disjoint ranges, covering all color
+///   channels.  Circular channels (*hue*) wrap around from `MAX` to `MIN`.
+/// * `ALPHA` is the channel after the color channels.  Pixel types without
+///   *alpha* have no channel at that index.
+/// * `into_rgba` / `from_rgba` must not change the *alpha* or *gamma* mode;
+///   channels are in the modes of the pixel type `P`.
+/// * With the `rayon` feature, the model type must be `Send + Sync`.
+///
+/// ### Example: Color infrared model
+/// ```
+/// use pix::chan::{Ch8, Linear, Straight};
+/// use pix::el::{Pix3, PixRgba, Pixel};
+/// use pix::rgb::Rgb8;
+/// use pix::ColorModel;
+/// use std::ops::Range;
+///
+/// /// Near-infrared, red and green
+/// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// struct Cir;
+///
+/// impl ColorModel for Cir {
+///     const CIRCULAR: Range<usize> = 0..0;
+///     const LINEAR: Range<usize> = 0..3;
+///     const ALPHA: usize = 3;
+///
+///     fn into_rgba<P: Pixel<Model = Self>>(p: P) -> PixRgba<P> {
+///         // false color: infrared as red, red as green, green as blue
+///         let (ir, red, green) = (p.one(), p.two(), p.three());
+///         PixRgba::<P>::new::<P::Chan>(ir, red, green, p.alpha())
+///     }
+///
+///     fn from_rgba<P: Pixel<Model = Self>>(rgba: PixRgba<P>) -> P {
+///         P::from_channels(rgba.channels())
+///     }
+/// }
+///
+/// type Cir8 = Pix3<Ch8, Cir, Straight, Linear>;
+///
+/// let p = Cir8::new(0xFF, 0x80, 0x40);
+/// assert_eq!(p.convert::<Rgb8>(), Rgb8::new(0xFF, 0x80, 0x40));
+/// ```
 ///
 /// [bgr]: bgr/struct.Bgr.html
 /// [cmy]: cmy/struct.Cmy.html
@@ -21,9 +165,15 @@ use std::ops::Range;
 /// [hsv]: hsv/struct.Hsv.html
 /// [hwb]: hwb/struct.Hwb.html
 /// [matte]: matte/struct.Matte.html
+/// [oklab]: oklab/struct.Oklab.html
+/// [pix]: el/struct.Pix.html
+/// [pixel]: el/trait.Pixel.html
 /// [rgb]: rgb/struct.Rgb.html
+/// [xyz]: xyz/struct.Xyz.html
 /// [ycbcr]: ycc/struct.YCbCr.html
-pub trait ColorModel: Clone + Copy + Debug + Default + PartialEq + Any {
+pub trait ColorModel:
+    Clone + Copy + Debug + Default + PartialEq + MaybeSend + MaybeSync + Any
+{
     /// Range of circular channel numbers
     const CIRCULAR: Range<usize>;
 
@@ -33,6 +183,11 @@ pub trait ColorModel: Clone + Copy + Debug + Default + PartialEq + Any {
     /// Alpha channel number
     const ALPHA: usize;
 
+    /// Labels of color channels, in order
+    ///
+    /// Channels without a label are [Unknown](enum.ChannelLabel.html).
+    const LABELS: &'static [ChannelLabel] = &[];
+
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
     where
@@ -42,4 +197,65 @@ pub trait ColorModel: Clone + Copy + Debug + Default + PartialEq + Any {
     fn from_rgba<P>(rgba: PixRgba<P>) -> P
     where
         P: Pixel<Model = Self>;
+
+    /// Convert into *linear* *red*, *green* and *blue* values, without
+    /// clamping or *alpha*
+    ///
+    /// Models which can represent colors outside the sRGB gamut should
+    /// override this, so those colors can be [gamut mapped].  The default
+    /// implementation uses [into_rgba].
+    ///
+    /// [gamut mapped]: el/trait.Pixel.html#method.convert_gamut
+    /// [into_rgba]: #tymethod.into_rgba
+    fn into_linear_rgb<P>(p: P) -> [f32; 3]
+    where
+        P: Pixel<Model = Self>,
+    {
+        let [r, g, b, _a] = p.convert_lossy::<Rgba32>().into_array();
+        [r, g, b].map(Channel::to_f32)
+    }
 }
+
+/// Define a model accessor trait, implemented for all pixels of that model
+macro_rules! model_pixel {
+    (
+        $(#[$meta:meta])*
+        $trait:ident: $model:ident { $($get:ident, $get_mut:ident, $with:ident;)+ }
+    ) => {
+        $(#[$meta])*
+        pub trait $trait: Pixel<Model = $model> {
+            $(
+                #[doc = concat!(
+                    "Get the *", stringify!($get), "* component.\n\n",
+                    "See [", stringify!($model), "::", stringify!($get), "]",
+                    "(struct.", stringify!($model), ".html#method.",
+                    stringify!($get), ")."
+                )]
+                fn $get(self) -> Self::Chan {
+                    $model::$get(self)
+                }
+
+                #[doc = concat!(
+                    "Get a mutable reference to the *", stringify!($get),
+                    "* component."
+                )]
+                fn $get_mut(&mut self) -> &mut Self::Chan {
+                    $model::$get_mut(self)
+                }
+
+                #[doc = concat!(
+                    "Make a copy with a new *", stringify!($get),
+                    "* component."
+                )]
+                fn $with(mut self, c: Self::Chan) -> Self {
+                    *self.$get_mut() = c;
+                    self
+                }
+            )+
+        }
+
+        impl<P: Pixel<Model = $model>> $trait for P {}
+    };
+}
+
+pub(crate) use model_pixel;
@@ -0,0 +1,162 @@
+// ndarr.rs     Conversions with the `ndarray` crate.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::el::{channel_count, Pixel};
+use crate::raster::{BufferError, Raster};
+use ndarray::{Array3, ArrayView3, ArrayViewMut3};
+
+impl<P: Pixel> Raster<P> {
+    /// Get a view of the channels as an `ndarray` array, without copying.
+    ///
+    /// The array shape is (*height*, *width*, *channels*).
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<SRgb8>::with_clear(4, 3);
+    /// *r.pixel_mut(1, 2) = SRgb8::new(10, 20, 30);
+    /// let v = r.as_array_view();
+    /// assert_eq!(v.shape(), &[3, 4, 3]);
+    /// assert_eq!(v[[2, 1, 2]], Ch8::new(30));
+    /// ```
+    pub fn as_array_view(&self) -> ArrayView3<'_, P::Chan> {
+        let shape = (self.height() as usize, self.width() as usize);
+        let shape = (shape.0, shape.1, channel_count::<P>());
+        let chans = unsafe {
+            // Pixel structs are repr(C), so channels are laid out contiguously
+            let (prefix, v, suffix) = self.pixels().align_to::<P::Chan>();
+            debug_assert!(prefix.is_empty());
+            debug_assert!(suffix.is_empty());
+            v
+        };
+        ArrayView3::from_shape(shape, chans).expect("invalid shape")
+    }
+
+    /// Get a mutable view of the channels as an `ndarray` array, without
+    /// copying.
+    ///
+    /// The array shape is (*height*, *width*, *channels*).
+    pub fn as_array_view_mut(&mut self) -> ArrayViewMut3<'_, P::Chan> {
+        let shape = (self.height() as usize, self.width() as usize);
+        let shape = (shape.0, shape.1, channel_count::<P>());
+        let chans = unsafe {
+            // Pixel structs are repr(C), so channels are laid out contiguously
+            let (prefix, v, suffix) =
+                self.pixels_mut().align_to_mut::<P::Chan>();
+            debug_assert!(prefix.is_empty());
+            debug_assert!(suffix.is_empty());
+            v
+        };
+        ArrayViewMut3::from_shape(shape, chans).expect("invalid shape")
+    }
+}
+
+impl<P: Pixel> From<&Raster<P>> for Array3<P::Chan> {
+    /// Copy a raster into an array with shape (*height*, *width*,
+    /// *channels*).
+    fn from(raster: &Raster<P>) -> Self {
+        raster.as_array_view().to_owned()
+    }
+}
+
+impl<P: Pixel> TryFrom<ArrayView3<'_, P::Chan>> for Raster<P> {
+    type Error = BufferError;
+
+    /// Copy an array with shape (*height*, *width*, *channels*) into a
+    /// raster.
+    ///
+    /// The array can have any memory layout.
+    fn try_from(view: ArrayView3<'_, P::Chan>) -> Result<Self, Self::Error> {
+        let (height, width, chans) = view.dim();
+        let height = u32::try_from(height).map_err(|_| BufferError::TooBig)?;
+        let width = u32::try_from(width).map_err(|_| BufferError::TooBig)?;
+        let n = channel_count::<P>();
+        if chans != n {
+            return Err(BufferError::WrongLength {
+                expected: view.len() / chans.max(1) * n,
+                actual: view.len(),
+            });
+        }
+        if i32::try_from(width)
+            .ok()
+            .zip(i32::try_from(height).ok())
+            .and_then(|(w, h)| w.checked_mul(h))
+            .is_none()
+        {
+            return Err(BufferError::TooBig);
+        }
+        let pixels = view
+            .rows()
+            .into_iter()
+            .map(|ch| match ch.as_slice() {
+                Some(ch) => P::try_from_channels(ch),
+                None => P::try_from_channels(&ch.to_vec()),
+            })
+            .collect::<Result<Vec<P>, _>>()
+            .map_err(|_| BufferError::WrongLength {
+                expected: view.len() / chans.max(1) * n,
+                actual: view.len(),
+            })?;
+        Ok(Raster::with_pixels(width, height, pixels))
+    }
+}
+
+impl<P: Pixel> TryFrom<Array3<P::Chan>> for Raster<P> {
+    type Error = BufferError;
+
+    /// Copy an array with shape (*height*, *width*, *channels*) into a
+    /// raster.
+    fn try_from(array: Array3<P::Chan>) -> Result<Self, Self::Error> {
+        Raster::try_from(array.view())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chan::{Ch16, Ch8};
+    use crate::gray::Gray16;
+    use crate::rgb::Rgba8;
+    use ndarray::{s, Axis};
+
+    #[test]
+    fn views() {
+        let mut r = Raster::<Rgba8>::with_clear(3, 2);
+        r.as_array_view_mut()
+            .slice_mut(s![.., .., 3])
+            .fill(Ch8::new(0xFF));
+        r.as_array_view_mut()[[1, 2, 0]] = Ch8::new(7);
+        assert_eq!(r.pixel(0, 0), Rgba8::new(0, 0, 0, 0xFF));
+        assert_eq!(r.pixel(2, 1), Rgba8::new(7, 0, 0, 0xFF));
+        let v = r.as_array_view();
+        let red = v.index_axis(Axis(2), 0);
+        assert_eq!(red.column(2).to_vec(), [Ch8::new(0), Ch8::new(7)]);
+    }
+
+    #[test]
+    fn convert() {
+        let mut r = Raster::<Gray16>::with_clear(4, 2);
+        *r.pixel_mut(3, 1) = Gray16::new(0x1234);
+        let a = Array3::from(&r);
+        assert_eq!(a.dim(), (2, 4, 1));
+        // transposed array has non-standard layout
+        let t = a.view().permuted_axes([1, 0, 2]);
+        let r2 = Raster::<Gray16>::try_from(t).unwrap();
+        assert_eq!((r2.width(), r2.height()), (2, 4));
+        assert_eq!(r2.pixel(1, 3), Gray16::new(0x1234));
+        let r3 = Raster::<Gray16>::try_from(a).unwrap();
+        assert_eq!(r3.pixels(), r.pixels());
+        let a = Array3::from_elem((2, 2, 3), Ch16::new(0));
+        assert_eq!(
+            Raster::<Gray16>::try_from(a).err(),
+            Some(BufferError::WrongLength {
+                expected: 4,
+                actual: 12
+            })
+        );
+    }
+}
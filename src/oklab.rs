@@ -7,9 +7,12 @@
 //! [OkLab]: https://bottosson.github.io/posts/oklab/
 #![allow(clippy::excessive_precision)]
 
-use crate::chan::{Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Straight};
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::chan::{
+    Alpha, Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Straight,
+};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [Oklab] [color model]
@@ -132,10 +135,37 @@ impl Oklab {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Oklab] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch32;
+    /// use pix::oklab::{OklabPixel, Oklab32};
+    ///
+    /// let mut p = Oklab32::new(0.5, 0.25, 0.75);
+    /// assert_eq!(p.l(), Ch32::new(0.5));
+    /// *p.b_mut() = Ch32::new(0.125);
+    /// assert_eq!(p.b(), Ch32::new(0.125));
+    /// ```
+    ///
+    /// [Oklab]: struct.Oklab.html
+    OklabPixel: Oklab {
+        l, l_mut, with_l;
+        a, a_mut, with_a;
+        b, b_mut, with_b;
+    }
+}
+
 impl ColorModel for Oklab {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] = &[
+        ChannelLabel::Lightness,
+        ChannelLabel::GreenRed,
+        ChannelLabel::BlueYellow,
+    ];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -145,18 +175,7 @@ impl ColorModel for Oklab {
         let pl = Self::l(p).to_f32();
         let pa = Self::a(p).to_f32();
         let pb = Self::b(p).to_f32();
-
-        let l_ = pl + 0.3963377774 * pa + 0.2158037573 * pb;
-        let m_ = pl - 0.1055613458 * pa - 0.0638541728 * pb;
-        let s_ = pl - 0.0894841775 * pa - 1.2914855480 * pb;
-
-        let l = l_ * l_ * l_;
-        let m = m_ * m_ * m_;
-        let s = s_ * s_ * s_;
-
-        let red = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
-        let green = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
-        let blue = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+        let [red, green, blue] = oklab_to_linear_rgb([pl, pa, pb]);
         PixRgba::<P>::new(red, green, blue, p.alpha().to_f32())
     }
 
@@ -166,25 +185,87 @@ impl ColorModel for Oklab {
         P: Pixel<Model = Self>,
     {
         let chan = rgba.channels();
-        let red = chan[0].to_f32();
-        let green = chan[1].to_f32();
-        let blue = chan[2].to_f32();
+        let rgb = [chan[0].to_f32(), chan[1].to_f32(), chan[2].to_f32()];
         let alpha = chan[3];
+        let [pl, pa, pb] = linear_rgb_to_oklab(rgb);
+        from_model_channels(&[pl.into(), pa.into(), pb.into(), alpha])
+    }
+
+    /// Convert into *linear* *red*, *green* and *blue*, without clamping
+    fn into_linear_rgb<P>(p: P) -> [f32; 3]
+    where
+        P: Pixel<Model = Self>,
+    {
+        let a = p.alpha();
+        let pl = P::Alpha::decode(Self::l(p), a).to_f32();
+        let pa = P::Alpha::decode(Self::a(p), a).to_f32();
+        let pb = P::Alpha::decode(Self::b(p), a).to_f32();
+        oklab_to_linear_rgb([pl, pa, pb])
+    }
+}
 
-        let l = 0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue;
-        let m = 0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue;
-        let s = 0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue;
+/// Convert *L*, *a* and *b* to *linear* *red*, *green* and *blue*
+pub(crate) fn oklab_to_linear_rgb([pl, pa, pb]: [f32; 3]) -> [f32; 3] {
+    let l_ = pl + 0.3963377774 * pa + 0.2158037573 * pb;
+    let m_ = pl - 0.1055613458 * pa - 0.0638541728 * pb;
+    let s_ = pl - 0.0894841775 * pa - 1.2914855480 * pb;
 
-        let l_ = l.cbrt();
-        let m_ = m.cbrt();
-        let s_ = s.cbrt();
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
 
-        let pl = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
-        let pa = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
-        let pb = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
+    let red = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
+    let green = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
+    let blue = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+    [red, green, blue]
+}
 
-        P::from_channels(&[pl.into(), pa.into(), pb.into(), alpha])
+/// Map *linear* *red*, *green* and *blue* into the sRGB gamut.
+///
+/// Chroma is reduced in Oklab, keeping lightness and hue, until the color is
+/// within gamut.
+pub(crate) fn reduce_chroma(rgb: [f32; 3]) -> [f32; 3] {
+    const EPSILON: f32 = 1e-5;
+    let in_gamut = |rgb: [f32; 3]| {
+        rgb.iter().all(|c| (-EPSILON..=1.0 + EPSILON).contains(c))
+    };
+    if in_gamut(rgb) {
+        return rgb.map(|c| c.clamp(0.0, 1.0));
+    }
+    let [pl, pa, pb] = linear_rgb_to_oklab(rgb);
+    if pl >= 1.0 {
+        return [1.0; 3];
+    }
+    if pl.is_nan() || pl <= 0.0 {
+        return [0.0; 3];
+    }
+    // binary search for the largest chroma scale within gamut
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..20 {
+        let mid = (lo + hi) * 0.5;
+        if in_gamut(oklab_to_linear_rgb([pl, pa * mid, pb * mid])) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
     }
+    oklab_to_linear_rgb([pl, pa * lo, pb * lo]).map(|c| c.clamp(0.0, 1.0))
+}
+
+/// Convert *linear* *red*, *green* and *blue* to *L*, *a* and *b*
+pub(crate) fn linear_rgb_to_oklab([red, green, blue]: [f32; 3]) -> [f32; 3] {
+    let l = 0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue;
+    let m = 0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue;
+    let s = 0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue;
+
+    let l_ = l.cbrt();
+    let m_ = m.cbrt();
+    let s_ = s.cbrt();
+
+    let pl = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
+    let pa = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
+    let pb = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
+    [pl, pa, pb]
 }
 
 /// [Oklab](struct.Oklab.html) 8-bit opaque (no *alpha* channel)
@@ -240,8 +321,24 @@ pub type Oklaba32p = Pix4<Ch32, Oklab, Premultiplied, Linear>;
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
     #[test]
     fn oklab_to_rgb() {
         // TODO
     }
+
+    #[test]
+    fn chroma() {
+        let rgb = [0.25, 0.5, 0.75];
+        assert_eq!(reduce_chroma(rgb), rgb);
+        let rgb = reduce_chroma([1.5, 0.2, -0.1]);
+        assert!(rgb.iter().all(|c| (0.0..=1.0).contains(c)), "{rgb:?}");
+        let [l0, a0, b0] = linear_rgb_to_oklab([1.5, 0.2, -0.1]);
+        let [l1, a1, b1] = linear_rgb_to_oklab(rgb);
+        assert!((l0 - l1).abs() < 1e-3, "{l0} {l1}");
+        assert!((b0.atan2(a0) - b1.atan2(a1)).abs() < 1e-3);
+        assert_eq!(reduce_chroma([2.0, 2.0, 1.5]), [1.0; 3]);
+        assert_eq!(reduce_chroma([-0.1, 0.0, 0.0]), [0.0; 3]);
+    }
 }
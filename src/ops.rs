@@ -10,13 +10,31 @@
 //! [composite_color]: ../struct.Raster.html#method.composite_color
 //! [composite_matte]: ../struct.Raster.html#method.composite_matte
 //! [composite_raster]: ../struct.Raster.html#method.composite_raster
+//!
+//! The stochastic [Dissolve] operation is used in
+//! [composite_dissolve].
+//!
+//! [composite_dissolve]: ../struct.Raster.html#method.composite_dissolve
+//! [Dissolve]: struct.Dissolve.html
+//!
+//! Color [Adjust]ment operations are used in [adjust_slice] and [adjust].
+//!
+//! [adjust]: ../struct.Raster.html#method.adjust
+//! [Adjust]: trait.Adjust.html
+//! [adjust_slice]: ../el/trait.Pixel.html#method.adjust_slice
 use crate::chan::Channel;
+use crate::el::Pixel;
+use crate::private::{MaybeSend, MaybeSync};
+use crate::rgb::Rgba32;
 use std::any::Any;
 
+/// Rec. 709 luma coefficients for linear *red*, *green* and *blue*
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
 /// Blending operation for compositing.
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
-pub trait Blend: Any + Copy + Clone {
+pub trait Blend: Any + Copy + Clone + MaybeSend + MaybeSync {
     /// Composite a destination and source
     ///
     /// * `dst` Destination channel
@@ -26,6 +44,26 @@ pub trait Blend: Any + Copy + Clone {
     fn composite<C: Channel>(dst: &mut C, da1: C, src: &C, sa1: C);
 }
 
+/// Color adjustment operation.
+///
+/// Adjustments are applied to *red*, *green*, *blue* and *alpha* values, as
+/// `f32` with *linear* gamma and *straight* alpha.  Pixels of any format are
+/// converted to and from this representation.
+pub trait Adjust: Copy + MaybeSend + MaybeSync {
+    /// Adjust linear *red*, *green*, *blue* and *alpha* values
+    fn adjust(&self, rgba: &mut [f32; 4]);
+
+    /// Adjust 8-bit linear *red*, *green*, *blue* and *alpha* values.
+    ///
+    /// This is used for 8-bit *linear*, *straight* alpha `Rgb` pixels.  The
+    /// default implementation converts to `f32` and calls `adjust`.
+    fn adjust_u8(&self, rgba: &mut [u8; 4]) {
+        let mut v = rgba.map(|c| f32::from(c) / 255.0);
+        self.adjust(&mut v);
+        *rgba = v.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+}
+
 /// Source only (ignore destination)
 #[derive(Clone, Copy)]
 pub struct Src;
@@ -78,6 +116,18 @@ pub struct Clear;
 #[derive(Clone, Copy)]
 pub struct Plus;
 
+/// Dissolve compositing (stochastic source selection)
+///
+/// Each source pixel either replaces the destination or is discarded, with
+/// probability equal to its *alpha*.  This produces stippled transparency,
+/// even on destinations without an *alpha* channel.  The pattern is
+/// determined only by the seed and pixel position, so animating the seed
+/// gives a dissolve transition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dissolve {
+    seed: u64,
+}
+
 impl Blend for Src {
     fn composite<C: Channel>(dst: &mut C, _da1: C, src: &C, _sa1: C) {
         *dst = *src;
@@ -159,3 +209,444 @@ impl Blend for Plus {
         *dst = *src + *dst;
     }
 }
+
+impl Dissolve {
+    /// Create a new dissolve operation.
+    ///
+    /// * `seed` Random seed for the pattern.
+    pub fn new(seed: u64) -> Self {
+        Dissolve { seed }
+    }
+
+    /// Check if a source pixel is drawn.
+    ///
+    /// * `x` X-position of pixel.
+    /// * `y` Y-position of pixel.
+    /// * `alpha` Source *alpha* value.
+    pub fn is_drawn<C: Channel>(self, x: i32, y: i32, alpha: C) -> bool {
+        let pos = (u64::from(x as u32) << 32) | u64::from(y as u32);
+        let v = splitmix64(self.seed ^ pos);
+        // use the upper 24 bits for a value in [0, 1)
+        let r = (v >> 40) as f32 / (1 << 24) as f32;
+        r < alpha.to_f32()
+    }
+}
+
+/// Brightness adjustment (amount added to *red*, *green* and *blue*)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Brightness(pub f32);
+
+/// Contrast adjustment (factor applied to *red*, *green* and *blue*).
+///
+/// Values are scaled away from (factor greater than `1.0`) or toward
+/// (factor less than `1.0`) 18% middle gray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contrast(pub f32);
+
+/// Exposure adjustment, in stops.
+///
+/// *Red*, *green* and *blue* are multiplied by 2 ^ *stops* in linear light,
+/// so `1.0` doubles the light and `-1.0` halves it.
+///
+/// ### Example
+/// ```
+/// use pix::el::Pixel;
+/// use pix::ops::Exposure;
+/// use pix::rgb::SRgb8;
+///
+/// let mut p = [SRgb8::new(0x40, 0x80, 0xC0), SRgb8::new(0xFF, 0xFF, 0xFF)];
+/// SRgb8::adjust_slice(&mut p, Exposure(-1.0));
+/// assert_eq!(p[0], SRgb8::new(0x2E, 0x5D, 0x8C));
+/// assert_eq!(p[1], SRgb8::new(0xBC, 0xBC, 0xBC));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exposure(pub f32);
+
+/// Gamma curve adjustment of *red*, *green* and *blue*.
+///
+/// Each value `v` is replaced by `v` ^ (1 / *gamma*).  Values greater than
+/// `1.0` brighten midtones, and values less than `1.0` darken them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaCurve(pub f32);
+
+impl Adjust for Brightness {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        for v in &mut rgba[..3] {
+            *v += self.0;
+        }
+    }
+}
+
+impl Adjust for Contrast {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        const MIDDLE_GRAY: f32 = 0.18;
+        for v in &mut rgba[..3] {
+            *v = (*v - MIDDLE_GRAY) * self.0 + MIDDLE_GRAY;
+        }
+    }
+}
+
+impl Adjust for Exposure {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        let scale = self.0.exp2();
+        for v in &mut rgba[..3] {
+            *v *= scale;
+        }
+    }
+}
+
+impl Adjust for GammaCurve {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        for v in &mut rgba[..3] {
+            *v = v.max(0.0).powf(self.0.recip());
+        }
+    }
+}
+
+/// White balance adjustment.
+///
+/// *Red*, *green* and *blue* are scaled by separate gains in linear light, to
+/// remove a color cast.
+///
+/// ### Neutralize a sampled gray
+/// ```
+/// use pix::ops::WhiteBalance;
+/// use pix::rgb::SRgb8;
+/// use pix::Raster;
+///
+/// let mut r = Raster::with_color(4, 4, SRgb8::new(0xA0, 0x90, 0x70));
+/// let wb = WhiteBalance::from_neutral(r.pixel(0, 0));
+/// r.adjust((), wb);
+/// assert_eq!(r.pixel(0, 0), SRgb8::new(0x92, 0x92, 0x92));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WhiteBalance {
+    /// Gains for *red*, *green* and *blue*
+    gain: [f32; 3],
+}
+
+impl Default for WhiteBalance {
+    fn default() -> Self {
+        WhiteBalance { gain: [1.0; 3] }
+    }
+}
+
+impl WhiteBalance {
+    /// Create a white balance from channel gains.
+    ///
+    /// * `gain` Linear gains for *red*, *green* and *blue*.
+    ///
+    /// Gains are normalized to preserve the luminance of neutral colors, so
+    /// only their ratios matter.
+    pub fn new(gain: [f32; 3]) -> Self {
+        let y: f32 = gain.iter().zip(LUMA).map(|(g, l)| g * l).sum();
+        if y > 0.0 && y.is_finite() {
+            WhiteBalance {
+                gain: gain.map(|g| g.max(0.0) / y),
+            }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Create a white balance which neutralizes a sampled pixel.
+    ///
+    /// * `neutral` Pixel which should be gray, in any format.
+    ///
+    /// The sampled pixel becomes gray, keeping its luminance.  *Alpha* is
+    /// ignored.  Channels which are zero cannot be corrected, so they are
+    /// left unchanged.
+    pub fn from_neutral<P: Pixel>(neutral: P) -> Self {
+        let [r, g, b, _a] = neutral.convert_lossy::<Rgba32>().into_array();
+        let rgb = [r, g, b].map(Channel::to_f32);
+        let y: f32 = rgb.iter().zip(LUMA).map(|(c, l)| c * l).sum();
+        if y > 0.0 {
+            let gain = rgb.map(|c| if c > 0.0 { y / c } else { 1.0 });
+            WhiteBalance { gain }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Create a white balance from temperature and tint shifts.
+    ///
+    /// * `temperature` Shift from blue (`-1.0`) to yellow (`1.0`).
+    /// * `tint` Shift from green (`-1.0`) to magenta (`1.0`).
+    ///
+    /// A shift of `1.0` scales the affected channels by one stop (2x) in
+    /// total.  `0.0` for both is no change.
+    pub fn from_temperature_tint(temperature: f32, tint: f32) -> Self {
+        let t = temperature * 0.5;
+        Self::new([t.exp2(), (-tint * 0.5).exp2(), (-t).exp2()])
+    }
+
+    /// Get the normalized gains for *red*, *green* and *blue*.
+    pub fn gains(&self) -> [f32; 3] {
+        self.gain
+    }
+}
+
+impl Adjust for WhiteBalance {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        for (v, g) in rgba.iter_mut().zip(self.gain) {
+            *v *= g;
+        }
+    }
+}
+
+/// Color vision deficiency simulation.
+///
+/// Simulates dichromatic vision, where one type of cone is missing.
+/// *Protanopia* and *deuteranopia* use the single-plane projection of
+/// Viénot, Brettel and Mollon (1999).  *Tritanopia* uses the two half-planes
+/// of Brettel, Viénot and Mollon (1997), since a single plane is not
+/// accurate for it.  Matrices are applied to *linear* sRGB values.
+///
+/// ### Preview for red-green deficiency
+/// ```
+/// use pix::ops::ColorVision;
+/// use pix::rgb::SRgb8;
+/// use pix::Raster;
+///
+/// let mut r = Raster::with_color(4, 4, SRgb8::new(0xFF, 0x00, 0x00));
+/// *r.pixel_mut(1, 1) = SRgb8::new(0x00, 0x80, 0x00);
+/// r.adjust((), ColorVision::Deuteranopia);
+/// assert_eq!(r.pixel(0, 0), SRgb8::new(0x94, 0x94, 0x00));
+/// assert_eq!(r.pixel(1, 1), SRgb8::new(0x6D, 0x6D, 0x0D));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorVision {
+    /// Missing long-wavelength (red) cones
+    Protanopia,
+    /// Missing medium-wavelength (green) cones
+    Deuteranopia,
+    /// Missing short-wavelength (blue) cones
+    Tritanopia,
+}
+
+impl ColorVision {
+    /// Get the projection matrix for linear *red*, *green* and *blue*
+    fn matrix(self, rgb: [f32; 3]) -> [[f32; 3]; 3] {
+        match self {
+            ColorVision::Protanopia => [
+                [0.11238, 0.88762, 0.0],
+                [0.11238, 0.88762, 0.0],
+                [0.00401, -0.00401, 1.0],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.29275, 0.70725, 0.0],
+                [0.29275, 0.70725, 0.0],
+                [-0.02234, 0.02234, 1.0],
+            ],
+            ColorVision::Tritanopia => {
+                const NORMAL: [f32; 3] = [0.03901, -0.02788, -0.01113];
+                let side: f32 =
+                    rgb.iter().zip(NORMAL).map(|(c, n)| c * n).sum();
+                if side >= 0.0 {
+                    [
+                        [1.01277, 0.13548, -0.14826],
+                        [-0.01243, 0.86812, 0.14431],
+                        [0.07589, 0.80500, 0.11911],
+                    ]
+                } else {
+                    [
+                        [0.93678, 0.18979, -0.12657],
+                        [0.06154, 0.81526, 0.12320],
+                        [-0.37562, 1.12767, 0.24796],
+                    ]
+                }
+            }
+        }
+    }
+}
+
+impl Adjust for ColorVision {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        let rgb = [rgba[0], rgba[1], rgba[2]];
+        let m = self.matrix(rgb);
+        for (v, row) in rgba.iter_mut().zip(m) {
+            *v = row.iter().zip(rgb).map(|(m, c)| m * c).sum();
+        }
+    }
+}
+
+/// Color matrix adjustment.
+///
+/// A 4x5 affine matrix is applied to *red*, *green*, *blue* and *alpha*
+/// values.  Each row produces one output value; the first four columns are
+/// multiplied by the input values, and the last column is added as an
+/// offset.  This is the basis for saturation, sepia, channel mixing and
+/// color-blindness simulation.
+///
+/// 8-bit values are adjusted using fixed-point arithmetic.
+///
+/// ### Swap red and blue channels
+/// ```
+/// use pix::ops::ColorMatrix;
+/// use pix::rgb::Rgba8;
+/// use pix::Raster;
+///
+/// let mut r = Raster::with_color(4, 4, Rgba8::new(0x10, 0x20, 0x30, 0x40));
+/// let m = ColorMatrix::new([
+///     [0.0, 0.0, 1.0, 0.0, 0.0],
+///     [0.0, 1.0, 0.0, 0.0, 0.0],
+///     [1.0, 0.0, 0.0, 0.0, 0.0],
+///     [0.0, 0.0, 0.0, 1.0, 0.0],
+/// ]);
+/// r.adjust((), m);
+/// assert_eq!(r.pixel(0, 0), Rgba8::new(0x30, 0x20, 0x10, 0x40));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorMatrix {
+    /// Matrix values
+    m: [[f32; 5]; 4],
+    /// Fixed-point matrix values for 8-bit channels
+    fixed: [[i64; 5]; 4],
+}
+
+impl Default for ColorMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ColorMatrix {
+    /// Identity matrix (no adjustment)
+    pub const IDENTITY: Self = ColorMatrix {
+        m: [
+            [1.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ],
+        fixed: [
+            [Self::ONE, 0, 0, 0, 0],
+            [0, Self::ONE, 0, 0, 0],
+            [0, 0, Self::ONE, 0, 0],
+            [0, 0, 0, Self::ONE, 0],
+        ],
+    };
+
+    /// Fixed-point scale (16 fractional bits)
+    const ONE: i64 = 1 << 16;
+
+    /// Create a new color matrix.
+    ///
+    /// * `m` Rows of matrix values: four multipliers (*red*, *green*,
+    ///   *blue*, *alpha*) and an offset.
+    pub fn new(m: [[f32; 5]; 4]) -> Self {
+        let one = Self::ONE as f32;
+        let fixed = m.map(|row| {
+            let mut r = row.map(|v| (v * one).round() as i64);
+            r[4] = (row[4] * one * 255.0).round() as i64;
+            r
+        });
+        ColorMatrix { m, fixed }
+    }
+
+    /// Create a saturation matrix.
+    ///
+    /// * `amount` Saturation factor: `0.0` for grayscale, `1.0` for no
+    ///   change, or greater than `1.0` to increase saturation.
+    ///
+    /// Luminance is preserved, using Rec. 709 coefficients.
+    pub fn saturation(amount: f32) -> Self {
+        let mut m = Self::IDENTITY.m;
+        for (r, row) in m.iter_mut().take(3).enumerate() {
+            for (c, v) in row.iter_mut().take(3).enumerate() {
+                let ident = if r == c { 1.0 } else { 0.0 };
+                *v = LUMA[c] * (1.0 - amount) + ident * amount;
+            }
+        }
+        Self::new(m)
+    }
+
+    /// Create a sepia tone matrix.
+    pub fn sepia() -> Self {
+        Self::new([
+            [0.393, 0.769, 0.189, 0.0, 0.0],
+            [0.349, 0.686, 0.168, 0.0, 0.0],
+            [0.272, 0.534, 0.131, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Get the matrix values.
+    pub fn values(&self) -> [[f32; 5]; 4] {
+        self.m
+    }
+
+    /// Combine with another matrix, applied after `self`.
+    ///
+    /// * `rhs` Matrix to apply after `self`.
+    pub fn then(&self, rhs: &Self) -> Self {
+        let (a, b) = (&self.m, &rhs.m);
+        let mut m = [[0.0; 5]; 4];
+        for (r, row) in m.iter_mut().enumerate() {
+            for (c, v) in row.iter_mut().enumerate() {
+                *v = (0..4).map(|k| b[r][k] * a[k][c]).sum();
+            }
+            row[4] += b[r][4];
+        }
+        Self::new(m)
+    }
+}
+
+impl Adjust for ColorMatrix {
+    fn adjust(&self, rgba: &mut [f32; 4]) {
+        let v = *rgba;
+        for (d, row) in rgba.iter_mut().zip(&self.m) {
+            *d = row[0] * v[0]
+                + row[1] * v[1]
+                + row[2] * v[2]
+                + row[3] * v[3]
+                + row[4];
+        }
+    }
+
+    fn adjust_u8(&self, rgba: &mut [u8; 4]) {
+        let v = rgba.map(i64::from);
+        for (d, row) in rgba.iter_mut().zip(&self.fixed) {
+            let acc = row[0] * v[0]
+                + row[1] * v[1]
+                + row[2] * v[2]
+                + row[3] * v[3]
+                + row[4]
+                + (Self::ONE >> 1);
+            *d = (acc >> 16).clamp(0, 255) as u8;
+        }
+    }
+}
+
+/// Mix bits of a 64-bit value (SplitMix64 finalizer)
+fn splitmix64(v: u64) -> u64 {
+    let mut z = v.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgb32;
+    use crate::Raster;
+
+    #[test]
+    fn white_balance() {
+        let wb = WhiteBalance::from_temperature_tint(0.0, 0.0);
+        assert_eq!(wb, WhiteBalance::default());
+        let warm = WhiteBalance::from_temperature_tint(1.0, 0.0).gains();
+        assert!(warm[0] > 1.0 && warm[2] < 1.0, "{warm:?}");
+        let magenta = WhiteBalance::from_temperature_tint(0.0, 1.0).gains();
+        assert!(magenta[1] < 1.0 && magenta[0] == magenta[2], "{magenta:?}");
+        let wb = WhiteBalance::from_neutral(Rgb32::new(0.5, 0.25, 0.0));
+        let mut r = Raster::with_color(2, 2, Rgb32::new(0.5, 0.25, 0.25));
+        r.adjust((), wb);
+        let [red, green, blue] = r.pixel(0, 0).into_array().map(f32::from);
+        assert!((red - 0.2851).abs() < 1e-5, "{red}");
+        assert!((green - 0.2851).abs() < 1e-5, "{green}");
+        assert_eq!(blue, 0.25);
+        assert_eq!(WhiteBalance::new([0.0; 3]), WhiteBalance::default());
+    }
+}
@@ -0,0 +1,176 @@
+// packed.rs    Packed 32-bit pixel values.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Packed `u32` values for 8-bit RGBA pixels.
+//!
+//! Windowing systems and video APIs often store a pixel as a single `u32`,
+//! with channels in a specific order.  The order names the channels from the
+//! most significant byte to the least significant.  For example,
+//! [PackOrder::Argb] packs alpha in bits 24-31 and blue in bits 0-7, so the
+//! value is `0xAARRGGBB`.
+//!
+//! Packing works on *values*, not bytes in memory.  On little-endian
+//! machines, an `Argb` value is stored as the bytes `B, G, R, A`.  To match a
+//! byte order in memory, pick the reversed order (e.g. [PackOrder::Abgr] for
+//! `R, G, B, A` bytes on little-endian).
+//!
+//! [PackOrder::Abgr]: enum.PackOrder.html#variant.Abgr
+//! [PackOrder::Argb]: enum.PackOrder.html#variant.Argb
+use crate::chan::{Alpha, Ch8, Gamma};
+use crate::el::{Pix4, Pixel};
+use crate::rgb::Rgb;
+
+/// Channel order of a packed `u32`, from most to least significant byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PackOrder {
+    /// `0xAARRGGBB`
+    Argb,
+    /// `0xAABBGGRR`
+    Abgr,
+    /// `0xRRGGBBAA`
+    Rgba,
+    /// `0xBBGGRRAA`
+    Bgra,
+}
+
+impl PackOrder {
+    /// Get the index of the source channel (RGBA) for each byte, from most
+    /// to least significant
+    const fn order(self) -> [usize; 4] {
+        match self {
+            PackOrder::Argb => [3, 0, 1, 2],
+            PackOrder::Abgr => [3, 2, 1, 0],
+            PackOrder::Rgba => [0, 1, 2, 3],
+            PackOrder::Bgra => [2, 1, 0, 3],
+        }
+    }
+}
+
+impl<A, G> Pix4<Ch8, Rgb, A, G>
+where
+    A: Alpha,
+    G: Gamma,
+{
+    /// Pack into a `u32` value.
+    ///
+    /// * `order` Channel order of packed value.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::packed::PackOrder;
+    /// use pix::rgb::SRgba8;
+    ///
+    /// let p = SRgba8::new(0x11, 0x22, 0x33, 0x44);
+    /// assert_eq!(p.to_u32(PackOrder::Argb), 0x4411_2233);
+    /// assert_eq!(p.to_u32(PackOrder::Bgra), 0x3322_1144);
+    /// ```
+    pub fn to_u32(self, order: PackOrder) -> u32 {
+        let ch = self.channels();
+        let bytes = order.order().map(|i| u8::from(ch[i]));
+        u32::from_be_bytes(bytes)
+    }
+
+    /// Unpack from a `u32` value.
+    ///
+    /// * `value` Packed value.
+    /// * `order` Channel order of packed value.
+    pub fn from_u32(value: u32, order: PackOrder) -> Self {
+        let bytes = value.to_be_bytes();
+        let mut ch = [Ch8::new(0); 4];
+        for (b, i) in bytes.iter().zip(order.order()) {
+            ch[i] = Ch8::new(*b);
+        }
+        Self::from_array(ch)
+    }
+}
+
+/// Pack a slice of pixels into `u32` values.
+///
+/// * `src` Source pixels.
+/// * `dst` Destination values.
+/// * `order` Channel order of packed values.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn pack<A, G>(
+    src: &[Pix4<Ch8, Rgb, A, G>],
+    dst: &mut [u32],
+    order: PackOrder,
+) where
+    A: Alpha,
+    G: Gamma,
+{
+    assert_eq!(src.len(), dst.len());
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = s.to_u32(order);
+    }
+}
+
+/// Unpack a slice of `u32` values into pixels.
+///
+/// * `src` Source values.
+/// * `dst` Destination pixels.
+/// * `order` Channel order of packed values.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+///
+/// ### Example
+/// ```
+/// use pix::packed::{self, PackOrder};
+/// use pix::rgb::SRgba8;
+/// use pix::Raster;
+///
+/// let fb = [0xFF10_2030; 16];
+/// let mut r = Raster::<SRgba8>::with_clear(4, 4);
+/// packed::unpack(&fb, r.pixels_mut(), PackOrder::Argb);
+/// assert_eq!(r.pixel(3, 3), SRgba8::new(0x10, 0x20, 0x30, 0xFF));
+/// ```
+pub fn unpack<A, G>(
+    src: &[u32],
+    dst: &mut [Pix4<Ch8, Rgb, A, G>],
+    order: PackOrder,
+) where
+    A: Alpha,
+    G: Gamma,
+{
+    assert_eq!(src.len(), dst.len());
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = Pix4::from_u32(*s, order);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::{Rgba8p, SRgba8};
+
+    #[test]
+    fn orders() {
+        let p = SRgba8::new(0x11, 0x22, 0x33, 0x44);
+        let orders = [
+            (PackOrder::Argb, 0x4411_2233),
+            (PackOrder::Abgr, 0x4433_2211),
+            (PackOrder::Rgba, 0x1122_3344),
+            (PackOrder::Bgra, 0x3322_1144),
+        ];
+        for (order, v) in orders {
+            assert_eq!(p.to_u32(order), v);
+            assert_eq!(SRgba8::from_u32(v, order), p);
+        }
+    }
+
+    #[test]
+    fn slices() {
+        let src = [Rgba8p::new(1, 2, 3, 4), Rgba8p::new(5, 6, 7, 8)];
+        let mut packed = [0; 2];
+        pack(&src, &mut packed, PackOrder::Abgr);
+        assert_eq!(packed, [0x0403_0201, 0x0807_0605]);
+        let mut dst = [Rgba8p::default(); 2];
+        unpack(&packed, &mut dst, PackOrder::Abgr);
+        assert_eq!(dst, src);
+    }
+}
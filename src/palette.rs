@@ -3,16 +3,54 @@
 // Copyright (c) 2019-2023  Douglas P Lau
 //
 use crate::chan::{Ch8, Srgb, Straight};
+use crate::dither::OrderedDither;
 use crate::el::{Pix3, Pixel};
 use crate::gray::Gray8;
+use crate::oklab::linear_rgb_to_oklab;
 use crate::raster::Raster;
-use crate::rgb::{Rgb, SRgb8};
+use crate::rgb::{Rgb, Rgb32, SRgb8};
+use std::collections::HashMap;
+#[cfg(feature = "palette-formats")]
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Error parsing a palette file
+#[cfg(feature = "palette-formats")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaletteError {
+    /// Missing or invalid file header
+    InvalidHeader,
+    /// Invalid line (line number)
+    InvalidLine(usize),
+    /// Number of colors does not match header (actual count)
+    WrongCount(usize),
+    /// Invalid file length (bytes)
+    InvalidLength(usize),
+}
+
+#[cfg(feature = "palette-formats")]
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaletteError::InvalidHeader => write!(f, "invalid header"),
+            PaletteError::InvalidLine(n) => write!(f, "invalid line: {n}"),
+            PaletteError::WrongCount(c) => write!(f, "wrong color count: {c}"),
+            PaletteError::InvalidLength(n) => write!(f, "invalid length: {n}"),
+        }
+    }
+}
+
+#[cfg(feature = "palette-formats")]
+impl std::error::Error for PaletteError {}
 
 /// Color table for use with indexed `Raster`s.
 #[derive(Clone)]
 pub struct Palette {
     table: Vec<SRgb8>,
     threshold_fn: fn(usize) -> SRgb8,
+    /// Nearest color lookup tree, built when needed
+    lookup: OnceLock<KdTree>,
 }
 
 impl Palette {
@@ -25,6 +63,7 @@ impl Palette {
         Palette {
             table,
             threshold_fn,
+            lookup: OnceLock::new(),
         }
     }
 
@@ -41,9 +80,8 @@ impl Palette {
     /// Set the threshold function for matching entries.
     ///
     /// * `threshold_fn` Called when checking whether a color matches an
-    ///                  existing entry.  The parameter is the palette table
-    ///                  size.  Returns the maximum `Channel`-wise difference
-    ///                  to match.
+    ///   existing entry.  The parameter is the palette table size.  Returns
+    ///   the maximum `Channel`-wise difference to match.
     pub fn set_threshold_fn(&mut self, threshold_fn: fn(usize) -> SRgb8) {
         self.threshold_fn = threshold_fn;
     }
@@ -84,6 +122,7 @@ impl Palette {
         let i = self.table.len();
         if i < self.table.capacity() {
             self.table.push(clr);
+            self.lookup = OnceLock::new();
             Some(i)
         } else {
             None
@@ -107,6 +146,38 @@ impl Palette {
         best
     }
 
+    /// Find the nearest entry to a color.
+    ///
+    /// * `clr` Color to match.
+    ///
+    /// Colors are compared by distance in the [Oklab] color space, which
+    /// matches perceived differences better than *sRGB* distance.  A k-d tree
+    /// is built on the first lookup after the palette is modified, so each
+    /// search is logarithmic in the number of entries.
+    ///
+    /// # Returns
+    /// Index of nearest entry, or `None` if the palette is empty.  The first
+    /// of equally near entries is returned.
+    ///
+    /// [Oklab]: oklab/struct.Oklab.html
+    ///
+    /// # Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Palette;
+    ///
+    /// let mut p = Palette::new(3);
+    /// p.set_entry(SRgb8::new(0x00, 0x00, 0x00));
+    /// p.set_entry(SRgb8::new(0x80, 0x80, 0x80));
+    /// p.set_entry(SRgb8::new(0x00, 0x00, 0xFF));
+    /// assert_eq!(p.nearest(SRgb8::new(0x20, 0x20, 0xA0)), Some(2));
+    /// ```
+    pub fn nearest(&self, clr: SRgb8) -> Option<usize> {
+        self.lookup
+            .get_or_init(|| KdTree::new(&self.table))
+            .nearest(oklab(clr))
+    }
+
     /// Replace a `Palette` entry.
     ///
     /// * `i` Index of entry.
@@ -118,6 +189,7 @@ impl Palette {
         if i < self.table.len() {
             let old = self.table[i];
             self.table[i] = clr;
+            self.lookup = OnceLock::new();
             Some(old)
         } else {
             None
@@ -161,12 +233,761 @@ impl Palette {
         }
         indexed
     }
+
+    /// Create a `Palette` holding a list of colors
+    #[cfg(any(feature = "palette-formats", feature = "serde"))]
+    fn with_colors(colors: Vec<SRgb8>) -> Self {
+        let mut palette = Palette::new(colors.len());
+        palette.table = colors;
+        palette
+    }
+
+    /// Parse a `Palette` from the contents of a GIMP `.gpl` file.
+    ///
+    /// * `gpl` Text of `.gpl` file.
+    ///
+    /// Color names, `Name:` and `Columns:` lines are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Palette;
+    ///
+    /// let gpl = "GIMP Palette\nName: Mono\n#\n  0   0   0\tBlack\n\
+    ///     255 255 255\tWhite\n";
+    /// let palette = Palette::from_gpl(gpl).unwrap();
+    /// assert_eq!(palette.entry(1), Some(SRgb8::new(255, 255, 255)));
+    /// ```
+    #[cfg(feature = "palette-formats")]
+    pub fn from_gpl(gpl: &str) -> Result<Self, PaletteError> {
+        let mut lines = gpl.lines();
+        if lines.next().map(str::trim) != Some("GIMP Palette") {
+            return Err(PaletteError::InvalidHeader);
+        }
+        let mut colors = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+            let clr =
+                parse_rgb(line).ok_or(PaletteError::InvalidLine(i + 2))?;
+            colors.push(clr);
+        }
+        Ok(Self::with_colors(colors))
+    }
+
+    /// Write the `Palette` as the contents of a GIMP `.gpl` file.
+    ///
+    /// * `name` Palette name.
+    #[cfg(feature = "palette-formats")]
+    pub fn to_gpl(&self, name: &str) -> String {
+        let mut gpl = format!("GIMP Palette\nName: {name}\n#\n");
+        for (i, clr) in self.table.iter().enumerate() {
+            let [r, g, b] = rgb_array(*clr);
+            gpl.push_str(&format!("{r:3} {g:3} {b:3}\tIndex {i}\n"));
+        }
+        gpl
+    }
+
+    /// Parse a `Palette` from the contents of a JASC `.pal` file.
+    ///
+    /// * `pal` Text of `.pal` file.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Palette;
+    ///
+    /// let pal = "JASC-PAL\r\n0100\r\n2\r\n0 0 0\r\n255 0 0\r\n";
+    /// let palette = Palette::from_jasc_pal(pal).unwrap();
+    /// assert_eq!(palette.entry(1), Some(SRgb8::new(255, 0, 0)));
+    /// ```
+    #[cfg(feature = "palette-formats")]
+    pub fn from_jasc_pal(pal: &str) -> Result<Self, PaletteError> {
+        let mut lines = pal.lines().map(str::trim);
+        if lines.next() != Some("JASC-PAL") || lines.next() != Some("0100") {
+            return Err(PaletteError::InvalidHeader);
+        }
+        let count: usize = lines
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or(PaletteError::InvalidHeader)?;
+        let mut colors = Vec::with_capacity(count.min(256));
+        for (i, line) in lines.enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let clr =
+                parse_rgb(line).ok_or(PaletteError::InvalidLine(i + 4))?;
+            colors.push(clr);
+        }
+        if colors.len() != count {
+            return Err(PaletteError::WrongCount(colors.len()));
+        }
+        Ok(Self::with_colors(colors))
+    }
+
+    /// Write the `Palette` as the contents of a JASC `.pal` file.
+    #[cfg(feature = "palette-formats")]
+    pub fn to_jasc_pal(&self) -> String {
+        let mut pal = format!("JASC-PAL\r\n0100\r\n{}\r\n", self.len());
+        for clr in &self.table {
+            let [r, g, b] = rgb_array(*clr);
+            pal.push_str(&format!("{r} {g} {b}\r\n"));
+        }
+        pal
+    }
+
+    /// Parse a `Palette` from the contents of an Adobe `.act` file.
+    ///
+    /// * `act` Bytes of `.act` file.
+    ///
+    /// Files are 768 bytes (256 colors), or 772 bytes with a color count and
+    /// transparent index.  The transparent index is ignored.
+    #[cfg(feature = "palette-formats")]
+    pub fn from_act(act: &[u8]) -> Result<Self, PaletteError> {
+        let count = match act.len() {
+            768 => 256,
+            772 => usize::from(u16::from_be_bytes([act[768], act[769]])),
+            len => return Err(PaletteError::InvalidLength(len)),
+        };
+        if count > 256 {
+            return Err(PaletteError::WrongCount(count));
+        }
+        let colors = act[..count * 3]
+            .chunks_exact(3)
+            .map(|c| SRgb8::new(c[0], c[1], c[2]))
+            .collect();
+        Ok(Self::with_colors(colors))
+    }
+
+    /// Write the `Palette` as the contents of an Adobe `.act` file.
+    ///
+    /// The file is 772 bytes, including the color count, with no transparent
+    /// index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette has more than 256 entries.
+    #[cfg(feature = "palette-formats")]
+    pub fn to_act(&self) -> Vec<u8> {
+        assert!(self.len() <= 256, "Palette too large for ACT file");
+        let mut act = Vec::with_capacity(772);
+        for clr in &self.table {
+            act.extend_from_slice(&rgb_array(*clr));
+        }
+        act.resize(768, 0);
+        act.extend_from_slice(&(self.len() as u16).to_be_bytes());
+        act.extend_from_slice(&0xFFFF_u16.to_be_bytes());
+        act
+    }
+
+    /// Find the nearest entry to a clamped color
+    fn nearest_i32(&self, clr: [i32; 3]) -> usize {
+        let [r, g, b] = clr.map(|c| c as u8);
+        self.nearest(SRgb8::new(r, g, b)).unwrap_or(0)
+    }
+
+    /// Make an indexed raster using Floyd-Steinberg dithering.
+    ///
+    /// * `raster` Source raster.
+    ///
+    /// Each pixel is mapped to the [nearest] entry, and the error is diffused
+    /// to neighboring pixels.  Rows are scanned in alternating directions
+    /// (serpentine), to avoid directional artifacts.  Pixels are converted
+    /// to `SRgb8`, ignoring *alpha*, and adjusted colors are clamped to the
+    /// channel range.  The palette is not modified.
+    ///
+    /// [nearest]: #method.nearest
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty or has more than 256 entries.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::{Palette, Raster};
+    ///
+    /// let mut p = Palette::new(2);
+    /// p.set_entry(SRgb8::new(0x00, 0x00, 0x00));
+    /// p.set_entry(SRgb8::new(0xFF, 0xFF, 0xFF));
+    /// let r = Raster::with_color(8, 8, SRgb8::new(0x80, 0x80, 0x80));
+    /// let indexed = p.make_indexed_dithered(&r);
+    /// let white = indexed.pixels().iter().filter(|i| i.one() == 1.into());
+    /// assert_eq!(white.count(), 32);
+    /// ```
+    pub fn make_indexed_dithered<S>(&self, raster: &Raster<S>) -> Raster<Gray8>
+    where
+        S: Pixel,
+        Ch8: From<S::Chan>,
+    {
+        assert!(!self.is_empty(), "Empty palette");
+        assert!(self.len() <= 256, "Palette too large for indexed raster");
+        let entries: Vec<[i32; 3]> = self
+            .table
+            .iter()
+            .map(|c| rgb_array(*c).map(i32::from))
+            .collect();
+        let width = raster.width() as usize;
+        let mut indexed = Raster::with_clear(raster.width(), raster.height());
+        // error rows, in 1/16 units, padded by one pixel on each side
+        let mut errs = vec![[0; 3]; width + 2];
+        let mut next_errs = vec![[0; 3]; width + 2];
+        let rows = raster.rows(()).zip(indexed.rows_mut(()));
+        for (y, (srow, drow)) in rows.enumerate() {
+            let reverse = y % 2 == 1;
+            for k in 0..width {
+                let x = if reverse { width - 1 - k } else { k };
+                let rgb = rgb_array(srow[x].convert());
+                let e = errs[x + 1];
+                let mut clr = [0; 3];
+                for c in 0..3 {
+                    clr[c] =
+                        (i32::from(rgb[c]) + ((e[c] + 8) >> 4)).clamp(0, 255);
+                }
+                let i = self.nearest_i32(clr);
+                drow[x] = Gray8::new::<u8>(i as u8);
+                // diffuse error: 7/16 ahead, 3/16, 5/16 and 1/16 below
+                let (ahead, behind) =
+                    if reverse { (x, x + 2) } else { (x + 2, x) };
+                for c in 0..3 {
+                    let err = clr[c] - entries[i][c];
+                    errs[ahead][c] += err * 7;
+                    next_errs[behind][c] += err * 3;
+                    next_errs[x + 1][c] += err * 5;
+                    next_errs[ahead][c] += err;
+                }
+            }
+            std::mem::swap(&mut errs, &mut next_errs);
+            next_errs.fill([0; 3]);
+        }
+        indexed
+    }
+
+    /// Make an indexed raster using ordered dithering.
+    ///
+    /// * `raster` Source raster.
+    /// * `dither` Dither matrix, tiled from the raster origin.
+    ///
+    /// Each pixel is offset by the matrix threshold, scaled to the typical
+    /// spacing between entries, then mapped to the [nearest] entry.  Unlike
+    /// error diffusion, each pixel is independent, so results are
+    /// deterministic and tileable.  Pixels are converted to `SRgb8`, ignoring
+    /// *alpha*.  The palette is not modified.
+    ///
+    /// [nearest]: #method.nearest
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty or has more than 256 entries.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::dither::OrderedDither;
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::{Palette, Raster};
+    ///
+    /// let mut p = Palette::new(2);
+    /// p.set_entry(SRgb8::new(0x00, 0x00, 0x00));
+    /// p.set_entry(SRgb8::new(0xFF, 0xFF, 0xFF));
+    /// let r = Raster::with_color(8, 8, SRgb8::new(0x80, 0x80, 0x80));
+    /// let indexed = p.make_indexed_ordered(&r, &OrderedDither::bayer(4));
+    /// let white = indexed.pixels().iter().filter(|i| i.one() == 1.into());
+    /// let white = white.count();
+    /// assert!(white > 16 && white < 48);
+    /// ```
+    pub fn make_indexed_ordered<S>(
+        &self,
+        raster: &Raster<S>,
+        dither: &OrderedDither,
+    ) -> Raster<Gray8>
+    where
+        S: Pixel,
+        Ch8: From<S::Chan>,
+    {
+        assert!(!self.is_empty(), "Empty palette");
+        assert!(self.len() <= 256, "Palette too large for indexed raster");
+        // approximate spacing between entries in each channel
+        let spread = 255.0 / (self.len() as f32).cbrt();
+        let mut indexed = Raster::with_clear(raster.width(), raster.height());
+        let rows = raster.rows(()).zip(indexed.rows_mut(()));
+        for (y, (srow, drow)) in (0..).zip(rows) {
+            for (x, (s, d)) in (0..).zip(srow.iter().zip(drow.iter_mut())) {
+                let offset = (dither.threshold(x, y) - 0.5) * spread;
+                let clr = rgb_array(s.convert()).map(|c| {
+                    (f32::from(c) + offset).round().clamp(0.0, 255.0) as i32
+                });
+                let i = self.nearest_i32(clr);
+                *d = Gray8::new::<u8>(i as u8);
+            }
+        }
+        indexed
+    }
+
+    /// Quantize a raster to a palette using the median cut algorithm.
+    ///
+    /// * `raster` Source raster.
+    /// * `colors` Maximum number of palette entries, from 1 to 256.
+    ///
+    /// The color space is split into boxes, each time dividing the box with
+    /// the largest channel range at its median (weighted by pixel count).
+    /// Each entry is the average color of one box.  Pixels are converted to
+    /// `SRgb8`, ignoring *alpha*.  An empty raster produces an empty palette.
+    ///
+    /// # Returns
+    /// Tuple of palette and indexed raster.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is not between 1 and 256.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::{Palette, Raster};
+    ///
+    /// let mut r = Raster::with_color(8, 8, SRgb8::new(0xFF, 0x00, 0x00));
+    /// r.copy_color((0, 0, 4, 8), SRgb8::new(0x00, 0x00, 0xFF));
+    /// r.copy_color((0, 0, 4, 1), SRgb8::new(0x00, 0x10, 0xFF));
+    /// let (palette, indexed) = Palette::quantize_median_cut(&r, 2);
+    /// assert_eq!(palette.len(), 2);
+    /// let i = indexed.pixel(7, 7).one();
+    /// assert_eq!(palette.entry(u8::from(i).into()), Some(r.pixel(7, 7)));
+    /// ```
+    pub fn quantize_median_cut<S>(
+        raster: &Raster<S>,
+        colors: usize,
+    ) -> (Self, Raster<Gray8>)
+    where
+        S: Pixel,
+        Ch8: From<S::Chan>,
+    {
+        assert!((1..=256).contains(&colors), "Invalid palette size");
+        let pixels: Vec<SRgb8> =
+            raster.pixels().iter().map(|p| p.convert()).collect();
+        let mut hist = color_histogram(&pixels);
+        let mut boxes = Vec::with_capacity(colors);
+        if !hist.is_empty() {
+            boxes.push(0..hist.len());
+        }
+        while boxes.len() < colors {
+            let Some((b, chan)) = widest_box(&hist, &boxes) else {
+                break;
+            };
+            let range = boxes[b].clone();
+            let entries = &mut hist[range.clone()];
+            entries.sort_unstable_by_key(|(c, _)| c[chan]);
+            let mid = median_index(entries);
+            boxes[b] = range.start..range.start + mid;
+            boxes.push(range.start + mid..range.end);
+        }
+        let mut palette = Palette::new(colors);
+        let mut lookup = HashMap::with_capacity(hist.len());
+        for (i, range) in boxes.into_iter().enumerate() {
+            let entries = &hist[range];
+            palette.table.push(box_average(entries));
+            for (c, _) in entries {
+                lookup.insert(*c, i as u8);
+            }
+        }
+        let mut indexed = Raster::with_clear(raster.width(), raster.height());
+        for (dst, src) in indexed.pixels_mut().iter_mut().zip(&pixels) {
+            *dst = Gray8::new::<u8>(lookup[&rgb_array(*src)]);
+        }
+        (palette, indexed)
+    }
+
+    /// Quantize pixels to a palette using an octree.
+    ///
+    /// * `pixels` Source pixels, which can be streamed from any iterator.
+    /// * `max_colors` Maximum number of palette entries.
+    ///
+    /// Colors are inserted into an octree, merging the deepest branches
+    /// whenever there are more than `max_colors` leaves.  Memory use is
+    /// bounded by `max_colors`, not by the number of pixels.  Each entry is
+    /// the average color of one leaf.  Pixels are converted to `SRgb8`,
+    /// ignoring *alpha*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_colors` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::{Palette, Raster};
+    ///
+    /// let mut r = Raster::with_color(8, 8, SRgb8::new(0xFF, 0x00, 0x00));
+    /// r.copy_color((0, 0, 4, 8), SRgb8::new(0x00, 0x00, 0xFF));
+    /// let palette = Palette::quantize_octree(r.pixels().iter().copied(), 16);
+    /// assert_eq!(palette.len(), 2);
+    /// ```
+    pub fn quantize_octree<S, I>(pixels: I, max_colors: usize) -> Self
+    where
+        S: Pixel,
+        Ch8: From<S::Chan>,
+        I: IntoIterator<Item = S>,
+    {
+        assert!(max_colors > 0, "Invalid palette size");
+        let mut tree = Octree::default();
+        for p in pixels {
+            tree.insert(rgb_array(p.convert()));
+            while tree.leaves > max_colors {
+                tree.reduce();
+            }
+        }
+        let mut palette = Palette::new(max_colors);
+        tree.collect_leaves(0, &mut palette.table);
+        palette
+    }
+}
+
+/// Palettes are serialized as a sequence of colors.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Palette {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.table.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Palette {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let colors = Vec::<SRgb8>::deserialize(deserializer)?;
+        Ok(Self::with_colors(colors))
+    }
+}
+
+/// Maximum depth of octree leaves (one level per bit)
+const OCTREE_DEPTH: usize = 8;
+
+/// Octree node
+#[derive(Clone, Copy, Default)]
+struct OctreeNode {
+    /// Child node indices (0 for none, since root cannot be a child)
+    children: [usize; 8],
+    /// Sums of red, green and blue channels
+    sums: [u64; 3],
+    /// Count of pixels
+    count: u64,
+    /// Leaf node flag
+    is_leaf: bool,
+}
+
+/// Octree for color quantization
+struct Octree {
+    /// Node arena, with root at index 0
+    nodes: Vec<OctreeNode>,
+    /// Free node indices, for reuse
+    free: Vec<usize>,
+    /// Reducible (branch) nodes at each level
+    reducible: [Vec<usize>; OCTREE_DEPTH],
+    /// Number of leaf nodes
+    leaves: usize,
+    /// Depth of new leaf nodes
+    depth: usize,
+}
+
+impl Default for Octree {
+    fn default() -> Self {
+        let mut reducible: [Vec<usize>; OCTREE_DEPTH] = Default::default();
+        reducible[0].push(0);
+        Octree {
+            nodes: vec![OctreeNode::default()],
+            free: Vec::new(),
+            reducible,
+            leaves: 0,
+            depth: OCTREE_DEPTH,
+        }
+    }
+}
+
+impl Octree {
+    /// Allocate a new node at a level
+    fn alloc(&mut self, level: usize) -> usize {
+        let node = OctreeNode {
+            is_leaf: level >= self.depth,
+            ..Default::default()
+        };
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        if node.is_leaf {
+            self.leaves += 1;
+        } else {
+            self.reducible[level].push(idx);
+        }
+        idx
+    }
+
+    /// Insert a color
+    fn insert(&mut self, rgb: [u8; 3]) {
+        let mut idx = 0;
+        let mut level = 0;
+        while !self.nodes[idx].is_leaf {
+            let shift = 7 - level;
+            let ci = (usize::from(rgb[0] >> shift & 1) << 2)
+                | (usize::from(rgb[1] >> shift & 1) << 1)
+                | usize::from(rgb[2] >> shift & 1);
+            let mut child = self.nodes[idx].children[ci];
+            if child == 0 {
+                child = self.alloc(level + 1);
+                self.nodes[idx].children[ci] = child;
+            }
+            idx = child;
+            level += 1;
+        }
+        let node = &mut self.nodes[idx];
+        for (s, v) in node.sums.iter_mut().zip(rgb) {
+            *s += u64::from(v);
+        }
+        node.count += 1;
+    }
+
+    /// Merge the children of the deepest branch node into it
+    fn reduce(&mut self) {
+        let Some(level) = (0..self.depth)
+            .rev()
+            .find(|l| !self.reducible[*l].is_empty())
+        else {
+            return;
+        };
+        let idx = self.reducible[level].pop().unwrap();
+        let mut node = self.nodes[idx];
+        let mut merged = 0;
+        for child in node.children.iter_mut().filter(|c| **c > 0) {
+            let c = self.nodes[*child];
+            for (s, v) in node.sums.iter_mut().zip(c.sums) {
+                *s += v;
+            }
+            node.count += c.count;
+            self.free.push(*child);
+            *child = 0;
+            merged += 1;
+        }
+        node.is_leaf = true;
+        self.nodes[idx] = node;
+        self.leaves = self.leaves + 1 - merged;
+        // new leaves must not be deeper than the reduced level
+        self.depth = level + 1;
+    }
+
+    /// Collect average colors of leaf nodes
+    fn collect_leaves(&self, idx: usize, colors: &mut Vec<SRgb8>) {
+        let node = &self.nodes[idx];
+        if node.is_leaf {
+            if node.count > 0 {
+                let n = node.count;
+                let [r, g, b] = node.sums.map(|s| ((s + n / 2) / n) as u8);
+                colors.push(SRgb8::new(r, g, b));
+            }
+        } else {
+            for child in node.children.iter().filter(|c| **c > 0) {
+                self.collect_leaves(*child, colors);
+            }
+        }
+    }
+}
+
+/// Get the channels of a color as an array
+fn rgb_array(clr: SRgb8) -> [u8; 3] {
+    [
+        u8::from(Rgb::red(clr)),
+        u8::from(Rgb::green(clr)),
+        u8::from(Rgb::blue(clr)),
+    ]
+}
+
+/// Parse a color from the first three values of a line
+#[cfg(feature = "palette-formats")]
+fn parse_rgb(line: &str) -> Option<SRgb8> {
+    let mut values = line.split_whitespace().map(|v| v.parse::<u8>().ok());
+    let r = values.next()??;
+    let g = values.next()??;
+    let b = values.next()??;
+    Some(SRgb8::new(r, g, b))
+}
+
+/// Convert an *sRGB* color to Oklab components
+fn oklab(clr: SRgb8) -> [f32; 3] {
+    let rgb: Rgb32 = clr.convert();
+    let [r, g, b] = [Rgb::red(rgb), Rgb::green(rgb), Rgb::blue(rgb)];
+    linear_rgb_to_oklab([r.into(), g.into(), b.into()])
+}
+
+/// Squared distance between two points
+fn dist_sq(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|c| (a[c] - b[c]) * (a[c] - b[c])).sum()
+}
+
+/// K-d tree of palette entries, in Oklab space
+#[derive(Clone)]
+struct KdTree {
+    /// Points with entry indices, arranged as an implicit tree: the median
+    /// of each slice is its root, with subtrees on either side
+    points: Vec<([f32; 3], usize)>,
+}
+
+impl KdTree {
+    /// Build a tree from palette entries
+    fn new(table: &[SRgb8]) -> Self {
+        let mut points: Vec<_> = table
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (oklab(*c), i))
+            .collect();
+        Self::arrange(&mut points, 0);
+        KdTree { points }
+    }
+
+    /// Arrange a slice of points into a subtree
+    fn arrange(points: &mut [([f32; 3], usize)], axis: usize) {
+        if points.len() > 1 {
+            let mid = points.len() / 2;
+            points.select_nth_unstable_by(mid, |a, b| {
+                a.0[axis].total_cmp(&b.0[axis]).then(a.1.cmp(&b.1))
+            });
+            let (left, right) = points.split_at_mut(mid);
+            Self::arrange(left, (axis + 1) % 3);
+            Self::arrange(&mut right[1..], (axis + 1) % 3);
+        }
+    }
+
+    /// Find the index of the nearest entry
+    fn nearest(&self, pt: [f32; 3]) -> Option<usize> {
+        let mut best = None;
+        Self::search(&self.points, 0, pt, &mut best);
+        best.map(|(_, i)| i)
+    }
+
+    /// Search a subtree for the nearest entry
+    fn search(
+        points: &[([f32; 3], usize)],
+        axis: usize,
+        pt: [f32; 3],
+        best: &mut Option<(f32, usize)>,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+        let mid = points.len() / 2;
+        let (p, i) = points[mid];
+        let dist = dist_sq(pt, p);
+        let better = match *best {
+            Some((d, j)) => dist < d || (dist == d && i < j),
+            None => true,
+        };
+        if better {
+            *best = Some((dist, i));
+        }
+        let (left, right) = (&points[..mid], &points[mid + 1..]);
+        let diff = pt[axis] - p[axis];
+        let (near, far) = if diff < 0.0 {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let next = (axis + 1) % 3;
+        Self::search(near, next, pt, best);
+        if best.is_some_and(|(d, _)| diff * diff <= d) {
+            Self::search(far, next, pt, best);
+        }
+    }
+}
+
+/// Make a histogram of unique colors, with pixel counts
+fn color_histogram(pixels: &[SRgb8]) -> Vec<([u8; 3], usize)> {
+    let mut colors: Vec<[u8; 3]> =
+        pixels.iter().map(|p| rgb_array(*p)).collect();
+    colors.sort_unstable();
+    let mut hist: Vec<([u8; 3], usize)> = Vec::new();
+    for c in colors {
+        match hist.last_mut() {
+            Some((lc, n)) if *lc == c => *n += 1,
+            _ => hist.push((c, 1)),
+        }
+    }
+    hist
+}
+
+/// Find the splittable box with the widest channel range.
+///
+/// Returns the box index and channel.
+fn widest_box(
+    hist: &[([u8; 3], usize)],
+    boxes: &[Range<usize>],
+) -> Option<(usize, usize)> {
+    let mut widest = None;
+    let mut extent = 0;
+    for (b, range) in boxes.iter().enumerate() {
+        let entries = &hist[range.clone()];
+        for chan in 0..3 {
+            let lo = entries.iter().map(|(c, _)| c[chan]).min().unwrap_or(0);
+            let hi = entries.iter().map(|(c, _)| c[chan]).max().unwrap_or(0);
+            if hi - lo > extent {
+                extent = hi - lo;
+                widest = Some((b, chan));
+            }
+        }
+    }
+    widest
+}
+
+/// Find the index to split sorted box entries at the weighted median.
+///
+/// The result is always between 1 and `entries.len() - 1`.
+fn median_index(entries: &[([u8; 3], usize)]) -> usize {
+    let total: usize = entries.iter().map(|(_, n)| n).sum();
+    let mut count = 0;
+    for (i, (_, n)) in entries.iter().enumerate() {
+        count += n;
+        if count * 2 >= total {
+            return (i + 1).clamp(1, entries.len() - 1);
+        }
+    }
+    entries.len() - 1
+}
+
+/// Get the average color of box entries, weighted by count
+fn box_average(entries: &[([u8; 3], usize)]) -> SRgb8 {
+    let mut sums = [0; 3];
+    let mut total = 0;
+    for (c, n) in entries {
+        for (s, v) in sums.iter_mut().zip(c) {
+            *s += usize::from(*v) * n;
+        }
+        total += n;
+    }
+    let total = total.max(1);
+    let [r, g, b] = sums.map(|s| ((s + total / 2) / total) as u8);
+    SRgb8::new(r, g, b)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::rgb::*;
-    use crate::Palette;
+    use super::*;
 
     #[test]
     fn fill_16() {
@@ -222,6 +1043,156 @@ mod test {
         assert_eq!(p.histogram(&v[..]), Some(vec![18, 6, 10, 4, 8, 0, 2]));
     }
 
+    #[test]
+    fn median_cut() {
+        let mut r = Raster::with_color(4, 4, SRgb8::new(0, 0, 0));
+        r.copy_color((0, 0, 4, 1), SRgb8::new(0xFF, 0xFF, 0xFF));
+        r.copy_color((0, 1, 2, 1), SRgb8::new(0x80, 0x00, 0x00));
+        r.copy_color((2, 1, 2, 1), SRgb8::new(0x90, 0x00, 0x00));
+        let (p, ind) = Palette::quantize_median_cut(&r, 3);
+        assert_eq!(p.len(), 3);
+        let clr = |x, y| p.entry(u8::from(ind.pixel(x, y).one()).into());
+        assert_eq!(clr(0, 0), Some(SRgb8::new(0xFF, 0xFF, 0xFF)));
+        assert_eq!(clr(0, 1), Some(SRgb8::new(0x88, 0x00, 0x00)));
+        assert_eq!(clr(3, 1), Some(SRgb8::new(0x88, 0x00, 0x00)));
+        assert_eq!(clr(3, 3), Some(SRgb8::new(0x00, 0x00, 0x00)));
+        // fewer unique colors than requested
+        let (p, _) = Palette::quantize_median_cut(&r, 16);
+        assert_eq!(p.len(), 4);
+        let r = Raster::<SRgb8>::with_clear(0, 0);
+        let (p, ind) = Palette::quantize_median_cut(&r, 16);
+        assert!(p.is_empty());
+        assert!(ind.pixels().is_empty());
+        let p = Palette::quantize_octree(r.pixels().iter().copied(), 16);
+        assert!(p.is_empty());
+    }
+
+    #[test]
+    fn octree() {
+        let pixels = (0..=255).map(|v| SRgb8::new(v, 0, 255 - v));
+        let p = Palette::quantize_octree(pixels.clone(), 256);
+        assert_eq!(p.len(), 256);
+        let p = Palette::quantize_octree(pixels.clone(), 2);
+        assert_eq!(
+            p.colors(),
+            [SRgb8::new(0x40, 0, 0xC0), SRgb8::new(0xC0, 0, 0x40)]
+        );
+        for n in 1..20 {
+            let p = Palette::quantize_octree(pixels.clone(), n);
+            assert!(p.len() <= n);
+        }
+        let p = Palette::quantize_octree([SRgb8::new(1, 2, 3); 4], 1);
+        assert_eq!(p.colors(), [SRgb8::new(1, 2, 3)]);
+    }
+
+    #[test]
+    fn dither() {
+        let mut p = Palette::new(4);
+        p.set_entry(SRgb8::new(0, 0, 0));
+        p.set_entry(SRgb8::new(0xFF, 0, 0));
+        p.set_entry(SRgb8::new(0, 0, 0xFF));
+        let mut r = Raster::with_color(16, 16, SRgb8::new(0x40, 0, 0xC0));
+        r.copy_color((0, 0, 16, 8), SRgb8::new(0xFF, 0, 0));
+        let ind = p.make_indexed_dithered(&r);
+        let hist = p.histogram(ind.as_u8_slice()).unwrap();
+        // exact colors are not dithered
+        assert!(ind
+            .rows((0, 0, 16, 8))
+            .flatten()
+            .all(|i| i.one() == 1.into()));
+        // average of dithered area is close to source color
+        assert_eq!(hist.iter().sum::<usize>(), 256);
+        assert!((hist[1] as i32 - 128 - 32).abs() <= 4);
+        assert!((hist[2] as i32 - 96).abs() <= 4);
+    }
+
+    #[test]
+    fn ordered() {
+        let mut p = Palette::new(4);
+        for v in [0x00, 0x55, 0xAA, 0xFF] {
+            p.set_entry(SRgb8::new(v, v, v));
+        }
+        let r = Raster::with_color(8, 8, SRgb8::new(0x80, 0x80, 0x80));
+        let ind = p.make_indexed_ordered(&r, &OrderedDither::bayer(8));
+        let hist = p.histogram(ind.as_u8_slice()).unwrap();
+        assert_eq!(hist, [0, 31, 33, 0]);
+        // matrix is tiled from the origin
+        let r = Raster::with_color(16, 16, SRgb8::new(0x80, 0x80, 0x80));
+        let ind2 = p.make_indexed_ordered(&r, &OrderedDither::bayer(8));
+        assert_eq!(ind2.pixel(9, 10), ind.pixel(1, 2));
+    }
+
+    #[test]
+    fn nearest() {
+        let mut p = Palette::new(64);
+        assert_eq!(p.nearest(SRgb8::new(1, 2, 3)), None);
+        for i in 0..64 {
+            let v = i * 4;
+            p.set_entry(SRgb8::new(v, 255 - v, v.wrapping_mul(7)));
+        }
+        // compare with linear search
+        for v in (0..=255).step_by(5) {
+            let clr = SRgb8::new(v, v.wrapping_mul(3), 255 - v);
+            let pt = oklab(clr);
+            let mut best = 0;
+            for (i, c) in p.colors().iter().enumerate() {
+                if dist_sq(pt, oklab(*c)) < dist_sq(pt, oklab(p.colors()[best]))
+                {
+                    best = i;
+                }
+            }
+            assert_eq!(p.nearest(clr), Some(best));
+        }
+        p.replace_entry(0, SRgb8::new(0x40, 0x40, 0x40));
+        assert_eq!(p.nearest(SRgb8::new(0x41, 0x40, 0x40)), Some(0));
+        // first of equal entries
+        let mut p = Palette::new(3);
+        p.set_threshold_fn(|_| SRgb8::new(0, 0, 0));
+        p.set_entry(SRgb8::new(9, 9, 9));
+        p.set_entry(SRgb8::new(0, 0, 0));
+        p.replace_entry(0, SRgb8::new(0, 0, 0));
+        assert_eq!(p.nearest(SRgb8::new(1, 1, 1)), Some(0));
+    }
+
+    #[test]
+    #[cfg(feature = "palette-formats")]
+    fn formats() {
+        let mut p = Palette::new(3);
+        p.set_entry(SRgb8::new(0x10, 0x20, 0x30));
+        p.set_entry(SRgb8::new(0xFF, 0x00, 0x80));
+        p.set_entry(SRgb8::new(0x00, 0x00, 0x00));
+        let gpl = p.to_gpl("Test");
+        assert!(gpl.starts_with("GIMP Palette\nName: Test\n"));
+        assert_eq!(Palette::from_gpl(&gpl).unwrap().colors(), p.colors());
+        let pal = p.to_jasc_pal();
+        assert_eq!(Palette::from_jasc_pal(&pal).unwrap().colors(), p.colors());
+        let act = p.to_act();
+        assert_eq!(act.len(), 772);
+        assert_eq!(Palette::from_act(&act).unwrap().colors(), p.colors());
+        assert_eq!(Palette::from_act(&act[..768]).unwrap().len(), 256);
+    }
+
+    #[test]
+    #[cfg(feature = "palette-formats")]
+    fn format_errors() {
+        assert_eq!(
+            Palette::from_gpl("GIMP Palette\n1 2 3\n1 2\n").err(),
+            Some(PaletteError::InvalidLine(3))
+        );
+        assert_eq!(
+            Palette::from_gpl("JASC-PAL\n").err(),
+            Some(PaletteError::InvalidHeader)
+        );
+        assert_eq!(
+            Palette::from_jasc_pal("JASC-PAL\n0100\n2\n1 2 3\n").err(),
+            Some(PaletteError::WrongCount(1))
+        );
+        assert_eq!(
+            Palette::from_act(&[0; 100]).err(),
+            Some(PaletteError::InvalidLength(100))
+        );
+    }
+
     #[test]
     fn matching() {
         let mut p = Palette::new(8);
@@ -234,4 +1205,17 @@ mod test {
         p.set_threshold_fn(|_| SRgb8::new(5, 5, 5));
         assert_eq!(p.set_entry(SRgb8::new(35, 35, 35)), Some(2));
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let mut p = Palette::new(4);
+        p.set_entry(SRgb8::new(1, 2, 3));
+        p.set_entry(SRgb8::new(4, 5, 6));
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "[[1,2,3],[4,5,6]]");
+        let p = serde_json::from_str::<Palette>(&json).unwrap();
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.entry(1), Some(SRgb8::new(4, 5, 6)));
+    }
 }
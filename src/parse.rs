@@ -0,0 +1,395 @@
+// parse.rs     CSS color parsing and formatting.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::{Alpha, Channel, Gamma, IntChannel, Srgb, Straight};
+use crate::el::{Pix3, Pix4, Pixel};
+use crate::rgb::{css, Rgb};
+use std::fmt;
+use std::str::FromStr;
+
+/// Error parsing a CSS color string
+///
+/// *sRGB* RGB pixel types can be parsed from CSS hex colors, [named colors],
+/// or `rgb()`, `rgba()`, `hsl()` and `hsla()` functions.
+///
+/// ### Example
+/// ```
+/// use pix::rgb::SRgba8;
+/// use pix::ParseColorError;
+///
+/// let clr: SRgba8 = "hsl(120deg 100% 25% / 50%)".parse().unwrap();
+/// assert_eq!(clr, SRgba8::new(0, 128, 0, 128));
+/// let err = "#12".parse::<SRgba8>();
+/// assert_eq!(err, Err(ParseColorError::InvalidHex));
+/// ```
+///
+/// [named colors]: rgb/css/index.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// Not a hex color, named color or supported function
+    UnknownFormat,
+    /// Invalid hex digits or length
+    InvalidHex,
+    /// Missing closing parenthesis
+    MissingParen,
+    /// Wrong number of components (actual count)
+    WrongCount(usize),
+    /// Invalid component (index)
+    InvalidComponent(usize),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::UnknownFormat => write!(f, "unknown color format"),
+            ParseColorError::InvalidHex => write!(f, "invalid hex color"),
+            ParseColorError::MissingParen => {
+                write!(f, "missing closing parenthesis")
+            }
+            ParseColorError::WrongCount(c) => {
+                write!(f, "wrong component count: {c}")
+            }
+            ParseColorError::InvalidComponent(i) => {
+                write!(f, "invalid component: {i}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parse a CSS color into straight *sRGB* components (`0.0` to `1.0`)
+fn parse_css(s: &str) -> Result<[f32; 4], ParseColorError> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    let Some((name, args)) = s.split_once('(') else {
+        return parse_name(s);
+    };
+    let args = args
+        .strip_suffix(')')
+        .ok_or(ParseColorError::MissingParen)?;
+    let args: Vec<&str> = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::WrongCount(args.len()));
+    }
+    let alpha = match args.get(3) {
+        Some(a) => parse_unit(a).ok_or(ParseColorError::InvalidComponent(3))?,
+        None => 1.0,
+    };
+    let comp = |i: usize, parse: fn(&str) -> Option<f32>| {
+        parse(args[i]).ok_or(ParseColorError::InvalidComponent(i))
+    };
+    match name.trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => Ok([
+            comp(0, parse_rgb)?,
+            comp(1, parse_rgb)?,
+            comp(2, parse_rgb)?,
+            alpha,
+        ]),
+        "hsl" | "hsla" => {
+            let hue = comp(0, parse_hue)?;
+            let sat = comp(1, parse_percent)?;
+            let lum = comp(2, parse_percent)?;
+            let [r, g, b] = hsl_to_rgb(hue, sat, lum);
+            Ok([r, g, b, alpha])
+        }
+        _ => Err(ParseColorError::UnknownFormat),
+    }
+}
+
+/// Parse a CSS named color
+fn parse_name(name: &str) -> Result<[f32; 4], ParseColorError> {
+    let [r, g, b] = css::from_name(name)
+        .ok_or(ParseColorError::UnknownFormat)?
+        .into_array()
+        .map(|c| c.to_f32());
+    Ok([r, g, b, 1.0])
+}
+
+/// Parse hex digits (`rgb`, `rgba`, `rrggbb` or `rrggbbaa`)
+fn parse_hex(hex: &str) -> Result<[f32; 4], ParseColorError> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ParseColorError::InvalidHex);
+    }
+    let digit = |i: usize, len: usize| {
+        let v = u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).unwrap();
+        if len == 1 {
+            v * 0x11
+        } else {
+            v
+        }
+    };
+    let (count, len) = match hex.len() {
+        3 => (3, 1),
+        4 => (4, 1),
+        6 => (3, 2),
+        8 => (4, 2),
+        _ => return Err(ParseColorError::InvalidHex),
+    };
+    let mut rgba = [1.0; 4];
+    for (i, c) in rgba.iter_mut().enumerate().take(count) {
+        *c = f32::from(digit(i, len)) / 255.0;
+    }
+    Ok(rgba)
+}
+
+/// Parse a percentage, without the `%` sign
+fn parse_pct(s: &str) -> Option<f32> {
+    let v = s.strip_suffix('%')?.parse::<f32>().ok()?;
+    Some(v / 100.0)
+}
+
+/// Parse an RGB component (`0` to `255` or percentage)
+fn parse_rgb(s: &str) -> Option<f32> {
+    let v = parse_pct(s).or_else(|| Some(s.parse::<f32>().ok()? / 255.0))?;
+    v.is_finite().then_some(v.clamp(0.0, 1.0))
+}
+
+/// Parse a unit value (`0` to `1` or percentage)
+fn parse_unit(s: &str) -> Option<f32> {
+    let v = parse_pct(s).or_else(|| s.parse::<f32>().ok())?;
+    v.is_finite().then_some(v.clamp(0.0, 1.0))
+}
+
+/// Parse a percentage (`%` sign optional)
+fn parse_percent(s: &str) -> Option<f32> {
+    let v = parse_pct(s).or_else(|| Some(s.parse::<f32>().ok()? / 100.0))?;
+    v.is_finite().then_some(v.clamp(0.0, 1.0))
+}
+
+/// Parse a hue angle, in turns (`0` to `1`)
+fn parse_hue(s: &str) -> Option<f32> {
+    let s = s.to_ascii_lowercase();
+    let (v, per_turn) = if let Some(v) = s.strip_suffix("deg") {
+        (v, 360.0)
+    } else if let Some(v) = s.strip_suffix("grad") {
+        (v, 400.0)
+    } else if let Some(v) = s.strip_suffix("rad") {
+        (v, std::f32::consts::TAU)
+    } else if let Some(v) = s.strip_suffix("turn") {
+        (v, 1.0)
+    } else {
+        (s.as_str(), 360.0)
+    };
+    let v = v.parse::<f32>().ok()? / per_turn;
+    v.is_finite().then_some(v.rem_euclid(1.0))
+}
+
+/// Convert CSS HSL to RGB
+fn hsl_to_rgb(hue: f32, sat: f32, lum: f32) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lum - 1.0).abs()) * sat;
+    let f = |n: f32| {
+        let k = (n + hue * 12.0) % 12.0;
+        let v = lum - chroma / 2.0 * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        v.clamp(0.0, 1.0)
+    };
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+impl<C, A> FromStr for Pix3<C, Rgb, A, Srgb>
+where
+    C: Channel,
+    A: Alpha,
+{
+    type Err = ParseColorError;
+
+    /// Parse a CSS color, such as `#ff8000`, `rgb(255 128 0)` or
+    /// `hsl(30deg, 100%, 50%)`.
+    ///
+    /// Any *alpha* component is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [r, g, b, _a] = parse_css(s)?;
+        Ok(Self::new::<f32>(r, g, b))
+    }
+}
+
+impl<C, A> FromStr for Pix4<C, Rgb, A, Srgb>
+where
+    C: Channel,
+    A: Alpha,
+{
+    type Err = ParseColorError;
+
+    /// Parse a CSS color, such as `#ff800080`, `rgba(255, 128, 0, 0.5)` or
+    /// `hsl(30deg 100% 50% / 50%)`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [r, g, b, a] = parse_css(s)?;
+        Ok(Pix4::<C, Rgb, Straight, Srgb>::new::<f32>(r, g, b, a).convert())
+    }
+}
+
+/// Write channels as hex digits, with a `#` prefix
+fn write_hex<C: IntChannel>(
+    f: &mut fmt::Formatter,
+    channels: &[C],
+    upper: bool,
+) -> fmt::Result {
+    let width = std::mem::size_of::<C>() * 2;
+    let max = ((1u32 << (width * 4)) - 1) as f32;
+    write!(f, "#")?;
+    for c in channels {
+        let v = (c.to_f32() * max).round() as u32;
+        if upper {
+            write!(f, "{v:0width$X}")?;
+        } else {
+            write!(f, "{v:0width$x}")?;
+        }
+    }
+    Ok(())
+}
+
+impl<C, A, G> fmt::LowerHex for Pix3<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.channels(), false)
+    }
+}
+
+impl<C, A, G> fmt::UpperHex for Pix3<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.channels(), true)
+    }
+}
+
+impl<C, A, G> fmt::LowerHex for Pix4<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.channels(), false)
+    }
+}
+
+impl<C, A, G> fmt::UpperHex for Pix4<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.channels(), true)
+    }
+}
+
+impl<C, A, G> Pix3<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Format as a hex string, such as `#ff8000`.
+    ///
+    /// 16-bit channels are written with four digits each.
+    pub fn to_hex_string(&self) -> String {
+        format!("{self:x}")
+    }
+}
+
+impl<C, A, G> Pix4<C, Rgb, A, G>
+where
+    C: IntChannel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Format as a hex string, such as `#ff800080`.
+    ///
+    /// 16-bit channels are written with four digits each.  Channels are
+    /// written as stored, so *premultiplied* colors are not converted.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::rgb::{Rgba16, SRgba8};
+    ///
+    /// let clr = SRgba8::new(0xFF, 0x80, 0x00, 0x40);
+    /// assert_eq!(clr.to_hex_string(), "#ff800040");
+    /// assert_eq!(format!("{clr:X}"), "#FF800040");
+    /// let clr = Rgba16::new(0x1234, 0, 0xFFFF, 0xABCD);
+    /// assert_eq!(clr.to_hex_string(), "#12340000ffffabcd");
+    /// ```
+    pub fn to_hex_string(&self) -> String {
+        format!("{self:x}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::*;
+
+    #[test]
+    fn hex() {
+        assert_eq!("#f80".parse(), Ok(SRgb8::new(0xFF, 0x88, 0)));
+        assert_eq!("#F808".parse(), Ok(SRgba8::new(0xFF, 0x88, 0, 0x88)));
+        assert_eq!(
+            "#12345678".parse(),
+            Ok(SRgba8::new(0x12, 0x34, 0x56, 0x78))
+        );
+        assert_eq!(
+            " #123456 ".parse(),
+            Ok(SRgba8::new(0x12, 0x34, 0x56, 0xFF))
+        );
+        assert_eq!("#12345".parse::<SRgb8>(), Err(ParseColorError::InvalidHex));
+        assert_eq!("#12g".parse::<SRgb8>(), Err(ParseColorError::InvalidHex));
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!("rgb(255, 128, 0)".parse(), Ok(SRgb8::new(255, 128, 0)));
+        assert_eq!(
+            "RGBA(100% 50% 0% / 0.5)".parse(),
+            Ok(SRgba8::new(255, 128, 0, 128))
+        );
+        assert_eq!("hsl(120, 100%, 50%)".parse(), Ok(SRgb8::new(0, 255, 0)));
+        assert_eq!(
+            "hsla(0.5turn 100% 25% / 100%)".parse(),
+            Ok(SRgba8::new(0, 128, 128, 255))
+        );
+        assert_eq!(
+            "rgba(255, 0, 0, 0.5)".parse(),
+            Ok(SRgba8::new(255, 0, 0, 128).convert::<SRgba8p>())
+        );
+        assert_eq!("hsl(-120deg 0% 100%)".parse(), Ok(SRgb16::WHITE));
+        assert_eq!(" Teal ".parse(), Ok(SRgb8::new(0, 0x80, 0x80)));
+        assert_eq!("red".parse(), Ok(SRgba8::new(0xFF, 0, 0, 0xFF)));
+    }
+
+    #[test]
+    fn errors() {
+        use ParseColorError::*;
+        assert_eq!("reddish".parse::<SRgb8>(), Err(UnknownFormat));
+        assert_eq!("cmy(1, 2, 3)".parse::<SRgb8>(), Err(UnknownFormat));
+        assert_eq!("rgb(1, 2, 3".parse::<SRgb8>(), Err(MissingParen));
+        assert_eq!("rgb(1, 2)".parse::<SRgb8>(), Err(WrongCount(2)));
+        assert_eq!("rgb(1, x, 3)".parse::<SRgb8>(), Err(InvalidComponent(1)));
+        assert_eq!(
+            "hsl(1, 2%, 3%, y)".parse::<SRgb8>(),
+            Err(InvalidComponent(3))
+        );
+    }
+
+    #[test]
+    fn hex_format() {
+        assert_eq!(SRgb8::new(1, 0x23, 0xFF).to_hex_string(), "#0123ff");
+        assert_eq!(format!("{:X}", Rgb8::new(0xAB, 0xCD, 0xEF)), "#ABCDEF");
+        assert_eq!(format!("{:x}", Rgb16::new(1, 2, 3)), "#000100020003");
+        let clr = SRgba8::new(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(clr.to_hex_string().parse(), Ok(clr));
+    }
+}
@@ -0,0 +1,215 @@
+// planar.rs    Planar raster images.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::Channel;
+use crate::el::{channel_count, Pixel};
+use crate::raster::Raster;
+
+/// Image with each channel stored in a separate contiguous plane.
+///
+/// Planes are ordered the same as the pixel's channels, and each plane has
+/// one `Channel` value per pixel, arranged the same way as a [Raster].  This
+/// layout is used by many video codecs and SIMD filters.
+///
+/// ### Process the alpha plane of an image
+/// ```
+/// use pix::chan::Ch8;
+/// use pix::rgb::Rgba8;
+/// use pix::{PlanarRaster, Raster};
+///
+/// let r = Raster::with_color(4, 4, Rgba8::new(0x10, 0x20, 0x30, 0x40));
+/// let mut p = PlanarRaster::with_raster(&r);
+/// p.plane_mut(3).fill(Ch8::new(0xFF));
+/// let r = Raster::with_planar(&p);
+/// assert_eq!(r.pixel(2, 2), Rgba8::new(0x10, 0x20, 0x30, 0xFF));
+/// ```
+///
+/// [raster]: struct.Raster.html
+#[derive(Clone)]
+pub struct PlanarRaster<P: Pixel> {
+    /// Width of raster
+    width: u32,
+    /// Height of raster
+    height: u32,
+    /// Channel values of all planes, in order
+    planes: Box<[P::Chan]>,
+}
+
+/// Get the number of pixels in one plane
+fn plane_len(width: u32, height: u32) -> usize {
+    i32::try_from(width).expect("Raster width too big");
+    i32::try_from(height).expect("Raster height too big");
+    (width as usize)
+        .checked_mul(height as usize)
+        .expect("Raster too big")
+}
+
+impl<P: Pixel> PlanarRaster<P> {
+    /// Construct a `PlanarRaster` with all channels set to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is greater than `std::i32::MAX`.
+    pub fn with_clear(width: u32, height: u32) -> Self {
+        let len = plane_len(width, height) * channel_count::<P>();
+        let planes = vec![P::Chan::MIN; len].into_boxed_slice();
+        PlanarRaster {
+            width,
+            height,
+            planes,
+        }
+    }
+
+    /// Construct a `PlanarRaster` by splitting the channels of a [Raster].
+    ///
+    /// [raster]: struct.Raster.html
+    pub fn with_raster(src: &Raster<P>) -> Self {
+        let mut planar = Self::with_clear(src.width(), src.height());
+        for i in 0..planar.plane_count() {
+            let plane = planar.plane_mut(i);
+            for (d, p) in plane.iter_mut().zip(src.pixels()) {
+                *d = p.channels()[i];
+            }
+        }
+        planar
+    }
+
+    /// Construct a `PlanarRaster` from channel values.
+    ///
+    /// * `B` Owned channel type (`Vec` or boxed slice).
+    /// * `width` Width of `PlanarRaster`.
+    /// * `height` Height of `PlanarRaster`.
+    /// * `planes` Channel values of each plane, in order.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `planes` length is not equal to `width` * `height` * channel
+    ///   count
+    pub fn with_planes<B>(width: u32, height: u32, planes: B) -> Self
+    where
+        B: Into<Box<[P::Chan]>>,
+    {
+        let len = plane_len(width, height) * channel_count::<P>();
+        let planes = planes.into();
+        assert_eq!(len, planes.len());
+        PlanarRaster {
+            width,
+            height,
+            planes,
+        }
+    }
+
+    /// Get width of `PlanarRaster`.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get height of `PlanarRaster`.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the number of planes (one per channel).
+    pub fn plane_count(&self) -> usize {
+        channel_count::<P>()
+    }
+
+    /// Get one plane.
+    ///
+    /// * `i` Index of plane (same as channel index).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `plane_count`.
+    pub fn plane(&self, i: usize) -> &[P::Chan] {
+        assert!(i < self.plane_count());
+        let len = self.width as usize * self.height as usize;
+        &self.planes[i * len..(i + 1) * len]
+    }
+
+    /// Get one plane mutably.
+    ///
+    /// * `i` Index of plane (same as channel index).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `plane_count`.
+    pub fn plane_mut(&mut self, i: usize) -> &mut [P::Chan] {
+        assert!(i < self.plane_count());
+        let len = self.width as usize * self.height as usize;
+        &mut self.planes[i * len..(i + 1) * len]
+    }
+
+    /// Get channel values of all planes, in order.
+    pub fn planes(&self) -> &[P::Chan] {
+        &self.planes
+    }
+
+    /// Get channel values of all planes mutably, in order.
+    pub fn planes_mut(&mut self) -> &mut [P::Chan] {
+        &mut self.planes
+    }
+}
+
+impl<P: Pixel> From<PlanarRaster<P>> for Box<[P::Chan]> {
+    /// Get channel values of all planes as a boxed slice.
+    fn from(planar: PlanarRaster<P>) -> Self {
+        planar.planes
+    }
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a `Raster` by interleaving the planes of a [PlanarRaster].
+    ///
+    /// [planarraster]: struct.PlanarRaster.html
+    pub fn with_planar(src: &PlanarRaster<P>) -> Self {
+        let mut r = Raster::<P>::with_clear(src.width(), src.height());
+        for i in 0..src.plane_count() {
+            let plane = src.plane(i);
+            for (p, s) in r.pixels_mut().iter_mut().zip(plane) {
+                p.channels_mut()[i] = *s;
+            }
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chan::*;
+    use crate::gray::*;
+    use crate::rgb::*;
+    use crate::*;
+
+    #[test]
+    fn round_trip() {
+        let p = vec![
+            SRgb16::new(1, 2, 3),
+            SRgb16::new(4, 5, 6),
+            SRgb16::new(7, 8, 9),
+            SRgb16::new(10, 11, 12),
+            SRgb16::new(13, 14, 15),
+            SRgb16::new(16, 17, 18),
+        ];
+        let r = Raster::with_pixels(3, 2, p.clone());
+        let planar = PlanarRaster::with_raster(&r);
+        assert_eq!(planar.plane_count(), 3);
+        let ch: Vec<u16> =
+            planar.plane(1).iter().map(|c| u16::from(*c)).collect();
+        assert_eq!(ch, [2, 5, 8, 11, 14, 17]);
+        let r = Raster::with_planar(&planar);
+        assert_eq!(r.pixels(), &p[..]);
+    }
+
+    #[test]
+    fn with_planes() {
+        let planes = vec![Ch8::new(1), Ch8::new(2), Ch8::new(3), Ch8::new(4)];
+        let planar = PlanarRaster::<SGraya8>::with_planes(2, 1, planes);
+        let r = Raster::with_planar(&planar);
+        assert_eq!(r.pixels(), &[SGraya8::new(1, 3), SGraya8::new(2, 4)]);
+        let planes: Box<[Ch8]> = planar.into();
+        assert_eq!(planes.len(), 4);
+    }
+}
@@ -0,0 +1,285 @@
+// pnm.rs       Netpbm (PGM / PPM / PAM) codec.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::codec::{pixel_count, CodecError};
+use crate::el::Pixel;
+use crate::gray::{SGray8, SGraya8};
+use crate::raster::Raster;
+use crate::rgb::{SRgb8, SRgba8};
+
+/// Netpbm header fields
+struct Header {
+    width: u32,
+    height: u32,
+    depth: usize,
+    maxval: u32,
+}
+
+/// Reader for Netpbm headers
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Create a reader, checking the magic number
+    fn new(bytes: &'a [u8], magic: &[u8]) -> Result<Self, CodecError> {
+        if bytes.starts_with(magic) {
+            Ok(Reader {
+                bytes,
+                pos: magic.len(),
+            })
+        } else {
+            Err(CodecError::InvalidHeader)
+        }
+    }
+
+    /// Read a whitespace-separated token, skipping comments
+    fn token(&mut self) -> Result<&'a str, CodecError> {
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(b'#') => {
+                    while !matches!(
+                        self.bytes.get(self.pos),
+                        Some(b'\n') | None
+                    ) {
+                        self.pos += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => self.pos += 1,
+                Some(_) => break,
+                None => return Err(CodecError::InvalidHeader),
+            }
+        }
+        let start = self.pos;
+        while matches!(self.bytes.get(self.pos), Some(b) if !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .map_err(|_| CodecError::InvalidHeader)
+    }
+
+    /// Read a decimal number
+    fn number(&mut self) -> Result<u32, CodecError> {
+        self.token()?.parse().map_err(|_| CodecError::InvalidHeader)
+    }
+
+    /// Read a PGM / PPM header
+    fn header(&mut self, depth: usize) -> Result<Header, CodecError> {
+        let width = self.number()?;
+        let height = self.number()?;
+        let maxval = self.number()?;
+        Ok(Header {
+            width,
+            height,
+            depth,
+            maxval,
+        })
+    }
+
+    /// Read a PAM header
+    fn pam_header(&mut self) -> Result<Header, CodecError> {
+        let (mut width, mut height, mut depth, mut maxval) =
+            (None, None, None, None);
+        loop {
+            match self.token()? {
+                "WIDTH" => width = Some(self.number()?),
+                "HEIGHT" => height = Some(self.number()?),
+                "DEPTH" => depth = Some(self.number()? as usize),
+                "MAXVAL" => maxval = Some(self.number()?),
+                "TUPLTYPE" => {
+                    self.token()?;
+                }
+                "ENDHDR" => break,
+                _ => return Err(CodecError::InvalidHeader),
+            }
+        }
+        Ok(Header {
+            width: width.ok_or(CodecError::InvalidHeader)?,
+            height: height.ok_or(CodecError::InvalidHeader)?,
+            depth: depth.ok_or(CodecError::InvalidHeader)?,
+            maxval: maxval.ok_or(CodecError::InvalidHeader)?,
+        })
+    }
+
+    /// Read samples, scaled to 8 bits
+    fn samples(mut self, hdr: &Header) -> Result<Vec<u8>, CodecError> {
+        if hdr.maxval == 0 || hdr.maxval > 0xFFFF {
+            return Err(CodecError::InvalidHeader);
+        }
+        // exactly one whitespace byte follows the header
+        self.pos += 1;
+        let count = pixel_count(hdr.width, hdr.height)?
+            .checked_mul(hdr.depth)
+            .ok_or(CodecError::TooBig)?;
+        let size = if hdr.maxval > 0xFF { 2 } else { 1 };
+        let data = self
+            .bytes
+            .get(self.pos..)
+            .and_then(|d| d.get(..count.checked_mul(size)?))
+            .ok_or(CodecError::Truncated)?;
+        let maxval = hdr.maxval;
+        Ok(data
+            .chunks_exact(size)
+            .map(|s| {
+                let v = s.iter().fold(0, |v, b| (v << 8) | u32::from(*b));
+                ((v.min(maxval) * 255 + maxval / 2) / maxval) as u8
+            })
+            .collect())
+    }
+}
+
+/// Make a raster from 8-bit samples
+fn raster<P: Pixel>(hdr: &Header, samples: Vec<u8>) -> Raster<P> {
+    Raster::from_u8_buffer(hdr.width, hdr.height, samples)
+        .expect("invalid buffer length")
+}
+
+/// Encode a PGM / PPM file
+fn encode<P: Pixel>(magic: &str, raster: &Raster<P>) -> Vec<u8> {
+    let mut bytes =
+        format!("{magic}\n{} {}\n255\n", raster.width(), raster.height())
+            .into_bytes();
+    bytes.extend_from_slice(raster.as_u8_slice());
+    bytes
+}
+
+impl Raster<SGray8> {
+    /// Decode a binary PGM (`P5`) file.
+    ///
+    /// * `pgm` File contents.
+    ///
+    /// Samples with `MAXVAL` other than 255 are scaled to 8 bits.
+    pub fn from_pgm(pgm: &[u8]) -> Result<Self, CodecError> {
+        let mut reader = Reader::new(pgm, b"P5")?;
+        let hdr = reader.header(1)?;
+        let samples = reader.samples(&hdr)?;
+        Ok(raster(&hdr, samples))
+    }
+
+    /// Encode as a binary PGM (`P5`) file.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        encode("P5", self)
+    }
+}
+
+impl Raster<SRgb8> {
+    /// Decode a binary PPM (`P6`) file.
+    ///
+    /// * `ppm` File contents.
+    ///
+    /// Samples with `MAXVAL` other than 255 are scaled to 8 bits.
+    ///
+    /// ### Round trip
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(4, 3, SRgb8::new(0x80, 0x40, 0x20));
+    /// let ppm = r.to_ppm();
+    /// assert!(ppm.starts_with(b"P6\n4 3\n255\n"));
+    /// let r2 = Raster::<SRgb8>::from_ppm(&ppm).unwrap();
+    /// assert_eq!(r2.pixels(), r.pixels());
+    /// ```
+    pub fn from_ppm(ppm: &[u8]) -> Result<Self, CodecError> {
+        let mut reader = Reader::new(ppm, b"P6")?;
+        let hdr = reader.header(3)?;
+        let samples = reader.samples(&hdr)?;
+        Ok(raster(&hdr, samples))
+    }
+
+    /// Encode as a binary PPM (`P6`) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        encode("P6", self)
+    }
+}
+
+impl Raster<SRgba8> {
+    /// Decode a PAM (`P7`) file.
+    ///
+    /// * `pam` File contents.
+    ///
+    /// Images with a `DEPTH` of 1 (gray), 2 (gray with alpha), 3 (RGB) or 4
+    /// (RGB with alpha) are supported.  Samples with `MAXVAL` other than 255
+    /// are scaled to 8 bits.
+    pub fn from_pam(pam: &[u8]) -> Result<Self, CodecError> {
+        let mut reader = Reader::new(pam, b"P7")?;
+        let hdr = reader.pam_header()?;
+        if !(1..=4).contains(&hdr.depth) {
+            return Err(CodecError::Unsupported);
+        }
+        let samples = reader.samples(&hdr)?;
+        Ok(match hdr.depth {
+            1 => Raster::with_raster(&raster::<SGray8>(&hdr, samples)),
+            2 => Raster::with_raster(&raster::<SGraya8>(&hdr, samples)),
+            3 => Raster::with_raster(&raster::<SRgb8>(&hdr, samples)),
+            _ => raster(&hdr, samples),
+        })
+    }
+
+    /// Encode as a PAM (`P7`) file, with `RGB_ALPHA` tuple type.
+    pub fn to_pam(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\n\
+             TUPLTYPE RGB_ALPHA\nENDHDR\n",
+            self.width(),
+            self.height()
+        )
+        .into_bytes();
+        bytes.extend_from_slice(self.as_u8_slice());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pgm() {
+        let pgm = b"P5 # comment\n2 2\n# max\n15\n\x00\x05\x0A\x0F";
+        let r = Raster::<SGray8>::from_pgm(pgm).unwrap();
+        assert_eq!(r.pixel(1, 1), SGray8::new(0xFF));
+        assert_eq!(r.pixel(1, 0), SGray8::new(0x55));
+        let r2 = Raster::<SGray8>::from_pgm(&r.to_pgm()).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+        let pgm = b"P5\n1 1\n65535\n\x80\x00";
+        let r = Raster::<SGray8>::from_pgm(pgm).unwrap();
+        assert_eq!(r.pixel(0, 0), SGray8::new(0x80));
+    }
+
+    #[test]
+    fn pam() {
+        let pam = b"P7\nWIDTH 2\nHEIGHT 1\nDEPTH 2\nMAXVAL 255\n\
+            TUPLTYPE GRAYSCALE_ALPHA\nENDHDR\n\x10\x20\x30\x40";
+        let r = Raster::<SRgba8>::from_pam(pam).unwrap();
+        assert_eq!(r.pixel(1, 0), SRgba8::new(0x30, 0x30, 0x30, 0x40));
+        let r2 = Raster::<SRgba8>::from_pam(&r.to_pam()).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+    }
+
+    #[test]
+    fn errors() {
+        use CodecError::*;
+        assert_eq!(
+            Raster::<SRgb8>::from_ppm(b"P5\n1 1\n255\n\0").err(),
+            Some(InvalidHeader)
+        );
+        assert_eq!(
+            Raster::<SRgb8>::from_ppm(b"P6\n1 1\n").err(),
+            Some(InvalidHeader)
+        );
+        assert_eq!(
+            Raster::<SRgb8>::from_ppm(b"P6\n2 1\n255\n\0\0\0").err(),
+            Some(Truncated)
+        );
+        assert_eq!(
+            Raster::<SRgb8>::from_ppm(b"P6\n99999 99999\n255\n").err(),
+            Some(TooBig)
+        );
+        let pam = b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 5\nMAXVAL 255\nENDHDR\n";
+        assert_eq!(Raster::<SRgba8>::from_pam(pam).err(), Some(Unsupported));
+    }
+}
@@ -1,4 +1,4 @@
-// private.rs     Private sealed trait
+// private.rs     Private sealed and helper traits
 //
 // Copyright (c) 2020  Douglas P Lau
 //
@@ -16,7 +16,7 @@ use crate::chan::{
     Alpha, Ch16, Ch32, Ch8, Channel, Gamma, Linear, Premultiplied, Srgb,
     Straight,
 };
-use crate::el::{Pix1, Pix2, Pix3, Pix4};
+use crate::el::Pix;
 use crate::ColorModel;
 use std::any::Any;
 
@@ -37,7 +37,7 @@ impl Sealed for Linear {}
 
 impl Sealed for Srgb {}
 
-impl<C, M, A, G> Sealed for Pix1<C, M, A, G>
+impl<C, M, A, G, const N: usize> Sealed for Pix<C, M, A, G, N>
 where
     C: Channel,
     M: ColorModel,
@@ -46,29 +46,30 @@ where
 {
 }
 
-impl<C, M, A, G> Sealed for Pix2<C, M, A, G>
-where
-    C: Channel,
-    M: ColorModel,
-    A: Alpha,
-    G: Gamma,
-{
-}
+/// `Send` when the `rayon` feature is enabled, otherwise any type
+#[cfg(feature = "rayon")]
+pub trait MaybeSend: Send {}
 
-impl<C, M, A, G> Sealed for Pix3<C, M, A, G>
-where
-    C: Channel,
-    M: ColorModel,
-    A: Alpha,
-    G: Gamma,
-{
-}
+#[cfg(feature = "rayon")]
+impl<T: Send> MaybeSend for T {}
 
-impl<C, M, A, G> Sealed for Pix4<C, M, A, G>
-where
-    C: Channel,
-    M: ColorModel,
-    A: Alpha,
-    G: Gamma,
-{
-}
+/// `Send` when the `rayon` feature is enabled, otherwise any type
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSend {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSend for T {}
+
+/// `Sync` when the `rayon` feature is enabled, otherwise any type
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Sync> MaybeSync for T {}
+
+/// `Sync` when the `rayon` feature is enabled, otherwise any type
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSync for T {}
@@ -0,0 +1,318 @@
+// qoi.rs       QOI (Quite OK Image) codec.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::chan::Ch8;
+use crate::codec::{pixel_count, CodecError};
+use crate::el::Pixel;
+use crate::raster::Raster;
+use crate::rgb::{SRgb8, SRgba8};
+
+/// QOI magic value
+const MAGIC: &[u8] = b"qoif";
+
+/// Length of QOI header
+const HEADER_LEN: usize = 14;
+
+/// End of stream marker
+const END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+/// Op code: index into previously seen pixels
+const OP_INDEX: u8 = 0b0000_0000;
+
+/// Op code: small difference from previous pixel
+const OP_DIFF: u8 = 0b0100_0000;
+
+/// Op code: difference from previous pixel, relative to green
+const OP_LUMA: u8 = 0b1000_0000;
+
+/// Op code: run of previous pixel
+const OP_RUN: u8 = 0b1100_0000;
+
+/// Op code: literal RGB values
+const OP_RGB: u8 = 0xFE;
+
+/// Op code: literal RGBA values
+const OP_RGBA: u8 = 0xFF;
+
+/// Mask for 2-bit op codes
+const MASK: u8 = 0b1100_0000;
+
+/// Maximum run length
+const MAX_RUN: u8 = 62;
+
+/// Hash a pixel into the index table
+fn hash([r, g, b, a]: [u8; 4]) -> usize {
+    let h = r
+        .wrapping_mul(3)
+        .wrapping_add(g.wrapping_mul(5))
+        .wrapping_add(b.wrapping_mul(7))
+        .wrapping_add(a.wrapping_mul(11));
+    usize::from(h % 64)
+}
+
+/// Encode RGBA pixels
+fn encode(
+    width: u32,
+    height: u32,
+    channels: u8,
+    pixels: impl Iterator<Item = [u8; 4]>,
+) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + END.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&width.to_be_bytes());
+    bytes.extend_from_slice(&height.to_be_bytes());
+    // colorspace 0: sRGB with linear alpha
+    bytes.extend_from_slice(&[channels, 0]);
+    let mut index = [[0; 4]; 64];
+    let mut prev = [0, 0, 0, 255];
+    let mut run = 0;
+    for px in pixels {
+        if px == prev {
+            run += 1;
+            if run == MAX_RUN {
+                bytes.push(OP_RUN | (run - 1));
+                run = 0;
+            }
+            continue;
+        }
+        if run > 0 {
+            bytes.push(OP_RUN | (run - 1));
+            run = 0;
+        }
+        let h = hash(px);
+        if index[h] == px {
+            bytes.push(OP_INDEX | h as u8);
+        } else if px[3] == prev[3] {
+            let dr = px[0].wrapping_sub(prev[0]) as i8;
+            let dg = px[1].wrapping_sub(prev[1]) as i8;
+            let db = px[2].wrapping_sub(prev[2]) as i8;
+            let dr_dg = dr.wrapping_sub(dg);
+            let db_dg = db.wrapping_sub(dg);
+            if (-2..2).contains(&dr)
+                && (-2..2).contains(&dg)
+                && (-2..2).contains(&db)
+            {
+                let d = ((dr + 2) << 4) | ((dg + 2) << 2) | (db + 2);
+                bytes.push(OP_DIFF | d as u8);
+            } else if (-32..32).contains(&dg)
+                && (-8..8).contains(&dr_dg)
+                && (-8..8).contains(&db_dg)
+            {
+                bytes.push(OP_LUMA | (dg + 32) as u8);
+                bytes.push((((dr_dg + 8) << 4) | (db_dg + 8)) as u8);
+            } else {
+                bytes.extend_from_slice(&[OP_RGB, px[0], px[1], px[2]]);
+            }
+        } else {
+            bytes.push(OP_RGBA);
+            bytes.extend_from_slice(&px);
+        }
+        index[h] = px;
+        prev = px;
+    }
+    if run > 0 {
+        bytes.push(OP_RUN | (run - 1));
+    }
+    bytes.extend_from_slice(&END);
+    bytes
+}
+
+/// Decode into RGBA pixels
+fn decode(qoi: &[u8]) -> Result<Raster<SRgba8>, CodecError> {
+    if qoi.len() < HEADER_LEN || !qoi.starts_with(MAGIC) {
+        return Err(CodecError::InvalidHeader);
+    }
+    let width = u32::from_be_bytes([qoi[4], qoi[5], qoi[6], qoi[7]]);
+    let height = u32::from_be_bytes([qoi[8], qoi[9], qoi[10], qoi[11]]);
+    if !matches!(qoi[12], 3 | 4) || qoi[13] > 1 {
+        return Err(CodecError::InvalidHeader);
+    }
+    let count = pixel_count(width, height)?;
+    let mut data = qoi[HEADER_LEN..].iter().copied();
+    // each op code decodes at most one run
+    let cap = count.min(data.len().saturating_mul(usize::from(MAX_RUN)));
+    let mut pixels = Vec::with_capacity(cap);
+    let mut index = [[0; 4]; 64];
+    let mut px = [0, 0, 0, 255];
+    while pixels.len() < count {
+        let op = data.next().ok_or(CodecError::Truncated)?;
+        let mut next = || data.next().ok_or(CodecError::Truncated);
+        match op {
+            OP_RGB => {
+                px = [next()?, next()?, next()?, px[3]];
+            }
+            OP_RGBA => {
+                px = [next()?, next()?, next()?, next()?];
+            }
+            _ => match op & MASK {
+                OP_INDEX => px = index[usize::from(op)],
+                OP_DIFF => {
+                    px[0] = px[0].wrapping_add((op >> 4) & 3).wrapping_sub(2);
+                    px[1] = px[1].wrapping_add((op >> 2) & 3).wrapping_sub(2);
+                    px[2] = px[2].wrapping_add(op & 3).wrapping_sub(2);
+                }
+                OP_LUMA => {
+                    let b = next()?;
+                    let dg = (op & 0x3F).wrapping_sub(32);
+                    px[0] = px[0]
+                        .wrapping_add(dg)
+                        .wrapping_add(b >> 4)
+                        .wrapping_sub(8);
+                    px[1] = px[1].wrapping_add(dg);
+                    px[2] = px[2]
+                        .wrapping_add(dg)
+                        .wrapping_add(b & 0xF)
+                        .wrapping_sub(8);
+                }
+                _ => {
+                    let run = usize::from(op & 0x3F) + 1;
+                    let n = run.min(count - pixels.len());
+                    index[hash(px)] = px;
+                    pixels.extend(std::iter::repeat_n(SRgba8::from(px), n));
+                    continue;
+                }
+            },
+        }
+        index[hash(px)] = px;
+        pixels.push(SRgba8::from(px));
+    }
+    Ok(Raster::with_pixels(width, height, pixels))
+}
+
+/// Get RGBA values of a pixel
+fn rgba<P: Pixel<Chan = Ch8>>(p: &P) -> [u8; 4] {
+    let ch = p.channels();
+    let a = ch.get(3).map_or(255, |a| u8::from(*a));
+    [u8::from(ch[0]), u8::from(ch[1]), u8::from(ch[2]), a]
+}
+
+impl Raster<SRgb8> {
+    /// Decode a [QOI] file.
+    ///
+    /// * `qoi` File contents.
+    ///
+    /// If the file has an alpha channel, it is discarded.
+    ///
+    /// [QOI]: https://qoiformat.org/
+    pub fn from_qoi(qoi: &[u8]) -> Result<Self, CodecError> {
+        Ok(Raster::with_raster(&decode(qoi)?))
+    }
+
+    /// Encode as a [QOI] file, with 3 channels.
+    ///
+    /// [QOI]: https://qoiformat.org/
+    pub fn to_qoi(&self) -> Vec<u8> {
+        let pixels = self.pixels().iter().map(rgba);
+        encode(self.width(), self.height(), 3, pixels)
+    }
+}
+
+impl Raster<SRgba8> {
+    /// Decode a [QOI] file.
+    ///
+    /// * `qoi` File contents.
+    ///
+    /// ### Round trip
+    /// ```
+    /// use pix::rgb::SRgba8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_clear(16, 16);
+    /// *r.pixel_mut(3, 4) = SRgba8::new(0x20, 0x40, 0x60, 0x80);
+    /// let qoi = r.to_qoi();
+    /// let r2 = Raster::<SRgba8>::from_qoi(&qoi).unwrap();
+    /// assert_eq!(r2.pixels(), r.pixels());
+    /// ```
+    ///
+    /// [QOI]: https://qoiformat.org/
+    pub fn from_qoi(qoi: &[u8]) -> Result<Self, CodecError> {
+        decode(qoi)
+    }
+
+    /// Encode as a [QOI] file, with 4 channels.
+    ///
+    /// [QOI]: https://qoiformat.org/
+    pub fn to_qoi(&self) -> Vec<u8> {
+        let pixels = self.pixels().iter().map(rgba);
+        encode(self.width(), self.height(), 4, pixels)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ops() {
+        let mut r = Raster::<SRgba8>::with_clear(71, 1);
+        let pixels = [
+            [0, 0, 0, 255],
+            [1, 255, 0, 255],
+            [9, 11, 7, 255],
+            [200, 100, 50, 255],
+            [200, 100, 50, 10],
+            [0, 0, 0, 255],
+            [200, 100, 50, 255],
+        ];
+        for (x, p) in pixels.iter().enumerate() {
+            *r.pixel_mut(x as i32, 0) = SRgba8::from(*p);
+        }
+        let qoi = r.to_qoi();
+        assert_eq!(
+            qoi[HEADER_LEN..qoi.len() - END.len()],
+            [
+                OP_RUN,
+                OP_DIFF | 0b11_01_10,
+                OP_LUMA | 44,
+                0x43,
+                OP_RGB,
+                200,
+                100,
+                50,
+                OP_RGBA,
+                200,
+                100,
+                50,
+                10,
+                OP_RGBA,
+                0,
+                0,
+                0,
+                255,
+                OP_INDEX | 31,
+                OP_INDEX,
+                OP_RUN | 61,
+                OP_RUN,
+            ]
+        );
+        let r2 = Raster::<SRgba8>::from_qoi(&qoi).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+    }
+
+    #[test]
+    fn rgb() {
+        let mut r = Raster::<SRgb8>::with_color(5, 3, SRgb8::new(9, 8, 7));
+        *r.pixel_mut(4, 2) = SRgb8::new(255, 0, 128);
+        let qoi = r.to_qoi();
+        assert_eq!(qoi[12], 3);
+        let r2 = Raster::<SRgb8>::from_qoi(&qoi).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+    }
+
+    #[test]
+    fn errors() {
+        use CodecError::*;
+        let qoi = Raster::<SRgb8>::with_clear(4, 4).to_qoi();
+        let r = Raster::<SRgb8>::from_qoi(&qoi[..10]);
+        assert_eq!(r.err(), Some(InvalidHeader));
+        let r = Raster::<SRgb8>::from_qoi(&qoi[..HEADER_LEN]);
+        assert_eq!(r.err(), Some(Truncated));
+        let mut bad = qoi.clone();
+        bad[12] = 5;
+        assert_eq!(Raster::<SRgb8>::from_qoi(&bad).err(), Some(InvalidHeader));
+        bad[12] = 3;
+        bad[4] = 0xFF;
+        assert_eq!(Raster::<SRgb8>::from_qoi(&bad).err(), Some(TooBig));
+    }
+}
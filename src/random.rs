@@ -0,0 +1,220 @@
+// random.rs    Random pixel sampling.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Random sampling of pixels, with [rand].
+//!
+//! The `StandardUniform` distribution samples each channel uniformly,
+//! including *alpha*.  [Opaque] samples color channels uniformly, with
+//! maximum *alpha*.
+//!
+//! *Premultiplied* pixels are sampled as *straight*, then converted, so
+//! they are always valid.
+//!
+//! ### Noise texture
+//! ```
+//! use pix::el::Pixel;
+//! use pix::random::Opaque;
+//! use pix::rgb::SRgba8;
+//! use pix::Raster;
+//! use rand::rngs::SmallRng;
+//! use rand::{Rng, SeedableRng};
+//!
+//! let mut rng = SmallRng::seed_from_u64(1);
+//! let mut r = Raster::<SRgba8>::with_clear(16, 16);
+//! for p in r.pixels_mut() {
+//!     *p = rng.sample(Opaque);
+//! }
+//! assert!(r.pixels().iter().all(|p| p.alpha() == 255.into()));
+//! ```
+//!
+//! [Opaque]: struct.Opaque.html
+//! [rand]: https://docs.rs/rand
+use crate::chan::{Alpha, Ch16, Ch32, Ch8, Channel, Gamma, Straight};
+use crate::el::{Pix1, Pix2, Pix3, Pix4, Pixel};
+use crate::ColorModel;
+use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
+
+/// Distribution of pixels with uniform color channels and opaque *alpha*.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Opaque;
+
+impl Distribution<Ch8> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ch8 {
+        Ch8::new(rng.random())
+    }
+}
+
+impl Distribution<Ch16> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ch16 {
+        Ch16::new(rng.random())
+    }
+}
+
+impl Distribution<Ch32> for StandardUniform {
+    /// Sample a value between `0.0` and `1.0` (inclusive).
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ch32 {
+        Ch32::new(rng.random_range(0.0..=1.0))
+    }
+}
+
+/// Sample a pixel as *straight* alpha, then convert
+fn sample_pixel<P, S, R>(rng: &mut R, opaque: bool) -> P
+where
+    P: Pixel,
+    S: Pixel<Chan = P::Chan, Model = P::Model, Gamma = P::Gamma>,
+    StandardUniform: Distribution<P::Chan>,
+    R: Rng + ?Sized,
+{
+    let mut p = S::default();
+    for c in p.channels_mut() {
+        *c = rng.sample(StandardUniform);
+    }
+    if opaque {
+        if let Some(a) = p.channels_mut().get_mut(P::Model::ALPHA) {
+            *a = P::Chan::MAX;
+        }
+    }
+    p.convert()
+}
+
+impl<C, M, A, G> Distribution<Pix1<C, M, A, G>> for StandardUniform
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix1<C, M, A, G> {
+        sample_pixel::<_, Pix1<C, M, Straight, G>, _>(rng, false)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix2<C, M, A, G>> for StandardUniform
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix2<C, M, A, G> {
+        sample_pixel::<_, Pix2<C, M, Straight, G>, _>(rng, false)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix3<C, M, A, G>> for StandardUniform
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix3<C, M, A, G> {
+        sample_pixel::<_, Pix3<C, M, Straight, G>, _>(rng, false)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix4<C, M, A, G>> for StandardUniform
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix4<C, M, A, G> {
+        sample_pixel::<_, Pix4<C, M, Straight, G>, _>(rng, false)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix1<C, M, A, G>> for Opaque
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix1<C, M, A, G> {
+        sample_pixel::<_, Pix1<C, M, Straight, G>, _>(rng, true)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix2<C, M, A, G>> for Opaque
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix2<C, M, A, G> {
+        sample_pixel::<_, Pix2<C, M, Straight, G>, _>(rng, true)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix3<C, M, A, G>> for Opaque
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix3<C, M, A, G> {
+        sample_pixel::<_, Pix3<C, M, Straight, G>, _>(rng, true)
+    }
+}
+
+impl<C, M, A, G> Distribution<Pix4<C, M, A, G>> for Opaque
+where
+    C: Channel,
+    M: ColorModel,
+    A: Alpha,
+    G: Gamma,
+    StandardUniform: Distribution<C>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Pix4<C, M, A, G> {
+        sample_pixel::<_, Pix4<C, M, Straight, G>, _>(rng, true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gray::*;
+    use crate::matte::*;
+    use crate::rgb::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn standard() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let px: Vec<Rgba8> = (0..256).map(|_| rng.random()).collect();
+        assert!(px.iter().any(|p| p.alpha() < Ch8::MID));
+        assert!(px.iter().any(|p| p.alpha() > Ch8::MID));
+        let px: Vec<Rgba16p> = (0..256).map(|_| rng.random()).collect();
+        for p in px {
+            assert!(p.channels()[..3].iter().all(|c| *c <= p.alpha()));
+        }
+        let _: Gray32 = rng.random();
+        let _: Matte8 = rng.random();
+    }
+
+    #[test]
+    fn opaque() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..64 {
+            let p: Graya32p = rng.sample(Opaque);
+            assert_eq!(p.alpha(), Ch32::MAX);
+            let p: Matte16 = rng.sample(Opaque);
+            assert_eq!(p.alpha(), Ch16::MAX);
+            let _: SRgb8 = rng.sample(Opaque);
+        }
+    }
+}
@@ -3,13 +3,27 @@
 // Copyright (c) 2017-2024  Douglas P Lau
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
-use crate::chan::{Ch16, Ch8, Linear, Premultiplied};
-use crate::el::Pixel;
-use crate::matte::Matte;
-use crate::ops::Blend;
+use crate::chan::{
+    Alpha, Ch16, Ch32, Ch8, Channel, IntChannel, Linear, Premultiplied,
+    Straight,
+};
+use crate::dither::{Dither, OrderedDither};
+use crate::el::{Pix1, PixGray, Pixel};
+use crate::gradient::GradientFill;
+use crate::gray::{Gray, Gray8};
+use crate::icc::{IccError, IccProfile};
+use crate::matte::{Matte, MatteOp};
+use crate::ops::{Adjust, Blend, ColorMatrix, Dissolve, Exposure};
+use crate::palette::Palette;
+use crate::private::{MaybeSend, MaybeSync};
+use crate::rgb::Rgb;
+use crate::ColorModel;
+use std::any::TypeId;
 use std::convert::TryFrom;
 use std::ops::Range;
-use std::slice::{from_raw_parts_mut, ChunksExact, ChunksExactMut};
+use std::ptr::slice_from_raw_parts_mut;
+use std::slice::{Chunks, ChunksMut};
+use std::sync::Arc;
 
 /// Message for width too big
 const WIDTH_TOO_BIG: &str = "Raster width too big";
@@ -20,6 +34,10 @@ const HEIGHT_TOO_BIG: &str = "Raster height too big";
 /// Message for raster too big
 const TOO_BIG: &str = "Raster too big";
 
+/// Minimum number of pixels to split work across threads
+#[cfg(feature = "rayon")]
+const PAR_THRESHOLD: usize = 1 << 16;
+
 /// Image arranged as a rectangular array of pixels.  Rows are ordered top to
 /// bottom, and pixels within rows are left to right.
 ///
@@ -30,6 +48,9 @@ const TOO_BIG: &str = "Raster too big";
 /// * [with_pixels](#method.with_pixels)
 /// * [with_u8_buffer](#method.with_u8_buffer)
 /// * [with_u16_buffer](#method.with_u16_buffer)
+/// * [from_u8_buffer](#method.from_u8_buffer) (fallible)
+/// * [with_planar](#method.with_planar)
+/// * [with_tiled](#method.with_tiled)
 ///
 /// ### Working with byte buffers
 ///
@@ -52,6 +73,7 @@ pub struct Raster<P: Pixel> {
     width: i32,
     height: i32,
     pixels: Box<[P]>,
+    profile: Option<Arc<IccProfile>>,
 }
 
 /// `Iterator` of *rows* in a [raster], as slices of [pixel]s.
@@ -63,7 +85,7 @@ pub struct Raster<P: Pixel> {
 /// [rows]: struct.Raster.html#method.rows
 pub struct Rows<'a, P: Pixel> {
     /// Chunks iterator of full rows
-    chunks: ChunksExact<'a, P>,
+    chunks: Chunks<'a, P>,
     /// Range of requested columns
     columns: Range<usize>,
 }
@@ -77,11 +99,73 @@ pub struct Rows<'a, P: Pixel> {
 /// [rows_mut]: struct.Raster.html#method.rows_mut
 pub struct RowsMut<'a, P: Pixel> {
     /// Chunks iterator of full rows
-    chunks: ChunksExactMut<'a, P>,
+    chunks: ChunksMut<'a, P>,
+    /// Range of requested columns
+    columns: Range<usize>,
+}
+
+/// `Iterator` of horizontal bands of *rows* in a [raster].
+///
+/// Each band is a [RowsMut] `Iterator`, and bands are disjoint, so they can
+/// be processed by separate threads.
+///
+/// This struct is created by the [rows_mut_chunks] method of [Raster].
+///
+/// [raster]: struct.Raster.html
+/// [rows_mut_chunks]: struct.Raster.html#method.rows_mut_chunks
+/// [RowsMut]: struct.RowsMut.html
+pub struct RowsMutChunks<'a, P: Pixel> {
+    /// Chunks iterator of bands of full rows
+    chunks: ChunksMut<'a, P>,
+    /// Width of full rows
+    width: usize,
     /// Range of requested columns
     columns: Range<usize>,
 }
 
+/// View of a rectangular region of a [Raster].
+///
+/// Pixels are borrowed from the parent `Raster` without copying.  Coordinates
+/// and regions are relative to the top-left corner of the view.
+///
+/// This struct is created by the [view] method of [Raster].
+///
+/// [raster]: struct.Raster.html
+/// [view]: struct.Raster.html#method.view
+#[derive(Clone, Copy)]
+pub struct RasterView<'a, P: Pixel> {
+    /// Pixels of full rows in parent raster
+    pixels: &'a [P],
+    /// Width of parent raster rows
+    stride: usize,
+    /// Region within parent raster
+    region: Region,
+    /// Color profile of parent raster
+    profile: Option<&'a Arc<IccProfile>>,
+}
+
+/// Mutable view of a rectangular region of a [Raster].
+///
+/// Pixels are borrowed from the parent `Raster` without copying.  Coordinates
+/// and regions are relative to the top-left corner of the view.
+///
+/// This struct is created by the [view_mut] method of [Raster].  It can also
+/// borrow caller-owned memory, such as a frame buffer, using [with_pixels] or
+/// [with_u8_buffer].  Drawing into it avoids copying a full frame.
+///
+/// [raster]: struct.Raster.html
+/// [view_mut]: struct.Raster.html#method.view_mut
+/// [with_pixels]: #method.with_pixels
+/// [with_u8_buffer]: #method.with_u8_buffer
+pub struct RasterViewMut<'a, P: Pixel> {
+    /// Pixels of full rows in parent raster
+    pixels: &'a mut [P],
+    /// Width of parent raster rows
+    stride: usize,
+    /// Region within parent raster
+    region: Region,
+}
+
 /// Location / dimensions of pixels relative to a [Raster](struct.Raster.html).
 ///
 /// ### Create directly
@@ -100,6 +184,11 @@ pub struct RowsMut<'a, P: Pixel> {
 /// let reg = r.region(); // (0, 0, 100, 100)
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "RegionDef", try_from = "RegionDef")
+)]
 pub struct Region {
     x: i32,
     y: i32,
@@ -107,6 +196,36 @@ pub struct Region {
     height: i32,
 }
 
+/// Error constructing a [Raster](struct.Raster.html) from a buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferError {
+    /// Width or height greater than `std::i32::MAX`, or too many pixels
+    TooBig,
+    /// Buffer length does not match dimensions
+    WrongLength {
+        /// Expected buffer length
+        expected: usize,
+        /// Actual buffer length
+        actual: usize,
+    },
+    /// Floating-point channel value is NaN or out of range
+    InvalidValue,
+}
+
+impl std::fmt::Display for BufferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BufferError::TooBig => write!(f, "{TOO_BIG}"),
+            BufferError::WrongLength { expected, actual } => {
+                write!(f, "buffer length {actual}, expected {expected}")
+            }
+            BufferError::InvalidValue => write!(f, "invalid channel value"),
+        }
+    }
+}
+
+impl std::error::Error for BufferError {}
+
 impl<P: Pixel> From<Raster<P>> for Box<[P]> {
     /// Get internal pixel data as boxed slice.
     fn from(raster: Raster<P>) -> Self {
@@ -132,12 +251,52 @@ where
         let slice = Box::<[P]>::into_raw(pixels);
         let buffer: Box<[u8]> = unsafe {
             let ptr = (*slice).as_mut_ptr() as *mut u8;
-            Box::from_raw(from_raw_parts_mut(ptr, capacity))
+            Box::from_raw(slice_from_raw_parts_mut(ptr, capacity))
         };
         buffer
     }
 }
 
+impl<P> AsRef<[u8]> for Raster<P>
+where
+    P: Pixel,
+    P::Chan: IntChannel,
+{
+    /// Get pixel data as a `u8` slice, in native-endian byte order.
+    fn as_ref(&self) -> &[u8] {
+        self.as_u8_slice()
+    }
+}
+
+impl<P> AsMut<[u8]> for Raster<P>
+where
+    P: Pixel,
+    P::Chan: IntChannel,
+{
+    /// Get pixel data as a mutable `u8` slice, in native-endian byte order.
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_u8_slice_mut()
+    }
+}
+
+impl<P> TryFrom<(u32, u32, &[u8])> for Raster<P>
+where
+    P: Pixel,
+    P::Chan: IntChannel,
+{
+    type Error = BufferError;
+
+    /// Copy pixel data from a (`width`, `height`, `buffer`) tuple.
+    ///
+    /// The `buffer` length must be `width` * `height` *
+    /// `std::mem::size_of::<P>()`.
+    fn try_from(
+        (width, height, buffer): (u32, u32, &[u8]),
+    ) -> Result<Self, Self::Error> {
+        Raster::from_u8_buffer(width, height, buffer)
+    }
+}
+
 impl<P> From<Raster<P>> for Box<[u16]>
 where
     P: Pixel<Chan = Ch16>,
@@ -149,7 +308,7 @@ where
         let slice = Box::<[P]>::into_raw(pixels);
         let buffer: Box<[u16]> = unsafe {
             let ptr = (*slice).as_mut_ptr() as *mut u16;
-            Box::from_raw(from_raw_parts_mut(ptr, capacity))
+            Box::from_raw(slice_from_raw_parts_mut(ptr, capacity))
         };
         buffer
     }
@@ -201,6 +360,7 @@ impl<P: Pixel> Raster<P> {
             width,
             height,
             pixels,
+            profile: None,
         }
     }
 
@@ -225,16 +385,444 @@ impl<P: Pixel> Raster<P> {
         P::Chan: From<S::Chan>,
     {
         let mut r = Raster::with_clear(src.width(), src.height());
+        r.profile = src.profile_for::<P>();
+        let len = src.pixels.len();
         let srows = src.rows(());
         let drows = r.rows_mut(());
-        for (drow, srow) in drows.zip(srows) {
+        for_each_row(drows.zip(srows), len, |(drow, srow)| {
             for (d, s) in drow.iter_mut().zip(srow) {
                 *d = s.convert();
             }
+        });
+        r
+    }
+
+    /// Construct a `Raster` with another `Raster`, using its color profile.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    ///
+    /// If the source has an attached [IccProfile], its pixels are transformed
+    /// with the [MatrixProfile] of that profile.  Otherwise, this is the same
+    /// as [with_raster](#method.with_raster).
+    ///
+    /// [IccProfile]: icc/struct.IccProfile.html
+    /// [MatrixProfile]: icc/struct.MatrixProfile.html
+    pub fn with_color_managed<S>(src: &Raster<S>) -> Result<Self, IccError>
+    where
+        S: Pixel<Model = Rgb>,
+        Ch32: From<S::Chan>,
+        P::Chan: From<S::Chan> + From<Ch32>,
+    {
+        match src.profile() {
+            Some(profile) => Ok(profile.matrix_profile()?.transform(src)),
+            None => Ok(Raster::with_raster(src)),
+        }
+    }
+
+    /// Construct a `Raster` by reordering the channels of another `Raster`.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    /// * `order` Index of source channel for each channel of `P`.
+    ///
+    /// Channels are copied as stored, without any color model, *alpha* or
+    /// gamma conversion.  See [Pixel::swizzle_slice] for details.
+    ///
+    /// [Pixel::swizzle_slice]: el/trait.Pixel.html#method.swizzle_slice
+    ///
+    /// ### Swizzle RGBA to BGRA for a framebuffer
+    /// ```
+    /// use pix::bgr::Bgra8;
+    /// use pix::rgb::Rgba8;
+    /// use pix::Raster;
+    ///
+    /// let r0 = Raster::with_color(4, 4, Rgba8::new(0x10, 0x20, 0x30, 0xFF));
+    /// let r1 = Raster::<Bgra8>::with_swizzle(&r0, &[2, 1, 0, 3]);
+    /// assert_eq!(r1.pixel(0, 0), Bgra8::new(0x30, 0x20, 0x10, 0xFF));
+    /// ```
+    pub fn with_swizzle<S>(src: &Raster<S>, order: &[usize]) -> Self
+    where
+        S: Pixel<Chan = P::Chan>,
+    {
+        let mut r = Raster::with_clear(src.width(), src.height());
+        r.profile = src.profile.clone();
+        let len = src.pixels.len();
+        let srows = src.rows(());
+        let drows = r.rows_mut(());
+        for_each_row(drows.zip(srows), len, |(drow, srow)| {
+            S::swizzle_slice(drow, srow, order);
+        });
+        r
+    }
+
+    /// Convert the pixel format of a `Raster` without allocating.
+    ///
+    /// Each pixel is converted and written back to the same storage.
+    ///
+    /// * `D` `Pixel` format of converted `Raster`.  It must have the same size
+    ///   and alignment as `P`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if size or alignment of `D` differs from `P`.
+    ///
+    /// ### Convert from Rgba8 to SRgba8p
+    /// ```
+    /// use pix::rgb::{Rgba8, SRgba8p};
+    /// use pix::Raster;
+    ///
+    /// let r0 = Raster::with_color(4, 4, Rgba8::new(0xFF, 0x80, 0x00, 0x80));
+    /// let r1 = r0.convert_in_place::<SRgba8p>();
+    /// assert_eq!(r1.pixel(0, 0), SRgba8p::new(0xBC, 0x89, 0x00, 0x80));
+    /// ```
+    pub fn convert_in_place<D>(self) -> Raster<D>
+    where
+        D: Pixel,
+        D::Chan: From<P::Chan>,
+    {
+        assert_eq!(std::mem::size_of::<P>(), std::mem::size_of::<D>());
+        assert_eq!(std::mem::align_of::<P>(), std::mem::align_of::<D>());
+        let profile = self.profile_for::<D>();
+        let len = self.pixels.len();
+        let slice = Box::<[P]>::into_raw(self.pixels);
+        let pixels: Box<[D]> = unsafe {
+            let src = (*slice).as_mut_ptr();
+            let dst = src as *mut D;
+            for i in 0..len {
+                // read before writing, since storage is shared
+                let p = src.add(i).read();
+                dst.add(i).write(p.convert());
+            }
+            Box::from_raw(slice_from_raw_parts_mut(dst, len))
+        };
+        Raster {
+            width: self.width,
+            height: self.height,
+            pixels,
+            profile,
+        }
+    }
+
+    /// Create a new `Raster` by calling a function on each pixel.
+    ///
+    /// * `D` `Pixel` format of new `Raster`.
+    /// * `f` Function to map each pixel.
+    ///
+    /// With the `rayon` feature, large rasters are split across threads.
+    ///
+    /// ### Extract the alpha channel
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::matte::Matte8;
+    /// use pix::rgb::SRgba8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(4, 4, SRgba8::new(0x10, 0x20, 0x30, 0x40));
+    /// let m = r.map(|p| Matte8::new(p.alpha()));
+    /// assert_eq!(m.pixel(2, 2), Matte8::new(0x40));
+    /// ```
+    pub fn map<D, F>(&self, f: F) -> Raster<D>
+    where
+        D: Pixel,
+        F: Fn(P) -> D + MaybeSend + MaybeSync,
+    {
+        let mut r = Raster::with_clear(self.width(), self.height());
+        r.profile = self.profile_for::<D>();
+        let len = self.pixels.len();
+        let srows = self.rows(());
+        let drows = r.rows_mut(());
+        for_each_row(drows.zip(srows), len, |(drow, srow)| {
+            for (d, s) in drow.iter_mut().zip(srow) {
+                *d = f(*s);
+            }
+        });
+        r
+    }
+
+    /// Modify each pixel by calling a function.
+    ///
+    /// * `f` Function to map each pixel.
+    ///
+    /// With the `rayon` feature, large rasters are split across threads.
+    ///
+    /// ### Swap red and blue
+    /// ```
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(4, 4, SRgb8::new(0x10, 0x20, 0x30));
+    /// r.map_in_place(|p| SRgb8::new(p.three(), p.two(), p.one()));
+    /// assert_eq!(r.pixel(1, 3), SRgb8::new(0x30, 0x20, 0x10));
+    /// ```
+    pub fn map_in_place<F>(&mut self, f: F)
+    where
+        F: Fn(P) -> P + MaybeSend + MaybeSync,
+    {
+        let len = self.pixels.len();
+        for_each_row(self.rows_mut(()), len, |row| {
+            for p in row.iter_mut() {
+                *p = f(*p);
+            }
+        });
+    }
+
+    /// Extract the *alpha* channel into a matte `Raster`.
+    ///
+    /// Pixel formats without an *alpha* channel produce an opaque matte.
+    ///
+    /// ### Cut out a shape
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::rgb::SRgba8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_clear(4, 4);
+    /// *r.pixel_mut(1, 1) = SRgba8::new(0xFF, 0x80, 0x00, 0xC0);
+    /// let m = r.alpha_matte();
+    /// assert_eq!(m.pixel(1, 1), Matte8::new(0xC0));
+    /// assert_eq!(m.pixel(2, 1), Matte8::new(0x00));
+    /// ```
+    pub fn alpha_matte(
+        &self,
+    ) -> Raster<Pix1<P::Chan, Matte, Premultiplied, Linear>> {
+        self.map(|p| Pix1::new(p.alpha()))
+    }
+
+    /// Multiply the *alpha* channel by a matte `Raster`.
+    ///
+    /// * `matte` Matte `Raster`, with the same dimensions as `self`.
+    ///
+    /// For *premultiplied* pixel formats, the color channels are also
+    /// multiplied, so they stay associated with *alpha*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel format does not contain an *alpha* channel, or if
+    /// `matte` dimensions do not match.
+    ///
+    /// ### Apply a circular mask
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(16, 16, Rgba8p::new(0x80, 0x40, 0, 0xFF));
+    /// let mut m = Raster::<Matte8>::with_clear(16, 16);
+    /// for (i, p) in m.pixels_mut().iter_mut().enumerate() {
+    ///     let (x, y) = ((i % 16) as f32 - 7.5, (i / 16) as f32 - 7.5);
+    ///     if x.hypot(y) < 8.0 {
+    ///         *p = Matte8::new(0xFF);
+    ///     }
+    /// }
+    /// r.apply_matte(&m);
+    /// assert_eq!(r.pixel(8, 8), Rgba8p::new(0x80, 0x40, 0, 0xFF));
+    /// assert_eq!(r.pixel(0, 0), Rgba8p::new(0, 0, 0, 0));
+    /// ```
+    pub fn apply_matte<M>(&mut self, matte: &Raster<M>)
+    where
+        M: Pixel<Chan = P::Chan, Model = Matte>,
+    {
+        assert_eq!(self.width(), matte.width(), "Matte width differs");
+        assert_eq!(self.height(), matte.height(), "Matte height differs");
+        for (d, m) in self.pixels.iter_mut().zip(matte.pixels()) {
+            let m = m.alpha();
+            let chan = d.channels_mut();
+            for c in &mut chan[P::Model::LINEAR] {
+                *c = P::Alpha::encode(*c, m);
+            }
+            let a = d.alpha_mut();
+            *a = *a * m;
+        }
+    }
+
+    /// Split into one gray `Raster` per channel.
+    ///
+    /// Channels are copied as stored, in order, without any *alpha* or gamma
+    /// conversion.  The rasters can be joined again with [merge_channels].
+    ///
+    /// [merge_channels]: #method.merge_channels
+    ///
+    /// ### Inspect the green channel
+    /// ```
+    /// use pix::gray::SGray8;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(4, 4, SRgb8::new(0x10, 0x20, 0x30));
+    /// let planes = r.split_channels();
+    /// assert_eq!(planes.len(), 3);
+    /// assert_eq!(planes[1].pixel(0, 0), SGray8::new(0x20));
+    /// ```
+    pub fn split_channels(&self) -> Vec<Raster<PixGray<P>>> {
+        (0..P::default().channels().len())
+            .map(|c| self.map(|p| Pix1::new(p.channels()[c])))
+            .collect()
+    }
+
+    /// Construct a `Raster` by merging gray rasters, one per channel.
+    ///
+    /// * `planes` Gray rasters, in channel order.
+    ///
+    /// This is the inverse of [split_channels].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of planes does not match the number of channels
+    /// in `P`, or if the planes have different dimensions.
+    ///
+    /// [split_channels]: #method.split_channels
+    ///
+    /// ### Process a single channel
+    /// ```
+    /// use pix::gray::SGray8;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(4, 4, SRgb8::new(0x10, 0x20, 0x30));
+    /// let mut planes = r.split_channels();
+    /// planes[0].copy_color((), SGray8::new(0xFF));
+    /// let r = Raster::<SRgb8>::merge_channels(&planes);
+    /// assert_eq!(r.pixel(3, 3), SRgb8::new(0xFF, 0x20, 0x30));
+    /// ```
+    pub fn merge_channels<G>(planes: &[Raster<G>]) -> Self
+    where
+        G: Pixel<Chan = P::Chan, Model = Gray>,
+    {
+        let count = P::default().channels().len();
+        assert_eq!(planes.len(), count, "Wrong number of planes");
+        let mut r =
+            Raster::<P>::with_clear(planes[0].width(), planes[0].height());
+        for (c, plane) in planes.iter().enumerate() {
+            assert_eq!(plane.width(), r.width(), "Plane widths differ");
+            assert_eq!(plane.height(), r.height(), "Plane heights differ");
+            for (d, s) in r.pixels.iter_mut().zip(plane.pixels()) {
+                d.channels_mut()[c] = s.one();
+            }
         }
         r
     }
 
+    /// Quantize to a palette and indexed `Raster`.
+    ///
+    /// * `max_colors` Maximum number of palette entries, from 1 to 256.
+    /// * `dither` Dithering method.
+    ///
+    /// The palette is built using [Palette::quantize_median_cut].  Indexed
+    /// rasters use `Gray8` pixels, with each value an index into the palette.
+    /// Pixels are converted to `SRgb8`, ignoring *alpha*.  To expand back to
+    /// colors, use [with_indexed].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_colors` is not between 1 and 256.
+    ///
+    /// [Palette::quantize_median_cut]: struct.Palette.html#method.quantize_median_cut
+    /// [with_indexed]: #method.with_indexed
+    ///
+    /// ### Quantize to 16 colors
+    /// ```
+    /// use pix::dither::Dither;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(32, 32, SRgb8::new(0x20, 0x40, 0x80));
+    /// r.copy_color((0, 0, 16, 16), SRgb8::new(0xFF, 0xC0, 0x00));
+    /// let (palette, indexed) = r.quantized(16, Dither::FloydSteinberg);
+    /// assert_eq!(palette.len(), 2);
+    /// let r2 = Raster::<SRgb8>::with_indexed(&indexed, &palette);
+    /// assert_eq!(r2.pixels(), r.pixels());
+    /// ```
+    pub fn quantized(
+        &self,
+        max_colors: usize,
+        dither: Dither,
+    ) -> (Palette, Raster<Gray8>)
+    where
+        Ch8: From<P::Chan>,
+    {
+        let (palette, indexed) = Palette::quantize_median_cut(self, max_colors);
+        let indexed = match dither {
+            Dither::None => indexed,
+            Dither::FloydSteinberg => palette.make_indexed_dithered(self),
+            Dither::Ordered(d) => palette.make_indexed_ordered(self, &d),
+        };
+        (palette, indexed)
+    }
+
+    /// Construct a `Raster` by expanding an indexed `Raster`.
+    ///
+    /// * `indexed` Indexed `Raster`, with each value an index into `palette`.
+    /// * `palette` Color palette.
+    ///
+    /// Indices without a palette entry are set to the default pixel value.
+    pub fn with_indexed(indexed: &Raster<Gray8>, palette: &Palette) -> Self
+    where
+        P::Chan: From<Ch8>,
+    {
+        let colors: Vec<P> =
+            palette.colors().iter().map(|c| c.convert()).collect();
+        indexed.map(|i| {
+            let i = usize::from(u8::from(i.one()));
+            colors.get(i).copied().unwrap_or_default()
+        })
+    }
+
+    /// Construct a *premultiplied* `Raster` from a *straight* `Raster`.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    ///
+    /// ### Premultiply an Rgba8 raster
+    /// ```
+    /// use pix::rgb::{Rgba8, Rgba8p};
+    /// use pix::Raster;
+    ///
+    /// let r0 = Raster::<Rgba8>::with_clear(50, 50);
+    /// let r1 = Raster::<Rgba8p>::with_premultiplied(&r0);
+    /// ```
+    pub fn with_premultiplied<S>(src: &Raster<S>) -> Self
+    where
+        S: Pixel<
+            Chan = P::Chan,
+            Model = P::Model,
+            Alpha = Straight,
+            Gamma = P::Gamma,
+        >,
+        P: Pixel<Alpha = Premultiplied>,
+    {
+        let mut r = Raster::with_clear(src.width(), src.height());
+        r.profile = src.profile.clone();
+        S::premultiply_slice(r.pixels_mut(), src.pixels());
+        r
+    }
+
+    /// Construct a *straight* `Raster` from a *premultiplied* `Raster`.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    ///
+    /// ### Unpremultiply an Rgba8p raster
+    /// ```
+    /// use pix::rgb::{Rgba8, Rgba8p};
+    /// use pix::Raster;
+    ///
+    /// let r0 = Raster::<Rgba8p>::with_clear(50, 50);
+    /// let r1 = Raster::<Rgba8>::with_unpremultiplied(&r0);
+    /// ```
+    pub fn with_unpremultiplied<S>(src: &Raster<S>) -> Self
+    where
+        S: Pixel<
+            Chan = P::Chan,
+            Model = P::Model,
+            Alpha = Premultiplied,
+            Gamma = P::Gamma,
+        >,
+        P: Pixel<Alpha = Straight>,
+    {
+        let mut r = Raster::with_clear(src.width(), src.height());
+        r.profile = src.profile.clone();
+        S::unpremultiply_slice(r.pixels_mut(), src.pixels());
+        r
+    }
+
     /// Construct a `Raster` with owned pixel data.  You can get ownership of
     /// the pixel data back from the `Raster` as either a `Vec<P>` or a
     /// `Box<[P]>` by calling `into()`.
@@ -275,6 +863,7 @@ impl<P: Pixel> Raster<P> {
             width,
             height,
             pixels,
+            profile: None,
         }
     }
 
@@ -309,13 +898,103 @@ impl<P: Pixel> Raster<P> {
         let slice = Box::<[u8]>::into_raw(buffer);
         let pixels: Box<[P]> = unsafe {
             let ptr = (*slice).as_mut_ptr() as *mut P;
-            Box::from_raw(from_raw_parts_mut(ptr, len))
+            Box::from_raw(slice_from_raw_parts_mut(ptr, len))
         };
         Raster {
             width,
             height,
             pixels,
+            profile: None,
+        }
+    }
+
+    /// Construct a `Raster` from a `u8` buffer, checking its length.
+    ///
+    /// * `B` Owned buffer type (`Vec` or boxed slice).
+    /// * `width` Width of `Raster`.
+    /// * `height` Height of `Raster`.
+    /// * `buffer` Buffer of pixel data (in native-endian byte order).
+    ///
+    /// Pixel formats with 8-bit channels reuse the buffer without copying.
+    /// Other formats have stricter alignment than a `u8` allocation, so the
+    /// pixel data is copied.
+    ///
+    /// For formats with [Ch32] channels, every value must be within the range
+    /// 0.0 to 1.0 (and not NaN), or [BufferError::InvalidValue] is returned.
+    ///
+    /// [Ch32]: chan/struct.Ch32.html
+    /// [BufferError::InvalidValue]: enum.BufferError.html#variant.InvalidValue
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::gray::Gray16;
+    /// use pix::{BufferError, Raster};
+    ///
+    /// let buf = vec![0x80; 4 * 3 * 2];
+    /// let r = Raster::<Gray16>::from_u8_buffer(4, 3, buf).unwrap();
+    /// assert_eq!(r.pixel(3, 2), Gray16::new(0x8080));
+    /// let e = Raster::<Gray16>::from_u8_buffer(4, 4, vec![0; 24]);
+    /// assert_eq!(
+    ///     e.err(),
+    ///     Some(BufferError::WrongLength { expected: 32, actual: 24 })
+    /// );
+    /// ```
+    pub fn from_u8_buffer<B>(
+        width: u32,
+        height: u32,
+        buffer: B,
+    ) -> Result<Self, BufferError>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let r = Self::from_u8_buffer_unchecked(width, height, buffer.into())?;
+        let valid = |c: &P::Chan| (0.0..=1.0).contains(&c.to_f32());
+        if std::any::TypeId::of::<P::Chan>() == std::any::TypeId::of::<Ch32>()
+            && !r.pixels.iter().all(|p| p.channels().iter().all(valid))
+        {
+            return Err(BufferError::InvalidValue);
+        }
+        Ok(r)
+    }
+
+    /// Construct a `Raster` from a `u8` buffer, checking only its length.
+    fn from_u8_buffer_unchecked(
+        width: u32,
+        height: u32,
+        buffer: Box<[u8]>,
+    ) -> Result<Self, BufferError> {
+        let width = i32::try_from(width).map_err(|_| BufferError::TooBig)?;
+        let height = i32::try_from(height).map_err(|_| BufferError::TooBig)?;
+        let len =
+            width.checked_mul(height).ok_or(BufferError::TooBig)? as usize;
+        let expected = len
+            .checked_mul(std::mem::size_of::<P>())
+            .ok_or(BufferError::TooBig)?;
+        if buffer.len() != expected {
+            return Err(BufferError::WrongLength {
+                expected,
+                actual: buffer.len(),
+            });
         }
+        let pixels: Box<[P]> = if std::mem::align_of::<P>() == 1 {
+            let slice = Box::<[u8]>::into_raw(buffer);
+            // Same size and alignment, so the allocation can be reused
+            unsafe {
+                let ptr = (*slice).as_mut_ptr() as *mut P;
+                Box::from_raw(slice_from_raw_parts_mut(ptr, len))
+            }
+        } else {
+            let mut pixels = vec![P::default(); len].into_boxed_slice();
+            let (_, bytes, _) = unsafe { pixels.align_to_mut::<u8>() };
+            bytes.copy_from_slice(&buffer);
+            pixels
+        };
+        Ok(Raster {
+            width,
+            height,
+            pixels,
+            profile: None,
+        })
     }
 
     /// Construct a `Raster` from a `u16` buffer.
@@ -349,12 +1028,13 @@ impl<P: Pixel> Raster<P> {
         let slice = Box::<[u16]>::into_raw(buffer);
         let pixels: Box<[P]> = unsafe {
             let ptr = (*slice).as_mut_ptr() as *mut P;
-            Box::from_raw(from_raw_parts_mut(ptr, len))
+            Box::from_raw(slice_from_raw_parts_mut(ptr, len))
         };
         Raster {
             width,
             height,
             pixels,
+            profile: None,
         }
     }
 
@@ -368,10 +1048,77 @@ impl<P: Pixel> Raster<P> {
         self.height as u32
     }
 
-    /// Clear all pixels to default value.
-    pub fn clear(&mut self) {
-        for p in self.pixels.iter_mut() {
-            *p = P::default();
+    /// Get the attached ICC color profile.
+    pub fn profile(&self) -> Option<&IccProfile> {
+        self.profile.as_deref()
+    }
+
+    /// Attach an ICC color profile, or remove it with `None`.
+    ///
+    /// The profile is kept by `clone`, and by methods which do not change the
+    /// color space, such as [scaled], [rotated_90], [flipped_horizontal],
+    /// filters ([gaussian_blur], [convolved], *etc.*), [with_view] and
+    /// [with_swizzle].  Conversions ([with_raster], [map] and
+    /// [convert_in_place]) keep it only if the color model and gamma are
+    /// unchanged.  Constructors from pixels or buffers ([with_clear],
+    /// [with_pixels], [with_u8_buffer], *etc.*) have no profile.
+    ///
+    /// [convert_in_place]: #method.convert_in_place
+    /// [convolved]: #method.convolved
+    /// [flipped_horizontal]: #method.flipped_horizontal
+    /// [gaussian_blur]: #method.gaussian_blur
+    /// [map]: #method.map
+    /// [rotated_90]: #method.rotated_90
+    /// [scaled]: #method.scaled
+    /// [with_clear]: #method.with_clear
+    /// [with_pixels]: #method.with_pixels
+    /// [with_raster]: #method.with_raster
+    /// [with_swizzle]: #method.with_swizzle
+    /// [with_u8_buffer]: #method.with_u8_buffer
+    /// [with_view]: #method.with_view
+    ///
+    /// ### Tag a raster
+    /// ```
+    /// use pix::icc::IccProfile;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// # fn load_profile() -> Vec<u8> {
+    /// #     let mut data = vec![0; 132];
+    /// #     data[36..40].copy_from_slice(b"acsp");
+    /// #     data
+    /// # }
+    /// let mut r = Raster::<SRgb8>::with_clear(4, 4);
+    /// r.set_profile(Some(IccProfile::new(load_profile()).unwrap()));
+    /// assert!(r.clone().profile().is_some());
+    /// ```
+    pub fn set_profile(&mut self, profile: Option<IccProfile>) {
+        self.profile = profile.map(Arc::new);
+    }
+
+    /// Get the attached profile, if it applies to pixel format `D`
+    fn profile_for<D: Pixel>(&self) -> Option<Arc<IccProfile>> {
+        let same = TypeId::of::<P::Model>() == TypeId::of::<D::Model>()
+            && TypeId::of::<P::Gamma>() == TypeId::of::<D::Gamma>();
+        if same {
+            self.profile.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Construct a `Raster` with all pixels set to the default value,
+    /// keeping the attached profile
+    pub(crate) fn blank(&self, width: u32, height: u32) -> Self {
+        let mut r = Raster::with_clear(width, height);
+        r.profile = self.profile.clone();
+        r
+    }
+
+    /// Clear all pixels to default value.
+    pub fn clear(&mut self) {
+        for p in self.pixels.iter_mut() {
+            *p = P::default();
         }
     }
 
@@ -404,21 +1151,95 @@ impl<P: Pixel> Raster<P> {
     /// Get an `Iterator` of rows within a `Raster`.
     ///
     /// * `reg` Region of the Raster to iterate.
-    pub fn rows<R>(&self, reg: R) -> Rows<P>
+    pub fn rows<R>(&self, reg: R) -> Rows<'_, P>
     where
         R: Into<Region>,
     {
-        Rows::new(self, self.intersection(reg.into()))
+        let width = self.width() as usize;
+        Rows::new(&self.pixels, width, self.intersection(reg.into()))
     }
 
     /// Get an `Iterator` of mutable rows within a `Raster`.
     ///
     /// * `reg` Region of the Raster to iterate.
-    pub fn rows_mut<R>(&mut self, reg: R) -> RowsMut<P>
+    pub fn rows_mut<R>(&mut self, reg: R) -> RowsMut<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        let width = self.width() as usize;
+        RowsMut::new(&mut self.pixels, width, reg)
+    }
+
+    /// Get an `Iterator` of bands of mutable rows within a `Raster`.
+    ///
+    /// * `reg` Region of the Raster to iterate.
+    /// * `n` Number of rows in each band (the last band may have fewer).
+    ///
+    /// # Panics
+    ///
+    /// * If `n` is zero
+    ///
+    /// ### Process bands in parallel
+    /// ```
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(100, 100);
+    /// std::thread::scope(|s| {
+    ///     for band in r.rows_mut_chunks((), 25) {
+    ///         s.spawn(move || {
+    ///             for row in band {
+    ///                 row.fill(Gray8::new(0x80));
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    /// assert_eq!(r.pixel(50, 99), Gray8::new(0x80));
+    /// ```
+    pub fn rows_mut_chunks<R>(&mut self, reg: R, n: u32) -> RowsMutChunks<'_, P>
+    where
+        R: Into<Region>,
+    {
+        assert!(n > 0);
+        RowsMutChunks::new(self, self.intersection(reg.into()), n as usize)
+    }
+
+    /// Get a view of a region of the `Raster`.
+    ///
+    /// * `reg` Region of the Raster to view.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(10, 10);
+    /// *r.pixel_mut(5, 6) = Gray8::new(0xFF);
+    /// let v = r.view((4, 4, 4, 4));
+    /// assert_eq!(v.pixel(1, 2), Gray8::new(0xFF));
+    /// ```
+    pub fn view<R>(&self, reg: R) -> RasterView<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        let mut view =
+            RasterView::new(&self.pixels, self.width() as usize, reg);
+        view.profile = self.profile.as_ref();
+        view
+    }
+
+    /// Get a mutable view of a region of the `Raster`.
+    ///
+    /// * `reg` Region of the Raster to view.
+    pub fn view_mut<R>(&mut self, reg: R) -> RasterViewMut<'_, P>
     where
         R: Into<Region>,
     {
-        RowsMut::new(self, self.intersection(reg.into()))
+        let reg = self.intersection(reg.into());
+        let width = self.width() as usize;
+        RasterViewMut::new(&mut self.pixels, width, reg)
     }
 
     /// Get `Region` of entire `Raster`.
@@ -435,11 +1256,287 @@ impl<P: Pixel> Raster<P> {
         reg.intersection(self.region())
     }
 
+    /// Adjust the colors of a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `op` [Adjust]ment operation.
+    ///
+    /// [adjust]: ops/trait.Adjust.html
+    ///
+    /// ### Increase contrast
+    /// ```
+    /// use pix::ops::Contrast;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(10, 10, SRgb8::new(0x20, 0x80, 0xD0));
+    /// r.adjust((), Contrast(1.5));
+    /// assert_eq!(r.pixel(0, 0), SRgb8::new(0x00, 0x85, 0xEE));
+    /// ```
+    pub fn adjust<R, O>(&mut self, reg: R, op: O)
+    where
+        R: Into<Region>,
+        O: Adjust,
+        Ch32: From<P::Chan>,
+        P::Chan: From<Ch32>,
+    {
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        for_each_row(self.rows_mut(reg), len, |row| P::adjust_slice(row, op));
+    }
+
+    /// Adjust the exposure of a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `stops` Exposure change; `1.0` doubles the light, `-1.0` halves it.
+    ///
+    /// See [Exposure] for details.
+    ///
+    /// [Exposure]: ops/struct.Exposure.html
+    pub fn exposure<R>(&mut self, reg: R, stops: f32)
+    where
+        R: Into<Region>,
+        Ch32: From<P::Chan>,
+        P::Chan: From<Ch32>,
+    {
+        self.adjust(reg, Exposure(stops));
+    }
+
+    /// Increase the saturation of a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `amount` Amount to increase; `0.0` for no change, `1.0` to double.
+    ///   Amounts below `-1.0` are clamped, producing gray.
+    ///
+    /// See [Pixel::saturate] for details.
+    ///
+    /// [Pixel::saturate]: el/trait.Pixel.html#method.saturate
+    pub fn saturate<R>(&mut self, reg: R, amount: f32)
+    where
+        R: Into<Region>,
+        Ch32: From<P::Chan>,
+        P::Chan: From<Ch32>,
+    {
+        self.adjust(reg, ColorMatrix::saturation((1.0 + amount).max(0.0)));
+    }
+
+    /// Decrease the saturation of a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `amount` Amount to decrease; `0.0` for no change, `1.0` for gray.
+    ///
+    /// ### Grayscale preview
+    /// ```
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(4, 4, SRgb8::new(0x20, 0x80, 0xD0));
+    /// r.desaturate((), 1.0);
+    /// assert_eq!(r.pixel(0, 0), SRgb8::new(0x7D, 0x7D, 0x7D));
+    /// ```
+    pub fn desaturate<R>(&mut self, reg: R, amount: f32)
+    where
+        R: Into<Region>,
+        Ch32: From<P::Chan>,
+        P::Chan: From<Ch32>,
+    {
+        self.adjust(reg, ColorMatrix::saturation((1.0 - amount).max(0.0)));
+    }
+
+    /// Posterize a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `levels` Number of levels for each channel.
+    ///
+    /// See [Pixel::posterize_slice] for details.
+    ///
+    /// [Pixel::posterize_slice]: el/trait.Pixel.html#method.posterize_slice
+    pub fn posterize<R>(&mut self, reg: R, levels: u32)
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        for_each_row(self.rows_mut(reg), len, |row| {
+            P::posterize_slice(row, levels)
+        });
+    }
+
+    /// Posterize a region of the `Raster` with ordered dithering.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `levels` Number of levels for each channel.
+    /// * `dither` Dither matrix, tiled from the `Raster` origin.
+    ///
+    /// Channels are quantized as stored, using the pixel's gamma mode, with
+    /// the matrix threshold in place of rounding.  *Alpha* and circular
+    /// channels (*hue*) are not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is less than 2.
+    ///
+    /// ### Reduce to 5 bits per channel
+    /// ```
+    /// use pix::dither::OrderedDither;
+    /// use pix::el::Pixel;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(16, 16, SRgb8::new(0x20, 0x80, 0xC0));
+    /// r.posterize_dithered((), 32, &OrderedDither::bayer(8));
+    /// for p in r.pixels() {
+    ///     assert!(p.one() == 0x19.into() || p.one() == 0x21.into());
+    /// }
+    /// ```
+    pub fn posterize_dithered<R>(
+        &mut self,
+        reg: R,
+        levels: u32,
+        dither: &OrderedDither,
+    ) where
+        R: Into<Region>,
+    {
+        assert!(levels >= 2);
+        let steps = (levels - 1) as f32;
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        let rows = (reg.y..).zip(self.rows_mut(reg));
+        for_each_row(rows, len, |(y, row)| {
+            for (x, p) in (reg.x..).zip(row.iter_mut()) {
+                let t = dither.threshold(x, y);
+                let alpha = p.alpha();
+                for c in &mut p.channels_mut()[P::Model::LINEAR] {
+                    let v = P::Alpha::decode(*c, alpha).to_f32();
+                    let v = ((v * steps + t).floor() / steps).min(1.0);
+                    *c = P::Alpha::encode(P::Chan::from(v), alpha);
+                }
+            }
+        });
+    }
+
+    /// Threshold a region of the `Raster` to black and white.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `value` Minimum luminance to be set to white; lower values are set
+    ///   to black.
+    ///
+    /// See [Pixel::threshold_slice] for details.  To produce a matte instead,
+    /// use [with_threshold].
+    ///
+    /// [Pixel::threshold_slice]: el/trait.Pixel.html#method.threshold_slice
+    /// [with_threshold]: #method.with_threshold
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::gray::SGray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(4, 4, SGray8::new(0x60));
+    /// r.threshold((0, 0, 2, 4), 0x40.into());
+    /// assert_eq!(r.pixel(1, 3), SGray8::new(0xFF));
+    /// assert_eq!(r.pixel(2, 3), SGray8::new(0x60));
+    /// ```
+    pub fn threshold<R>(&mut self, reg: R, value: P::Chan)
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        for_each_row(self.rows_mut(reg), len, |row| {
+            P::threshold_slice(row, value)
+        });
+    }
+
+    /// Invert the channels of a region of the `Raster`.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    ///
+    /// See [Pixel::invert] for details.
+    ///
+    /// [Pixel::invert]: el/trait.Pixel.html#method.invert
+    ///
+    /// ### Invert a matte
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::Raster;
+    ///
+    /// let mut m = Raster::with_color(4, 4, Matte8::new(0x40));
+    /// m.invert(());
+    /// assert_eq!(m.pixel(3, 3), Matte8::new(0xBF));
+    /// ```
+    pub fn invert<R>(&mut self, reg: R)
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        for_each_row(self.rows_mut(reg), len, |row| P::invert_slice(row));
+    }
+
+    /// Fill a region of the `Raster` with a gradient.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `grad` [GradientFill] to sample.
+    ///
+    /// Gradient coordinates are relative to the `Raster`, not the region.
+    ///
+    /// [GradientFill]: gradient/struct.GradientFill.html
+    ///
+    /// ### Horizontal gradient
+    /// ```
+    /// use pix::gradient::GradientFill;
+    /// use pix::rgb::{SRgb8, SRgb32};
+    /// use pix::Raster;
+    ///
+    /// let mut g = GradientFill::linear((0.0, 0.0), (256.0, 0.0));
+    /// g.add_stop(0.0, SRgb32::new(1.0, 0.0, 0.0));
+    /// g.add_stop(1.0, SRgb32::new(0.0, 0.0, 1.0));
+    /// let mut r = Raster::<SRgb8>::with_clear(256, 16);
+    /// r.fill_gradient((), &g);
+    /// assert_eq!(r.pixel(0, 0), SRgb8::new(0xFF, 0x00, 0x00));
+    /// assert_eq!(r.pixel(128, 8), SRgb8::new(0x7F, 0x00, 0x80));
+    /// ```
+    pub fn fill_gradient<R, G>(&mut self, reg: R, grad: &GradientFill<G>)
+    where
+        R: Into<Region>,
+        G: Pixel,
+        P::Chan: From<G::Chan>,
+    {
+        let reg = self.intersection(reg.into());
+        let len = reg.width() as usize * reg.height() as usize;
+        let rows = self.rows_mut(reg).zip(reg.y..);
+        for_each_row(rows, len, |(row, y)| {
+            let y = y as f32 + 0.5;
+            for (d, x) in row.iter_mut().zip(reg.x..) {
+                *d = grad.color_at(x as f32 + 0.5, y).convert();
+            }
+        });
+    }
+
     /// Copy a color to a region of the `Raster`.
     ///
     /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
-    ///         (*x*, *y*, *width*, *height*) or the unit type `()`.  Using
-    ///         `()` has the same result as `Raster::region()`.
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
     /// * `clr` Source `Pixel` color.
     ///
     /// ### Copy a color to a rectangle region
@@ -526,19 +1623,74 @@ impl<P: Pixel> Raster<P> {
         R1: Into<Region>,
         Q: Pixel,
     {
-        let (to, from) = (to.into(), from.into());
-        let (tx, ty) = (to.x.min(0).abs(), to.y.min(0).abs());
-        let (fx, fy) = (from.x.min(0).abs(), from.y.min(0).abs());
-        let to = self.intersection(to);
-        let from = src.intersection(from);
-        let width = to.width().min(from.width());
-        let height = to.height().min(from.height());
-        let to = Region::new(to.x + fx, to.y + fy, width, height);
-        let from = Region::new(from.x + tx, from.y + ty, width, height);
-        (to, from)
+        clip_regions(self.region(), to.into(), src.region(), from.into())
+    }
+
+    /// Composite from a source `Raster` using stochastic [Dissolve].
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source `Raster` (*straight* alpha).
+    /// * `from` Region within source `Raster`.
+    /// * `op` Dissolve operation.
+    ///
+    /// Drawn source pixels are converted as *opaque* and replace destination
+    /// pixels.  Regions are clipped the same way as [composite_raster].
+    /// The dissolve pattern is positioned relative to `self`.
+    ///
+    /// [composite_raster]: #method.composite_raster
+    /// [Dissolve]: ops/struct.Dissolve.html
+    ///
+    /// ### Dissolve onto an opaque raster
+    /// ```
+    /// use pix::ops::Dissolve;
+    /// use pix::rgb::{Rgb8, Rgba8};
+    /// use pix::Raster;
+    ///
+    /// let mut r0 = Raster::<Rgb8>::with_clear(100, 100);
+    /// let r1 = Raster::with_color(50, 50, Rgba8::new(255, 0, 0, 128));
+    /// r0.composite_dissolve((10, 10), &r1, (), Dissolve::new(1234));
+    /// ```
+    pub fn composite_dissolve<R0, R1, S>(
+        &mut self,
+        to: R0,
+        src: &Raster<S>,
+        from: R1,
+        op: Dissolve,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        S: Pixel<Alpha = Straight>,
+        P::Chan: From<S::Chan>,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for ((drow, srow), y) in drows.zip(srows).zip(to.y..) {
+            for ((d, s), x) in drow.iter_mut().zip(srow).zip(to.x..) {
+                if op.is_drawn(x, y, s.alpha()) {
+                    let mut s = *s;
+                    if let Some(a) = s.channels_mut().get_mut(S::Model::ALPHA) {
+                        *a = S::Chan::MAX;
+                    }
+                    *d = s.convert();
+                }
+            }
+        }
     }
 
     /// Get view of pixels as a `u8` slice.
+    ///
+    /// Channels are in native-endian byte order.  This is useful for passing
+    /// pixel data to encoders or GPU uploads without copying.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::rgb::Rgba16;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(2, 2, Rgba16::new(1, 2, 3, 4));
+    /// assert_eq!(r.as_u8_slice().len(), 2 * 2 * 8);
+    /// ```
     pub fn as_u8_slice(&self) -> &[u8] {
         unsafe {
             let (prefix, v, suffix) = self.pixels.align_to::<u8>();
@@ -566,11 +1718,14 @@ where
     /// Composite a source color to a region of the `Raster`.
     ///
     /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
-    ///         (*x*, *y*, *width*, *height*) or the unit type `()`.  Using
-    ///         `()` has the same result as `Raster::region()`.
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
     /// * `clr` Source `Pixel` color.
     /// * `op` Compositing operation.
     ///
+    /// The region is clipped to the `Raster`, so it can extend beyond the
+    /// edges.  Translucent colors are blended with the destination.
+    ///
     /// ### Example
     /// ```
     /// use pix::ops::SrcOver;
@@ -590,100 +1745,1083 @@ where
         let width = reg.width();
         let height = reg.height();
         if width > 0 && height > 0 {
-            let drows = self.rows_mut(reg);
-            for drow in drows {
+            let len = width as usize * height as usize;
+            for_each_row(self.rows_mut(reg), len, |drow| {
                 P::composite_color(drow, &clr, op);
-            }
+            });
+        }
+    }
+
+    /// Fill a region of the `Raster` with a color, using a compositing
+    /// operation.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.
+    /// * `clr` Fill color.
+    /// * `op` Compositing operation.
+    ///
+    /// This is the same as [composite_color]: the region is clipped to the
+    /// `Raster`, and translucent colors are blended with the destination.
+    /// Use [copy_color] to replace pixels instead.
+    ///
+    /// [composite_color]: #method.composite_color
+    /// [copy_color]: #method.copy_color
+    ///
+    /// ### Translucent fill, clipped to the raster
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::bgr::Bgra8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(100, 100, Bgra8p::new(99, 0, 99, 255));
+    /// let clr = Bgra8p::new(100, 100, 0, 128);
+    /// r.fill_region((-20, 40, 45, 500), clr, SrcOver);
+    /// assert_eq!(r.pixel(0, 99), Bgra8p::new(149, 100, 49, 255));
+    /// assert_eq!(r.pixel(25, 99), Bgra8p::new(99, 0, 99, 255));
+    /// assert_eq!(r.pixel(0, 39), Bgra8p::new(99, 0, 99, 255));
+    /// ```
+    pub fn fill_region<R, O>(&mut self, reg: R, clr: P, op: O)
+    where
+        R: Into<Region>,
+        O: Blend,
+    {
+        self.composite_color(reg, clr, op);
+    }
+
+    /// Composite a source color to a region of the `Raster` at a constant
+    /// opacity.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `clr` Source `Pixel` color.
+    /// * `op` Compositing operation.
+    /// * `opacity` Multiplier for source *alpha*.
+    pub fn composite_color_opacity<R, O>(
+        &mut self,
+        reg: R,
+        clr: P,
+        op: O,
+        opacity: P::Chan,
+    ) where
+        R: Into<Region>,
+        O: Blend,
+    {
+        let reg = self.intersection(reg.into());
+        for drow in self.rows_mut(reg) {
+            P::composite_color_opacity(drow, &clr, op, opacity);
         }
     }
 
     /// Composite from a matte `Raster` and color.
     ///
     /// * `to` Region within `self` (destination).
-    /// * `src` Source `Raster` matte.
-    /// * `from` Region within source `Raster`.
-    /// * `clr` Color to apply to the matte.
-    /// * `op` Compositing operation.
-    ///
-    /// `to` / `from` can be `Region` structs, tuples of (*x*, *y*, *width*,
-    /// *height*) or the unit type `()`.  Using `()` has the same result as
-    /// `Raster::region()`.
+    /// * `src` Source `Raster` matte.
+    /// * `from` Region within source `Raster`.
+    /// * `clr` Color to apply to the matte.
+    /// * `op` Compositing operation.
+    ///
+    /// `to` / `from` can be `Region` structs, tuples of (*x*, *y*, *width*,
+    /// *height*) or the unit type `()`.  Using `()` has the same result as
+    /// `Raster::region()`.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r0 = Raster::with_clear(100, 100);
+    /// let r1 = Raster::with_color(10, 10, Matte8::new(37));
+    /// // ... load image data
+    /// let clr = Rgba8p::new(50, 100, 150, 200);
+    /// r0.composite_matte((30, 50, 10, 10), &r1, (), clr, SrcOver);
+    /// ```
+    pub fn composite_matte<R0, R1, M, O>(
+        &mut self,
+        to: R0,
+        src: &Raster<M>,
+        from: R1,
+        clr: P,
+        op: O,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        M: Pixel<Chan = P::Chan, Model = Matte, Gamma = P::Gamma>,
+        O: Blend,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for (drow, srow) in drows.zip(srows) {
+            P::composite_matte(drow, srow, &clr, op);
+        }
+    }
+
+    /// Composite from a source `Raster`.
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source `Raster`.
+    /// * `from` Region within source `Raster`.
+    /// * `op` Compositing operation.
+    ///
+    /// `to` / `from` can be `Region` structs, tuples of (*x*, *y*, *width*,
+    /// *height*) or the unit type `()`.  Using `()` has the same result as
+    /// `Raster::region()`.
+    ///
+    /// ```bob
+    /// *------------+      *-------------+
+    /// |            |      |    *------+ |
+    /// | *------+   |      |    |      | |
+    /// | |      |   |      |    | from | |
+    /// | |  to  |   | <--- |    +------+ |
+    /// | +------+   |      |             |
+    /// |            |      |     src     |
+    /// |    self    |      +-------------+
+    /// +------------+
+    /// ```
+    /// The composited `Region` is clamped to the smaller of `to` and `from` in
+    /// both `X` and `Y` dimensions.  Also, `to` and `from` are clipped to
+    /// their respective `Raster` dimensions.  A tuple of (*x*, *y*) can be
+    /// used as an offset, extending to the edge of the `Raster`.  Offsets may
+    /// be negative; clipping `to` also clips the matching part of `from`, and
+    /// vice versa.
+    ///
+    /// ### Blend one `Raster` onto another
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r0 = Raster::with_clear(100, 100);
+    /// let r1 = Raster::with_color(5, 5, Rgba8p::new(80, 0, 80, 200));
+    /// // ... load image data
+    /// r0.composite_raster((40, 40), &r1, (), SrcOver);
+    /// ```
+    #[doc(alias = "blit")]
+    pub fn composite_raster<R0, R1, O>(
+        &mut self,
+        to: R0,
+        src: &Raster<P>,
+        from: R1,
+        op: O,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        O: Blend,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let len = to.width() as usize * to.height() as usize;
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for_each_row(drows.zip(srows), len, |(drow, srow)| {
+            P::composite_slice(drow, srow, op);
+        });
+    }
+
+    /// Composite from a source `Raster` at a constant opacity.
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source `Raster`.
+    /// * `from` Region within source `Raster`.
+    /// * `op` Compositing operation.
+    /// * `opacity` Multiplier for source *alpha*.
+    ///
+    /// Regions are clipped the same way as [composite_raster].
+    ///
+    /// [composite_raster]: #method.composite_raster
+    ///
+    /// ### Draw a layer at 50% opacity
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r0 = Raster::with_clear(100, 100);
+    /// let r1 = Raster::with_color(5, 5, Rgba8p::new(80, 0, 80, 200));
+    /// r0.composite_raster_opacity((40, 40), &r1, (), SrcOver, 0x80.into());
+    /// ```
+    pub fn composite_raster_opacity<R0, R1, O>(
+        &mut self,
+        to: R0,
+        src: &Raster<P>,
+        from: R1,
+        op: O,
+        opacity: P::Chan,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        O: Blend,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for (drow, srow) in drows.zip(srows) {
+            P::composite_slice_opacity(drow, srow, op, opacity);
+        }
+    }
+
+    /// Composite from a source `Raster` of another pixel format.
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source `Raster`.
+    /// * `from` Region within source `Raster`.
+    /// * `op` Compositing operation.
+    ///
+    /// Source pixels are converted and composited in a single pass, without
+    /// an intermediate `Raster`.  Regions are clipped the same way as
+    /// [composite_raster].
+    ///
+    /// [composite_raster]: #method.composite_raster
+    ///
+    /// ### Blend a decoded image onto a canvas
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::{Rgba8p, SRgba8};
+    /// use pix::Raster;
+    ///
+    /// let mut r0 = Raster::<Rgba8p>::with_clear(100, 100);
+    /// let r1 = Raster::with_color(5, 5, SRgba8::new(80, 0, 80, 200));
+    /// r0.composite_raster_convert((40, 40), &r1, (), SrcOver);
+    /// ```
+    pub fn composite_raster_convert<R0, R1, S, O>(
+        &mut self,
+        to: R0,
+        src: &Raster<S>,
+        from: R1,
+        op: O,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        S: Pixel,
+        P::Chan: From<S::Chan>,
+        O: Blend,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for (drow, srow) in drows.zip(srows) {
+            P::composite_slice_convert(drow, srow, op);
+        }
+    }
+
+    /// Composite from one region to another within the same `Raster`.
+    ///
+    /// * `to` Destination region.
+    /// * `from` Source region.
+    /// * `op` Compositing operation.
+    ///
+    /// Regions are clipped the same way as [composite_raster], and may
+    /// overlap.
+    ///
+    /// [composite_raster]: #method.composite_raster
+    ///
+    /// ### Blend a region onto a shifted copy of itself
+    /// ```
+    /// use pix::ops::SrcOver;
+    /// use pix::rgb::Rgba8p;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_clear(100, 100);
+    /// r.copy_color((10, 10, 20, 20), Rgba8p::new(80, 0, 80, 200));
+    /// r.blend_region((15, 15, 20, 20), (10, 10, 20, 20), SrcOver);
+    /// ```
+    pub fn blend_region<R0, R1, O>(&mut self, to: R0, from: R1, op: O)
+    where
+        R0: Into<Region>,
+        R1: Into<Region>,
+        O: Blend,
+    {
+        let (to, from) = self.clip_regions(to, self, from);
+        let width = self.width() as usize;
+        let (tx, fx) = (to.x as usize, from.x as usize);
+        let w = to.width() as usize;
+        let mut buf = Vec::with_capacity(w);
+        let height = to.height() as usize;
+        for j in 0..height {
+            // when moving down, blend bottom rows first to avoid overwriting
+            let i = if to.y > from.y { height - 1 - j } else { j };
+            let s = (from.y as usize + i) * width + fx;
+            buf.clear();
+            buf.extend_from_slice(&self.pixels[s..s + w]);
+            let d = (to.y as usize + i) * width + tx;
+            P::composite_slice(&mut self.pixels[d..d + w], &buf, op);
+        }
+    }
+}
+
+impl<P> Raster<P>
+where
+    P: Pixel<Model = Matte>,
+{
+    /// Construct a matte `Raster` by thresholding another `Raster`.
+    ///
+    /// * `src` Source `Raster`.
+    /// * `value` Minimum luminance of source pixels to be set to `MAX`;
+    ///   lower values are set to `MIN`.
+    ///
+    /// Luminance is compared using the source pixel's gamma mode, ignoring
+    /// *alpha*.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::rgb::SRgb8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::with_color(4, 4, SRgb8::new(0x20, 0x20, 0x20));
+    /// *r.pixel_mut(1, 2) = SRgb8::new(0xA0, 0x90, 0x80);
+    /// let m = Raster::<Matte8>::with_threshold(&r, 0x80.into());
+    /// assert_eq!(m.pixel(1, 2), Matte8::new(0xFF));
+    /// assert_eq!(m.pixel(2, 2), Matte8::new(0x00));
+    /// ```
+    pub fn with_threshold<S>(src: &Raster<S>, value: S::Chan) -> Self
+    where
+        S: Pixel,
+    {
+        let mut r = Raster::<P>::with_clear(src.width(), src.height());
+        for (d, s) in r.pixels_mut().iter_mut().zip(src.pixels()) {
+            let g: Pix1<S::Chan, Gray, Straight, S::Gamma> = s.convert();
+            if g.one() >= value {
+                *d.alpha_mut() = P::Chan::MAX;
+            }
+        }
+        r
+    }
+
+    /// Threshold a region of a matte `Raster` to binary values.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.  Using `()` has
+    ///   the same result as `Raster::region()`.
+    /// * `value` Minimum alpha to be set to `MAX`; lower values are set to
+    ///   `MIN`.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::matte::Matte8;
+    /// use pix::Raster;
+    ///
+    /// let mut m = Raster::with_color(10, 10, Matte8::new(0x90));
+    /// m.threshold_matte((), 0x80.into());
+    /// assert_eq!(m.pixel(0, 0), Matte8::new(0xFF));
+    /// ```
+    pub fn threshold_matte<R>(&mut self, reg: R, value: P::Chan)
+    where
+        R: Into<Region>,
+    {
+        let reg = self.intersection(reg.into());
+        for drow in self.rows_mut(reg) {
+            Matte::threshold(drow, value);
+        }
+    }
+
+    /// Combine a source matte `Raster` using a boolean operation.
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source matte `Raster`.
+    /// * `from` Region within source `Raster`.
+    /// * `op` Boolean operation.
+    ///
+    /// `to` / `from` can be `Region` structs, tuples of (*x*, *y*, *width*,
+    /// *height*) or the unit type `()`.  Using `()` has the same result as
+    /// `Raster::region()`.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::matte::{Matte8, MatteOp};
+    /// use pix::Raster;
+    ///
+    /// let mut m0 = Raster::with_color(10, 10, Matte8::new(0xFF));
+    /// let m1 = Raster::with_color(5, 5, Matte8::new(0xFF));
+    /// m0.combine_matte((5, 5), &m1, (), MatteOp::AndNot);
+    /// assert_eq!(m0.pixel(4, 4), Matte8::new(0xFF));
+    /// assert_eq!(m0.pixel(5, 5), Matte8::new(0));
+    /// ```
+    pub fn combine_matte<R0, R1>(
+        &mut self,
+        to: R0,
+        src: &Raster<P>,
+        from: R1,
+        op: MatteOp,
+    ) where
+        R0: Into<Region>,
+        R1: Into<Region>,
+    {
+        let (to, from) = self.clip_regions(to, src, from);
+        let srows = src.rows(from);
+        let drows = self.rows_mut(to);
+        for (drow, srow) in drows.zip(srows) {
+            Matte::combine(drow, srow, op);
+        }
+    }
+}
+
+impl<'a, P: Pixel> Rows<'a, P> {
+    /// Create a new row `Iterator`.
+    ///
+    /// * `pixels` Pixels of full rows (last row may omit padding).
+    /// * `width` Width of full rows.
+    /// * `reg` Region within rows (must be clipped).
+    fn new(pixels: &'a [P], width: usize, reg: Region) -> Self {
+        let start = reg.y as usize * width;
+        let end = (reg.bottom() as usize * width).min(pixels.len());
+        let slice = &pixels[start.min(end)..end];
+        let chunks = slice.chunks(width.max(1));
+        let x = reg.x as usize;
+        let w = reg.width as usize;
+        let columns = x..x + w;
+        Rows { chunks, columns }
+    }
+}
+
+impl<'a, P: Pixel> Iterator for Rows<'a, P> {
+    type Item = &'a [P];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|s| &s[self.columns.clone()])
+    }
+}
+
+impl<'a, P: Pixel> RowsMut<'a, P> {
+    /// Create a new mutable row `Iterator`.
+    ///
+    /// * `pixels` Pixels of full rows (last row may omit padding).
+    /// * `width` Width of full rows.
+    /// * `reg` Region within rows (must be clipped).
+    fn new(pixels: &'a mut [P], width: usize, reg: Region) -> Self {
+        let start = reg.y as usize * width;
+        let end = (reg.bottom() as usize * width).min(pixels.len());
+        let slice = &mut pixels[start.min(end)..end];
+        let chunks = slice.chunks_mut(width.max(1));
+        let x = reg.x as usize;
+        let w = reg.width as usize;
+        let columns = x..x + w;
+        RowsMut { chunks, columns }
+    }
+}
+
+impl<'a, P: Pixel> Iterator for RowsMut<'a, P> {
+    type Item = &'a mut [P];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|s| &mut s[self.columns.clone()])
+    }
+}
+
+impl<'a, P: Pixel> RowsMutChunks<'a, P> {
+    /// Create a new `Iterator` of row bands.
+    fn new(raster: &'a mut Raster<P>, reg: Region, n: usize) -> Self {
+        let width = raster.width() as usize;
+        let start = reg.y as usize * width;
+        let end = reg.bottom() as usize * width;
+        let slice = &mut raster.pixels[start..end];
+        let chunks = slice.chunks_mut((width * n).max(1));
+        let x = reg.x as usize;
+        let w = reg.width as usize;
+        let columns = x..x + w;
+        RowsMutChunks {
+            chunks,
+            width,
+            columns,
+        }
+    }
+}
+
+impl<'a, P: Pixel> Iterator for RowsMutChunks<'a, P> {
+    type Item = RowsMut<'a, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|s| RowsMut {
+            chunks: s.chunks_mut(self.width),
+            columns: self.columns.clone(),
+        })
+    }
+}
+
+impl<'a, P: Pixel> RasterView<'a, P> {
+    /// Create a new view (`reg` must be clipped).
+    fn new(pixels: &'a [P], stride: usize, reg: Region) -> Self {
+        let start = reg.y as usize * stride;
+        let end = (reg.bottom() as usize * stride).min(pixels.len());
+        let pixels = &pixels[start.min(end)..end];
+        let region = Region::new(reg.x, 0, reg.width(), reg.height());
+        RasterView {
+            pixels,
+            stride,
+            region,
+            profile: None,
+        }
+    }
+
+    /// Construct a view of pixels with padded rows.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Distance between the start of each row, in pixels.
+    /// * `pixels` Pixel data.  Padding after the last row can be omitted.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `stride` is less than `width`
+    /// * If `pixels` is too small
+    pub fn with_pixels(
+        width: u32,
+        height: u32,
+        stride: usize,
+        pixels: &'a [P],
+    ) -> Self {
+        let reg = strided_region(width, height, stride, pixels.len());
+        RasterView::new(pixels, stride, reg)
+    }
+
+    /// Construct a view of a `u8` buffer with padded rows.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Distance between the start of each row, in bytes.
+    /// * `buffer` Buffer of pixel data.  Padding after the last row can be
+    ///   omitted.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `stride` is not a multiple of `std::mem::size_of::<P>()`
+    /// * If `stride` is less than `width`
+    /// * If `buffer` is too small
+    ///
+    /// ### Wrap a buffer with 256-byte aligned rows
+    /// ```
+    /// use pix::bgr::Bgra8;
+    /// use pix::RasterView;
+    ///
+    /// let buf = vec![0xFF; 256 * 9 + 50 * 4];
+    /// let v = RasterView::<Bgra8>::with_u8_buffer(50, 10, 256, &buf);
+    /// assert_eq!(v.rows(()).count(), 10);
+    /// ```
+    pub fn with_u8_buffer(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a [u8],
+    ) -> Self
+    where
+        P: Pixel<Chan = Ch8>,
+    {
+        let stride = pixel_stride::<P>(stride);
+        // Ch8 pixels have an alignment of 1, so there is no prefix
+        let (_, pixels, _) = unsafe { buffer.align_to::<P>() };
+        RasterView::with_pixels(width, height, stride, pixels)
+    }
+
+    /// Get width of view.
+    pub fn width(&self) -> u32 {
+        self.region.width()
+    }
+
+    /// Get height of view.
+    pub fn height(&self) -> u32 {
+        self.region.height()
+    }
+
+    /// Get `Region` of entire view.
+    pub fn region(&self) -> Region {
+        Region::new(0, 0, self.width(), self.height())
+    }
+
+    /// Get one pixel value.
+    pub fn pixel(&self, x: i32, y: i32) -> P {
+        assert!(x >= 0 && x < self.region.width);
+        assert!(y >= 0 && y < self.region.height);
+        self.pixels[y as usize * self.stride + (self.region.x + x) as usize]
+    }
+
+    /// Get an `Iterator` of rows within the view.
+    ///
+    /// * `reg` Region of the view to iterate.
+    pub fn rows<R>(&self, reg: R) -> Rows<'a, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.parent_region(reg.into());
+        Rows::new(self.pixels, self.stride, reg)
+    }
+
+    /// Get a view of a region within the view.
+    pub fn view<R>(&self, reg: R) -> RasterView<'a, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.parent_region(reg.into());
+        let mut view = RasterView::new(self.pixels, self.stride, reg);
+        view.profile = self.profile;
+        view
+    }
+
+    /// Clip a region and translate it to parent coordinates
+    fn parent_region(&self, reg: Region) -> Region {
+        let reg = reg.intersection(self.region());
+        Region::new(reg.x + self.region.x, reg.y, reg.width(), reg.height())
+    }
+}
+
+/// Get the clipped region of a strided pixel buffer
+fn strided_region(
+    width: u32,
+    height: u32,
+    stride: usize,
+    len: usize,
+) -> Region {
+    let reg = Region::new(0, 0, width, height);
+    assert!(stride >= width as usize);
+    if width > 0 && height > 0 {
+        assert!(len >= stride * (height as usize - 1) + width as usize);
+    }
+    reg
+}
+
+/// Convert a stride in bytes to pixels
+fn pixel_stride<P: Pixel>(stride: usize) -> usize {
+    let sz = std::mem::size_of::<P>();
+    assert_eq!(stride % sz, 0);
+    stride / sz
+}
+
+/// Call a function for each row, splitting work across threads
+///
+/// * `rows` Iterator of rows.
+/// * `len` Total number of pixels, used to decide whether to split.
+/// * `f` Function to call for each row.
+#[cfg(feature = "rayon")]
+pub(crate) fn for_each_row<I, F>(rows: I, len: usize, f: F)
+where
+    I: Iterator,
+    I::Item: Send,
+    F: Fn(I::Item) + Send + Sync,
+{
+    use rayon::prelude::*;
+    if len >= PAR_THRESHOLD {
+        let rows: Vec<_> = rows.collect();
+        rows.into_par_iter().for_each(f);
+    } else {
+        rows.for_each(f);
+    }
+}
+
+/// Call a function for each row
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn for_each_row<I, F>(rows: I, _len: usize, f: F)
+where
+    I: Iterator,
+    F: Fn(I::Item),
+{
+    rows.for_each(f);
+}
+
+/// Swap bytes of each channel between little-endian and native order
+#[cfg(feature = "serde")]
+fn swap_le_bytes<P: Pixel>(bytes: &mut [u8]) {
+    let n = std::mem::size_of::<P::Chan>();
+    if cfg!(target_endian = "big") && n > 1 {
+        for c in bytes.chunks_exact_mut(n) {
+            c.reverse();
+        }
+    }
+}
+
+/// Raster pixel data, serialized as bytes
+#[cfg(feature = "serde")]
+struct RasterBytes(Vec<u8>);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RasterBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RasterBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{SeqAccess, Visitor};
+
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = RasterBytes;
+
+            fn expecting(
+                &self,
+                f: &mut std::fmt::Formatter,
+            ) -> std::fmt::Result {
+                write!(f, "pixel bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(RasterBytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(RasterBytes(v))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(b) = seq.next_element()? {
+                    v.push(b);
+                }
+                Ok(RasterBytes(v))
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+/// Serialized form of a `Raster`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Raster")]
+struct RasterDef {
+    width: u32,
+    height: u32,
+    pixels: RasterBytes,
+}
+
+/// Rasters are serialized with `width`, `height` and `pixels`, which holds
+/// the channels as bytes (little-endian for 16- and 32-bit channels).
+#[cfg(feature = "serde")]
+impl<P: Pixel> serde::Serialize for Raster<P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut bytes = self.as_u8_slice().to_vec();
+        swap_le_bytes::<P>(&mut bytes);
+        let def = RasterDef {
+            width: self.width(),
+            height: self.height(),
+            pixels: RasterBytes(bytes),
+        };
+        def.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: Pixel> serde::Deserialize<'de> for Raster<P> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let def = RasterDef::deserialize(deserializer)?;
+        let mut bytes = def.pixels.0;
+        swap_le_bytes::<P>(&mut bytes);
+        let mut r = Raster::<P>::from_u8_buffer_unchecked(
+            def.width,
+            def.height,
+            bytes.into(),
+        )
+        .map_err(D::Error::custom)?;
+        if std::any::TypeId::of::<P::Chan>() == std::any::TypeId::of::<Ch32>() {
+            // clamp float channels to the valid range
+            for p in r.pixels_mut() {
+                for c in p.channels_mut() {
+                    *c = P::Chan::from(c.to_f32());
+                }
+            }
+        }
+        Ok(r)
+    }
+}
+
+/// Clip `to` / `from` regions within destination / source regions
+fn clip_regions(
+    dst: Region,
+    to: Region,
+    src: Region,
+    from: Region,
+) -> (Region, Region) {
+    // skip columns / rows which are outside either raster (negative)
+    let dx = to.x.min(from.x).min(0).saturating_neg();
+    let dy = to.y.min(from.y).min(0).saturating_neg();
+    let to = dst.intersection(to.shrink(dx, dy));
+    let from = src.intersection(from.shrink(dx, dy));
+    let width = to.width().min(from.width());
+    let height = to.height().min(from.height());
+    let to = Region::new(to.x, to.y, width, height);
+    let from = Region::new(from.x, from.y, width, height);
+    (to, from)
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a `Raster` by copying pixels from a view.
+    ///
+    /// ### Crop a `Raster`
+    /// ```
+    /// use pix::rgb::Rgb8;
+    /// use pix::Raster;
+    ///
+    /// let r = Raster::with_color(10, 10, Rgb8::new(40, 80, 120));
+    /// let c = Raster::with_view(&r.view((2, 2, 4, 3)));
+    /// assert_eq!((c.width(), c.height()), (4, 3));
+    /// ```
+    pub fn with_view(view: &RasterView<P>) -> Self {
+        let mut r = Raster::with_clear(view.width(), view.height());
+        r.profile = view.profile.cloned();
+        for (drow, srow) in r.rows_mut(()).zip(view.rows(())) {
+            drow.copy_from_slice(srow);
+        }
+        r
+    }
+}
+
+impl<'a, P: Pixel> RasterViewMut<'a, P> {
+    /// Create a new mutable view (`reg` must be clipped).
+    fn new(pixels: &'a mut [P], stride: usize, reg: Region) -> Self {
+        let start = reg.y as usize * stride;
+        let end = (reg.bottom() as usize * stride).min(pixels.len());
+        let pixels = &mut pixels[start.min(end)..end];
+        let region = Region::new(reg.x, 0, reg.width(), reg.height());
+        RasterViewMut {
+            pixels,
+            stride,
+            region,
+        }
+    }
+
+    /// Construct a mutable view of pixels with padded rows.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Distance between the start of each row, in pixels.
+    /// * `pixels` Pixel data.  Padding after the last row can be omitted.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `stride` is less than `width`
+    /// * If `pixels` is too small
+    #[doc(alias = "from_slice_mut")]
+    pub fn with_pixels(
+        width: u32,
+        height: u32,
+        stride: usize,
+        pixels: &'a mut [P],
+    ) -> Self {
+        let reg = strided_region(width, height, stride, pixels.len());
+        RasterViewMut::new(pixels, stride, reg)
+    }
+
+    /// Construct a mutable view of a `u8` buffer with padded rows.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Distance between the start of each row, in bytes.
+    /// * `buffer` Buffer of pixel data.  Padding after the last row can be
+    ///   omitted.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `stride` is not a multiple of `std::mem::size_of::<P>()`
+    /// * If `stride` is less than `width`
+    /// * If `buffer` is too small
+    pub fn with_u8_buffer(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Self
+    where
+        P: Pixel<Chan = Ch8>,
+    {
+        let stride = pixel_stride::<P>(stride);
+        // Ch8 pixels have an alignment of 1, so there is no prefix
+        let (_, pixels, _) = unsafe { buffer.align_to_mut::<P>() };
+        RasterViewMut::with_pixels(width, height, stride, pixels)
+    }
+
+    /// Get an immutable view.
+    pub fn as_view(&self) -> RasterView<'_, P> {
+        RasterView {
+            pixels: self.pixels,
+            stride: self.stride,
+            region: self.region,
+            profile: None,
+        }
+    }
+
+    /// Get width of view.
+    pub fn width(&self) -> u32 {
+        self.region.width()
+    }
+
+    /// Get height of view.
+    pub fn height(&self) -> u32 {
+        self.region.height()
+    }
+
+    /// Get `Region` of entire view.
+    pub fn region(&self) -> Region {
+        Region::new(0, 0, self.width(), self.height())
+    }
+
+    /// Get one pixel value.
+    pub fn pixel(&self, x: i32, y: i32) -> P {
+        self.as_view().pixel(x, y)
+    }
+
+    /// Get a mutable pixel.
+    pub fn pixel_mut(&mut self, x: i32, y: i32) -> &mut P {
+        assert!(x >= 0 && x < self.region.width);
+        assert!(y >= 0 && y < self.region.height);
+        &mut self.pixels
+            [y as usize * self.stride + (self.region.x + x) as usize]
+    }
+
+    /// Get an `Iterator` of rows within the view.
+    ///
+    /// * `reg` Region of the view to iterate.
+    pub fn rows<R>(&self, reg: R) -> Rows<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.as_view().parent_region(reg.into());
+        Rows::new(self.pixels, self.stride, reg)
+    }
+
+    /// Get an `Iterator` of mutable rows within the view.
+    ///
+    /// * `reg` Region of the view to iterate.
+    ///
+    /// ### Fill a view
+    /// ```
+    /// use pix::gray::Gray8;
+    /// use pix::Raster;
+    ///
+    /// let mut r = Raster::<Gray8>::with_clear(10, 10);
+    /// let mut v = r.view_mut((2, 2, 4, 4));
+    /// for row in v.rows_mut(()) {
+    ///     row.fill(Gray8::new(0x80));
+    /// }
+    /// assert_eq!(r.pixel(5, 5), Gray8::new(0x80));
+    /// assert_eq!(r.pixel(6, 6), Gray8::new(0x00));
+    /// ```
+    pub fn rows_mut<R>(&mut self, reg: R) -> RowsMut<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.as_view().parent_region(reg.into());
+        RowsMut::new(self.pixels, self.stride, reg)
+    }
+
+    /// Get a mutable view of a region within the view.
+    pub fn view_mut<R>(&mut self, reg: R) -> RasterViewMut<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.as_view().parent_region(reg.into());
+        RasterViewMut::new(self.pixels, self.stride, reg)
+    }
+
+    /// Copy a color to a region of the view.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.
+    /// * `clr` Source `Pixel` color.
+    pub fn copy_color<R>(&mut self, reg: R, clr: P)
+    where
+        R: Into<Region>,
+    {
+        for drow in self.rows_mut(reg) {
+            P::copy_color(drow, &clr);
+        }
+    }
+
+    /// Copy from a source view.
+    ///
+    /// * `to` Region within `self` (destination).
+    /// * `src` Source view.
+    /// * `from` Region within source view.
     ///
-    /// ### Example
-    /// ```
-    /// use pix::matte::Matte8;
-    /// use pix::ops::SrcOver;
-    /// use pix::rgb::Rgba8p;
-    /// use pix::Raster;
+    /// Regions are clipped the same way as [Raster::composite_raster].
     ///
-    /// let mut r0 = Raster::with_clear(100, 100);
-    /// let r1 = Raster::with_color(10, 10, Matte8::new(37));
-    /// // ... load image data
-    /// let clr = Rgba8p::new(50, 100, 150, 200);
-    /// r0.composite_matte((30, 50, 10, 10), &r1, (), clr, SrcOver);
-    /// ```
-    pub fn composite_matte<R0, R1, M, O>(
-        &mut self,
-        to: R0,
-        src: &Raster<M>,
-        from: R1,
-        clr: P,
-        op: O,
-    ) where
+    /// [Raster::composite_raster]: struct.Raster.html#method.composite_raster
+    pub fn copy_raster<R0, R1>(&mut self, to: R0, src: &RasterView<P>, from: R1)
+    where
         R0: Into<Region>,
         R1: Into<Region>,
-        M: Pixel<Chan = P::Chan, Model = Matte, Gamma = P::Gamma>,
-        O: Blend,
     {
-        let (to, from) = self.clip_regions(to, src, from);
+        let (to, from) =
+            clip_regions(self.region(), to.into(), src.region(), from.into());
         let srows = src.rows(from);
-        let drows = self.rows_mut(to);
-        for (drow, srow) in drows.zip(srows) {
-            P::composite_matte(drow, srow, &clr, op);
+        for (drow, srow) in self.rows_mut(to).zip(srows) {
+            P::copy_slice(drow, srow);
         }
     }
+}
 
-    /// Composite from a source `Raster`.
+impl<P> RasterViewMut<'_, P>
+where
+    P: Pixel<Alpha = Premultiplied, Gamma = Linear>,
+{
+    /// Composite a source color to a region of the view.
+    ///
+    /// * `reg` Region within `self`.  It can be a `Region` struct, tuple of
+    ///   (*x*, *y*, *width*, *height*) or the unit type `()`.
+    /// * `clr` Source `Pixel` color.
+    /// * `op` Compositing operation.
+    pub fn composite_color<R, O>(&mut self, reg: R, clr: P, op: O)
+    where
+        R: Into<Region>,
+        O: Blend,
+    {
+        for drow in self.rows_mut(reg) {
+            P::composite_color(drow, &clr, op);
+        }
+    }
+
+    /// Composite from a source view.
     ///
     /// * `to` Region within `self` (destination).
-    /// * `src` Source `Raster`.
-    /// * `from` Region within source `Raster`.
+    /// * `src` Source view.
+    /// * `from` Region within source view.
     /// * `op` Compositing operation.
     ///
-    /// `to` / `from` can be `Region` structs, tuples of (*x*, *y*, *width*,
-    /// *height*) or the unit type `()`.  Using `()` has the same result as
-    /// `Raster::region()`.
+    /// Regions are clipped the same way as [Raster::composite_raster].
     ///
-    /// ```bob
-    /// *------------+      *-------------+
-    /// |            |      |    *------+ |
-    /// | *------+   |      |    |      | |
-    /// | |      |   |      |    | from | |
-    /// | |  to  |   | <--- |    +------+ |
-    /// | +------+   |      |             |
-    /// |            |      |     src     |
-    /// |    self    |      +-------------+
-    /// +------------+
-    /// ```
-    /// The composited `Region` is clamped to the smaller of `to` and `from` in
-    /// both `X` and `Y` dimensions.  Also, `to` and `from` are clipped to
-    /// their respective `Raster` dimensions.
+    /// [Raster::composite_raster]: struct.Raster.html#method.composite_raster
     ///
-    /// ### Blend one `Raster` onto another
+    /// ### Draw a sprite directly into a frame buffer
     /// ```
+    /// use pix::bgr::Bgra8p;
     /// use pix::ops::SrcOver;
-    /// use pix::rgb::Rgba8p;
-    /// use pix::Raster;
+    /// use pix::{Raster, RasterViewMut};
     ///
-    /// let mut r0 = Raster::with_clear(100, 100);
-    /// let r1 = Raster::with_color(5, 5, Rgba8p::new(80, 0, 80, 200));
-    /// // ... load image data
-    /// r0.composite_raster((40, 40), &r1, (), SrcOver);
+    /// let sprite = Raster::with_color(8, 8, Bgra8p::new(0, 0, 0x80, 0x80));
+    /// let mut frame = vec![0xFF; 64 * 48 * 4];
+    /// let mut fb =
+    ///     RasterViewMut::<Bgra8p>::with_u8_buffer(64, 48, 64 * 4, &mut frame);
+    /// fb.composite_raster((10, 10), &sprite.view(()), (), SrcOver);
+    /// assert_eq!(fb.pixel(12, 12), Bgra8p::new(0x7F, 0x7F, 0xFF, 0xFF));
     /// ```
+    #[doc(alias = "blit")]
     pub fn composite_raster<R0, R1, O>(
         &mut self,
         to: R0,
-        src: &Raster<P>,
+        src: &RasterView<P>,
         from: R1,
         op: O,
     ) where
@@ -691,58 +2829,46 @@ where
         R1: Into<Region>,
         O: Blend,
     {
-        let (to, from) = self.clip_regions(to, src, from);
+        let (to, from) =
+            clip_regions(self.region(), to.into(), src.region(), from.into());
         let srows = src.rows(from);
-        let drows = self.rows_mut(to);
-        for (drow, srow) in drows.zip(srows) {
+        for (drow, srow) in self.rows_mut(to).zip(srows) {
             P::composite_slice(drow, srow, op);
         }
     }
 }
 
-impl<'a, P: Pixel> Rows<'a, P> {
-    /// Create a new row `Iterator`.
-    fn new(raster: &'a Raster<P>, reg: Region) -> Self {
-        let width = raster.width() as usize;
-        let start = reg.y as usize * width;
-        let end = reg.bottom() as usize * width;
-        let slice = &raster.pixels[start..end];
-        let chunks = slice.chunks_exact(width);
-        let x = reg.x as usize;
-        let w = reg.width as usize;
-        let columns = x..x + w;
-        Rows { chunks, columns }
-    }
-}
-
-impl<'a, P: Pixel> Iterator for Rows<'a, P> {
-    type Item = &'a [P];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.chunks.next().map(|s| &s[self.columns.clone()])
-    }
+/// Serialized form of a `Region`, with unsigned dimensions
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Region")]
+struct RegionDef {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
 }
 
-impl<'a, P: Pixel> RowsMut<'a, P> {
-    /// Create a new mutable row `Iterator`.
-    fn new(raster: &'a mut Raster<P>, reg: Region) -> Self {
-        let width = raster.width() as usize;
-        let start = reg.y as usize * width;
-        let end = reg.bottom() as usize * width;
-        let slice = &mut raster.pixels[start..end];
-        let chunks = slice.chunks_exact_mut(width);
-        let x = reg.x as usize;
-        let w = reg.width as usize;
-        let columns = x..x + w;
-        RowsMut { chunks, columns }
+#[cfg(feature = "serde")]
+impl From<Region> for RegionDef {
+    fn from(reg: Region) -> Self {
+        RegionDef {
+            x: reg.x,
+            y: reg.y,
+            width: reg.width(),
+            height: reg.height(),
+        }
     }
 }
 
-impl<'a, P: Pixel> Iterator for RowsMut<'a, P> {
-    type Item = &'a mut [P];
+#[cfg(feature = "serde")]
+impl TryFrom<RegionDef> for Region {
+    type Error = &'static str;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.chunks.next().map(|s| &mut s[self.columns.clone()])
+    fn try_from(reg: RegionDef) -> Result<Self, Self::Error> {
+        let width = i32::try_from(reg.width).map_err(|_| WIDTH_TOO_BIG)?;
+        let height = i32::try_from(reg.height).map_err(|_| HEIGHT_TOO_BIG)?;
+        Ok(Region::new(reg.x, reg.y, width as u32, height as u32))
     }
 }
 
@@ -798,6 +2924,16 @@ impl Region {
         }
     }
 
+    /// Shrink by removing columns from the left and rows from the top
+    fn shrink(self, dx: i32, dy: i32) -> Self {
+        Region {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+            width: self.width.saturating_sub(dx).max(0),
+            height: self.height.saturating_sub(dy).max(0),
+        }
+    }
+
     /// Get the left side
     pub fn left(self) -> i32 {
         self.x
@@ -831,8 +2967,15 @@ impl Region {
 
 #[cfg(test)]
 #[rustfmt::skip]
+#[allow(clippy::useless_vec)]
 mod test {
+    use crate::bgr::*;
+    use crate::chan::*;
+    use crate::dither::*;
+    use crate::el::Pixel;
+    use crate::filter::*;
     use crate::gray::*;
+    use crate::hsv::*;
     use crate::hwb::*;
     use crate::matte::*;
     use crate::ops::*;
@@ -844,6 +2987,40 @@ mod test {
         assert_eq!(std::mem::size_of::<Region>(), 16);
     }
 
+    #[test]
+    fn profile_kept() {
+        let mut data = vec![0; 132];
+        data[36..40].copy_from_slice(b"acsp");
+        let mut r = Raster::with_color(4, 3, SRgb8::new(0x40, 0x80, 0xC0));
+        r.set_profile(Some(icc::IccProfile::new(data).unwrap()));
+        let k = Kernel::new(3, 3, vec![1.0 / 9.0; 9]);
+        assert!(r.scaled(8, 6, Filter::Bilinear).profile().is_some());
+        assert!(r.rotated_90().profile().is_some());
+        assert!(r.flipped_horizontal().profile().is_some());
+        let bg = SRgb8::default();
+        assert!(r.rotated(1.0, Filter::Bilinear, bg).profile().is_some());
+        assert!(r.gaussian_blur(1.0).profile().is_some());
+        assert!(r.convolved(&k, EdgeMode::Clamp).profile().is_some());
+        assert!(r.mipmaps()[0].profile().is_some());
+        let v = r.view(()).view((1, 1, 2, 2));
+        assert!(Raster::with_view(&v).profile().is_some());
+        let s = Raster::<SBgr8>::with_swizzle(&r, &[2, 1, 0]);
+        assert!(s.profile().is_some());
+        assert!(Raster::<SRgba16>::with_raster(&r).profile().is_some());
+        let m = r.map(|p| SRgba8::new(p.one(), p.two(), p.three(), Ch8::MAX));
+        assert!(m.profile().is_some());
+        // color model or gamma changed
+        assert!(Raster::<Rgb8>::with_raster(&r).profile().is_none());
+        assert!(Raster::<SGray8>::with_raster(&r).profile().is_none());
+        assert!(r.map(|p| Matte8::new(p.one())).profile().is_none());
+        assert!(r.clone().convert_in_place::<Rgb8>().profile().is_none());
+        assert!(r.convert_in_place::<SRgb8>().profile().is_some());
+        // constructed from pixels
+        let pixels = [SRgb8::default(); 4];
+        let v = RasterView::with_pixels(2, 2, 2, &pixels);
+        assert!(Raster::with_view(&v).profile().is_none());
+    }
+
     #[test]
     fn buffers() {
         let buf = vec![0x80; 64];
@@ -1044,6 +3221,56 @@ mod test {
         assert_eq!(rgb.pixels(), &v[..]);
     }
 
+    #[test]
+    fn composite_raster_clipped() {
+        let mut r0 = Raster::<Matte8>::with_clear(3, 3);
+        let mut r1 = Raster::<Matte8>::with_clear(3, 3);
+        for (i, p) in r1.pixels_mut().iter_mut().enumerate() {
+            *p = Matte8::new(i as u8 + 1);
+        }
+        r0.composite_raster((-1, 2), &r1, (), Src);
+        r0.composite_raster((1, 0, 2, 2), &r1, (-1, 0), Src);
+        r0.composite_raster((0, 0, 1, 1), &r1, (2, 2, 5, 5), Src);
+        let v = [
+            Matte8::new(9), Matte8::new(0), Matte8::new(1),
+            Matte8::new(0), Matte8::new(0), Matte8::new(4),
+            Matte8::new(2), Matte8::new(3), Matte8::new(0),
+        ];
+        assert_eq!(r0.pixels(), &v);
+    }
+
+    #[test]
+    fn blend_region_overlap() {
+        let mut r = Raster::<Matte8>::with_clear(3, 3);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Matte8::new(i as u8 + 1);
+        }
+        r.blend_region((1, 1), (), Src);
+        r.blend_region((-1, 0, 2, 1), (0, 2, 3, 1), Src);
+        let v = [
+            Matte8::new(4), Matte8::new(2), Matte8::new(3),
+            Matte8::new(4), Matte8::new(1), Matte8::new(2),
+            Matte8::new(7), Matte8::new(4), Matte8::new(5),
+        ];
+        assert_eq!(r.pixels(), &v);
+    }
+
+    #[test]
+    fn composite_raster_negative() {
+        let mut r0 = Raster::<Matte8>::with_clear(3, 2);
+        let mut r1 = Raster::<Matte8>::with_clear(3, 2);
+        for (i, p) in r1.pixels_mut().iter_mut().enumerate() {
+            *p = Matte8::new(i as u8 + 1);
+        }
+        r0.composite_raster((), &r1, (-1, 0), Src);
+        r0.composite_raster((-1, 1), &r1, (-2, 0), Src);
+        let v = [
+            Matte8::new(0), Matte8::new(1), Matte8::new(2),
+            Matte8::new(0), Matte8::new(1), Matte8::new(2),
+        ];
+        assert_eq!(r0.pixels(), &v);
+    }
+
     #[test]
     fn composite_matte_full() {
         let mut r = Raster::<Rgba8p>::with_clear(2, 2);
@@ -1107,6 +3334,459 @@ mod test {
         assert_eq!(g0.pixels(), &v[..]);
     }
 
+    #[test]
+    fn matte_threshold_combine() {
+        let mut m0 = Raster::<Matte8>::with_clear(3, 3);
+        m0.copy_color((0, 0, 2, 2), Matte8::new(0x60));
+        m0.threshold_matte((1, 1, 2, 2), 0x40.into());
+        let m1 = Raster::with_color(2, 2, Matte8::new(0xFF));
+        m0.combine_matte((1, 0), &m1, (), MatteOp::Xor);
+        let v = [
+            Matte8::new(0x60), Matte8::new(0x9F), Matte8::new(0xFF),
+            Matte8::new(0x60), Matte8::new(0x00), Matte8::new(0xFF),
+            Matte8::new(0x00), Matte8::new(0x00), Matte8::new(0x00),
+        ];
+        assert_eq!(m0.pixels(), &v);
+    }
+
+    #[test]
+    fn premultiply_raster() {
+        let mut r0 = Raster::<Graya8>::with_clear(2, 2);
+        r0.copy_color((1, 0, 1, 2), Graya8::new(0xFF, 0x40));
+        let r1 = Raster::<Graya8p>::with_premultiplied(&r0);
+        let v = [
+            Graya8p::new(0x00, 0x00), Graya8p::new(0x40, 0x40),
+            Graya8p::new(0x00, 0x00), Graya8p::new(0x40, 0x40),
+        ];
+        assert_eq!(r1.pixels(), &v);
+        let r2 = Raster::<Graya8>::with_unpremultiplied(&r1);
+        assert_eq!(r2.pixels(), r0.pixels());
+    }
+
+    #[test]
+    fn composite_convert() {
+        let mut r0 = Raster::<Rgba8p>::with_clear(3, 3);
+        let mut r1 = Raster::<SRgba8>::with_clear(3, 3);
+        r1.copy_color((1, 1, 2, 2), SRgba8::new(0xFF, 0xBC, 0x00, 0x80));
+        let mut r2 = r0.clone();
+        r0.composite_raster_convert((1, 0), &r1, (), SrcOver);
+        r2.composite_raster((1, 0), &Raster::with_raster(&r1), (), SrcOver);
+        assert_eq!(r0.pixels(), r2.pixels());
+        assert_eq!(r0.pixel(2, 1), Rgba8p::new(0x80, 0x40, 0x00, 0x80));
+    }
+
+    #[test]
+    fn composite_opacity() {
+        let mut r2 = Raster::with_color(2, 2, Graya8p::new(0x40, 0xFF));
+        let r1 = Raster::with_color(2, 1, Graya8p::new(0x80, 0x80));
+        r2.composite_raster_opacity((), &r1, (), SrcOver, 0x80.into());
+        r2.composite_color_opacity((0, 1, 1, 1), r1.pixel(0, 0), SrcOver,
+            0xFF.into());
+        let v = [
+            Graya8p::new(0x70, 0xFF), Graya8p::new(0x70, 0xFF),
+            Graya8p::new(0x9F, 0xFF), Graya8p::new(0x40, 0xFF),
+        ];
+        assert_eq!(r2.pixels(), &v);
+    }
+
+    #[test]
+    fn dissolve() {
+        let mut r0 = Raster::<Gray8>::with_clear(64, 64);
+        let r1 = Raster::with_color(64, 64, Graya8::new(0xFF, 0x40));
+        r0.composite_dissolve((), &r1, (), Dissolve::new(0));
+        let n = r0.pixels().iter().filter(|p| **p != Gray8::new(0x00)).count();
+        assert!(n > 900 && n < 1150);
+        assert!(r0.pixels().iter().all(|p| *p != Gray8::new(0x40)));
+        let mut r2 = Raster::<Gray8>::with_clear(64, 64);
+        r2.composite_dissolve((), &r1, (), Dissolve::new(0));
+        assert_eq!(r0.pixels(), r2.pixels());
+        let r3 = Raster::with_color(64, 64, Graya8::new(0xFF, 0x00));
+        r2.composite_dissolve((), &r3, (), Dissolve::new(1));
+        assert_eq!(r0.pixels(), r2.pixels());
+        let r4 = Raster::with_color(64, 64, Graya8::new(0x80, 0xFF));
+        r2.composite_dissolve((), &r4, (), Dissolve::new(2));
+        assert!(r2.pixels().iter().all(|p| *p == Gray8::new(0x80)));
+    }
+
+    #[test]
+    fn rows_mut_chunks() {
+        let mut r = Raster::<Gray8>::with_clear(4, 5);
+        let bands: Vec<_> = r.rows_mut_chunks((1, 1, 2, 4), 3).collect();
+        assert_eq!(bands.len(), 2);
+        std::thread::scope(|s| {
+            for (i, band) in bands.into_iter().enumerate() {
+                s.spawn(move || {
+                    for row in band {
+                        assert_eq!(row.len(), 2);
+                        row.fill(Gray8::new(i as u8 + 1));
+                    }
+                });
+            }
+        });
+        let v = [
+            Gray8::new(0), Gray8::new(0), Gray8::new(0), Gray8::new(0),
+            Gray8::new(0), Gray8::new(1), Gray8::new(1), Gray8::new(0),
+            Gray8::new(0), Gray8::new(1), Gray8::new(1), Gray8::new(0),
+            Gray8::new(0), Gray8::new(1), Gray8::new(1), Gray8::new(0),
+            Gray8::new(0), Gray8::new(2), Gray8::new(2), Gray8::new(0),
+        ];
+        assert_eq!(r.pixels(), &v);
+    }
+
+    #[test]
+    fn views() {
+        let mut r = Raster::<Gray8>::with_clear(4, 4);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray8::new(i as u8);
+        }
+        let v = r.view((1, 1, 5, 2));
+        assert_eq!((v.width(), v.height()), (3, 2));
+        assert_eq!(v.pixel(0, 0), Gray8::new(5));
+        let vv = v.view((1, 1, 1, 1));
+        assert_eq!(vv.pixel(0, 0), Gray8::new(10));
+        let c = Raster::with_view(&v.view((-1, 1, 2, 2)));
+        assert_eq!(c.pixels(), &[Gray8::new(9)]);
+        let mut m = r.view_mut((2, 0, 2, 4));
+        *m.pixel_mut(1, 3) = Gray8::new(0xFF);
+        for row in m.view_mut((0, 1, 1, 2)).rows_mut(()) {
+            row[0] = Gray8::new(0x80);
+        }
+        assert_eq!(m.rows((1, 0)).count(), 4);
+        assert_eq!(r.pixel(3, 3), Gray8::new(0xFF));
+        assert_eq!(r.pixel(2, 1), Gray8::new(0x80));
+        assert_eq!(r.pixel(2, 2), Gray8::new(0x80));
+        assert_eq!(r.pixel(2, 3), Gray8::new(14));
+    }
+
+    #[test]
+    fn strided_views() {
+        let mut buf = [0u8; 12 * 2 + 3];
+        let mut m = RasterViewMut::<Rgb8>::with_u8_buffer(1, 3, 12, &mut buf);
+        for (i, row) in m.rows_mut(()).enumerate() {
+            row[0] = Rgb8::new(i as u8 + 1, 0, 0);
+        }
+        let v = RasterView::<Rgb8>::with_u8_buffer(1, 3, 12, &buf);
+        assert_eq!(v.pixel(0, 2), Rgb8::new(3, 0, 0));
+        assert_eq!(buf[12], 2);
+        let p = [
+            Gray8::new(1), Gray8::new(2), Gray8::new(0),
+            Gray8::new(3), Gray8::new(4),
+        ];
+        let v = RasterView::with_pixels(2, 2, 3, &p);
+        let r = Raster::with_view(&v.view((1, 0, 1, 2)));
+        assert_eq!(r.pixels(), &[Gray8::new(2), Gray8::new(4)]);
+        let r = Raster::with_view(&v);
+        assert_eq!(r.pixels(), &[p[0], p[1], p[3], p[4]]);
+    }
+
+    #[test]
+    fn borrowed_composite() {
+        let mut buf = [Graya8p::new(0x10, 0xFF); 4 * 3];
+        let mut v = RasterViewMut::with_pixels(3, 3, 4, &mut buf);
+        v.copy_color((1, 1, 5, 5), Graya8p::new(0x20, 0xFF));
+        let src = Raster::with_color(2, 2, Graya8p::new(0x80, 0x80));
+        v.composite_raster((-1, 2), &src.view(()), (), SrcOver);
+        v.copy_raster((2, 0), &src.view(()), (1, 1));
+        assert_eq!(buf, [
+            Graya8p::new(0x10, 0xFF), Graya8p::new(0x10, 0xFF),
+            Graya8p::new(0x80, 0x80), Graya8p::new(0x10, 0xFF),
+            Graya8p::new(0x10, 0xFF), Graya8p::new(0x20, 0xFF),
+            Graya8p::new(0x20, 0xFF), Graya8p::new(0x10, 0xFF),
+            Graya8p::new(0x87, 0xFF), Graya8p::new(0x20, 0xFF),
+            Graya8p::new(0x20, 0xFF), Graya8p::new(0x10, 0xFF),
+        ]);
+    }
+
+    #[test]
+    fn padded_stride() {
+        let pad = Graya8p::new(0x55, 0x55);
+        let a = Graya8p::new(0x10, 0xFF);
+        let b = Graya8p::new(0x20, 0xFF);
+        let c = Graya8p::new(0x30, 0xFF);
+        let d = Graya8p::new(0x40, 0xFF);
+        let p = [a, b, pad, c, d, pad];
+        let src = RasterView::with_pixels(2, 2, 3, &p);
+        let mut buf = [pad; 4 * 2];
+        let mut v = RasterViewMut::with_pixels(2, 2, 4, &mut buf);
+        v.copy_raster((), &src, ());
+        assert_eq!(buf, [a, b, pad, pad, c, d, pad, pad]);
+        let mut buf = [pad; 4 * 2];
+        let mut v = RasterViewMut::with_pixels(2, 2, 4, &mut buf);
+        v.copy_color((), Graya8p::new(0, 0));
+        v.composite_raster((), &src.view((1, 0, 1, 2)), (), SrcOver);
+        v.composite_color((1, 0, 1, 2), a, SrcOver);
+        assert_eq!(buf, [b, a, pad, pad, d, a, pad, pad]);
+    }
+
+    #[test]
+    fn adjust() {
+        let mut r = Raster::with_color(3, 3, SRgba8::new(0x40, 0x80, 0xC0, 0x80));
+        r.adjust((1, 1, 1, 1), Brightness(0.0));
+        assert_eq!(r.pixel(1, 1), SRgba8::new(0x40, 0x80, 0xC0, 0x80));
+        r.adjust((1, 1, 1, 1), GammaCurve(2.0));
+        assert_eq!(r.pixel(1, 1), SRgba8::new(0x83, 0xB5, 0xDD, 0x80));
+        assert_eq!(r.pixel(0, 1), SRgba8::new(0x40, 0x80, 0xC0, 0x80));
+        let mut r = Raster::with_color(2, 2, Rgba8p::new(0x20, 0x40, 0x00, 0x80));
+        r.adjust((), Brightness(0.25));
+        assert_eq!(r.pixel(1, 1), Rgba8p::new(0x40, 0x60, 0x20, 0x80));
+        let mut r = Raster::with_color(2, 2, Gray16::new(0x4000));
+        r.adjust((), Contrast(0.0));
+        assert_eq!(r.pixel(0, 0), Gray16::new(0x2E14));
+        r.exposure((0, 0, 1, 1), 1.0);
+        assert_eq!(r.pixel(0, 0), Gray16::new(0x5C28));
+        assert_eq!(r.pixel(1, 0), Gray16::new(0x2E14));
+        let mut r = Raster::with_color(2, 2, SRgba8::new(0x80, 0x40, 0x00, 0x80));
+        r.exposure((), 0.0);
+        assert_eq!(r.pixel(1, 1), SRgba8::new(0x80, 0x40, 0x00, 0x80));
+        r.exposure((), -2.0);
+        assert_eq!(r.pixel(1, 1), SRgba8::new(0x42, 0x1C, 0x00, 0x80));
+    }
+
+    #[test]
+    fn color_matrix() {
+        let m = ColorMatrix::saturation(0.25).then(&ColorMatrix::new([
+            [1.0, 0.0, 0.0, 0.0, 0.1],
+            [0.0, 1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0, -0.1],
+            [0.0, 0.0, 0.0, 0.5, 0.0],
+        ]));
+        let mut r = Raster::<Rgba8>::with_clear(16, 16);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            let v = i as u8;
+            *p = Rgba8::new(v, v.wrapping_mul(3), v.wrapping_mul(7), !v);
+        }
+        let mut r16 = Raster::<Rgba16>::with_raster(&r);
+        r.adjust((), m);
+        r16.adjust((), m);
+        // fixed-point 8-bit path within 1 of floating-point path
+        for (p, p16) in r.pixels().iter().zip(r16.pixels()) {
+            let p16: Rgba8 = p16.convert();
+            for (c, c16) in p.channels().iter().zip(p16.channels()) {
+                let (c, c16) = (u8::from(*c), u8::from(*c16));
+                assert!(c.abs_diff(c16) <= 1, "{p:?} {p16:?}");
+            }
+        }
+        let mut r = Raster::with_color(2, 2, Rgb8::new(0x40, 0x80, 0xC0));
+        r.adjust((), ColorMatrix::saturation(0.0));
+        assert_eq!(r.pixel(1, 0), Rgb8::new(0x77, 0x77, 0x77));
+        r.adjust((), ColorMatrix::IDENTITY);
+        assert_eq!(r.pixel(1, 0), Rgb8::new(0x77, 0x77, 0x77));
+    }
+
+    #[test]
+    fn color_vision() {
+        let gray = SRgb8::new(0x80, 0x80, 0x80);
+        let white = SRgb8::new(0xFF, 0xFF, 0xFF);
+        for cv in [
+            ColorVision::Protanopia,
+            ColorVision::Deuteranopia,
+            ColorVision::Tritanopia,
+        ] {
+            let mut r = Raster::with_color(2, 1, gray);
+            *r.pixel_mut(1, 0) = white;
+            r.adjust((), cv);
+            assert_eq!(r.pixel(0, 0), gray, "{cv:?}");
+            assert_eq!(r.pixel(1, 0), white, "{cv:?}");
+        }
+        let mut r = Raster::with_color(2, 1, SRgba8::new(0xFF, 0, 0, 0x80));
+        *r.pixel_mut(1, 0) = SRgba8::new(0, 0, 0xFF, 0xFF);
+        r.adjust((), ColorVision::Protanopia);
+        let [red, green, _, alpha] = r.pixel(0, 0).into_array();
+        assert_eq!(red, green);
+        assert_eq!(alpha, Ch8::new(0x80));
+        r.adjust((), ColorVision::Tritanopia);
+        assert_eq!(r.pixel(1, 0), SRgba8::new(0x00, 0x62, 0x88, 0xFF));
+    }
+
+    #[test]
+    fn posterize_threshold() {
+        let mut r = Raster::with_color(3, 2, Rgba8p::new(0x30, 0x50, 0x80, 0x80));
+        r.posterize((), 2);
+        assert_eq!(r.pixel(2, 1), Rgba8p::new(0x00, 0x80, 0x80, 0x80));
+        let mut r = Raster::with_color(2, 2, Hwb8::new(0x55, 0x30, 0xA0));
+        r.posterize((0, 0, 1, 1), 3);
+        assert_eq!(r.pixel(0, 0), Hwb8::new(0x55, 0x00, 0x80));
+        assert_eq!(r.pixel(1, 0), Hwb8::new(0x55, 0x30, 0xA0));
+        let mut r = Raster::with_color(2, 2, Graya16p::new(0x2000, 0x8000));
+        r.threshold((), Ch16::new(0x4000));
+        assert_eq!(r.pixel(0, 0), Graya16p::new(0x8000, 0x8000));
+        *r.pixel_mut(1, 0) = Graya16p::new(0x1000, 0x8000);
+        r.threshold((), Ch16::new(0x4000));
+        assert_eq!(r.pixel(1, 0), Graya16p::new(0, 0x8000));
+        let m = Raster::<Matte16>::with_threshold(&r, Ch16::new(0x4000));
+        assert_eq!(m.pixel(1, 1), Matte16::new(0xFFFF));
+        assert_eq!(m.pixel(1, 0), Matte16::new(0));
+    }
+
+    #[test]
+    fn invert() {
+        let mut r = Raster::with_color(2, 2, Rgba16p::new(0x1000, 0, 0x8000, 0x8000));
+        r.invert((1, 0, 1, 2));
+        assert_eq!(r.pixel(1, 1), Rgba16p::new(0x7000, 0x8000, 0, 0x8000));
+        assert_eq!(r.pixel(0, 1), Rgba16p::new(0x1000, 0, 0x8000, 0x8000));
+        let mut r = Raster::with_color(2, 2, Hsv32::new(0.75, 0.25, 1.0));
+        r.invert(());
+        // RGB negative of (0.875, 0.75, 1.0)
+        assert_eq!(r.pixel(0, 0), Hsv32::new(0.25, 1.0, 0.25));
+        let rgb: Rgb32 = r.pixel(0, 0).convert();
+        assert_eq!(rgb, Rgb32::new(0.125, 0.25, 0.0));
+    }
+
+    #[test]
+    fn from_u8_buffer() {
+        let buf: Vec<u8> = (0..24).collect();
+        let r = Raster::<SRgb8>::from_u8_buffer(4, 2, buf.clone()).unwrap();
+        assert_eq!(r.pixel(1, 1), SRgb8::new(15, 16, 17));
+        assert_eq!(r.as_u8_slice(), &buf[..]);
+        let r = Raster::<Rgb16>::from_u8_buffer(2, 2, buf.clone()).unwrap();
+        assert_eq!(r.as_u8_slice(), &buf[..]);
+        let r = Raster::<Gray32>::from_u8_buffer(2, 3, buf).unwrap();
+        assert_eq!(r.pixels().len(), 6);
+        let buf: Vec<u8> = [0.0, 0.5, 1.0].iter()
+            .flat_map(|v: &f32| v.to_ne_bytes())
+            .collect();
+        let r = Raster::<Gray32>::from_u8_buffer(3, 1, buf).unwrap();
+        assert_eq!(r.pixel(1, 0), Gray32::new(0.5));
+        let r = Raster::<Gray32>::from_u8_buffer(1, 1, f32::NAN.to_ne_bytes());
+        assert_eq!(r.err(), Some(BufferError::InvalidValue));
+        let r = Raster::<Gray8>::from_u8_buffer(1 << 31, 1, vec![]);
+        assert_eq!(r.err(), Some(BufferError::TooBig));
+        let r = Raster::<Gray8>::from_u8_buffer(1 << 16, 1 << 16, vec![]);
+        assert_eq!(r.err(), Some(BufferError::TooBig));
+    }
+
+    #[test]
+    fn map() {
+        let mut r = Raster::<Gray16>::with_clear(300, 300);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray16::new(i as u16);
+        }
+        let m = r.map(|p| Gray32::new(f32::from(p.one())));
+        assert_eq!(m.pixel(299, 299), Gray32::new(f32::from(Ch16::new(24463))));
+        r.map_in_place(|p| Gray16::new(u16::from(p.one()).wrapping_add(1)));
+        assert_eq!(r.pixel(10, 0), Gray16::new(11));
+        assert_eq!(r.pixel(299, 299), Gray16::new(24464));
+    }
+
+    #[test]
+    fn alpha_matte() {
+        let mut r = Raster::with_color(3, 2, Rgba16::new(1, 2, 3, 0x8000));
+        *r.pixel_mut(2, 1) = Rgba16::new(4, 5, 6, 0xFFFF);
+        let mut m = r.alpha_matte();
+        assert_eq!(m.pixel(0, 0), Matte16::new(0x8000));
+        assert_eq!(m.pixel(2, 1), Matte16::new(0xFFFF));
+        *m.pixel_mut(0, 0) = Matte16::new(0);
+        r.apply_matte(&m);
+        assert_eq!(r.pixel(0, 0), Rgba16::new(1, 2, 3, 0));
+        assert_eq!(r.pixel(1, 0), Rgba16::new(1, 2, 3, 0x4000));
+        assert_eq!(r.pixel(2, 1), Rgba16::new(4, 5, 6, 0xFFFF));
+        let o = Raster::<SRgb8>::with_clear(2, 2).alpha_matte();
+        assert_eq!(o.pixel(1, 1), Matte8::new(0xFF));
+    }
+
+    #[test]
+    fn split_merge() {
+        let mut r = Raster::with_color(3, 2, Hsva16::new(1, 2, 3, 4));
+        *r.pixel_mut(1, 1) = Hsva16::new(5, 6, 7, 8);
+        let planes = r.split_channels();
+        assert_eq!(planes.len(), 4);
+        assert_eq!(planes[3].pixel(0, 0), Gray16::new(4));
+        assert_eq!(planes[2].pixel(1, 1), Gray16::new(7));
+        let m = Raster::<Hsva16>::merge_channels(&planes);
+        assert_eq!(m.pixels(), r.pixels());
+    }
+
+    #[test]
+    #[should_panic]
+    fn merge_wrong_count() {
+        let planes = Raster::<Rgba8>::with_clear(2, 2).split_channels();
+        Raster::<Rgb8>::merge_channels(&planes);
+    }
+
+    #[test]
+    fn swizzle() {
+        let mut r = Raster::with_color(3, 3, SRgba16::new(1, 2, 3, 4));
+        *r.pixel_mut(2, 0) = SRgba16::new(5, 6, 7, 8);
+        let a = Raster::<SGraya16>::with_swizzle(&r, &[3, 0]);
+        assert_eq!(a.pixel(0, 0), SGraya16::new(4, 1));
+        assert_eq!(a.pixel(2, 0), SGraya16::new(8, 5));
+        let b = Raster::<SBgra16>::with_raster(&r);
+        let c = Raster::<SBgra16>::with_swizzle(&r, &[2, 1, 0, 3]);
+        assert_eq!(b.pixels(), c.pixels());
+    }
+
+    #[test]
+    fn posterize_dithered() {
+        let d = OrderedDither::bayer(2);
+        let mut r = Raster::with_color(4, 2, Graya32::new(0.75, 0.25));
+        r.posterize_dithered((1, 0, 3, 2), 3, &d);
+        let v: Vec<f32> = r.pixels().iter().map(|p| p.one().to_f32()).collect();
+        assert_eq!(v, [0.75, 1.0, 0.5, 1.0, 0.75, 0.5, 1.0, 0.5]);
+        assert!(r.pixels().iter().all(|p| p.two() == Ch32::new(0.25)));
+        let mut r = Raster::with_color(4, 4, SRgb8::new(0x40, 0xFF, 0));
+        r.posterize_dithered((), 2, &d);
+        let on = r.pixels().iter().filter(|p| p.one() == Ch8::new(0xFF));
+        assert_eq!(on.count(), 4);
+        assert!(r.pixels().iter().all(|p| p.two() == Ch8::new(0xFF)));
+    }
+
+    #[test]
+    fn quantized() {
+        let mut r = Raster::with_color(8, 8, SRgba8::new(0x80, 0x80, 0x80, 0x40));
+        r.copy_color((0, 0, 8, 2), SRgba8::new(0xFF, 0, 0, 0xFF));
+        let (p, ind) = r.quantized(4, Dither::None);
+        assert_eq!(p.len(), 2);
+        let (p2, ind2) = r.quantized(4, Dither::Ordered(OrderedDither::bayer(4)));
+        assert_eq!(p.colors(), p2.colors());
+        assert_eq!(ind.pixels(), ind2.pixels());
+        let r2 = Raster::<SRgba8>::with_indexed(&ind, &p);
+        assert_eq!(r2.pixel(0, 0), SRgba8::new(0xFF, 0, 0, 0xFF));
+        assert_eq!(r2.pixel(7, 7), SRgba8::new(0x80, 0x80, 0x80, 0xFF));
+        let ind = Raster::with_color(2, 2, Gray8::new(5));
+        let r3 = Raster::<SRgba8>::with_indexed(&ind, &p);
+        assert_eq!(r3.pixel(1, 1), SRgba8::default());
+    }
+
+    #[test]
+    fn large_rows() {
+        let mut r = Raster::<Rgba8p>::with_clear(300, 300);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            let v = i as u8;
+            *p = Rgba8p::new(v >> 1, v >> 2, v >> 3, v);
+        }
+        let c = Raster::<SRgba16>::with_raster(&r);
+        for (p, q) in r.pixels().iter().zip(c.pixels()) {
+            assert_eq!(p.convert::<SRgba16>(), *q);
+        }
+        let mut d = Raster::with_color(300, 300, Rgba8p::new(0, 0, 0x80, 0xFF));
+        d.composite_raster((), &r, (), SrcOver);
+        let p = r.pixel(299, 299);
+        let mut q = Rgba8p::new(0, 0, 0x80, 0xFF);
+        Rgba8p::composite_slice(std::slice::from_mut(&mut q), &[p], SrcOver);
+        assert_eq!(d.pixel(299, 299), q);
+    }
+
+    #[test]
+    fn convert_in_place() {
+        let mut r = Raster::<Rgba8>::with_clear(16, 16);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            let v = i as u8;
+            *p = Rgba8::new(v, v.wrapping_mul(3), !v, v.wrapping_add(7));
+        }
+        let c = Raster::<SRgba8p>::with_raster(&r);
+        let r = r.convert_in_place::<SRgba8p>();
+        assert_eq!(r.pixels(), c.pixels());
+        let r = r.convert_in_place::<Hwba8>();
+        assert_eq!(r.width(), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn convert_in_place_size() {
+        let r = Raster::<Rgb8>::with_clear(4, 4);
+        let _ = r.convert_in_place::<Rgba8>();
+    }
+
     #[test]
     fn with_raster_rgb() {
         let r = Raster::<Rgba8p>::with_clear(50, 50);
@@ -1145,4 +3825,50 @@ mod test {
         let _ = Raster::<Matte16>::with_raster(&r);
         let _ = Raster::<Matte32>::with_raster(&r);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let reg = Region::new(-1, 2, 3, 4);
+        let json = serde_json::to_string(&reg).unwrap();
+        assert_eq!(json, r#"{"x":-1,"y":2,"width":3,"height":4}"#);
+        assert_eq!(serde_json::from_str::<Region>(&json).unwrap(), reg);
+        let json = r#"{"x":0,"y":0,"width":4294967295,"height":1}"#;
+        assert!(serde_json::from_str::<Region>(json).is_err());
+        let mut r = Raster::<Rgba8>::with_clear(2, 1);
+        *r.pixel_mut(1, 0) = Rgba8::new(1, 2, 3, 4);
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, r#"{"width":2,"height":1,"pixels":[0,0,0,0,1,2,3,4]}"#);
+        let r2 = serde_json::from_str::<Raster<Rgba8>>(&json).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+        let r = Raster::with_color(1, 1, Rgb16::new(0x0102, 0x0304, 0x0506));
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, r#"{"width":1,"height":1,"pixels":[2,1,4,3,6,5]}"#);
+        let r2 = serde_json::from_str::<Raster<Rgb16>>(&json).unwrap();
+        assert_eq!(r2.pixels(), r.pixels());
+        let json = r#"{"width":2,"height":2,"pixels":[0,0,0]}"#;
+        assert!(serde_json::from_str::<Raster<Gray8>>(json).is_err());
+        // out of range float channels are clamped
+        let json = r#"{"width":1,"height":1,"pixels":[0,0,0,64]}"#;
+        let r = serde_json::from_str::<Raster<Gray32>>(json).unwrap();
+        assert_eq!(r.pixel(0, 0), Gray32::new(1.0));
+    }
+
+    #[test]
+    fn byte_traits() {
+        let buf: Vec<u8> = (0..12).collect();
+        let mut r = Raster::<Rgb8>::try_from((2, 2, &buf[..])).unwrap();
+        assert_eq!(r.pixel(1, 1), Rgb8::new(9, 10, 11));
+        assert_eq!(r.as_ref(), &buf[..]);
+        r.as_mut()[0] = 0xFF;
+        assert_eq!(r.pixel(0, 0), Rgb8::new(0xFF, 1, 2));
+        let r = Raster::<Gray16>::try_from((2, 2, &buf[..]));
+        assert_eq!(
+            r.err(),
+            Some(BufferError::WrongLength {
+                expected: 8,
+                actual: 12
+            })
+        );
+    }
 }
@@ -6,11 +6,17 @@
 //! [RGB] color model and types.
 //!
 //! [rgb]: https://en.wikipedia.org/wiki/RGB_color_model
-use crate::chan::{Ch16, Ch32, Ch8, Linear, Premultiplied, Srgb, Straight};
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::chan::{
+    Alpha, Ch16, Ch32, Ch8, Channel, Gamma, Linear, Premultiplied, Srgb,
+    Straight,
+};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
+pub mod css;
+
 /// [RGB] additive [color model].
 ///
 /// The components are *[red]*, *[green]*, *[blue]* and optional *[alpha]*.
@@ -155,7 +161,56 @@ impl Rgb {
         } else {
             Pixel::alpha(rhs) - Pixel::alpha(p)
         };
-        P::from_channels(&[red, green, blue, alpha])
+        from_model_channels(&[red, green, blue, alpha])
+    }
+
+    /// Get the color of a blackbody radiator.
+    ///
+    /// * `kelvin` Color temperature, clamped to 1667 K - 25000 K.
+    ///
+    /// The chromaticity is approximated with the Planckian locus cubic
+    /// splines of Kim et al. (2002), which are within 0.0005 of the exact
+    /// CIE 1931 *x*, *y* values.  This is converted to *linear* sRGB
+    /// primaries and scaled so the brightest channel is `MAX`.  Below about
+    /// 1900 K the color is outside the sRGB gamut, so *blue* is clipped.
+    ///
+    /// The pixel format selects *linear* or *sRGB* gamma.
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::rgb::{Rgb, SRgb8};
+    ///
+    /// let candle: SRgb8 = Rgb::from_kelvin(1900.0);
+    /// let daylight: SRgb8 = Rgb::from_kelvin(6500.0);
+    /// assert_eq!(candle, SRgb8::new(0xFF, 0x84, 0x00));
+    /// assert_eq!(daylight, SRgb8::new(0xFF, 0xF9, 0xFE));
+    /// ```
+    #[allow(clippy::excessive_precision)]
+    pub fn from_kelvin<P>(kelvin: f32) -> P
+    where
+        P: Pixel<Model = Self>,
+    {
+        let t = kelvin.clamp(1667.0, 25000.0);
+        let (t1, t2, t3) = (1e3 / t, 1e6 / (t * t), 1e9 / (t * t * t));
+        let x = if t <= 4000.0 {
+            -0.2661239 * t3 - 0.2343589 * t2 + 0.8776956 * t1 + 0.179910
+        } else {
+            -3.0258469 * t3 + 2.1070379 * t2 + 0.2226347 * t1 + 0.240390
+        };
+        let (x2, x3) = (x * x, x * x * x);
+        let y = if t <= 2222.0 {
+            -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+        } else if t <= 4000.0 {
+            -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+        } else {
+            3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+        };
+        let (px, pz) = (x / y, (1.0 - x - y) / y);
+        let red = px * 3.2406 - 1.5372 - pz * 0.4986;
+        let green = px * -0.9689 + 1.8758 + pz * 0.0415;
+        let blue = px * 0.0557 - 0.2040 + pz * 1.0570;
+        let max = red.max(green).max(blue);
+        Rgb32::new(red / max, green / max, blue / max).convert_lossy()
     }
 
     /// Check if all `Channel`s are within threshold
@@ -170,10 +225,36 @@ impl Rgb {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Rgb] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::rgb::{RgbPixel, Rgba8};
+    ///
+    /// let mut p = Rgba8::new(1, 2, 3, 4);
+    /// assert_eq!(p.red(), Ch8::new(1));
+    /// *p.green_mut() = Ch8::new(9);
+    /// assert_eq!(p.green(), Ch8::new(9));
+    /// let p = p.with_red(Ch8::new(5)).with_blue(Ch8::new(6));
+    /// assert_eq!(p, Rgba8::new(5, 9, 6, 4));
+    /// ```
+    ///
+    /// [Rgb]: struct.Rgb.html
+    RgbPixel: Rgb {
+        red, red_mut, with_red;
+        green, green_mut, with_green;
+        blue, blue_mut, with_blue;
+    }
+}
+
 impl ColorModel for Rgb {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] =
+        &[ChannelLabel::Red, ChannelLabel::Green, ChannelLabel::Blue];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -191,10 +272,77 @@ impl ColorModel for Rgb {
     where
         P: Pixel<Model = Self>,
     {
-        P::from_channels(rgba.channels())
+        from_model_channels(rgba.channels())
     }
 }
 
+impl<C, A, G> Pix3<C, Rgb, A, G>
+where
+    C: Channel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Opaque black
+    pub const BLACK: Self = Self::from_array([C::MIN, C::MIN, C::MIN]);
+
+    /// Opaque white
+    pub const WHITE: Self = Self::from_array([C::MAX, C::MAX, C::MAX]);
+
+    /// Opaque red
+    pub const RED: Self = Self::from_array([C::MAX, C::MIN, C::MIN]);
+
+    /// Opaque green
+    pub const GREEN: Self = Self::from_array([C::MIN, C::MAX, C::MIN]);
+
+    /// Opaque blue
+    pub const BLUE: Self = Self::from_array([C::MIN, C::MIN, C::MAX]);
+
+    /// Opaque yellow
+    pub const YELLOW: Self = Self::from_array([C::MAX, C::MAX, C::MIN]);
+
+    /// Opaque cyan
+    pub const CYAN: Self = Self::from_array([C::MIN, C::MAX, C::MAX]);
+
+    /// Opaque magenta
+    pub const MAGENTA: Self = Self::from_array([C::MAX, C::MIN, C::MAX]);
+}
+
+impl<C, A, G> Pix4<C, Rgb, A, G>
+where
+    C: Channel,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Opaque black
+    pub const BLACK: Self = Self::from_array([C::MIN, C::MIN, C::MIN, C::MAX]);
+
+    /// Opaque white
+    pub const WHITE: Self = Self::from_array([C::MAX, C::MAX, C::MAX, C::MAX]);
+
+    /// Opaque red
+    pub const RED: Self = Self::from_array([C::MAX, C::MIN, C::MIN, C::MAX]);
+
+    /// Opaque green
+    pub const GREEN: Self = Self::from_array([C::MIN, C::MAX, C::MIN, C::MAX]);
+
+    /// Opaque blue
+    pub const BLUE: Self = Self::from_array([C::MIN, C::MIN, C::MAX, C::MAX]);
+
+    /// Opaque yellow
+    pub const YELLOW: Self = Self::from_array([C::MAX, C::MAX, C::MIN, C::MAX]);
+
+    /// Opaque cyan
+    pub const CYAN: Self = Self::from_array([C::MIN, C::MAX, C::MAX, C::MAX]);
+
+    /// Opaque magenta
+    pub const MAGENTA: Self =
+        Self::from_array([C::MAX, C::MIN, C::MAX, C::MAX]);
+
+    /// Transparent black
+    pub const TRANSPARENT: Self =
+        Self::from_array([C::MIN, C::MIN, C::MIN, C::MIN]);
+}
+
 /// [Rgb](struct.Rgb.html) 8-bit opaque (no *alpha* channel)
 /// [linear](../chan/struct.Linear.html) gamma [pixel](../el/trait.Pixel.html)
 /// format.
@@ -291,8 +439,63 @@ pub type SRgba16p = Pix4<Ch16, Rgb, Premultiplied, Srgb>;
 /// format.
 pub type SRgba32p = Pix4<Ch32, Rgb, Premultiplied, Srgb>;
 
+#[cfg(feature = "rgb")]
+impl<C, A, G, H> From<::rgb::RGB<H>> for Pix3<C, Rgb, A, G>
+where
+    C: Channel + From<H>,
+    A: Alpha,
+    G: Gamma,
+{
+    /// Convert from an `rgb` crate color, such as `RGB8`.
+    fn from(c: ::rgb::RGB<H>) -> Self {
+        Self::new::<H>(c.r, c.g, c.b)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl<C, A, G, H> From<Pix3<C, Rgb, A, G>> for ::rgb::RGB<H>
+where
+    C: Channel,
+    A: Alpha,
+    G: Gamma,
+    H: From<C>,
+{
+    /// Convert into an `rgb` crate color, such as `RGB8`.
+    fn from(p: Pix3<C, Rgb, A, G>) -> Self {
+        let [r, g, b] = p.into_array().map(H::from);
+        ::rgb::RGB { r, g, b }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl<C, G, H> From<::rgb::RGBA<H>> for Pix4<C, Rgb, Straight, G>
+where
+    C: Channel + From<H>,
+    G: Gamma,
+{
+    /// Convert from an `rgb` crate color with straight alpha, such as `RGBA8`.
+    fn from(c: ::rgb::RGBA<H>) -> Self {
+        Self::new::<H>(c.r, c.g, c.b, c.a)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl<C, G, H> From<Pix4<C, Rgb, Straight, G>> for ::rgb::RGBA<H>
+where
+    C: Channel,
+    G: Gamma,
+    H: From<C>,
+{
+    /// Convert into an `rgb` crate color with straight alpha, such as `RGBA8`.
+    fn from(p: Pix4<C, Rgb, Straight, G>) -> Self {
+        let [r, g, b, a] = p.into_array().map(H::from);
+        ::rgb::RGBA { r, g, b, a }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::chan::{Ch32, Ch8, Channel};
     use crate::el::Pixel;
     use crate::ops::SrcOver;
     use crate::rgb::*;
@@ -312,4 +515,51 @@ mod tests {
         dst.composite_channels(&Rgba8p::new(0, 0, 0, 0), SrcOver);
         assert_eq!(dst, Rgba8p::new(0xFF, 0xFF, 0xFF, 0x00));
     }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn rgb_crate() {
+        let p = Rgb8::from(::rgb::RGB8::new(1, 2, 3));
+        assert_eq!(p, Rgb8::new(1, 2, 3));
+        assert_eq!(::rgb::RGB8::from(p), ::rgb::RGB8::new(1, 2, 3));
+        let p: SRgba16 = ::rgb::RGBA16::new(1, 2, 3, 4).into();
+        assert_eq!(p, SRgba16::new(1, 2, 3, 4));
+        let c: ::rgb::RGBA16 = p.into();
+        assert_eq!(c, ::rgb::RGBA16::new(1, 2, 3, 4));
+        let c: ::rgb::RGB<f32> = Rgb32::new(0.25, 0.5, 1.0).into();
+        assert_eq!(c, ::rgb::RGB::new(0.25, 0.5, 1.0));
+    }
+
+    #[test]
+    fn constants() {
+        const ORANGE: SRgb8 =
+            SRgb8::from_array([Ch8::MAX, Ch8::new(0x80), Ch8::MIN]);
+        assert_eq!(ORANGE, SRgb8::new(0xFF, 0x80, 0));
+        assert_eq!(SRgba8::MAGENTA, SRgba8::new(0xFF, 0, 0xFF, 0xFF));
+        assert_eq!(Rgb16::CYAN, Rgb16::new(0, 0xFFFF, 0xFFFF));
+        assert_eq!(Rgba32p::TRANSPARENT, Rgba32p::new(0.0, 0.0, 0.0, 0.0));
+        const TABLE: [Rgb32; 2] =
+            [Rgb32::BLACK, Rgb32::from_array([Ch32::new(0.5); 3])];
+        assert_eq!(TABLE[1], Rgb32::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn kelvin() {
+        let p: Rgb32 = Rgb::from_kelvin(6500.0);
+        assert!(p.channels().iter().all(|c| c.to_f32() > 0.9), "{p:?}");
+        let p: Rgb32 = Rgb::from_kelvin(2700.0);
+        assert_eq!(Rgb::red(p), Ch32::MAX);
+        assert!(Rgb::green(p) > Rgb::blue(p), "{p:?}");
+        let p: Rgb32 = Rgb::from_kelvin(12000.0);
+        assert_eq!(Rgb::blue(p), Ch32::MAX);
+        assert!(Rgb::red(p) < Rgb::green(p), "{p:?}");
+        let p: SRgb8 = Rgb::from_kelvin(1000.0);
+        assert_eq!(p, Rgb::from_kelvin(1667.0));
+        assert_eq!(Rgb::blue(p), Ch8::new(0));
+        let p: SRgb8 = Rgb::from_kelvin(f32::INFINITY);
+        assert_eq!(p, Rgb::from_kelvin(25000.0));
+        let p: SRgba8 = Rgb::from_kelvin(2700.0);
+        assert_eq!(p.alpha(), Ch8::MAX);
+        assert_eq!(p, Rgb::from_kelvin::<SRgb8>(2700.0).convert());
+    }
 }
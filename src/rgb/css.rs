@@ -0,0 +1,502 @@
+// css.rs       CSS named colors.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! CSS named colors.
+//!
+//! All 148 named colors from [CSS Color Module Level 4], which include the
+//! X11 colors.  Where CSS and X11 differ (`GRAY`, `GREEN`, `MAROON` and
+//! `PURPLE`), the CSS values are used.
+//!
+//! ### Example
+//! ```
+//! use pix::rgb::{css, SRgb8};
+//!
+//! assert_eq!(css::REBECCAPURPLE, SRgb8::new(0x66, 0x33, 0x99));
+//! assert_eq!(css::from_name("RebeccaPurple"), Some(css::REBECCAPURPLE));
+//! ```
+//!
+//! [CSS Color Module Level 4]: https://www.w3.org/TR/css-color-4/#named-colors
+use crate::chan::Ch8;
+use crate::rgb::SRgb8;
+
+/// Make an `SRgb8` color
+const fn rgb(red: u8, green: u8, blue: u8) -> SRgb8 {
+    SRgb8::from_array([Ch8::new(red), Ch8::new(green), Ch8::new(blue)])
+}
+
+/// `aliceblue` (`#f0f8ff`)
+pub const ALICEBLUE: SRgb8 = rgb(0xF0, 0xF8, 0xFF);
+/// `antiquewhite` (`#faebd7`)
+pub const ANTIQUEWHITE: SRgb8 = rgb(0xFA, 0xEB, 0xD7);
+/// `aqua` (`#00ffff`)
+pub const AQUA: SRgb8 = rgb(0x00, 0xFF, 0xFF);
+/// `aquamarine` (`#7fffd4`)
+pub const AQUAMARINE: SRgb8 = rgb(0x7F, 0xFF, 0xD4);
+/// `azure` (`#f0ffff`)
+pub const AZURE: SRgb8 = rgb(0xF0, 0xFF, 0xFF);
+/// `beige` (`#f5f5dc`)
+pub const BEIGE: SRgb8 = rgb(0xF5, 0xF5, 0xDC);
+/// `bisque` (`#ffe4c4`)
+pub const BISQUE: SRgb8 = rgb(0xFF, 0xE4, 0xC4);
+/// `black` (`#000000`)
+pub const BLACK: SRgb8 = rgb(0x00, 0x00, 0x00);
+/// `blanchedalmond` (`#ffebcd`)
+pub const BLANCHEDALMOND: SRgb8 = rgb(0xFF, 0xEB, 0xCD);
+/// `blue` (`#0000ff`)
+pub const BLUE: SRgb8 = rgb(0x00, 0x00, 0xFF);
+/// `blueviolet` (`#8a2be2`)
+pub const BLUEVIOLET: SRgb8 = rgb(0x8A, 0x2B, 0xE2);
+/// `brown` (`#a52a2a`)
+pub const BROWN: SRgb8 = rgb(0xA5, 0x2A, 0x2A);
+/// `burlywood` (`#deb887`)
+pub const BURLYWOOD: SRgb8 = rgb(0xDE, 0xB8, 0x87);
+/// `cadetblue` (`#5f9ea0`)
+pub const CADETBLUE: SRgb8 = rgb(0x5F, 0x9E, 0xA0);
+/// `chartreuse` (`#7fff00`)
+pub const CHARTREUSE: SRgb8 = rgb(0x7F, 0xFF, 0x00);
+/// `chocolate` (`#d2691e`)
+pub const CHOCOLATE: SRgb8 = rgb(0xD2, 0x69, 0x1E);
+/// `coral` (`#ff7f50`)
+pub const CORAL: SRgb8 = rgb(0xFF, 0x7F, 0x50);
+/// `cornflowerblue` (`#6495ed`)
+pub const CORNFLOWERBLUE: SRgb8 = rgb(0x64, 0x95, 0xED);
+/// `cornsilk` (`#fff8dc`)
+pub const CORNSILK: SRgb8 = rgb(0xFF, 0xF8, 0xDC);
+/// `crimson` (`#dc143c`)
+pub const CRIMSON: SRgb8 = rgb(0xDC, 0x14, 0x3C);
+/// `cyan` (`#00ffff`)
+pub const CYAN: SRgb8 = rgb(0x00, 0xFF, 0xFF);
+/// `darkblue` (`#00008b`)
+pub const DARKBLUE: SRgb8 = rgb(0x00, 0x00, 0x8B);
+/// `darkcyan` (`#008b8b`)
+pub const DARKCYAN: SRgb8 = rgb(0x00, 0x8B, 0x8B);
+/// `darkgoldenrod` (`#b8860b`)
+pub const DARKGOLDENROD: SRgb8 = rgb(0xB8, 0x86, 0x0B);
+/// `darkgray` (`#a9a9a9`)
+pub const DARKGRAY: SRgb8 = rgb(0xA9, 0xA9, 0xA9);
+/// `darkgreen` (`#006400`)
+pub const DARKGREEN: SRgb8 = rgb(0x00, 0x64, 0x00);
+/// `darkgrey` (`#a9a9a9`)
+pub const DARKGREY: SRgb8 = rgb(0xA9, 0xA9, 0xA9);
+/// `darkkhaki` (`#bdb76b`)
+pub const DARKKHAKI: SRgb8 = rgb(0xBD, 0xB7, 0x6B);
+/// `darkmagenta` (`#8b008b`)
+pub const DARKMAGENTA: SRgb8 = rgb(0x8B, 0x00, 0x8B);
+/// `darkolivegreen` (`#556b2f`)
+pub const DARKOLIVEGREEN: SRgb8 = rgb(0x55, 0x6B, 0x2F);
+/// `darkorange` (`#ff8c00`)
+pub const DARKORANGE: SRgb8 = rgb(0xFF, 0x8C, 0x00);
+/// `darkorchid` (`#9932cc`)
+pub const DARKORCHID: SRgb8 = rgb(0x99, 0x32, 0xCC);
+/// `darkred` (`#8b0000`)
+pub const DARKRED: SRgb8 = rgb(0x8B, 0x00, 0x00);
+/// `darksalmon` (`#e9967a`)
+pub const DARKSALMON: SRgb8 = rgb(0xE9, 0x96, 0x7A);
+/// `darkseagreen` (`#8fbc8f`)
+pub const DARKSEAGREEN: SRgb8 = rgb(0x8F, 0xBC, 0x8F);
+/// `darkslateblue` (`#483d8b`)
+pub const DARKSLATEBLUE: SRgb8 = rgb(0x48, 0x3D, 0x8B);
+/// `darkslategray` (`#2f4f4f`)
+pub const DARKSLATEGRAY: SRgb8 = rgb(0x2F, 0x4F, 0x4F);
+/// `darkslategrey` (`#2f4f4f`)
+pub const DARKSLATEGREY: SRgb8 = rgb(0x2F, 0x4F, 0x4F);
+/// `darkturquoise` (`#00ced1`)
+pub const DARKTURQUOISE: SRgb8 = rgb(0x00, 0xCE, 0xD1);
+/// `darkviolet` (`#9400d3`)
+pub const DARKVIOLET: SRgb8 = rgb(0x94, 0x00, 0xD3);
+/// `deeppink` (`#ff1493`)
+pub const DEEPPINK: SRgb8 = rgb(0xFF, 0x14, 0x93);
+/// `deepskyblue` (`#00bfff`)
+pub const DEEPSKYBLUE: SRgb8 = rgb(0x00, 0xBF, 0xFF);
+/// `dimgray` (`#696969`)
+pub const DIMGRAY: SRgb8 = rgb(0x69, 0x69, 0x69);
+/// `dimgrey` (`#696969`)
+pub const DIMGREY: SRgb8 = rgb(0x69, 0x69, 0x69);
+/// `dodgerblue` (`#1e90ff`)
+pub const DODGERBLUE: SRgb8 = rgb(0x1E, 0x90, 0xFF);
+/// `firebrick` (`#b22222`)
+pub const FIREBRICK: SRgb8 = rgb(0xB2, 0x22, 0x22);
+/// `floralwhite` (`#fffaf0`)
+pub const FLORALWHITE: SRgb8 = rgb(0xFF, 0xFA, 0xF0);
+/// `forestgreen` (`#228b22`)
+pub const FORESTGREEN: SRgb8 = rgb(0x22, 0x8B, 0x22);
+/// `fuchsia` (`#ff00ff`)
+pub const FUCHSIA: SRgb8 = rgb(0xFF, 0x00, 0xFF);
+/// `gainsboro` (`#dcdcdc`)
+pub const GAINSBORO: SRgb8 = rgb(0xDC, 0xDC, 0xDC);
+/// `ghostwhite` (`#f8f8ff`)
+pub const GHOSTWHITE: SRgb8 = rgb(0xF8, 0xF8, 0xFF);
+/// `gold` (`#ffd700`)
+pub const GOLD: SRgb8 = rgb(0xFF, 0xD7, 0x00);
+/// `goldenrod` (`#daa520`)
+pub const GOLDENROD: SRgb8 = rgb(0xDA, 0xA5, 0x20);
+/// `gray` (`#808080`)
+pub const GRAY: SRgb8 = rgb(0x80, 0x80, 0x80);
+/// `green` (`#008000`)
+pub const GREEN: SRgb8 = rgb(0x00, 0x80, 0x00);
+/// `greenyellow` (`#adff2f`)
+pub const GREENYELLOW: SRgb8 = rgb(0xAD, 0xFF, 0x2F);
+/// `grey` (`#808080`)
+pub const GREY: SRgb8 = rgb(0x80, 0x80, 0x80);
+/// `honeydew` (`#f0fff0`)
+pub const HONEYDEW: SRgb8 = rgb(0xF0, 0xFF, 0xF0);
+/// `hotpink` (`#ff69b4`)
+pub const HOTPINK: SRgb8 = rgb(0xFF, 0x69, 0xB4);
+/// `indianred` (`#cd5c5c`)
+pub const INDIANRED: SRgb8 = rgb(0xCD, 0x5C, 0x5C);
+/// `indigo` (`#4b0082`)
+pub const INDIGO: SRgb8 = rgb(0x4B, 0x00, 0x82);
+/// `ivory` (`#fffff0`)
+pub const IVORY: SRgb8 = rgb(0xFF, 0xFF, 0xF0);
+/// `khaki` (`#f0e68c`)
+pub const KHAKI: SRgb8 = rgb(0xF0, 0xE6, 0x8C);
+/// `lavender` (`#e6e6fa`)
+pub const LAVENDER: SRgb8 = rgb(0xE6, 0xE6, 0xFA);
+/// `lavenderblush` (`#fff0f5`)
+pub const LAVENDERBLUSH: SRgb8 = rgb(0xFF, 0xF0, 0xF5);
+/// `lawngreen` (`#7cfc00`)
+pub const LAWNGREEN: SRgb8 = rgb(0x7C, 0xFC, 0x00);
+/// `lemonchiffon` (`#fffacd`)
+pub const LEMONCHIFFON: SRgb8 = rgb(0xFF, 0xFA, 0xCD);
+/// `lightblue` (`#add8e6`)
+pub const LIGHTBLUE: SRgb8 = rgb(0xAD, 0xD8, 0xE6);
+/// `lightcoral` (`#f08080`)
+pub const LIGHTCORAL: SRgb8 = rgb(0xF0, 0x80, 0x80);
+/// `lightcyan` (`#e0ffff`)
+pub const LIGHTCYAN: SRgb8 = rgb(0xE0, 0xFF, 0xFF);
+/// `lightgoldenrodyellow` (`#fafad2`)
+pub const LIGHTGOLDENRODYELLOW: SRgb8 = rgb(0xFA, 0xFA, 0xD2);
+/// `lightgray` (`#d3d3d3`)
+pub const LIGHTGRAY: SRgb8 = rgb(0xD3, 0xD3, 0xD3);
+/// `lightgreen` (`#90ee90`)
+pub const LIGHTGREEN: SRgb8 = rgb(0x90, 0xEE, 0x90);
+/// `lightgrey` (`#d3d3d3`)
+pub const LIGHTGREY: SRgb8 = rgb(0xD3, 0xD3, 0xD3);
+/// `lightpink` (`#ffb6c1`)
+pub const LIGHTPINK: SRgb8 = rgb(0xFF, 0xB6, 0xC1);
+/// `lightsalmon` (`#ffa07a`)
+pub const LIGHTSALMON: SRgb8 = rgb(0xFF, 0xA0, 0x7A);
+/// `lightseagreen` (`#20b2aa`)
+pub const LIGHTSEAGREEN: SRgb8 = rgb(0x20, 0xB2, 0xAA);
+/// `lightskyblue` (`#87cefa`)
+pub const LIGHTSKYBLUE: SRgb8 = rgb(0x87, 0xCE, 0xFA);
+/// `lightslategray` (`#778899`)
+pub const LIGHTSLATEGRAY: SRgb8 = rgb(0x77, 0x88, 0x99);
+/// `lightslategrey` (`#778899`)
+pub const LIGHTSLATEGREY: SRgb8 = rgb(0x77, 0x88, 0x99);
+/// `lightsteelblue` (`#b0c4de`)
+pub const LIGHTSTEELBLUE: SRgb8 = rgb(0xB0, 0xC4, 0xDE);
+/// `lightyellow` (`#ffffe0`)
+pub const LIGHTYELLOW: SRgb8 = rgb(0xFF, 0xFF, 0xE0);
+/// `lime` (`#00ff00`)
+pub const LIME: SRgb8 = rgb(0x00, 0xFF, 0x00);
+/// `limegreen` (`#32cd32`)
+pub const LIMEGREEN: SRgb8 = rgb(0x32, 0xCD, 0x32);
+/// `linen` (`#faf0e6`)
+pub const LINEN: SRgb8 = rgb(0xFA, 0xF0, 0xE6);
+/// `magenta` (`#ff00ff`)
+pub const MAGENTA: SRgb8 = rgb(0xFF, 0x00, 0xFF);
+/// `maroon` (`#800000`)
+pub const MAROON: SRgb8 = rgb(0x80, 0x00, 0x00);
+/// `mediumaquamarine` (`#66cdaa`)
+pub const MEDIUMAQUAMARINE: SRgb8 = rgb(0x66, 0xCD, 0xAA);
+/// `mediumblue` (`#0000cd`)
+pub const MEDIUMBLUE: SRgb8 = rgb(0x00, 0x00, 0xCD);
+/// `mediumorchid` (`#ba55d3`)
+pub const MEDIUMORCHID: SRgb8 = rgb(0xBA, 0x55, 0xD3);
+/// `mediumpurple` (`#9370db`)
+pub const MEDIUMPURPLE: SRgb8 = rgb(0x93, 0x70, 0xDB);
+/// `mediumseagreen` (`#3cb371`)
+pub const MEDIUMSEAGREEN: SRgb8 = rgb(0x3C, 0xB3, 0x71);
+/// `mediumslateblue` (`#7b68ee`)
+pub const MEDIUMSLATEBLUE: SRgb8 = rgb(0x7B, 0x68, 0xEE);
+/// `mediumspringgreen` (`#00fa9a`)
+pub const MEDIUMSPRINGGREEN: SRgb8 = rgb(0x00, 0xFA, 0x9A);
+/// `mediumturquoise` (`#48d1cc`)
+pub const MEDIUMTURQUOISE: SRgb8 = rgb(0x48, 0xD1, 0xCC);
+/// `mediumvioletred` (`#c71585`)
+pub const MEDIUMVIOLETRED: SRgb8 = rgb(0xC7, 0x15, 0x85);
+/// `midnightblue` (`#191970`)
+pub const MIDNIGHTBLUE: SRgb8 = rgb(0x19, 0x19, 0x70);
+/// `mintcream` (`#f5fffa`)
+pub const MINTCREAM: SRgb8 = rgb(0xF5, 0xFF, 0xFA);
+/// `mistyrose` (`#ffe4e1`)
+pub const MISTYROSE: SRgb8 = rgb(0xFF, 0xE4, 0xE1);
+/// `moccasin` (`#ffe4b5`)
+pub const MOCCASIN: SRgb8 = rgb(0xFF, 0xE4, 0xB5);
+/// `navajowhite` (`#ffdead`)
+pub const NAVAJOWHITE: SRgb8 = rgb(0xFF, 0xDE, 0xAD);
+/// `navy` (`#000080`)
+pub const NAVY: SRgb8 = rgb(0x00, 0x00, 0x80);
+/// `oldlace` (`#fdf5e6`)
+pub const OLDLACE: SRgb8 = rgb(0xFD, 0xF5, 0xE6);
+/// `olive` (`#808000`)
+pub const OLIVE: SRgb8 = rgb(0x80, 0x80, 0x00);
+/// `olivedrab` (`#6b8e23`)
+pub const OLIVEDRAB: SRgb8 = rgb(0x6B, 0x8E, 0x23);
+/// `orange` (`#ffa500`)
+pub const ORANGE: SRgb8 = rgb(0xFF, 0xA5, 0x00);
+/// `orangered` (`#ff4500`)
+pub const ORANGERED: SRgb8 = rgb(0xFF, 0x45, 0x00);
+/// `orchid` (`#da70d6`)
+pub const ORCHID: SRgb8 = rgb(0xDA, 0x70, 0xD6);
+/// `palegoldenrod` (`#eee8aa`)
+pub const PALEGOLDENROD: SRgb8 = rgb(0xEE, 0xE8, 0xAA);
+/// `palegreen` (`#98fb98`)
+pub const PALEGREEN: SRgb8 = rgb(0x98, 0xFB, 0x98);
+/// `paleturquoise` (`#afeeee`)
+pub const PALETURQUOISE: SRgb8 = rgb(0xAF, 0xEE, 0xEE);
+/// `palevioletred` (`#db7093`)
+pub const PALEVIOLETRED: SRgb8 = rgb(0xDB, 0x70, 0x93);
+/// `papayawhip` (`#ffefd5`)
+pub const PAPAYAWHIP: SRgb8 = rgb(0xFF, 0xEF, 0xD5);
+/// `peachpuff` (`#ffdab9`)
+pub const PEACHPUFF: SRgb8 = rgb(0xFF, 0xDA, 0xB9);
+/// `peru` (`#cd853f`)
+pub const PERU: SRgb8 = rgb(0xCD, 0x85, 0x3F);
+/// `pink` (`#ffc0cb`)
+pub const PINK: SRgb8 = rgb(0xFF, 0xC0, 0xCB);
+/// `plum` (`#dda0dd`)
+pub const PLUM: SRgb8 = rgb(0xDD, 0xA0, 0xDD);
+/// `powderblue` (`#b0e0e6`)
+pub const POWDERBLUE: SRgb8 = rgb(0xB0, 0xE0, 0xE6);
+/// `purple` (`#800080`)
+pub const PURPLE: SRgb8 = rgb(0x80, 0x00, 0x80);
+/// `rebeccapurple` (`#663399`)
+pub const REBECCAPURPLE: SRgb8 = rgb(0x66, 0x33, 0x99);
+/// `red` (`#ff0000`)
+pub const RED: SRgb8 = rgb(0xFF, 0x00, 0x00);
+/// `rosybrown` (`#bc8f8f`)
+pub const ROSYBROWN: SRgb8 = rgb(0xBC, 0x8F, 0x8F);
+/// `royalblue` (`#4169e1`)
+pub const ROYALBLUE: SRgb8 = rgb(0x41, 0x69, 0xE1);
+/// `saddlebrown` (`#8b4513`)
+pub const SADDLEBROWN: SRgb8 = rgb(0x8B, 0x45, 0x13);
+/// `salmon` (`#fa8072`)
+pub const SALMON: SRgb8 = rgb(0xFA, 0x80, 0x72);
+/// `sandybrown` (`#f4a460`)
+pub const SANDYBROWN: SRgb8 = rgb(0xF4, 0xA4, 0x60);
+/// `seagreen` (`#2e8b57`)
+pub const SEAGREEN: SRgb8 = rgb(0x2E, 0x8B, 0x57);
+/// `seashell` (`#fff5ee`)
+pub const SEASHELL: SRgb8 = rgb(0xFF, 0xF5, 0xEE);
+/// `sienna` (`#a0522d`)
+pub const SIENNA: SRgb8 = rgb(0xA0, 0x52, 0x2D);
+/// `silver` (`#c0c0c0`)
+pub const SILVER: SRgb8 = rgb(0xC0, 0xC0, 0xC0);
+/// `skyblue` (`#87ceeb`)
+pub const SKYBLUE: SRgb8 = rgb(0x87, 0xCE, 0xEB);
+/// `slateblue` (`#6a5acd`)
+pub const SLATEBLUE: SRgb8 = rgb(0x6A, 0x5A, 0xCD);
+/// `slategray` (`#708090`)
+pub const SLATEGRAY: SRgb8 = rgb(0x70, 0x80, 0x90);
+/// `slategrey` (`#708090`)
+pub const SLATEGREY: SRgb8 = rgb(0x70, 0x80, 0x90);
+/// `snow` (`#fffafa`)
+pub const SNOW: SRgb8 = rgb(0xFF, 0xFA, 0xFA);
+/// `springgreen` (`#00ff7f`)
+pub const SPRINGGREEN: SRgb8 = rgb(0x00, 0xFF, 0x7F);
+/// `steelblue` (`#4682b4`)
+pub const STEELBLUE: SRgb8 = rgb(0x46, 0x82, 0xB4);
+/// `tan` (`#d2b48c`)
+pub const TAN: SRgb8 = rgb(0xD2, 0xB4, 0x8C);
+/// `teal` (`#008080`)
+pub const TEAL: SRgb8 = rgb(0x00, 0x80, 0x80);
+/// `thistle` (`#d8bfd8`)
+pub const THISTLE: SRgb8 = rgb(0xD8, 0xBF, 0xD8);
+/// `tomato` (`#ff6347`)
+pub const TOMATO: SRgb8 = rgb(0xFF, 0x63, 0x47);
+/// `turquoise` (`#40e0d0`)
+pub const TURQUOISE: SRgb8 = rgb(0x40, 0xE0, 0xD0);
+/// `violet` (`#ee82ee`)
+pub const VIOLET: SRgb8 = rgb(0xEE, 0x82, 0xEE);
+/// `wheat` (`#f5deb3`)
+pub const WHEAT: SRgb8 = rgb(0xF5, 0xDE, 0xB3);
+/// `white` (`#ffffff`)
+pub const WHITE: SRgb8 = rgb(0xFF, 0xFF, 0xFF);
+/// `whitesmoke` (`#f5f5f5`)
+pub const WHITESMOKE: SRgb8 = rgb(0xF5, 0xF5, 0xF5);
+/// `yellow` (`#ffff00`)
+pub const YELLOW: SRgb8 = rgb(0xFF, 0xFF, 0x00);
+/// `yellowgreen` (`#9acd32`)
+pub const YELLOWGREEN: SRgb8 = rgb(0x9A, 0xCD, 0x32);
+
+/// Named colors, sorted by name
+const NAMES: &[(&str, SRgb8)] = &[
+    ("aliceblue", ALICEBLUE),
+    ("antiquewhite", ANTIQUEWHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUAMARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHEDALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUEVIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLYWOOD),
+    ("cadetblue", CADETBLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWERBLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARKBLUE),
+    ("darkcyan", DARKCYAN),
+    ("darkgoldenrod", DARKGOLDENROD),
+    ("darkgray", DARKGRAY),
+    ("darkgreen", DARKGREEN),
+    ("darkgrey", DARKGREY),
+    ("darkkhaki", DARKKHAKI),
+    ("darkmagenta", DARKMAGENTA),
+    ("darkolivegreen", DARKOLIVEGREEN),
+    ("darkorange", DARKORANGE),
+    ("darkorchid", DARKORCHID),
+    ("darkred", DARKRED),
+    ("darksalmon", DARKSALMON),
+    ("darkseagreen", DARKSEAGREEN),
+    ("darkslateblue", DARKSLATEBLUE),
+    ("darkslategray", DARKSLATEGRAY),
+    ("darkslategrey", DARKSLATEGREY),
+    ("darkturquoise", DARKTURQUOISE),
+    ("darkviolet", DARKVIOLET),
+    ("deeppink", DEEPPINK),
+    ("deepskyblue", DEEPSKYBLUE),
+    ("dimgray", DIMGRAY),
+    ("dimgrey", DIMGREY),
+    ("dodgerblue", DODGERBLUE),
+    ("firebrick", FIREBRICK),
+    ("floralwhite", FLORALWHITE),
+    ("forestgreen", FORESTGREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOSTWHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDENROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREENYELLOW),
+    ("grey", GREY),
+    ("honeydew", HONEYDEW),
+    ("hotpink", HOTPINK),
+    ("indianred", INDIANRED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDERBLUSH),
+    ("lawngreen", LAWNGREEN),
+    ("lemonchiffon", LEMONCHIFFON),
+    ("lightblue", LIGHTBLUE),
+    ("lightcoral", LIGHTCORAL),
+    ("lightcyan", LIGHTCYAN),
+    ("lightgoldenrodyellow", LIGHTGOLDENRODYELLOW),
+    ("lightgray", LIGHTGRAY),
+    ("lightgreen", LIGHTGREEN),
+    ("lightgrey", LIGHTGREY),
+    ("lightpink", LIGHTPINK),
+    ("lightsalmon", LIGHTSALMON),
+    ("lightseagreen", LIGHTSEAGREEN),
+    ("lightskyblue", LIGHTSKYBLUE),
+    ("lightslategray", LIGHTSLATEGRAY),
+    ("lightslategrey", LIGHTSLATEGREY),
+    ("lightsteelblue", LIGHTSTEELBLUE),
+    ("lightyellow", LIGHTYELLOW),
+    ("lime", LIME),
+    ("limegreen", LIMEGREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUMAQUAMARINE),
+    ("mediumblue", MEDIUMBLUE),
+    ("mediumorchid", MEDIUMORCHID),
+    ("mediumpurple", MEDIUMPURPLE),
+    ("mediumseagreen", MEDIUMSEAGREEN),
+    ("mediumslateblue", MEDIUMSLATEBLUE),
+    ("mediumspringgreen", MEDIUMSPRINGGREEN),
+    ("mediumturquoise", MEDIUMTURQUOISE),
+    ("mediumvioletred", MEDIUMVIOLETRED),
+    ("midnightblue", MIDNIGHTBLUE),
+    ("mintcream", MINTCREAM),
+    ("mistyrose", MISTYROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJOWHITE),
+    ("navy", NAVY),
+    ("oldlace", OLDLACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVEDRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGERED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALEGOLDENROD),
+    ("palegreen", PALEGREEN),
+    ("paleturquoise", PALETURQUOISE),
+    ("palevioletred", PALEVIOLETRED),
+    ("papayawhip", PAPAYAWHIP),
+    ("peachpuff", PEACHPUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDERBLUE),
+    ("purple", PURPLE),
+    ("rebeccapurple", REBECCAPURPLE),
+    ("red", RED),
+    ("rosybrown", ROSYBROWN),
+    ("royalblue", ROYALBLUE),
+    ("saddlebrown", SADDLEBROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDYBROWN),
+    ("seagreen", SEAGREEN),
+    ("seashell", SEASHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKYBLUE),
+    ("slateblue", SLATEBLUE),
+    ("slategray", SLATEGRAY),
+    ("slategrey", SLATEGREY),
+    ("snow", SNOW),
+    ("springgreen", SPRINGGREEN),
+    ("steelblue", STEELBLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITESMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOWGREEN),
+];
+
+/// Look up a named color.
+///
+/// * `name` Color name, ignoring ASCII case.
+pub fn from_name(name: &str) -> Option<SRgb8> {
+    let name = name.to_ascii_lowercase();
+    NAMES
+        .binary_search_by(|(n, _)| (*n).cmp(name.as_str()))
+        .ok()
+        .map(|i| NAMES[i].1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(NAMES.len(), 148);
+        assert!(NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(from_name("GRAY"), Some(SRgb8::new(0x80, 0x80, 0x80)));
+        assert_eq!(from_name("grey"), from_name("gray"));
+        assert_eq!(from_name("yellowgreen"), Some(YELLOWGREEN));
+        assert_eq!(from_name("aliceblue"), Some(ALICEBLUE));
+        assert_eq!(from_name("transparent"), None);
+    }
+}
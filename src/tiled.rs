@@ -0,0 +1,262 @@
+// tiled.rs     Tiled raster images.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+use crate::el::Pixel;
+use crate::raster::{Raster, Region};
+
+/// Default tile width / height
+const TILE_SIZE: u32 = 64;
+
+/// Image stored as a grid of fixed-size tiles.
+///
+/// Each tile is a [Raster], which is only allocated when first accessed
+/// mutably.  This keeps memory local for operations on very large images,
+/// and sparse regions do not allocate.  Tiles at the right and bottom edges
+/// are clipped to the image dimensions.
+///
+/// Pixels in unallocated tiles have the default value.
+///
+/// ### Draw into a large sparse image
+/// ```
+/// use pix::gray::Gray8;
+/// use pix::TiledRaster;
+///
+/// let mut t = TiledRaster::<Gray8>::with_clear(100_000, 100_000);
+/// *t.pixel_mut(70_000, 30) = Gray8::new(0x80);
+/// assert_eq!(t.pixel(70_000, 30), Gray8::new(0x80));
+/// assert_eq!(t.tiles().count(), 1);
+/// ```
+///
+/// [raster]: struct.Raster.html
+#[derive(Clone)]
+pub struct TiledRaster<P: Pixel> {
+    /// Width of raster
+    width: u32,
+    /// Height of raster
+    height: u32,
+    /// Width / height of tiles
+    tile_size: u32,
+    /// Tiles in row-major order
+    tiles: Vec<Option<Raster<P>>>,
+}
+
+impl<P: Pixel> TiledRaster<P> {
+    /// Construct a `TiledRaster` with 64x64 tiles and no allocated pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is greater than `std::i32::MAX`.
+    pub fn with_clear(width: u32, height: u32) -> Self {
+        Self::with_tile_size(width, height, TILE_SIZE)
+    }
+
+    /// Construct a `TiledRaster` with a specified tile size and no allocated
+    /// pixels.
+    ///
+    /// * `width` Width of raster.
+    /// * `height` Height of raster.
+    /// * `tile_size` Width / height of tiles.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `tile_size` is zero
+    pub fn with_tile_size(width: u32, height: u32, tile_size: u32) -> Self {
+        i32::try_from(width).expect("Raster width too big");
+        i32::try_from(height).expect("Raster height too big");
+        assert!(tile_size > 0);
+        let len = width.div_ceil(tile_size) as usize
+            * height.div_ceil(tile_size) as usize;
+        let mut tiles = Vec::with_capacity(len);
+        tiles.resize_with(len, || None);
+        TiledRaster {
+            width,
+            height,
+            tile_size,
+            tiles,
+        }
+    }
+
+    /// Construct a `TiledRaster` by copying a [Raster].
+    ///
+    /// * `src` Source raster.
+    /// * `tile_size` Width / height of tiles.
+    ///
+    /// [raster]: struct.Raster.html
+    pub fn with_raster(src: &Raster<P>, tile_size: u32) -> Self {
+        let mut t = Self::with_tile_size(src.width(), src.height(), tile_size);
+        for ty in 0..t.tile_rows() {
+            for tx in 0..t.tile_columns() {
+                let reg = t.tile_region(tx, ty);
+                t.tile_mut(tx, ty).copy_raster((), src, reg);
+            }
+        }
+        t
+    }
+
+    /// Get width of raster.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get height of raster.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get width / height of tiles.
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Get the number of tile columns.
+    pub fn tile_columns(&self) -> u32 {
+        self.width.div_ceil(self.tile_size)
+    }
+
+    /// Get the number of tile rows.
+    pub fn tile_rows(&self) -> u32 {
+        self.height.div_ceil(self.tile_size)
+    }
+
+    /// Get the `Region` covered by a tile.
+    ///
+    /// * `tx` Tile column.
+    /// * `ty` Tile row.
+    pub fn tile_region(&self, tx: u32, ty: u32) -> Region {
+        let x = tx * self.tile_size;
+        let y = ty * self.tile_size;
+        let width = self.tile_size.min(self.width.saturating_sub(x));
+        let height = self.tile_size.min(self.height.saturating_sub(y));
+        Region::new(x as i32, y as i32, width, height)
+    }
+
+    /// Get index of a tile
+    fn tile_index(&self, tx: u32, ty: u32) -> usize {
+        assert!(tx < self.tile_columns() && ty < self.tile_rows());
+        ty as usize * self.tile_columns() as usize + tx as usize
+    }
+
+    /// Get a tile, if allocated.
+    ///
+    /// * `tx` Tile column.
+    /// * `ty` Tile row.
+    pub fn tile(&self, tx: u32, ty: u32) -> Option<&Raster<P>> {
+        self.tiles[self.tile_index(tx, ty)].as_ref()
+    }
+
+    /// Get a mutable tile, allocating it if necessary.
+    ///
+    /// * `tx` Tile column.
+    /// * `ty` Tile row.
+    pub fn tile_mut(&mut self, tx: u32, ty: u32) -> &mut Raster<P> {
+        let i = self.tile_index(tx, ty);
+        let reg = self.tile_region(tx, ty);
+        self.tiles[i].get_or_insert_with(|| {
+            Raster::with_clear(reg.width(), reg.height())
+        })
+    }
+
+    /// Deallocate a tile, resetting its pixels to the default value.
+    ///
+    /// * `tx` Tile column.
+    /// * `ty` Tile row.
+    pub fn clear_tile(&mut self, tx: u32, ty: u32) {
+        let i = self.tile_index(tx, ty);
+        self.tiles[i] = None;
+    }
+
+    /// Get an `Iterator` of allocated tiles, with their regions.
+    pub fn tiles(&self) -> impl Iterator<Item = (Region, &Raster<P>)> {
+        let cols = self.tile_columns();
+        self.tiles.iter().enumerate().filter_map(move |(i, t)| {
+            let (tx, ty) = (i as u32 % cols, i as u32 / cols);
+            t.as_ref().map(|t| (self.tile_region(tx, ty), t))
+        })
+    }
+
+    /// Get an `Iterator` of mutable allocated tiles, with their regions.
+    pub fn tiles_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (Region, &mut Raster<P>)> {
+        let cols = self.tile_columns();
+        let (ts, width, height) = (self.tile_size, self.width, self.height);
+        self.tiles.iter_mut().enumerate().filter_map(move |(i, t)| {
+            let (x, y) = ((i as u32 % cols) * ts, (i as u32 / cols) * ts);
+            let reg = Region::new(
+                x as i32,
+                y as i32,
+                ts.min(width - x),
+                ts.min(height - y),
+            );
+            t.as_mut().map(|t| (reg, t))
+        })
+    }
+
+    /// Get one pixel value.
+    pub fn pixel(&self, x: i32, y: i32) -> P {
+        assert!(x >= 0 && (x as u32) < self.width);
+        assert!(y >= 0 && (y as u32) < self.height);
+        let ts = self.tile_size as i32;
+        match self.tile((x / ts) as u32, (y / ts) as u32) {
+            Some(t) => t.pixel(x % ts, y % ts),
+            None => P::default(),
+        }
+    }
+
+    /// Get a mutable pixel, allocating its tile if necessary.
+    pub fn pixel_mut(&mut self, x: i32, y: i32) -> &mut P {
+        assert!(x >= 0 && (x as u32) < self.width);
+        assert!(y >= 0 && (y as u32) < self.height);
+        let ts = self.tile_size as i32;
+        self.tile_mut((x / ts) as u32, (y / ts) as u32)
+            .pixel_mut(x % ts, y % ts)
+    }
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a `Raster` by copying all tiles of a [TiledRaster].
+    ///
+    /// [tiledraster]: struct.TiledRaster.html
+    pub fn with_tiled(src: &TiledRaster<P>) -> Self {
+        let mut r = Raster::with_clear(src.width(), src.height());
+        for (reg, tile) in src.tiles() {
+            r.copy_raster(reg, tile, ());
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::gray::*;
+    use crate::*;
+
+    #[test]
+    fn edge_tiles() {
+        let mut t = TiledRaster::<Gray8>::with_tile_size(10, 5, 4);
+        assert_eq!((t.tile_columns(), t.tile_rows()), (3, 2));
+        assert_eq!(t.tile_region(2, 1), Region::new(8, 4, 2, 1));
+        *t.pixel_mut(9, 4) = Gray8::new(9);
+        assert_eq!(t.tile(2, 1).unwrap().region(), Region::new(0, 0, 2, 1));
+        assert!(t.tile(0, 0).is_none());
+        let regs: Vec<Region> = t.tiles_mut().map(|(r, _)| r).collect();
+        assert_eq!(regs, [Region::new(8, 4, 2, 1)]);
+        t.clear_tile(2, 1);
+        assert_eq!(t.pixel(9, 4), Gray8::new(0));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut r = Raster::<Gray8>::with_clear(7, 6);
+        for (i, p) in r.pixels_mut().iter_mut().enumerate() {
+            *p = Gray8::new(i as u8);
+        }
+        let t = TiledRaster::with_raster(&r, 3);
+        assert_eq!(t.tiles().count(), 6);
+        assert_eq!(t.pixel(6, 5), Gray8::new(41));
+        let r2 = Raster::with_tiled(&t);
+        assert_eq!(r.pixels(), r2.pixels());
+    }
+}
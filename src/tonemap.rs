@@ -0,0 +1,194 @@
+// tonemap.rs   HDR tone mapping.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Tone mapping of high dynamic range (HDR) images.
+//!
+//! `Ch32` channels are limited to `0.0` to `1.0`, so HDR source values are
+//! provided as *linear* `[f32; 3]` *red*, *green* and *blue* arrays.  A
+//! [ToneMap] operator compresses them into a display-referred `Raster`, with
+//! [with_tone_mapped].
+//!
+//! ### Tone map a bright gradient
+//! ```
+//! use pix::rgb::SRgb8;
+//! use pix::tonemap::ToneMap;
+//! use pix::Raster;
+//!
+//! let hdr: Vec<[f32; 3]> = (0..16).map(|i| [i as f32; 3]).collect();
+//! let r = Raster::<SRgb8>::with_tone_mapped(4, 4, &hdr, 0.0, ToneMap::Aces);
+//! assert_eq!(r.pixel(0, 0), SRgb8::new(0, 0, 0));
+//! assert_eq!(r.pixel(1, 0), SRgb8::new(0xD6, 0xD6, 0xD6));
+//! assert_eq!(r.pixel(3, 3), SRgb8::new(0xFF, 0xFF, 0xFF));
+//! ```
+//!
+//! [ToneMap]: enum.ToneMap.html
+//! [with_tone_mapped]: ../struct.Raster.html#method.with_tone_mapped
+use crate::el::Pixel;
+use crate::raster::Raster;
+use crate::rgb::Rgb32;
+
+/// Rec. 709 luma coefficients for linear *red*, *green* and *blue*
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Tone mapping operator
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToneMap {
+    /// Reinhard operator, *L* / (1 + *L*), applied to luminance
+    ///
+    /// Never reaches white, so highlights are dimmed.
+    Reinhard,
+    /// Extended Reinhard operator, applied to luminance
+    ///
+    /// Luminance at `white` and above is mapped to white.
+    ReinhardExtended {
+        /// Smallest luminance mapped to white
+        white: f32,
+    },
+    /// ACES filmic curve, applied to each channel
+    ///
+    /// Uses the fit by Krzysztof Narkowicz (2015), including its `0.6`
+    /// input scale.  Highlights desaturate toward white.
+    Aces,
+}
+
+impl ToneMap {
+    /// Map a *linear* HDR value to the range `0.0` to `1.0`.
+    ///
+    /// * `rgb` Linear *red*, *green* and *blue* (not clamped).
+    ///
+    /// Negative and `NaN` values are treated as `0.0`.
+    pub fn map_rgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        let rgb = rgb.map(|c| if c > 0.0 { c } else { 0.0 });
+        match self {
+            ToneMap::Reinhard => scale_luma(rgb, |y| y / (1.0 + y)),
+            ToneMap::ReinhardExtended { white } => {
+                let w2 = (white * white).max(f32::MIN_POSITIVE);
+                scale_luma(rgb, |y| y * (1.0 + y / w2) / (1.0 + y))
+            }
+            ToneMap::Aces => rgb.map(|c| {
+                let x = c * 0.6;
+                let v =
+                    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14);
+                v.clamp(0.0, 1.0)
+            }),
+        }
+    }
+}
+
+/// Scale color values by a luminance curve, clamping the result
+fn scale_luma<F>(rgb: [f32; 3], curve: F) -> [f32; 3]
+where
+    F: Fn(f32) -> f32,
+{
+    let y: f32 = rgb.iter().zip(LUMA).map(|(c, l)| c * l).sum();
+    if y > 0.0 && y.is_finite() {
+        let s = curve(y) / y;
+        rgb.map(|c| (c * s).clamp(0.0, 1.0))
+    } else if y > 0.0 {
+        [1.0; 3]
+    } else {
+        [0.0; 3]
+    }
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a `Raster` by tone mapping HDR values.
+    ///
+    /// * `width` Width of `Raster`.
+    /// * `height` Height of `Raster`.
+    /// * `hdr` Linear *red*, *green* and *blue* values, in row-major order.
+    /// * `exposure` Exposure adjustment in stops, applied before mapping.
+    /// * `op` Tone mapping operator.
+    ///
+    /// # Panics
+    ///
+    /// * If `width` or `height` is greater than `std::i32::MAX`
+    /// * If `hdr` length is not equal to `width` * `height`
+    pub fn with_tone_mapped(
+        width: u32,
+        height: u32,
+        hdr: &[[f32; 3]],
+        exposure: f32,
+        op: ToneMap,
+    ) -> Self {
+        let scale = exposure.exp2();
+        let pixels: Vec<P> = hdr
+            .iter()
+            .map(|rgb| {
+                let [r, g, b] = op.map_rgb(rgb.map(|c| c * scale));
+                Rgb32::new(r, g, b).convert_lossy()
+            })
+            .collect();
+        Raster::with_pixels(width, height, pixels)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reinhard() {
+        let op = ToneMap::Reinhard;
+        assert_eq!(op.map_rgb([0.0; 3]), [0.0; 3]);
+        assert_eq!(op.map_rgb([1.0; 3]), [0.5; 3]);
+        assert_eq!(op.map_rgb([3.0; 3]), [0.75; 3]);
+        assert_eq!(op.map_rgb([-1.0, f32::NAN, 0.0]), [0.0; 3]);
+        // hue is preserved
+        let [r, g, b] = op.map_rgb([0.4, 0.2, 0.1]);
+        assert!((r / g - 2.0).abs() < 1e-6 && (g / b - 2.0).abs() < 1e-6);
+        assert_eq!(op.map_rgb([f32::INFINITY; 3]), [1.0; 3]);
+    }
+
+    #[test]
+    fn reinhard_extended() {
+        let op = ToneMap::ReinhardExtended { white: 4.0 };
+        assert_eq!(op.map_rgb([4.0; 3]), [1.0; 3]);
+        assert_eq!(op.map_rgb([8.0; 3]), [1.0; 3]);
+        let [v, _, _] = op.map_rgb([1.0; 3]);
+        assert!((v - 0.53125).abs() < 1e-6, "{v}");
+        let op = ToneMap::ReinhardExtended { white: 0.0 };
+        assert_eq!(op.map_rgb([0.0; 3]), [0.0; 3]);
+    }
+
+    #[test]
+    fn aces() {
+        let op = ToneMap::Aces;
+        assert_eq!(op.map_rgb([0.0; 3]), [0.0; 3]);
+        assert_eq!(op.map_rgb([100.0; 3]), [1.0; 3]);
+        let [v, _, _] = op.map_rgb([0.18; 3]);
+        assert!((v - 0.1401).abs() < 1e-4, "{v}");
+    }
+
+    #[test]
+    fn exposure() {
+        use crate::gray::Gray32;
+        let hdr = [[1.0; 3], [0.5; 3]];
+        let r = Raster::<Gray32>::with_tone_mapped(
+            2,
+            1,
+            &hdr,
+            1.0,
+            ToneMap::Reinhard,
+        );
+        assert_eq!(r.pixel(1, 0), Gray32::new(0.5));
+    }
+
+    #[test]
+    fn alpha_destination() {
+        use crate::rgb::{Rgba32, SRgba8};
+        let hdr = [[0.0; 3], [1.0; 3]];
+        let r =
+            Raster::<SRgba8>::with_tone_mapped(2, 1, &hdr, 0.0, ToneMap::Aces);
+        assert_eq!(r.pixel(0, 0), SRgba8::new(0, 0, 0, 0xFF));
+        let r = Raster::<Rgba32>::with_tone_mapped(
+            2,
+            1,
+            &hdr,
+            0.0,
+            ToneMap::Reinhard,
+        );
+        assert_eq!(r.pixel(1, 0), Rgba32::new(0.5, 0.5, 0.5, 1.0));
+    }
+}
@@ -0,0 +1,194 @@
+// vk.rs        Vulkan formats.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Vulkan `VkFormat` mapping.
+//!
+//! Pixel types with a matching format implement [VkFormat], so renderers
+//! can derive the format from a raster's type parameter at compile time.
+//! Values are plain `i32`s, so no Vulkan bindings are required.
+//!
+//! Only RGB, BGR, gray and matte pixels are supported.  Alpha mode is not
+//! part of the format; *premultiplied* pixels map to the same format as
+//! *straight*.  Vulkan only has *sRGB* formats for 8-bit channels, and
+//! *sRGB* gray with alpha is not supported, since Vulkan would decode the
+//! alpha channel as *sRGB*.
+//!
+//! ### Texture format
+//! ```
+//! use pix::el::Pixel;
+//! use pix::rgb::SRgba8;
+//! use pix::vk::{self, VkFormat};
+//! use pix::Raster;
+//!
+//! fn format<P: Pixel + VkFormat>(_r: &Raster<P>) -> i32 {
+//!     P::FORMAT
+//! }
+//!
+//! let r = Raster::<SRgba8>::with_clear(16, 16);
+//! assert_eq!(format(&r), vk::R8G8B8A8_SRGB);
+//! ```
+//!
+//! [VkFormat]: trait.VkFormat.html
+use crate::bgr::Bgr;
+use crate::chan::{Alpha, Ch16, Ch32, Ch8, Linear, Srgb};
+use crate::el::{Pix1, Pix2, Pix3, Pix4, Pixel};
+use crate::gray::Gray;
+use crate::matte::Matte;
+use crate::rgb::Rgb;
+
+/// `VK_FORMAT_R8_UNORM`
+pub const R8_UNORM: i32 = 9;
+/// `VK_FORMAT_R8_SRGB`
+pub const R8_SRGB: i32 = 15;
+/// `VK_FORMAT_R8G8_UNORM`
+pub const R8G8_UNORM: i32 = 16;
+/// `VK_FORMAT_R8G8B8_UNORM`
+pub const R8G8B8_UNORM: i32 = 23;
+/// `VK_FORMAT_R8G8B8_SRGB`
+pub const R8G8B8_SRGB: i32 = 29;
+/// `VK_FORMAT_B8G8R8_UNORM`
+pub const B8G8R8_UNORM: i32 = 30;
+/// `VK_FORMAT_B8G8R8_SRGB`
+pub const B8G8R8_SRGB: i32 = 36;
+/// `VK_FORMAT_R8G8B8A8_UNORM`
+pub const R8G8B8A8_UNORM: i32 = 37;
+/// `VK_FORMAT_R8G8B8A8_SRGB`
+pub const R8G8B8A8_SRGB: i32 = 43;
+/// `VK_FORMAT_B8G8R8A8_UNORM`
+pub const B8G8R8A8_UNORM: i32 = 44;
+/// `VK_FORMAT_B8G8R8A8_SRGB`
+pub const B8G8R8A8_SRGB: i32 = 50;
+/// `VK_FORMAT_R16_UNORM`
+pub const R16_UNORM: i32 = 70;
+/// `VK_FORMAT_R16G16_UNORM`
+pub const R16G16_UNORM: i32 = 77;
+/// `VK_FORMAT_R16G16B16_UNORM`
+pub const R16G16B16_UNORM: i32 = 84;
+/// `VK_FORMAT_R16G16B16A16_UNORM`
+pub const R16G16B16A16_UNORM: i32 = 91;
+/// `VK_FORMAT_R32_SFLOAT`
+pub const R32_SFLOAT: i32 = 100;
+/// `VK_FORMAT_R32G32_SFLOAT`
+pub const R32G32_SFLOAT: i32 = 103;
+/// `VK_FORMAT_R32G32B32_SFLOAT`
+pub const R32G32B32_SFLOAT: i32 = 106;
+/// `VK_FORMAT_R32G32B32A32_SFLOAT`
+pub const R32G32B32A32_SFLOAT: i32 = 109;
+
+/// [Pixel] type with a `VkFormat`.
+///
+/// [pixel]: ../el/trait.Pixel.html
+pub trait VkFormat: Pixel {
+    /// `VkFormat` value
+    const FORMAT: i32;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch8, Gray, A, Linear> {
+    const FORMAT: i32 = R8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch8, Gray, A, Srgb> {
+    const FORMAT: i32 = R8_SRGB;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch16, Gray, A, Linear> {
+    const FORMAT: i32 = R16_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch32, Gray, A, Linear> {
+    const FORMAT: i32 = R32_SFLOAT;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch8, Matte, A, Linear> {
+    const FORMAT: i32 = R8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch16, Matte, A, Linear> {
+    const FORMAT: i32 = R16_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix1<Ch32, Matte, A, Linear> {
+    const FORMAT: i32 = R32_SFLOAT;
+}
+
+impl<A: Alpha> VkFormat for Pix2<Ch8, Gray, A, Linear> {
+    const FORMAT: i32 = R8G8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix2<Ch16, Gray, A, Linear> {
+    const FORMAT: i32 = R16G16_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix2<Ch32, Gray, A, Linear> {
+    const FORMAT: i32 = R32G32_SFLOAT;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch8, Rgb, A, Linear> {
+    const FORMAT: i32 = R8G8B8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch8, Rgb, A, Srgb> {
+    const FORMAT: i32 = R8G8B8_SRGB;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch16, Rgb, A, Linear> {
+    const FORMAT: i32 = R16G16B16_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch32, Rgb, A, Linear> {
+    const FORMAT: i32 = R32G32B32_SFLOAT;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch8, Bgr, A, Linear> {
+    const FORMAT: i32 = B8G8R8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix3<Ch8, Bgr, A, Srgb> {
+    const FORMAT: i32 = B8G8R8_SRGB;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch8, Rgb, A, Linear> {
+    const FORMAT: i32 = R8G8B8A8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch8, Rgb, A, Srgb> {
+    const FORMAT: i32 = R8G8B8A8_SRGB;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch16, Rgb, A, Linear> {
+    const FORMAT: i32 = R16G16B16A16_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch32, Rgb, A, Linear> {
+    const FORMAT: i32 = R32G32B32A32_SFLOAT;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch8, Bgr, A, Linear> {
+    const FORMAT: i32 = B8G8R8A8_UNORM;
+}
+
+impl<A: Alpha> VkFormat for Pix4<Ch8, Bgr, A, Srgb> {
+    const FORMAT: i32 = B8G8R8A8_SRGB;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bgr::*;
+    use crate::gray::*;
+    use crate::matte::*;
+    use crate::rgb::*;
+
+    #[test]
+    fn formats() {
+        assert_eq!(Rgba8::FORMAT, R8G8B8A8_UNORM);
+        assert_eq!(SRgba8p::FORMAT, R8G8B8A8_SRGB);
+        assert_eq!(SBgra8::FORMAT, B8G8R8A8_SRGB);
+        assert_eq!(Rgb16::FORMAT, R16G16B16_UNORM);
+        assert_eq!(Rgba32p::FORMAT, R32G32B32A32_SFLOAT);
+        assert_eq!(SGray8::FORMAT, R8_SRGB);
+        assert_eq!(Graya16::FORMAT, R16G16_UNORM);
+        assert_eq!(Matte8::FORMAT, R8_UNORM);
+    }
+}
@@ -0,0 +1,127 @@
+// wgpu.rs      wgpu texture formats.
+//
+// Copyright (c) 2026  Douglas P Lau
+//
+//! Texture format mapping for [wgpu].
+//!
+//! [texture_format] finds the `TextureFormat` matching a pixel type, with
+//! the correct *sRGB* encoding.  Pixel types with no exact match, such as
+//! three-channel RGB or *sRGB* float formats, have no texture format;
+//! convert them to a supported type first.
+//!
+//! ### Upload a raster
+//! ```
+//! use pix::rgb::SRgba8;
+//! use pix::wgpu::{padded_bytes_per_row, texture_format, upload_bytes};
+//! use pix::Raster;
+//! use wgpu_types::TextureFormat;
+//!
+//! let r = Raster::<SRgba8>::with_clear(100, 20);
+//! assert_eq!(
+//!     texture_format::<SRgba8>(),
+//!     Some(TextureFormat::Rgba8UnormSrgb)
+//! );
+//! let bytes_per_row = padded_bytes_per_row::<SRgba8>(r.width());
+//! assert_eq!(bytes_per_row, 512);
+//! assert_eq!(upload_bytes(&r).len(), 512 * 20);
+//! ```
+//!
+//! [texture_format]: fn.texture_format.html
+//! [wgpu]: https://wgpu.rs
+use crate::el::Pixel;
+use crate::format::{Depth, Layout, Order};
+use crate::raster::Raster;
+use wgpu_types::{TextureFormat, COPY_BYTES_PER_ROW_ALIGNMENT};
+
+/// Get the `TextureFormat` of a pixel type.
+///
+/// Alpha mode is not part of the texture format; *premultiplied* pixels map
+/// to the same format as *straight*.
+pub fn texture_format<P: Pixel>() -> Option<TextureFormat> {
+    use TextureFormat::*;
+    let layout = Layout::of::<P>()?;
+    Some(match (layout.order, layout.depth, layout.srgb) {
+        (Order::R, Depth::U8, false) => R8Unorm,
+        (Order::R, Depth::U16, false) => R16Unorm,
+        (Order::R, Depth::F32, false) => R32Float,
+        (Order::Rg, Depth::U8, false) => Rg8Unorm,
+        (Order::Rg, Depth::U16, false) => Rg16Unorm,
+        (Order::Rg, Depth::F32, false) => Rg32Float,
+        (Order::Rgba, Depth::U8, false) => Rgba8Unorm,
+        (Order::Rgba, Depth::U8, true) => Rgba8UnormSrgb,
+        (Order::Rgba, Depth::U16, false) => Rgba16Unorm,
+        (Order::Rgba, Depth::F32, false) => Rgba32Float,
+        (Order::Bgra, Depth::U8, false) => Bgra8Unorm,
+        (Order::Bgra, Depth::U8, true) => Bgra8UnormSrgb,
+        _ => return None,
+    })
+}
+
+/// Get the bytes per row for a buffer-to-texture copy.
+///
+/// * `width` Width of texture, in pixels.
+///
+/// Rows are padded to a multiple of `COPY_BYTES_PER_ROW_ALIGNMENT`.
+pub fn padded_bytes_per_row<P: Pixel>(width: u32) -> u32 {
+    let unpadded = width * std::mem::size_of::<P>() as u32;
+    unpadded.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+}
+
+/// Copy raster pixels into a buffer for uploading to a texture.
+///
+/// * `raster` Source raster.
+///
+/// Rows are padded to [padded_bytes_per_row], as required for copying from
+/// a buffer to a texture.
+///
+/// [padded_bytes_per_row]: fn.padded_bytes_per_row.html
+pub fn upload_bytes<P: Pixel>(raster: &Raster<P>) -> Vec<u8> {
+    let stride = padded_bytes_per_row::<P>(raster.width()) as usize;
+    let mut bytes = vec![0; stride * raster.height() as usize];
+    let row_len = raster.width() as usize * std::mem::size_of::<P>();
+    if row_len > 0 {
+        let src = raster.as_u8_slice().chunks_exact(row_len);
+        for (dst, src) in bytes.chunks_exact_mut(stride).zip(src) {
+            dst[..row_len].copy_from_slice(src);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bgr::*;
+    use crate::gray::*;
+    use crate::matte::*;
+    use crate::rgb::*;
+    use crate::xyz::*;
+
+    #[test]
+    fn formats() {
+        use TextureFormat::*;
+        assert_eq!(texture_format::<Rgba8>(), Some(Rgba8Unorm));
+        assert_eq!(texture_format::<SRgba8p>(), Some(Rgba8UnormSrgb));
+        assert_eq!(texture_format::<SBgra8>(), Some(Bgra8UnormSrgb));
+        assert_eq!(texture_format::<Rgba16>(), Some(Rgba16Unorm));
+        assert_eq!(texture_format::<Rgba32p>(), Some(Rgba32Float));
+        assert_eq!(texture_format::<Gray16>(), Some(R16Unorm));
+        assert_eq!(texture_format::<Graya8>(), Some(Rg8Unorm));
+        assert_eq!(texture_format::<Matte32>(), Some(R32Float));
+        assert_eq!(texture_format::<SRgba32>(), None);
+        assert_eq!(texture_format::<SGray8>(), None);
+        assert_eq!(texture_format::<Rgb8>(), None);
+        assert_eq!(texture_format::<Xyz32>(), None);
+    }
+
+    #[test]
+    fn upload() {
+        let mut r = Raster::<Gray16>::with_clear(3, 2);
+        *r.pixel_mut(2, 1) = Gray16::new(0x0102);
+        assert_eq!(padded_bytes_per_row::<Gray16>(3), 256);
+        let bytes = upload_bytes(&r);
+        assert_eq!(bytes.len(), 512);
+        assert_eq!(&bytes[256 + 4..256 + 6], &0x0102u16.to_ne_bytes());
+        assert!(upload_bytes(&Raster::<Gray8>::with_clear(0, 4)).is_empty());
+    }
+}
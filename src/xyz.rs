@@ -7,9 +7,12 @@
 //! [XYZ]: https://en.wikipedia.org/wiki/CIE_1931_color_space#Definition_of_the_CIE_XYZ_color_space
 #![allow(clippy::excessive_precision)]
 
-use crate::chan::{Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Straight};
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::chan::{
+    Alpha, Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Straight,
+};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel};
 use std::ops::Range;
 
 /// [Xyz] [color model] with D65 white point.
@@ -132,10 +135,34 @@ impl Xyz {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [Xyz] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch32;
+    /// use pix::xyz::{XyzPixel, Xyz32};
+    ///
+    /// let mut p = Xyz32::new(0.5, 0.25, 0.75);
+    /// assert_eq!(p.x(), Ch32::new(0.5));
+    /// *p.z_mut() = Ch32::new(0.125);
+    /// assert_eq!(p.z(), Ch32::new(0.125));
+    /// ```
+    ///
+    /// [Xyz]: struct.Xyz.html
+    XyzPixel: Xyz {
+        x, x_mut, with_x;
+        y, y_mut, with_y;
+        z, z_mut, with_z;
+    }
+}
+
 impl ColorModel for Xyz {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] =
+        &[ChannelLabel::X, ChannelLabel::Y, ChannelLabel::Z];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -145,11 +172,7 @@ impl ColorModel for Xyz {
         let px = Self::x(p).to_f32();
         let py = Self::y(p).to_f32();
         let pz = Self::z(p).to_f32();
-
-        let red = px * 3.2406 + py * -1.5372 + pz * -0.4986;
-        let green = px * -0.9689 + py * 1.8758 + pz * 0.0415;
-        let blue = px * 0.0557 + py * -0.2040 + pz * 1.0570;
-
+        let [red, green, blue] = xyz_to_linear_rgb([px, py, pz]);
         PixRgba::<P>::new(red, green, blue, p.alpha().to_f32())
     }
 
@@ -168,8 +191,28 @@ impl ColorModel for Xyz {
         let y = red * 0.2126 + green * 0.7152 + blue * 0.0722;
         let z = red * 0.0193 + green * 0.1192 + blue * 0.9505;
 
-        P::from_channels(&[x.into(), y.into(), z.into(), alpha])
+        from_model_channels(&[x.into(), y.into(), z.into(), alpha])
     }
+
+    /// Convert into *linear* *red*, *green* and *blue*, without clamping
+    fn into_linear_rgb<P>(p: P) -> [f32; 3]
+    where
+        P: Pixel<Model = Self>,
+    {
+        let a = p.alpha();
+        let px = P::Alpha::decode(Self::x(p), a).to_f32();
+        let py = P::Alpha::decode(Self::y(p), a).to_f32();
+        let pz = P::Alpha::decode(Self::z(p), a).to_f32();
+        xyz_to_linear_rgb([px, py, pz])
+    }
+}
+
+/// Convert *X*, *Y* and *Z* to *linear* *red*, *green* and *blue*
+fn xyz_to_linear_rgb([px, py, pz]: [f32; 3]) -> [f32; 3] {
+    let red = px * 3.2406 + py * -1.5372 + pz * -0.4986;
+    let green = px * -0.9689 + py * 1.8758 + pz * 0.0415;
+    let blue = px * 0.0557 + py * -0.2040 + pz * 1.0570;
+    [red, green, blue]
 }
 
 /// [Xyz](struct.Xyz.html) 8-bit opaque (no *alpha* channel)
@@ -6,9 +6,12 @@
 //! [YCbCr] color model and types.
 //!
 //! [ycbcr]: https://en.wikipedia.org/wiki/YCbCr
-use crate::chan::{Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Straight};
-use crate::el::{Pix3, Pix4, PixRgba, Pixel};
-use crate::ColorModel;
+use crate::chan::{
+    Ch16, Ch32, Ch8, Channel, Linear, Premultiplied, Srgb, Straight,
+};
+use crate::el::{from_model_channels, Pix3, Pix4, PixRgba, Pixel};
+use crate::model::model_pixel;
+use crate::{ChannelLabel, ColorModel, Raster};
 use std::ops::Range;
 
 /// [YCbCr] [color model] (used in JPEG and other formats).
@@ -137,10 +140,34 @@ impl YCbCr {
     }
 }
 
+model_pixel! {
+    /// Method accessors for pixels with [YCbCr] model
+    ///
+    /// ### Example
+    /// ```
+    /// use pix::chan::Ch8;
+    /// use pix::ycc::{YCbCrPixel, YCbCr8};
+    ///
+    /// let mut p = YCbCr8::new(1, 2, 3);
+    /// assert_eq!(p.y(), Ch8::new(1));
+    /// *p.cr_mut() = Ch8::new(9);
+    /// assert_eq!(p.cr(), Ch8::new(9));
+    /// ```
+    ///
+    /// [YCbCr]: struct.YCbCr.html
+    YCbCrPixel: YCbCr {
+        y, y_mut, with_y;
+        cb, cb_mut, with_cb;
+        cr, cr_mut, with_cr;
+    }
+}
+
 impl ColorModel for YCbCr {
     const CIRCULAR: Range<usize> = 0..0;
     const LINEAR: Range<usize> = 0..3;
     const ALPHA: usize = 3;
+    const LABELS: &'static [ChannelLabel] =
+        &[ChannelLabel::Luma, ChannelLabel::Cb, ChannelLabel::Cr];
 
     /// Convert into *red*, *green*, *blue* and *alpha* components
     fn into_rgba<P>(p: P) -> PixRgba<P>
@@ -172,7 +199,7 @@ impl ColorModel for YCbCr {
         let cb = 0.5 - (0.168_736 * red) - (0.331_264 * green) + (0.5 * blue);
         let cr = 0.5 + (0.5 * red) - (0.418_688 * green) - (0.081_312 * blue);
 
-        P::from_channels(&[y.into(), cb.into(), cr.into(), alpha])
+        from_model_channels(&[y.into(), cb.into(), cr.into(), alpha])
     }
 }
 
@@ -226,3 +253,490 @@ pub type YCbCra16p = Pix4<Ch16, YCbCr, Premultiplied, Linear>;
 /// [linear](../chan/struct.Linear.html) gamma [pixel](../el/trait.Pixel.html)
 /// format.
 pub type YCbCra32p = Pix4<Ch32, YCbCr, Premultiplied, Linear>;
+
+/// [YCbCr](struct.YCbCr.html) 8-bit opaque (no *alpha* channel)
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCr8 = Pix3<Ch8, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 16-bit opaque (no *alpha* channel)
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCr16 = Pix3<Ch16, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 32-bit opaque (no *alpha* channel)
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCr32 = Pix3<Ch32, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 8-bit
+/// [straight](../chan/struct.Straight.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra8 = Pix4<Ch8, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 16-bit
+/// [straight](../chan/struct.Straight.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra16 = Pix4<Ch16, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 32-bit
+/// [straight](../chan/struct.Straight.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra32 = Pix4<Ch32, YCbCr, Straight, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 8-bit
+/// [premultiplied](../chan/struct.Premultiplied.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra8p = Pix4<Ch8, YCbCr, Premultiplied, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 16-bit
+/// [premultiplied](../chan/struct.Premultiplied.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra16p = Pix4<Ch16, YCbCr, Premultiplied, Srgb>;
+
+/// [YCbCr](struct.YCbCr.html) 32-bit
+/// [premultiplied](../chan/struct.Premultiplied.html) alpha
+/// [sRGB](../chan/struct.Srgb.html) gamma [pixel](../el/trait.Pixel.html)
+/// format.
+pub type SYCbCra32p = Pix4<Ch32, YCbCr, Premultiplied, Srgb>;
+
+/// Chroma subsampling of a [YCbCrFrame](struct.YCbCrFrame.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Subsampling {
+    /// 4:2:0 -- half horizontal and half vertical chroma resolution
+    #[default]
+    S420,
+    /// 4:2:2 -- half horizontal chroma resolution
+    S422,
+}
+
+/// Location of chroma samples relative to luma samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChromaSiting {
+    /// Centered between luma samples (JPEG, MPEG-1)
+    #[default]
+    Center,
+    /// Co-sited horizontally with left luma samples, centered vertically
+    /// (MPEG-2, H.264)
+    Left,
+    /// Co-sited with top-left luma samples (BT.2020 / HEVC type 2)
+    TopLeft,
+}
+
+/// Frame of [YCbCr] planes with subsampled chroma, as produced by video
+/// decoders.
+///
+/// The *y* plane is full resolution, and the *Cb* / *Cr* planes are
+/// subsampled.  Odd dimensions are rounded up for chroma planes.  Samples
+/// are gamma-encoded (*Y'CbCr*), with [sRGB](../chan/struct.Srgb.html) gamma.
+///
+/// ### Convert an RGB raster to 4:2:0 and back
+/// ```
+/// use pix::chan::Ch8;
+/// use pix::rgb::SRgb8;
+/// use pix::ycc::{ChromaSiting, Subsampling, YCbCrFrame};
+/// use pix::Raster;
+///
+/// let r = Raster::with_color(64, 48, SRgb8::new(0x40, 0x80, 0xC0));
+/// let frame = YCbCrFrame::<Ch8>::with_raster(
+///     &r,
+///     Subsampling::S420,
+///     ChromaSiting::Center,
+/// );
+/// assert_eq!(frame.cb_plane().len(), 32 * 24);
+/// let r2 = Raster::<SRgb8>::with_ycbcr_frame(&frame);
+/// ```
+///
+/// [ycbcr]: struct.YCbCr.html
+#[derive(Clone)]
+pub struct YCbCrFrame<C: Channel> {
+    /// Width of frame
+    width: u32,
+    /// Height of frame
+    height: u32,
+    /// Chroma subsampling
+    subsampling: Subsampling,
+    /// Chroma siting
+    siting: ChromaSiting,
+    /// Luma plane
+    y: Box<[C]>,
+    /// Blue-difference chroma plane
+    cb: Box<[C]>,
+    /// Red-difference chroma plane
+    cr: Box<[C]>,
+}
+
+impl Subsampling {
+    /// Get chroma plane dimensions for a frame size
+    fn chroma_size(self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            Subsampling::S420 => (width.div_ceil(2), height.div_ceil(2)),
+            Subsampling::S422 => (width.div_ceil(2), height),
+        }
+    }
+}
+
+impl ChromaSiting {
+    /// Check whether chroma is co-sited horizontally
+    fn cosited_x(self) -> bool {
+        self != ChromaSiting::Center
+    }
+
+    /// Check whether chroma is co-sited vertically
+    fn cosited_y(self) -> bool {
+        self == ChromaSiting::TopLeft
+    }
+}
+
+/// Downsample a plane by 2 in one dimension.
+///
+/// * `src` Source plane.
+/// * `len` Number of source samples in the dimension.
+/// * `step` Distance between samples in the dimension.
+/// * `cosited` Whether output samples are co-sited with even inputs.
+fn downsample(src: &[f32], len: usize, step: usize, cosited: bool) -> Vec<f32> {
+    let other = src.len() / len;
+    let half = len.div_ceil(2);
+    let mut dst = vec![0.0; half * other];
+    let (dstep, ostep) = if step == 1 { (1, half) } else { (other, 1) };
+    let sostep = if step == 1 { len } else { 1 };
+    for o in 0..other {
+        for i in 0..half {
+            let x = 2 * i;
+            let x1 = (x + 1).min(len - 1);
+            let s = |x: usize| src[o * sostep + x * step];
+            let v = if cosited {
+                let x0 = x.saturating_sub(1);
+                (s(x0) + 2.0 * s(x) + s(x1)) * 0.25
+            } else {
+                (s(x) + s(x1)) * 0.5
+            };
+            dst[o * ostep + i * dstep] = v;
+        }
+    }
+    dst
+}
+
+/// Upsample a plane by 2 in one dimension, using linear interpolation.
+///
+/// * `src` Source plane.
+/// * `len` Number of destination samples in the dimension.
+/// * `step` Distance between source samples in the dimension.
+/// * `cosited` Whether source samples are co-sited with even outputs.
+fn upsample(src: &[f32], len: usize, step: usize, cosited: bool) -> Vec<f32> {
+    let half = len.div_ceil(2);
+    let other = src.len() / half;
+    let mut dst = vec![0.0; len * other];
+    let (dstep, ostep) = if step == 1 { (1, len) } else { (other, 1) };
+    let sostep = if step == 1 { half } else { 1 };
+    let offset = if cosited { 0.0 } else { 0.5 };
+    for o in 0..other {
+        let s = |i: usize| src[o * sostep + i.min(half - 1) * step];
+        for x in 0..len {
+            let u = ((x as f32 - offset) * 0.5).max(0.0);
+            let i = u as usize;
+            let t = u - i as f32;
+            dst[o * ostep + x * dstep] = s(i) * (1.0 - t) + s(i + 1) * t;
+        }
+    }
+    dst
+}
+
+impl<C: Channel> YCbCrFrame<C> {
+    /// Construct a `YCbCrFrame` with all pixels black.
+    ///
+    /// * `width` Width of frame.
+    /// * `height` Height of frame.
+    /// * `subsampling` Chroma subsampling.
+    pub fn with_clear(
+        width: u32,
+        height: u32,
+        subsampling: Subsampling,
+    ) -> Self {
+        let (cw, ch) = subsampling.chroma_size(width, height);
+        let len = width as usize * height as usize;
+        let clen = cw as usize * ch as usize;
+        YCbCrFrame {
+            width,
+            height,
+            subsampling,
+            siting: ChromaSiting::default(),
+            y: vec![C::MIN; len].into_boxed_slice(),
+            cb: vec![C::MID; clen].into_boxed_slice(),
+            cr: vec![C::MID; clen].into_boxed_slice(),
+        }
+    }
+
+    /// Construct a `YCbCrFrame` from planes, such as decoder output.
+    ///
+    /// * `B` Owned channel type (`Vec` or boxed slice).
+    /// * `width` Width of frame.
+    /// * `height` Height of frame.
+    /// * `subsampling` Chroma subsampling.
+    /// * `y` Luma plane.
+    /// * `cb` Blue-difference chroma plane.
+    /// * `cr` Red-difference chroma plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any plane length does not match its dimensions.
+    pub fn with_planes<B>(
+        width: u32,
+        height: u32,
+        subsampling: Subsampling,
+        y: B,
+        cb: B,
+        cr: B,
+    ) -> Self
+    where
+        B: Into<Box<[C]>>,
+    {
+        let (cw, ch) = subsampling.chroma_size(width, height);
+        let (y, cb, cr) = (y.into(), cb.into(), cr.into());
+        assert_eq!(y.len(), width as usize * height as usize);
+        assert_eq!(cb.len(), cw as usize * ch as usize);
+        assert_eq!(cr.len(), cw as usize * ch as usize);
+        YCbCrFrame {
+            width,
+            height,
+            subsampling,
+            siting: ChromaSiting::default(),
+            y,
+            cb,
+            cr,
+        }
+    }
+
+    /// Construct a `YCbCrFrame` by converting a [Raster].
+    ///
+    /// * `src` Source raster.
+    /// * `subsampling` Chroma subsampling.
+    /// * `siting` Chroma siting.
+    ///
+    /// Chroma is downsampled by averaging the pixels covered by each sample.
+    ///
+    /// [raster]: ../struct.Raster.html
+    pub fn with_raster<P>(
+        src: &Raster<P>,
+        subsampling: Subsampling,
+        siting: ChromaSiting,
+    ) -> Self
+    where
+        P: Pixel,
+        Ch32: From<P::Chan>,
+    {
+        let (width, height) = (src.width(), src.height());
+        let mut frame = Self::with_clear(width, height, subsampling);
+        frame.siting = siting;
+        if width == 0 || height == 0 {
+            return frame;
+        }
+        let len = src.pixels().len();
+        let mut cb = Vec::with_capacity(len);
+        let mut cr = Vec::with_capacity(len);
+        for (y, p) in frame.y.iter_mut().zip(src.pixels()) {
+            let p: SYCbCr32 = p.convert();
+            *y = YCbCr::y(p).to_f32().into();
+            cb.push(YCbCr::cb(p).to_f32());
+            cr.push(YCbCr::cr(p).to_f32());
+        }
+        for (plane, full) in [(&mut frame.cb, cb), (&mut frame.cr, cr)] {
+            let w = width as usize;
+            let mut half = downsample(&full, w, 1, siting.cosited_x());
+            if subsampling == Subsampling::S420 {
+                let w = w.div_ceil(2);
+                half =
+                    downsample(&half, height as usize, w, siting.cosited_y());
+            }
+            for (c, v) in plane.iter_mut().zip(half) {
+                *c = v.into();
+            }
+        }
+        frame
+    }
+
+    /// Get width of frame.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get height of frame.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get chroma subsampling.
+    pub fn subsampling(&self) -> Subsampling {
+        self.subsampling
+    }
+
+    /// Get chroma siting.
+    pub fn siting(&self) -> ChromaSiting {
+        self.siting
+    }
+
+    /// Set chroma siting.
+    pub fn set_siting(&mut self, siting: ChromaSiting) {
+        self.siting = siting;
+    }
+
+    /// Get width of chroma planes.
+    pub fn chroma_width(&self) -> u32 {
+        self.subsampling.chroma_size(self.width, self.height).0
+    }
+
+    /// Get height of chroma planes.
+    pub fn chroma_height(&self) -> u32 {
+        self.subsampling.chroma_size(self.width, self.height).1
+    }
+
+    /// Get the *y* (luma) plane.
+    pub fn y_plane(&self) -> &[C] {
+        &self.y
+    }
+
+    /// Get the *y* (luma) plane mutably.
+    pub fn y_plane_mut(&mut self) -> &mut [C] {
+        &mut self.y
+    }
+
+    /// Get the *Cb* (blue-difference chroma) plane.
+    pub fn cb_plane(&self) -> &[C] {
+        &self.cb
+    }
+
+    /// Get the *Cb* (blue-difference chroma) plane mutably.
+    pub fn cb_plane_mut(&mut self) -> &mut [C] {
+        &mut self.cb
+    }
+
+    /// Get the *Cr* (red-difference chroma) plane.
+    pub fn cr_plane(&self) -> &[C] {
+        &self.cr
+    }
+
+    /// Get the *Cr* (red-difference chroma) plane mutably.
+    pub fn cr_plane_mut(&mut self) -> &mut [C] {
+        &mut self.cr
+    }
+
+    /// Upsample a chroma plane to full resolution
+    fn upsampled(&self, plane: &[C]) -> Vec<f32> {
+        let full: Vec<f32> = plane.iter().map(|c| c.to_f32()).collect();
+        let full = match self.subsampling {
+            Subsampling::S420 => upsample(
+                &full,
+                self.height as usize,
+                self.chroma_width() as usize,
+                self.siting.cosited_y(),
+            ),
+            Subsampling::S422 => full,
+        };
+        upsample(&full, self.width as usize, 1, self.siting.cosited_x())
+    }
+}
+
+impl<P: Pixel> Raster<P> {
+    /// Construct a `Raster` by converting a [YCbCrFrame].
+    ///
+    /// Chroma is upsampled with linear interpolation, according to the
+    /// frame's [ChromaSiting].
+    ///
+    /// [chromasiting]: ycc/enum.ChromaSiting.html
+    /// [ycbcrframe]: ycc/struct.YCbCrFrame.html
+    pub fn with_ycbcr_frame<C>(frame: &YCbCrFrame<C>) -> Self
+    where
+        C: Channel,
+        P::Chan: From<Ch32>,
+    {
+        let mut r = Raster::<P>::with_clear(frame.width(), frame.height());
+        if frame.y.is_empty() {
+            return r;
+        }
+        let cb = frame.upsampled(&frame.cb);
+        let cr = frame.upsampled(&frame.cr);
+        let pixels = r.pixels_mut().iter_mut().zip(frame.y.iter());
+        for ((p, y), (cb, cr)) in pixels.zip(cb.iter().zip(cr.iter())) {
+            *p = SYCbCr32::new(y.to_f32(), *cb, *cr).convert();
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::*;
+
+    #[test]
+    fn downsample_siting() {
+        let src = [0.0, 1.0, 0.5, 0.5, 1.0];
+        assert_eq!(downsample(&src, 5, 1, false), [0.5, 0.5, 1.0]);
+        assert_eq!(downsample(&src, 5, 1, true), [0.25, 0.625, 0.875]);
+        let src = [0.0, 1.0, 0.5, 0.5];
+        assert_eq!(downsample(&src, 2, 2, false), [0.25, 0.75]);
+    }
+
+    #[test]
+    fn upsample_siting() {
+        let src = [0.0, 1.0];
+        assert_eq!(upsample(&src, 4, 1, false), [0.0, 0.25, 0.75, 1.0]);
+        assert_eq!(upsample(&src, 4, 1, true), [0.0, 0.5, 1.0, 1.0]);
+        let src = [0.0, 1.0, 1.0, 0.0];
+        assert_eq!(upsample(&src, 3, 2, true), [0.0, 1.0, 0.5, 0.5, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        let mut r = Raster::with_clear(5, 3);
+        r.copy_color((0, 0, 5, 3), SRgb8::new(0x40, 0x80, 0xC0));
+        for sub in [Subsampling::S420, Subsampling::S422] {
+            for siting in [
+                ChromaSiting::Center,
+                ChromaSiting::Left,
+                ChromaSiting::TopLeft,
+            ] {
+                let frame = YCbCrFrame::<Ch8>::with_raster(&r, sub, siting);
+                assert_eq!(frame.chroma_width(), 3);
+                let r2 = Raster::<SRgb8>::with_ycbcr_frame(&frame);
+                for (p, q) in r.pixels().iter().zip(r2.pixels()) {
+                    for (a, b) in p.channels().iter().zip(q.channels()) {
+                        assert!(u8::from(*a).abs_diff(u8::from(*b)) <= 2);
+                    }
+                }
+            }
+        }
+        let frame = YCbCrFrame::<Ch16>::with_clear(5, 3, Subsampling::S420);
+        assert_eq!(frame.cr_plane().len(), 3 * 2);
+        let frame = YCbCrFrame::<Ch16>::with_clear(5, 3, Subsampling::S422);
+        assert_eq!(frame.cr_plane().len(), 3 * 3);
+    }
+
+    #[test]
+    fn frame_bt601() {
+        // BT.601 full range Y'CbCr of pure red
+        let mut frame = YCbCrFrame::<Ch32>::with_clear(2, 2, Subsampling::S420);
+        frame.y_plane_mut().fill(0.299.into());
+        frame.cb_plane_mut().fill(0.331_264.into());
+        frame.cr_plane_mut().fill(1.0.into());
+        let r = Raster::<SRgb8>::with_ycbcr_frame(&frame);
+        assert_eq!(r.pixel(1, 1), SRgb8::new(0xFF, 0x00, 0x00));
+        // mid gray is not linearized twice
+        frame.y_plane_mut().fill(0.5.into());
+        frame.cb_plane_mut().fill(0.5.into());
+        frame.cr_plane_mut().fill(0.5.into());
+        let r = Raster::<SRgb8>::with_ycbcr_frame(&frame);
+        assert_eq!(r.pixel(0, 0), SRgb8::new(0x80, 0x80, 0x80));
+        let f2 = YCbCrFrame::<Ch8>::with_raster(
+            &r,
+            Subsampling::S420,
+            ChromaSiting::Center,
+        );
+        assert_eq!(f2.y_plane()[0], Ch8::new(0x80));
+    }
+}